@@ -7,6 +7,7 @@ use crate::errors::AssertionError;
 use core::{
     cmp::{Ord, Ordering, PartialOrd},
     fmt::{Display, Formatter},
+    ops::Range,
 };
 use math::FieldElement;
 use utils::collections::Vec;
@@ -20,6 +21,10 @@ mod tests;
 const MIN_STRIDE_LENGTH: usize = 2;
 const NO_STRIDE: usize = 0;
 
+/// Placeholder step of a single-value assertion placed against the last step of an execution
+/// trace; it is replaced with the actual step once the length of the trace is known.
+const LAST_STEP: usize = usize::MAX;
+
 // ASSERTION
 // ================================================================================================
 
@@ -69,6 +74,28 @@ impl<E: FieldElement> Assertion<E> {
         }
     }
 
+    /// Returns an assertion against the first cell of the specified `column`.
+    ///
+    /// The returned assertion requires that the value in the specified `column` at step 0 is
+    /// equal to the provided `value`.
+    pub fn initial_step(column: usize, value: E) -> Self {
+        Self::single(column, 0, value)
+    }
+
+    /// Returns an assertion against the last cell of the specified `column`.
+    ///
+    /// The returned assertion requires that the value in the specified `column` at the last step
+    /// of an execution trace is equal to the provided `value`. The step is resolved to
+    /// `trace_length - 1` once the length of the trace is known (e.g., in [Assertion::apply()]).
+    pub fn final_step(column: usize, value: E) -> Self {
+        Assertion {
+            column,
+            first_step: LAST_STEP,
+            stride: NO_STRIDE,
+            values: vec![value],
+        }
+    }
+
     /// Returns an single-value assertion against multiple cells of a single column.
     ///
     /// The returned assertion requires that values in the specified `column` must be equal to
@@ -121,6 +148,42 @@ impl<E: FieldElement> Assertion<E> {
         }
     }
 
+    /// Returns a list of single-value assertions against a contiguous range of cells in a single
+    /// column.
+    ///
+    /// The returned assertions require that values in the specified `column` at each of the
+    /// specified `steps` are equal to the corresponding value from `values`. That is, value at
+    /// step `steps.start` should be equal to `values[0]`, value at step `steps.start + 1` should
+    /// be equal to `values[1]` etc.
+    ///
+    /// Since the asserted steps are not spaced at power-of-two intervals, a range of steps cannot
+    /// be covered by a single periodic or sequence assertion; thus, a single-value assertion is
+    /// returned for each step in the range.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `steps` is an empty range.
+    /// * The number of `values` is not equal to the number of steps in `steps`.
+    pub fn range(column: usize, steps: Range<usize>, values: Vec<E>) -> Vec<Self> {
+        assert!(
+            !steps.is_empty(),
+            "invalid assertion for column {}: range of asserted steps must not be empty",
+            column
+        );
+        assert_eq!(
+            steps.len(),
+            values.len(),
+            "invalid assertion for column {}: expected {} asserted values, but was {}",
+            column,
+            steps.len(),
+            values.len()
+        );
+        steps
+            .zip(values)
+            .map(|(step, value)| Self::single(column, step, value))
+            .collect()
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -131,7 +194,10 @@ impl<E: FieldElement> Assertion<E> {
 
     /// Returns the first step of the execution trace against which this assertion is placed.
     ///
-    /// For single value assertions this is equivalent to the assertion step.
+    /// For single value assertions this is equivalent to the assertion step. For assertions
+    /// created via [Assertion::final_step()] the step is not known until the assertion is
+    /// resolved against a specific trace length via [Assertion::resolve()]; until then,
+    /// `usize::MAX` is returned.
     pub fn first_step(&self) -> usize {
         self.first_step
     }
@@ -150,6 +216,12 @@ impl<E: FieldElement> Assertion<E> {
         &self.values
     }
 
+    /// Returns true if this assertion is placed against the last step of an execution trace and
+    /// the step has not yet been resolved to a specific trace length.
+    pub fn is_final_step(&self) -> bool {
+        self.first_step == LAST_STEP
+    }
+
     /// Returns true if this is a single-value assertion (one value, one step).
     pub fn is_single(&self) -> bool {
         self.stride == NO_STRIDE
@@ -168,6 +240,50 @@ impl<E: FieldElement> Assertion<E> {
     // PUBLIC METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns a copy of this assertion moved to the column specified by the permutation.
+    ///
+    /// The permutation is interpreted as follows: a column at index `i` is moved to index
+    /// `perm[i]`. Thus, the returned assertion is placed against column `perm[self.column()]`;
+    /// all other properties of the assertion remain unchanged.
+    ///
+    /// # Panics
+    /// Panics if the column of this assertion is out of bounds for `perm`.
+    pub fn remap_column(&self, perm: &[usize]) -> Self {
+        assert!(
+            self.column < perm.len(),
+            "assertion column {} is out of bounds for a permutation of {} columns",
+            self.column,
+            perm.len()
+        );
+        Assertion {
+            column: perm[self.column],
+            first_step: self.first_step,
+            stride: self.stride,
+            values: self.values.clone(),
+        }
+    }
+
+    /// Returns a copy of this assertion with the step resolved against an execution trace of the
+    /// specified length.
+    ///
+    /// For assertions created via [Assertion::final_step()] the returned assertion is placed
+    /// against step `trace_length - 1`; all other assertions are returned unchanged.
+    ///
+    /// # Panics
+    /// Panics if the specified trace length is not valid for this assertion.
+    pub fn resolve(&self, trace_length: usize) -> Self {
+        self.validate_trace_length(trace_length)
+            .unwrap_or_else(|err| {
+                panic!("invalid trace length: {}", err);
+            });
+        Assertion {
+            column: self.column,
+            first_step: self.resolve_step(trace_length),
+            stride: self.stride,
+            values: self.values.clone(),
+        }
+    }
+
     /// Checks if this assertion overlaps with the provided assertion.
     ///
     /// Overlap is defined as asserting a value for the same step in the same column.
@@ -227,7 +343,7 @@ impl<E: FieldElement> Assertion<E> {
             return Err(AssertionError::TraceLengthNotPowerOfTwo(trace_length));
         }
         if self.is_single() {
-            if self.first_step >= trace_length {
+            if !self.is_final_step() && self.first_step >= trace_length {
                 return Err(AssertionError::TraceLengthTooShort(
                     (self.first_step + 1).next_power_of_two(),
                     trace_length,
@@ -266,7 +382,7 @@ impl<E: FieldElement> Assertion<E> {
                 panic!("invalid trace length: {}", err);
             });
         if self.is_single() {
-            f(self.first_step, self.values[0]);
+            f(self.resolve_step(trace_length), self.values[0]);
         } else if self.is_periodic() {
             for i in 0..(trace_length / self.stride) {
                 f(self.first_step + self.stride * i, self.values[0]);
@@ -300,6 +416,19 @@ impl<E: FieldElement> Assertion<E> {
             self.values.len()
         }
     }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the first step of this assertion in the context of an execution trace of the
+    /// specified length.
+    fn resolve_step(&self, trace_length: usize) -> usize {
+        if self.is_final_step() {
+            trace_length - 1
+        } else {
+            self.first_step
+        }
+    }
 }
 
 // OTHER TRAIT IMPLEMENTATIONS
@@ -331,6 +460,7 @@ impl<E: FieldElement> Display for Assertion<E> {
     fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "(column={}, ", self.column)?;
         match self.stride {
+            0 if self.is_final_step() => write!(f, "step=last, ")?,
             0 => write!(f, "step={}, ", self.first_step)?,
             _ => {
                 let second_step = self.first_step + self.stride;
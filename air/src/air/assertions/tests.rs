@@ -39,6 +39,55 @@ fn single_assertion() {
     );
 }
 
+#[test]
+fn initial_step_assertion() {
+    let value = rand_value::<BaseElement>();
+    let a = Assertion::initial_step(3, value);
+    assert_eq!(Assertion::single(3, 0, value), a);
+    assert!(!a.is_final_step());
+
+    for trace_length in [8, 16, 32] {
+        let mut steps = Vec::new();
+        a.apply(trace_length, |step, val| {
+            assert_eq!(value, val);
+            steps.push(step);
+        });
+        assert_eq!(vec![0], steps);
+        assert_eq!(Assertion::single(3, 0, value), a.resolve(trace_length));
+    }
+}
+
+#[test]
+fn final_step_assertion() {
+    let value = rand_value::<BaseElement>();
+    let a = Assertion::final_step(3, value);
+    assert_eq!(3, a.column());
+    assert_eq!(vec![value], a.values);
+    assert!(a.is_single());
+    assert!(a.is_final_step());
+
+    for trace_length in [8, 16, 32] {
+        assert_eq!(Ok(()), a.validate_trace_length(trace_length));
+        assert_eq!(1, a.get_num_steps(trace_length));
+
+        let mut steps = Vec::new();
+        a.apply(trace_length, |step, val| {
+            assert_eq!(value, val);
+            steps.push(step);
+        });
+        assert_eq!(vec![trace_length - 1], steps);
+
+        let resolved = a.resolve(trace_length);
+        assert!(!resolved.is_final_step());
+        assert_eq!(Assertion::single(3, trace_length - 1, value), resolved);
+    }
+
+    assert_eq!(
+        Err(AssertionError::TraceLengthNotPowerOfTwo(12)),
+        a.validate_trace_length(12)
+    );
+}
+
 // PERIODIC ASSERTIONS
 // ================================================================================================
 
@@ -108,6 +157,78 @@ fn periodic_assertion_get_num_steps_error() {
     let _ = a.get_num_steps(4);
 }
 
+#[test]
+fn periodic_assertion_trace_16() {
+    let value = rand_value::<BaseElement>();
+    let a = Assertion::periodic(1, 2, 4, value);
+    assert_eq!(4, a.get_num_steps(16));
+
+    let mut steps = Vec::new();
+    a.apply(16, |step, val| {
+        assert_eq!(value, val);
+        steps.push(step);
+    });
+    assert_eq!(vec![2, 6, 10, 14], steps);
+
+    // stride which does not divide trace length
+    let a = Assertion::periodic(1, 2, 32, value);
+    assert_eq!(
+        Err(AssertionError::TraceLengthTooShort(32, 16)),
+        a.validate_trace_length(16)
+    );
+
+    // overlapping assertions
+    let a = Assertion::periodic(1, 2, 4, value);
+    let b = Assertion::single(1, 10, value);
+    assert!(a.overlaps_with(&b));
+    assert!(b.overlaps_with(&a));
+}
+
+// RANGE ASSERTIONS
+// ================================================================================================
+
+#[test]
+fn range_assertion() {
+    let values = rand_vector::<BaseElement>(4);
+    let assertions = Assertion::range(3, 5..9, values.clone());
+    assert_eq!(4, assertions.len());
+
+    let mut steps = Vec::new();
+    for (a, &value) in assertions.iter().zip(values.iter()) {
+        assert_eq!(3, a.column);
+        assert!(a.is_single());
+        a.apply(16, |step, val| {
+            assert_eq!(value, val);
+            steps.push(step);
+        });
+    }
+    assert_eq!(vec![5, 6, 7, 8], steps);
+
+    // assertions in the range overlap with a periodic assertion covering step 6
+    let p = Assertion::periodic(3, 2, 4, BaseElement::ONE);
+    assert_eq!(1, assertions.iter().filter(|a| a.overlaps_with(&p)).count());
+
+    // steps past the end of the trace
+    assert_eq!(
+        Err(AssertionError::TraceLengthTooShort(16, 8)),
+        assertions[3].validate_trace_length(8)
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "invalid assertion for column 0: range of asserted steps must not be empty"
+)]
+fn range_assertion_empty_steps() {
+    let _ = Assertion::<BaseElement>::range(0, 4..4, Vec::new());
+}
+
+#[test]
+#[should_panic(expected = "invalid assertion for column 0: expected 4 asserted values, but was 3")]
+fn range_assertion_num_values_mismatch() {
+    let _ = Assertion::range(0, 4..8, rand_vector::<BaseElement>(3));
+}
+
 // SEQUENCE ASSERTIONS
 // ================================================================================================
 
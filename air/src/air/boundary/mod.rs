@@ -94,7 +94,7 @@ impl<E: FieldElement> BoundaryConstraints<E> {
 
         // compute inverse of the trace domain generator; this will be used for offset
         // computations when creating sequence constraints
-        let inv_g = context.trace_domain_generator.inv();
+        let inv_g = context.domain_info.trace_domain_generator().inv();
 
         // cache inverse twiddles for multi-value assertions in this map so that we don't have
         // to re-build them for assertions with identical strides
@@ -208,6 +208,9 @@ fn prepare_assertions<E: FieldElement>(
             .unwrap_or_else(|err| {
                 panic!("assertion {} is invalid: {}", assertion, err);
             });
+
+        // assertions against the last step can be resolved only once trace length is known
+        let assertion = assertion.resolve(trace_length);
         for a in result.iter().filter(|a| a.column == assertion.column) {
             assert!(
                 !a.overlaps_with(&assertion),
@@ -16,6 +16,11 @@ use utils::collections::Vec;
 /// function for each auxiliary trace segment. In the interactive version of the protocol, the
 /// verifier draws these elements uniformly at random from the extension field of the protocol
 /// after the prover commits to a previous trace segment.
+///
+/// The elements are stored as an ordered collection with one set of elements per auxiliary trace
+/// segment. This supports multi-round randomized AIRs: elements for segment `i` are drawn only
+/// after segments `0..i` (and the main segment) have been committed to, and thus, constraints
+/// over segment `i` may safely use random elements of all segments up to and including `i`.
 #[derive(Debug, Clone)]
 pub struct AuxTraceRandElements<E: FieldElement>(pub Vec<Vec<E>>);
 
@@ -25,12 +30,29 @@ impl<E: FieldElement> AuxTraceRandElements<E> {
         Self(Vec::new())
     }
 
+    /// Returns the number of auxiliary segments for which random elements have been added.
+    pub fn num_segments(&self) -> usize {
+        self.0.len()
+    }
+
     /// Returns a list of random elements for an auxiliary segment with the specified index.
+    ///
+    /// # Panics
+    /// Panics if random elements for the segment with the specified index have not been added.
     pub fn get_segment_elements(&self, aux_segment_idx: usize) -> &[E] {
+        assert!(
+            aux_segment_idx < self.0.len(),
+            "random elements for auxiliary segment {} are not available; only {} segments have been added",
+            aux_segment_idx,
+            self.0.len()
+        );
         &self.0[aux_segment_idx]
     }
 
     /// Adds random elements for a new auxiliary segment to this set of random elements.
+    ///
+    /// Segments must be added in order: the added elements are associated with the auxiliary
+    /// segment at index [num_segments()](Self::num_segments) before this call.
     pub fn add_segment_elements(&mut self, rand_elements: Vec<E>) {
         self.0.push(rand_elements);
     }
@@ -3,8 +3,16 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{air::TransitionConstraintDegree, ProofOptions, TraceInfo};
-use math::{log2, StarkField};
+use super::{
+    domain_info::DomainInfo,
+    lagrange::{get_lagrange_kernel_selector, LagrangeKernelInfo},
+    MIN_CYCLE_LENGTH,
+};
+use crate::{
+    air::{Assertion, TransitionConstraintDegree},
+    ProofOptions, TraceInfo,
+};
+use math::{log2, FieldElement, StarkField};
 use utils::collections::Vec;
 
 // AIR CONTEXT
@@ -19,9 +27,12 @@ pub struct AirContext<B: StarkField> {
     pub(super) num_main_assertions: usize,
     pub(super) num_aux_assertions: usize,
     pub(super) ce_blowup_factor: usize,
-    pub(super) trace_domain_generator: B,
-    pub(super) lde_domain_generator: B,
+    pub(super) domain_info: DomainInfo<B>,
     pub(super) transition_exemptions: Vec<usize>,
+    pub(super) num_blinding_rows: usize,
+    pub(super) periodic_columns: Vec<Vec<B>>,
+    pub(super) lagrange_kernel: Option<LagrangeKernelInfo>,
+    pub(super) composition_degree_hint: Option<usize>,
 }
 
 impl<B: StarkField> AirContext<B> {
@@ -41,6 +52,8 @@ impl<B: StarkField> AirContext<B> {
     /// * `num_assertions` is zero.
     /// * Blowup factor specified by the provided `options` is too small to accommodate degrees
     ///   of the specified transition constraints.
+    /// * Domain offset specified by the provided `options` is zero in the base field, or is an
+    ///   element of the LDE domain.
     /// * `trace_info` describes a multi-segment execution trace.
     pub fn new(
         trace_info: TraceInfo,
@@ -84,6 +97,8 @@ impl<B: StarkField> AirContext<B> {
     ///   - `num_aux_assertions` is greater than zero.
     /// * Blowup factor specified by the provided `options` is too small to accommodate degrees
     ///   of the specified transition constraints.
+    /// * Domain offset specified by the provided `options` is zero in the base field, or is an
+    ///   element of the LDE domain.
     pub fn new_multi_segment(
         trace_info: TraceInfo,
         main_transition_constraint_degrees: Vec<TransitionConstraintDegree>,
@@ -146,6 +161,19 @@ impl<B: StarkField> AirContext<B> {
         let trace_length = trace_info.length();
         let lde_domain_size = trace_length * options.blowup_factor();
 
+        // the LDE domain must be a proper coset of the subgroup of size lde_domain_size; otherwise
+        // it would intersect the trace domain and constraint divisors would evaluate to zero
+        let domain_offset: B = options.domain_offset();
+        assert!(
+            domain_offset != B::ZERO,
+            "domain offset cannot be zero in the base field"
+        );
+        assert!(
+            domain_offset.exp((lde_domain_size as u64).into()) != B::ONE,
+            "domain offset cannot be an element of the LDE domain of size {}",
+            lde_domain_size
+        );
+
         let n_degrees =
             main_transition_constraint_degrees.len() + aux_transition_constraint_degrees.len();
 
@@ -157,9 +185,12 @@ impl<B: StarkField> AirContext<B> {
             num_main_assertions,
             num_aux_assertions,
             ce_blowup_factor,
-            trace_domain_generator: B::get_root_of_unity(log2(trace_length)),
-            lde_domain_generator: B::get_root_of_unity(log2(lde_domain_size)),
+            domain_info: DomainInfo::new(trace_length, lde_domain_size),
             transition_exemptions: vec![1; n_degrees],
+            num_blinding_rows: 0,
+            periodic_columns: Vec::new(),
+            lagrange_kernel: None,
+            composition_degree_hint: None,
         }
     }
 
@@ -190,9 +221,26 @@ impl<B: StarkField> AirContext<B> {
     /// Returns the degree to which all constraint polynomials are normalized before they are
     /// composed together.
     ///
-    /// This degree is always `ce_domain_size` - 1.
+    /// This degree is always `trace_length * num_composition_columns` - 1. Unless a lower bound
+    /// was provided via [set_composition_degree_hint()](AirContext::set_composition_degree_hint),
+    /// this is the same as `ce_domain_size` - 1.
     pub fn composition_degree(&self) -> usize {
-        self.ce_domain_size() - 1
+        self.trace_len() * self.num_composition_columns() - 1
+    }
+
+    /// Returns the number of columns into which the constraint composition polynomial is split.
+    ///
+    /// Each column is a polynomial of degree `trace_length` - 1. By default, the number of
+    /// columns is equal to the constraint evaluation domain blowup factor; it can be reduced
+    /// via [set_composition_degree_hint()](AirContext::set_composition_degree_hint).
+    pub fn num_composition_columns(&self) -> usize {
+        match self.composition_degree_hint {
+            Some(degree) => {
+                let num_columns = (degree + self.trace_len()) / self.trace_len();
+                core::cmp::min(num_columns, self.ce_blowup_factor)
+            }
+            None => self.ce_blowup_factor,
+        }
     }
 
     /// Returns the size of the low-degree extension domain.
@@ -202,6 +250,11 @@ impl<B: StarkField> AirContext<B> {
         self.trace_info.length() * self.options.blowup_factor()
     }
 
+    /// Returns generators of the trace and LDE domains for an instance of a computation.
+    pub fn domain_info(&self) -> &DomainInfo<B> {
+        &self.domain_info
+    }
+
     /// Returns the number of transition constraints for a computation.
     ///
     /// The number of transition constraints is defined by the total number of transition
@@ -233,16 +286,255 @@ impl<B: StarkField> AirContext<B> {
     /// Returns the number of rows at the end of an execution trace to which transition constraints
     /// do not apply.
     ///
-    /// This is guaranteed to be at least 1 (which is the default value), but could be greater.
-    /// The maximum number of exemptions is determined by a combination of transition constraint
-    /// degrees and blowup factor specified for the computation.
+    /// This is at least 1 by default, but could be greater. The maximum number of exemptions is
+    /// determined by a combination of transition constraint degrees and blowup factor specified
+    /// for the computation. For computations with cyclic transition constraints (see
+    /// [set_cyclic_transitions()](AirContext::set_cyclic_transitions)), this is zero.
     pub fn num_transition_exemptions(&self) -> Vec<usize> {
         self.transition_exemptions.clone()
     }
 
+    /// Returns the number of rows at the end of an execution trace reserved for random blinding
+    /// values via [set_num_blinding_rows()](AirContext::set_num_blinding_rows).
+    ///
+    /// This is zero by default.
+    pub fn num_blinding_rows(&self) -> usize {
+        self.num_blinding_rows
+    }
+
+    /// Returns values of periodic columns added to this context via
+    /// [add_periodic_column_fn()](AirContext::add_periodic_column_fn).
+    ///
+    /// Each column contains the values of a single period of the column.
+    pub fn periodic_columns(&self) -> &[Vec<B>] {
+        &self.periodic_columns
+    }
+
+    /// Returns an assertion against a single cell of the column at index `column` of the
+    /// auxiliary trace segment at index `segment`.
+    ///
+    /// Columns of auxiliary assertions are zero-based across all auxiliary trace segments; this
+    /// helper translates the segment-local column index into such an index so that it does not
+    /// need to be computed manually. Otherwise, the returned assertion is the same as the one
+    /// returned by [Assertion::single()].
+    ///
+    /// # Panics
+    /// Panics if `segment` is not a valid auxiliary segment index, or if `column` is not smaller
+    /// than the width of the specified segment.
+    pub fn aux_assertion<E>(
+        &self,
+        segment: usize,
+        column: usize,
+        step: usize,
+        value: E,
+    ) -> Assertion<E>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        let column = self
+            .trace_info
+            .layout()
+            .get_aux_column_index(segment, column);
+        Assertion::single(column, step, value)
+    }
+
+    /// Returns the index of the auxiliary column designated as a Lagrange kernel column via
+    /// [set_lagrange_kernel_aux_column()](AirContext::set_lagrange_kernel_aux_column), or None
+    /// if no such column was designated.
+    ///
+    /// The index is zero-based across all auxiliary trace segments.
+    pub fn lagrange_kernel_aux_column(&self) -> Option<usize> {
+        self.lagrange_kernel.as_ref().map(|info| info.column)
+    }
+
     // DATA MUTATORS
     // --------------------------------------------------------------------------------------------
 
+    /// Adds a periodic column with the specified period to this context.
+    ///
+    /// Values of the column are computed by evaluating `f` at each step of a single period (i.e.,
+    /// for steps `0..period`). Unless [Air::get_periodic_column_values()] is overridden, the
+    /// columns added to the context are returned from this method in the order in which they were
+    /// added, and are interpolated into polynomials by [Air::get_periodic_column_polys()].
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `period` is smaller than 2 or is not a power of two.
+    /// * `period` is greater than the trace length.
+    ///
+    /// [Air::get_periodic_column_values()]: crate::Air::get_periodic_column_values
+    /// [Air::get_periodic_column_polys()]: crate::Air::get_periodic_column_polys
+    pub fn add_periodic_column_fn<F>(mut self, period: usize, f: F) -> Self
+    where
+        F: Fn(usize) -> B,
+    {
+        assert!(
+            period >= MIN_CYCLE_LENGTH,
+            "number of values in a periodic column must be at least {}, but was {}",
+            MIN_CYCLE_LENGTH,
+            period
+        );
+        assert!(
+            period.is_power_of_two(),
+            "number of values in a periodic column must be a power of two, but was {}",
+            period
+        );
+        assert!(
+            period <= self.trace_len(),
+            "number of values in a periodic column cannot exceed trace length {}, but was {}",
+            self.trace_len(),
+            period
+        );
+        self.periodic_columns.push((0..period).map(f).collect());
+        self
+    }
+
+    /// Designates the auxiliary column at the specified index as a Lagrange kernel column.
+    ///
+    /// For a trace of length $2^\nu$, a Lagrange kernel column contains values of the
+    /// multilinear Lagrange kernel eq(i, r) at all steps $i$, where $r$ is the point defined
+    /// by the first $\nu$ random elements drawn for the auxiliary segment containing the column
+    /// (see [build_lagrange_kernel_column()](crate::build_lagrange_kernel_column)). Such columns
+    /// are used, for example, to verify claims about evaluations of multilinear polynomials
+    /// made by GKR-based lookup arguments.
+    ///
+    /// Constraints for the column are added to the context automatically:
+    /// * A transition constraint is appended to the list of auxiliary transition constraints.
+    ///   This constraint is evaluated by the prover and the verifier after
+    ///   [Air::evaluate_aux_transition()](crate::Air::evaluate_aux_transition) is invoked, and
+    ///   thus, [Air::evaluate_aux_transition()](crate::Air::evaluate_aux_transition) should not
+    ///   write into the last slot of its `result` slice.
+    /// * An assertion against the first step of the column is appended to the list of auxiliary
+    ///   assertions by [Air::get_boundary_constraints()](crate::Air::get_boundary_constraints).
+    ///
+    /// The transition constraint relies on $\nu$ selector columns which are added to periodic
+    /// columns of this context. Thus, if [Air::get_periodic_column_values()] is overridden, its
+    /// result must start with the columns returned from
+    /// [periodic_columns()](AirContext::periodic_columns).
+    ///
+    /// `column` is zero-based across all auxiliary trace segments.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The trace described by this context is not a multi-segment trace.
+    /// * `column` is not a valid auxiliary column index.
+    /// * The number of random elements for the segment containing the column is smaller than
+    ///   log2 of the trace length.
+    /// * A Lagrange kernel column has already been designated for this context.
+    /// * Blowup factor specified by the options of this context is too small to accommodate the
+    ///   degree of the Lagrange kernel transition constraint.
+    ///
+    /// [Air::get_periodic_column_values()]: crate::Air::get_periodic_column_values
+    pub fn set_lagrange_kernel_aux_column(mut self, column: usize) -> Self {
+        assert!(
+            self.trace_info.is_multi_segment(),
+            "a Lagrange kernel column can be designated only for a multi-segment trace"
+        );
+        assert!(
+            self.lagrange_kernel.is_none(),
+            "a Lagrange kernel column has already been designated"
+        );
+
+        // find the segment which contains the column
+        let layout = self.trace_info.layout().clone();
+        let mut segment = 0;
+        let mut segment_column = column;
+        while segment < layout.num_aux_segments()
+            && segment_column >= layout.get_aux_segment_width(segment)
+        {
+            segment_column -= layout.get_aux_segment_width(segment);
+            segment += 1;
+        }
+        assert!(
+            segment < layout.num_aux_segments(),
+            "auxiliary column index must be smaller than {}, but was {}",
+            layout.aux_trace_width(),
+            column
+        );
+
+        let trace_length = self.trace_len();
+        let num_vars = log2(trace_length) as usize;
+        assert!(
+            layout.get_aux_segment_rand_elements(segment) >= num_vars,
+            "a Lagrange kernel column requires at least {} random elements, but auxiliary segment {} has {}",
+            num_vars,
+            segment,
+            layout.get_aux_segment_rand_elements(segment)
+        );
+
+        // add selector columns; the k-th selector identifies steps with k trailing ones
+        let selectors_offset = self.periodic_columns.len();
+        for k in 0..num_vars {
+            self = self
+                .add_periodic_column_fn(1 << (k + 1), |step| get_lagrange_kernel_selector(k, step));
+        }
+
+        // the transition constraint multiplies the column by selectors, the highest of which
+        // has a period equal to the trace length
+        let degree = TransitionConstraintDegree::with_cycles(1, vec![trace_length]);
+        if degree.min_blowup_factor() > self.ce_blowup_factor {
+            self.ce_blowup_factor = degree.min_blowup_factor();
+        }
+        assert!(
+            self.options.blowup_factor() >= self.ce_blowup_factor,
+            "blowup factor too small; expected at least {}, but was {}",
+            self.ce_blowup_factor,
+            self.options.blowup_factor()
+        );
+        self.aux_transition_constraint_degrees.push(degree);
+        self.transition_exemptions.push(1);
+        self.num_aux_assertions += 1;
+
+        self.lagrange_kernel = Some(LagrangeKernelInfo {
+            column,
+            segment,
+            selectors_offset,
+            num_vars,
+        });
+        self
+    }
+
+    /// Sets an upper bound on the degree of the constraint composition polynomial.
+    ///
+    /// By default, the composition polynomial is split into the number of columns equal to the
+    /// constraint evaluation domain blowup factor, which is the smallest power of two able to
+    /// accommodate the highest transition constraint degree. When a tighter bound is known, the
+    /// hint reduces the number of composition columns to `ceil((degree + 1) / trace_length)`,
+    /// and thus, reduces the size of the proof. A hint which does not reduce the number of
+    /// columns is ignored.
+    ///
+    /// If the actual degree of the composition polynomial exceeds the resulting bound (e.g.,
+    /// because transition constraint degrees were misreported), the prover returns an error
+    /// rather than generating a proof.
+    ///
+    /// # Panics
+    /// Panics if `degree` is smaller than the degree of the quotient of any of the transition
+    /// constraints defined in this context by its divisor, or smaller than `trace_length` - 2
+    /// (the highest degree of a boundary constraint quotient).
+    pub fn set_composition_degree_hint(mut self, degree: usize) -> Self {
+        let trace_length = self.trace_len();
+        let min_degree = self
+            .main_transition_constraint_degrees
+            .iter()
+            .chain(self.aux_transition_constraint_degrees.iter())
+            .zip(self.transition_exemptions.iter())
+            .map(|(constraint_degree, &num_exemptions)| {
+                let divisor_degree = trace_length - num_exemptions;
+                constraint_degree
+                    .get_evaluation_degree(trace_length)
+                    .saturating_sub(divisor_degree)
+            })
+            .fold(trace_length - 2, core::cmp::max);
+        assert!(
+            degree >= min_degree,
+            "composition degree hint is too low; expected at least {}, but was {}",
+            min_degree,
+            degree
+        );
+        self.composition_degree_hint = Some(degree);
+        self
+    }
+
     /// Sets the number of transition exemptions for this context.
     ///
     /// # Panics
@@ -262,6 +554,56 @@ impl<B: StarkField> AirContext<B> {
         self
     }
 
+    /// Makes all transition constraints of this context cyclic.
+    ///
+    /// Cyclic transition constraints have no exemptions: they are enforced on all steps of the
+    /// execution trace, including the transition from the last row back to the first row (i.e.,
+    /// for the last step, the "next" row of the evaluation frame is the first row of the trace).
+    /// This is useful for computations in which the trace wraps around, e.g., accumulators which
+    /// must return to their initial value at the end of the trace.
+    pub fn set_cyclic_transitions(mut self) -> Self {
+        self.transition_exemptions = vec![0; self.transition_exemptions.len()];
+        self
+    }
+
+    /// Reserves the last `n` rows of an execution trace for random blinding values.
+    ///
+    /// `frame_width` must be the number of rows in the evaluation frame of the AIR (e.g., 2 for
+    /// [DefaultEvaluationFrame](crate::DefaultEvaluationFrame)). A proof opens every trace
+    /// polynomial at `num_queries` points of the LDE domain and at `frame_width` out-of-domain
+    /// points, and thus, at least `num_queries + frame_width` blinding rows are required for
+    /// each of these openings to be masked by a blinding value.
+    ///
+    /// Blinding rows are excluded from all transition constraints; this includes the transition
+    /// from the last row of the computation into the first blinding row, and thus the number of
+    /// transition exemptions for all constraints is set to `n + 1`. Assertions must not be placed
+    /// against blinding rows. The rows are expected to be filled by the prover, e.g. via
+    /// `TraceTable::randomize_zk()`.
+    ///
+    /// Blinding rows randomize only the trace polynomials; openings of the constraint composition
+    /// polynomial, of the DEEP composition polynomial, and of FRI layers are not randomized.
+    /// Thus, blinding rows alone do not make proofs zero-knowledge.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `n` is smaller than the number of queries specified by the options of this context plus
+    ///   `frame_width`.
+    /// * Given the combination of transition constraints degrees and the blowup factor in this
+    ///   context, `n + 1` exemptions are too many for a valid computation of the constraint
+    ///   composition polynomial.
+    pub fn set_num_blinding_rows(mut self, n: usize, frame_width: usize) -> Self {
+        let min_blinding_rows = self.options.num_queries() + frame_width;
+        assert!(
+            n >= min_blinding_rows,
+            "number of blinding rows must be at least {}, but was {}",
+            min_blinding_rows,
+            n
+        );
+        self = self.set_num_transition_exemptions(n + 1);
+        self.num_blinding_rows = n;
+        self
+    }
+
     /// Write documentation
     pub fn set_vec_transition_exemptions(mut self, exemptions: Vec<usize>) -> Self {
         self.set_transition_exemptions(exemptions);
@@ -52,12 +52,9 @@ impl<B: StarkField> ConstraintDivisor<B> {
     /// domain, and $k$ is the number of exemption points. The default value for $k$ is $1$.
     ///
     /// The above divisor specifies that transition constraints must hold on all steps of the
-    /// execution trace except for the last $k$ steps.
+    /// execution trace except for the last $k$ steps. For cyclic transition constraints $k$ is
+    /// $0$, and thus, the divisor is simply $x^n - 1$.
     pub fn from_transition(trace_length: usize, num_exemptions: usize) -> Self {
-        assert!(
-            num_exemptions > 0,
-            "invalid number of transition exemptions: must be greater than zero"
-        );
         let exemptions = (trace_length - num_exemptions..trace_length)
             .map(|step| get_trace_domain_value_at::<B>(trace_length, step))
             .collect();
@@ -251,6 +248,27 @@ mod tests {
         assert_eq!(expected, div.evaluate_at(BaseElement::new(4)));
     }
 
+    #[test]
+    fn constraint_divisor_from_transition() {
+        let n = 8_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // default transition divisor: (x^8 - 1) / (x - g^7)
+        let divisor = ConstraintDivisor::<BaseElement>::from_transition(n, 1);
+        assert_eq!(7, divisor.degree());
+        assert_eq!(&[g.exp(7)], divisor.exemptions());
+
+        // cyclic transition divisor: x^8 - 1
+        let divisor = ConstraintDivisor::<BaseElement>::from_transition(n, 0);
+        assert_eq!(
+            ConstraintDivisor::new(vec![(n, BaseElement::ONE)], vec![]),
+            divisor
+        );
+        for i in 0..n {
+            assert_eq!(BaseElement::ZERO, divisor.evaluate_at(g.exp(i as u128)));
+        }
+    }
+
     #[test]
     fn constraint_divisor_equivalence() {
         let n = 8_usize;
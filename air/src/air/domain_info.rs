@@ -0,0 +1,66 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use math::{log2, StarkField};
+
+// DOMAIN INFO
+// ================================================================================================
+/// Generators of the domains used by a specific execution of a computation.
+///
+/// Domain info is built once when an [AirContext](crate::AirContext) is instantiated so that
+/// these values do not need to be recomputed every time they are needed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DomainInfo<B: StarkField> {
+    trace_domain_generator: B,
+    lde_domain_generator: B,
+}
+
+impl<B: StarkField> DomainInfo<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns domain info for an execution trace of the specified length extended into the LDE
+    /// domain of the specified size.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Either `trace_length` or `lde_domain_size` is not a power of two.
+    /// * `lde_domain_size` is smaller than `trace_length`.
+    pub fn new(trace_length: usize, lde_domain_size: usize) -> Self {
+        assert!(
+            trace_length.is_power_of_two(),
+            "trace length must be a power of two, but was {}",
+            trace_length
+        );
+        assert!(
+            lde_domain_size.is_power_of_two(),
+            "LDE domain size must be a power of two, but was {}",
+            lde_domain_size
+        );
+        assert!(
+            lde_domain_size >= trace_length,
+            "LDE domain size must be at least {}, but was {}",
+            trace_length,
+            lde_domain_size
+        );
+
+        DomainInfo {
+            trace_domain_generator: B::get_root_of_unity(log2(trace_length)),
+            lde_domain_generator: B::get_root_of_unity(log2(lde_domain_size)),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the generator of the trace domain.
+    pub fn trace_domain_generator(&self) -> &B {
+        &self.trace_domain_generator
+    }
+
+    /// Returns the generator of the low-degree extension domain.
+    pub fn lde_domain_generator(&self) -> &B {
+        &self.lde_domain_generator
+    }
+}
@@ -0,0 +1,108 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use math::{ExtensionOf, FieldElement, StarkField};
+use utils::collections::Vec;
+
+// LAGRANGE KERNEL COLUMN INFO
+// ================================================================================================
+/// Describes an auxiliary trace column designated as a Lagrange kernel column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LagrangeKernelInfo {
+    /// Index of the column across all auxiliary trace segments.
+    pub column: usize,
+    /// Index of the auxiliary segment containing the column.
+    pub segment: usize,
+    /// Index of the first selector column among the periodic columns of the AIR context.
+    pub selectors_offset: usize,
+    /// Number of variables of the kernel (log2 of the trace length).
+    pub num_vars: usize,
+}
+
+// LAGRANGE KERNEL
+// ================================================================================================
+
+/// Returns values of the Lagrange kernel column for the specified point `r`.
+///
+/// The column contains evaluations of the multilinear Lagrange kernel eq(i, r) over the boolean
+/// hypercube of dimension `r.len()`. That is, the value at index $i$ is computed as:
+///
+/// $$
+/// c_i = \prod_{j=0}^{\nu - 1} (i_j \cdot r_j + (1 - i_j) \cdot (1 - r_j))
+/// $$
+///
+/// where $i_j$ is the $j$th bit of $i$ (starting from the least significant bit), and $\nu$ is
+/// the length of `r`. Thus, the length of the returned vector is $2^\nu$.
+///
+/// An inner product of this column with any other column yields an evaluation of the multilinear
+/// extension of the other column at `r`.
+pub fn build_lagrange_kernel_column<E: FieldElement>(r: &[E]) -> Vec<E> {
+    let mut result = Vec::with_capacity(1 << r.len());
+    result.push(E::ONE);
+    for &r_j in r.iter() {
+        let n = result.len();
+        for i in 0..n {
+            let value = result[i];
+            result[i] = value * (E::ONE - r_j);
+            result.push(value * r_j);
+        }
+    }
+    result
+}
+
+/// Returns the value of the k-th Lagrange kernel selector at the specified step.
+///
+/// The k-th selector is set to ONE at steps which have exactly k trailing ones in their binary
+/// representation, and to ZERO otherwise; thus, the selector is periodic with period 2^(k+1).
+pub(crate) fn get_lagrange_kernel_selector<B: StarkField>(k: usize, step: usize) -> B {
+    if step.trailing_ones() as usize == k {
+        B::ONE
+    } else {
+        B::ZERO
+    }
+}
+
+/// Evaluates the transition constraint of a Lagrange kernel column for the specified point `r`.
+///
+/// When moving from step i to step i + 1, where i has k trailing ones, the lowest k bits of
+/// the step flip from ONE to ZERO and bit k flips from ZERO to ONE. Thus, the constraint
+/// enforces:
+///
+/// $$
+/// c_{i+1} \cdot (1 - r_k) \cdot \prod_{j<k} r_j = c_i \cdot r_k \cdot \prod_{j<k} (1 - r_j)
+/// $$
+///
+/// where the value of k is determined by `selectors`.
+pub(crate) fn evaluate_lagrange_kernel_transition<F, E>(
+    current: E,
+    next: E,
+    selectors: &[F],
+    r: &[E],
+) -> E
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    debug_assert_eq!(selectors.len(), r.len());
+    let mut next_factor = E::ZERO;
+    let mut current_factor = E::ZERO;
+
+    let mut prod_r = E::ONE;
+    let mut prod_one_minus_r = E::ONE;
+    for (&selector, &r_k) in selectors.iter().zip(r.iter()) {
+        let one_minus_r_k = E::ONE - r_k;
+        next_factor += (one_minus_r_k * prod_r).mul_base(selector);
+        current_factor += (r_k * prod_one_minus_r).mul_base(selector);
+        prod_r *= r_k;
+        prod_one_minus_r *= one_minus_r_k;
+    }
+
+    next * next_factor - current * current_factor
+}
+
+/// Returns the value of a Lagrange kernel column for point `r` at the first step.
+pub(crate) fn get_lagrange_kernel_first_value<E: FieldElement>(r: &[E]) -> E {
+    r.iter().fold(E::ONE, |acc, &r_j| acc * (E::ONE - r_j))
+}
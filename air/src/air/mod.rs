@@ -5,18 +5,24 @@
 
 use crate::ProofOptions;
 use crypto::{Hasher, RandomCoin, RandomCoinError};
-use math::{fft, ExtensibleField, ExtensionOf, FieldElement, StarkField};
-use utils::{
-    collections::{BTreeMap, Vec},
-    Serializable,
-};
+use math::{fft, polynom, ExtensibleField, ExtensionOf, FieldElement, StarkField};
+use utils::collections::{BTreeMap, Vec};
 
 mod trace_info;
 pub use trace_info::{TraceInfo, TraceLayout};
 
+mod trace_meta;
+pub use trace_meta::{TraceMeta, TraceMetaSchema};
+
 mod context;
 pub use context::AirContext;
 
+mod domain_info;
+pub use domain_info::DomainInfo;
+
+mod public_coin;
+pub use public_coin::{get_public_coin_seed, PublicCoin};
+
 mod assertions;
 pub use assertions::Assertion;
 
@@ -25,8 +31,8 @@ pub use boundary::{BoundaryConstraint, BoundaryConstraintGroup, BoundaryConstrai
 
 mod transition;
 pub use transition::{
-    DefaultEvaluationFrame, EvaluationFrame, TransitionConstraintDegree, TransitionConstraintGroup,
-    TransitionConstraints,
+    select_constraint, DefaultEvaluationFrame, EvaluationFrame, TransitionConstraintDegree,
+    TransitionConstraintGroup, TransitionConstraints,
 };
 
 mod coefficients;
@@ -37,6 +43,10 @@ pub use coefficients::{
 mod divisor;
 pub use divisor::ConstraintDivisor;
 
+mod lagrange;
+pub use lagrange::build_lagrange_kernel_column;
+use lagrange::{evaluate_lagrange_kernel_transition, get_lagrange_kernel_first_value};
+
 #[cfg(test)]
 mod tests;
 
@@ -81,6 +91,8 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// - A list of periodic values. When periodic columns are defined for a computation,
 ///   this will contain values of periodic columns at the current step of the computation.
 ///   Otherwise, this will be an empty list.
+/// - A list of next periodic values. This is similar to the list above, but contains values of
+///   periodic columns at the next step of the computation.
 /// - A mutable `result` slice. This is the slice where constraint evaluations should be
 ///   written to. The length of this slice will be equal to the number of transition
 ///   constraints defined for the computation.
@@ -149,7 +161,9 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// To define such columns for your computation, you can override
 /// [Air::get_periodic_column_values()] method. The values of the periodic columns at a given
 /// step of the computation will be supplied to the [Air::evaluate_transition()] method via the
-/// `periodic_values` parameter.
+/// `periodic_values` parameter. The values of the periodic columns at the next step of the
+/// computation are supplied via the `next_periodic_values` parameter; this allows defining
+/// constraints which depend on periodic values at both steps of a transition.
 ///
 /// ### Randomized AIR
 /// Randomized AIR is a powerful extension of AIR which enables, among other things, multiset and
@@ -185,8 +199,9 @@ pub trait Air: Send + Sync {
     type BaseField: StarkField + ExtensibleField<2> + ExtensibleField<3>;
 
     /// A type defining shape of public inputs for the computation described by this protocol.
-    /// This could be any type as long as it can be serialized into a sequence of bytes.
-    type PublicInputs: Serializable;
+    /// This could be any type as long as it can be reduced to a sequence of base field elements;
+    /// these elements are used to seed the public coin.
+    type PublicInputs: PublicCoin<BaseField = Self::BaseField>;
 
     type Frame<E: FieldElement>: EvaluationFrame<E>;
     type AuxFrame<E: FieldElement>: EvaluationFrame<E>;
@@ -214,6 +229,11 @@ pub trait Air: Send + Sync {
     /// for this AIR. Thus, the length of the `result` slice will equal to the number of
     /// transition constraints defined for this computation.
     ///
+    /// Values of periodic columns at the current step of the computation are passed in via
+    /// `periodic_values` parameter, and values of periodic columns at the next step of the
+    /// computation are passed in via `next_periodic_values` parameter. Both slices are ordered
+    /// in the same way as columns returned from [Air::get_periodic_column_values()].
+    ///
     /// We define type `E` separately from `Self::BaseField` to allow evaluation of constraints
     /// over the out-of-domain evaluation frame, which may be defined over an extension field
     /// (when extension fields are used).
@@ -221,6 +241,7 @@ pub trait Air: Send + Sync {
         &self,
         frame: &Self::Frame<E>,
         periodic_values: &[E],
+        next_periodic_values: &[E],
         result: &mut [E],
     );
 
@@ -260,6 +281,7 @@ pub trait Air: Send + Sync {
         main_frame: &Self::Frame<F>,
         aux_frame: &Self::AuxFrame<E>,
         periodic_values: &[F],
+        next_periodic_values: &[F],
         aux_rand_elements: &AuxTraceRandElements<E>,
         result: &mut [E],
     ) where
@@ -298,13 +320,28 @@ pub trait Air: Send + Sync {
     ///
     /// These values will be used to compute column values at specific states of the computation
     /// and passed in to the [evaluate_transition()](Air::evaluate_transition) method as
-    /// `periodic_values` parameter.
+    /// `periodic_values` and `next_periodic_values` parameters.
     ///
-    /// The default implementation of this method returns an empty vector. For computations which
-    /// rely on periodic columns, this method should be overridden in the specialized
-    /// implementation. Number of values for each periodic column must be a power of two.
+    /// The default implementation of this method returns periodic columns added to the context
+    /// of this AIR via [AirContext::add_periodic_column_fn()] (and thus, an empty vector if no
+    /// columns were added). Alternatively, for computations which rely on periodic columns, this
+    /// method can be overridden in the specialized implementation. Number of values for each
+    /// periodic column must be a power of two.
     fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
-        Vec::new()
+        self.context().periodic_columns().to_vec()
+    }
+
+    /// Returns the schema of execution trace metadata expected by this computation.
+    ///
+    /// When a schema is returned, the verifier parses trace metadata of a proof according to the
+    /// schema, and rejects the proof if the metadata does not conform to it (e.g., if the
+    /// metadata is too long, or if its version is different from the one declared by the
+    /// schema).
+    ///
+    /// The default implementation returns None, meaning that trace metadata is treated as an
+    /// opaque vector of bytes.
+    fn trace_meta_schema(&self) -> Option<TraceMetaSchema> {
+        None
     }
 
     /// Returns polynomial for all periodic columns.
@@ -347,6 +384,125 @@ pub trait Air: Send + Sync {
             .collect()
     }
 
+    /// Returns evaluations of all periodic columns over a domain of the specified size shifted
+    /// by `domain_offset`.
+    ///
+    /// The polynomials returned from [get_periodic_column_polys()](Air::get_periodic_column_polys)
+    /// are evaluated via FFT. Since periodic values repeat over the domain, only a single period
+    /// is computed for each column: for a column with `n` values, the returned vector contains
+    /// `n * domain_size / trace_length` evaluations, and the value at step `i` of the domain is
+    /// located at index `i % len` of this vector.
+    ///
+    /// When `domain_size` is equal to the trace length and `domain_offset` is ONE, the returned
+    /// values are the same as the values of periodic columns over the execution trace.
+    ///
+    /// # Panics
+    /// Panics if `domain_size` is not a power of two or is smaller than the trace length.
+    fn get_periodic_column_evaluations(
+        &self,
+        domain_size: usize,
+        domain_offset: Self::BaseField,
+    ) -> Vec<Vec<Self::BaseField>> {
+        assert!(
+            domain_size.is_power_of_two(),
+            "domain size must be a power of two, but was {}",
+            domain_size
+        );
+        assert!(
+            domain_size >= self.trace_length(),
+            "domain size must be at least trace length {}, but was {}",
+            self.trace_length(),
+            domain_size
+        );
+        let blowup_factor = domain_size / self.trace_length();
+
+        // cache twiddles for each cycle length so that we don't have to re-build them for
+        // columns with identical cycle lengths
+        let mut twiddle_map = BTreeMap::new();
+        self.get_periodic_column_polys()
+            .iter()
+            .map(|poly| {
+                let poly_size = poly.len();
+                let num_cycles = (self.trace_length() / poly_size) as u64;
+                let offset = domain_offset.exp(num_cycles.into());
+                let twiddles = twiddle_map
+                    .entry(poly_size)
+                    .or_insert_with(|| fft::get_twiddles::<Self::BaseField>(poly_size));
+                fft::evaluate_poly_with_offset(poly, twiddles, offset, blowup_factor)
+            })
+            .collect()
+    }
+
+    /// Returns values of all periodic columns at the specified point `x`.
+    ///
+    /// Unlike [get_periodic_column_evaluations()](Air::get_periodic_column_evaluations), `x`
+    /// does not need to belong to any evaluation domain; this is used, for example, to compute
+    /// periodic values at the out-of-domain point.
+    fn get_periodic_column_values_at<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        x: E,
+    ) -> Vec<E> {
+        self.get_periodic_column_polys()
+            .iter()
+            .map(|poly| {
+                let num_cycles = (self.trace_length() / poly.len()) as u32;
+                polynom::eval(poly, x.exp(num_cycles.into()))
+            })
+            .collect()
+    }
+
+    /// Evaluates transition constraints for the main trace segment at an arbitrary point `x`
+    /// and divides each evaluation by the divisor of the corresponding constraint.
+    ///
+    /// The first and the second rows of the `frame` are assumed to contain values of trace
+    /// polynomials at `x` and `x * g` respectively, where `g` is the generator of the trace
+    /// domain. Periodic column values at `x` and `x * g` are computed internally. The returned
+    /// vector contains one quotient per main transition constraint, in the same order as the
+    /// constraint degree descriptors used to instantiate [AirContext] for this AIR.
+    ///
+    /// The random linear combination of these quotients (with degree adjustment) is the value
+    /// which the verifier computes via [TransitionConstraints::combine_evaluations()] when `x`
+    /// is the out-of-domain point.
+    ///
+    /// # Panics
+    /// Panics if `x` is in the trace domain and is not exempt from any of the constraints (i.e.,
+    /// if the divisor of some constraint evaluates to zero at `x`).
+    fn evaluate_transition_at<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &Self::Frame<E>,
+        x: E,
+    ) -> Vec<E> {
+        let periodic_values = self.get_periodic_column_values_at(x);
+        let x_next = x * E::from(self.trace_domain_generator());
+        let next_periodic_values = self.get_periodic_column_values_at(x_next);
+
+        let context = self.context();
+        let mut evaluations = E::zeroed_vector(context.num_main_transition_constraints());
+        self.evaluate_transition(
+            frame,
+            &periodic_values,
+            &next_periodic_values,
+            &mut evaluations,
+        );
+
+        evaluations
+            .into_iter()
+            .zip(context.transition_exemptions.iter())
+            .map(|(evaluation, &num_exemptions)| {
+                let divisor =
+                    ConstraintDivisor::from_transition(self.trace_length(), num_exemptions);
+                let divisor_value = divisor.evaluate_at(x);
+                assert!(
+                    divisor_value != E::ZERO,
+                    "constraint divisor {} evaluates to zero at {}",
+                    divisor,
+                    x
+                );
+                evaluation / divisor_value
+            })
+            .collect()
+    }
+
     /// Groups transition constraints together by their degree.
     ///
     /// This function also assigns composition coefficients to each constraint. These coefficients
@@ -371,14 +527,64 @@ pub trait Air: Send + Sync {
         aux_rand_elements: &AuxTraceRandElements<E>,
         composition_coefficients: &[(E, E)],
     ) -> BoundaryConstraints<E> {
+        let mut aux_assertions = self.get_aux_assertions(aux_rand_elements);
+        if let Some(assertion) = self.get_lagrange_kernel_aux_assertion(aux_rand_elements) {
+            aux_assertions.push(assertion);
+        }
+
         BoundaryConstraints::new(
             self.context(),
             self.get_assertions(),
-            self.get_aux_assertions(aux_rand_elements),
+            aux_assertions,
             composition_coefficients,
         )
     }
 
+    // LAGRANGE KERNEL CONSTRAINTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates the transition constraint of the Lagrange kernel column designated via
+    /// [AirContext::set_lagrange_kernel_aux_column()] and writes the result into the last slot
+    /// of the `result` slice.
+    ///
+    /// If no Lagrange kernel column has been designated, this method does nothing. The first
+    /// and the second rows of the auxiliary evaluation frame are assumed to contain the current
+    /// and the next states of the computation respectively. `periodic_values` and `result` are
+    /// the same as the ones passed to [Air::evaluate_aux_transition()].
+    fn evaluate_lagrange_kernel_aux_transition<F, E>(
+        &self,
+        aux_frame: &Self::AuxFrame<E>,
+        periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        if let Some(info) = self.context().lagrange_kernel.as_ref() {
+            let r = &aux_rand_elements.get_segment_elements(info.segment)[..info.num_vars];
+            let selectors =
+                &periodic_values[info.selectors_offset..info.selectors_offset + info.num_vars];
+            let current = aux_frame.row(0)[info.column];
+            let next = aux_frame.row(1)[info.column];
+            result[result.len() - 1] =
+                evaluate_lagrange_kernel_transition(current, next, selectors, r);
+        }
+    }
+
+    /// Returns an assertion against the first step of the Lagrange kernel column designated via
+    /// [AirContext::set_lagrange_kernel_aux_column()], or None if no such column has been
+    /// designated.
+    fn get_lagrange_kernel_aux_assertion<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Option<Assertion<E>> {
+        self.context().lagrange_kernel.as_ref().map(|info| {
+            let r = &aux_rand_elements.get_segment_elements(info.segment)[..info.num_vars];
+            Assertion::single(info.column, 0, get_lagrange_kernel_first_value(r))
+        })
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -416,12 +622,20 @@ pub trait Air: Send + Sync {
         self.context().trace_poly_degree()
     }
 
+    /// Returns generators of the trace and LDE domains for an instance of the computation
+    /// described by this AIR.
+    ///
+    /// These are computed once when the [AirContext] for this AIR is instantiated.
+    fn domain_info(&self) -> &DomainInfo<Self::BaseField> {
+        self.context().domain_info()
+    }
+
     /// Returns the generator of the trace domain for an instance of the computation described
     /// by this AIR.
     ///
     /// The generator is the $n$th root of unity where $n$ is the length of the execution trace.
     fn trace_domain_generator(&self) -> Self::BaseField {
-        self.context().trace_domain_generator
+        *self.context().domain_info.trace_domain_generator()
     }
 
     /// Returns constraint evaluation domain blowup factor for the computation described by this
@@ -446,11 +660,22 @@ pub trait Air: Send + Sync {
     /// Returns the degree to which all constraint polynomials are normalized before they are
     /// composed together.
     ///
-    /// This degree is one less than the size of constraint evaluation domain.
+    /// Unless a composition degree hint was provided via
+    /// [AirContext::set_composition_degree_hint()], this degree is one less than the size of
+    /// constraint evaluation domain.
     fn composition_degree(&self) -> usize {
         self.context().composition_degree()
     }
 
+    /// Returns the number of columns into which the constraint composition polynomial is split.
+    ///
+    /// Each column is a polynomial of degree `trace_length` - 1; unless a composition degree hint
+    /// was provided via [AirContext::set_composition_degree_hint()], the number of columns is
+    /// equal to `ce_blowup_factor`.
+    fn num_composition_columns(&self) -> usize {
+        self.context().num_composition_columns()
+    }
+
     /// Returns low-degree extension domain blowup factor for the computation described by this
     /// AIR. This is guaranteed to be a power of two, and is always either equal to or greater
     /// than ce_blowup_factor.
@@ -471,7 +696,7 @@ pub trait Air: Send + Sync {
     /// The generator is the $n$th root of unity where $n$ is the size of the low-degree extension
     /// domain.
     fn lde_domain_generator(&self) -> Self::BaseField {
-        self.context().lde_domain_generator
+        *self.context().domain_info.lde_domain_generator()
     }
 
     /// Returns the offset by which the domain for low-degree extension is shifted in relation
@@ -563,9 +788,8 @@ pub trait Air: Send + Sync {
             t_coefficients.push(values);
         }
 
-        // self.ce_blowup_factor() is the same as number of composition columns
         let mut c_coefficients = Vec::new();
-        for _ in 0..self.ce_blowup_factor() {
+        for _ in 0..self.num_composition_columns() {
             c_coefficients.push(public_coin.draw()?);
         }
 
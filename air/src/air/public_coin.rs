@@ -0,0 +1,52 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use math::StarkField;
+use utils::collections::Vec;
+
+// PUBLIC COIN
+// ================================================================================================
+/// Defines how public inputs of a computation are absorbed into the public coin.
+///
+/// Public inputs are reduced to a sequence of base field elements which are then hashed (together
+/// with any extra seed elements) via
+/// [ElementHasher::hash_elements()](crypto::ElementHasher::hash_elements) to get the initial seed
+/// of the public coin. For algebraic hash functions (e.g., Pedersen), this is much cheaper to
+/// replicate inside a recursive verifier than hashing a byte serialization of the public inputs.
+///
+/// The trait is implemented for all [StarkField] elements, so a single base field element can be
+/// used as public inputs directly.
+pub trait PublicCoin {
+    /// Base field in which the public inputs are expressed.
+    type BaseField: StarkField;
+
+    /// Returns the public inputs as a sequence of base field elements.
+    fn to_elements(&self) -> Vec<Self::BaseField>;
+}
+
+impl<B: StarkField> PublicCoin for B {
+    type BaseField = B;
+
+    fn to_elements(&self) -> Vec<B> {
+        vec![*self]
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the elements with which the public coin is seeded for the specified public inputs:
+/// these are the elements of the public inputs followed by the `extra_seed` elements.
+///
+/// Both the prover and the verifier use this function to instantiate their public coins, which
+/// ensures that they start from the same state.
+pub fn get_public_coin_seed<P: PublicCoin>(
+    pub_inputs: &P,
+    extra_seed: &[P::BaseField],
+) -> Vec<P::BaseField> {
+    let mut seed = pub_inputs.to_elements();
+    seed.extend_from_slice(extra_seed);
+    seed
+}
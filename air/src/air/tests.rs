@@ -4,13 +4,21 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    Air, AirContext, Assertion, DefaultEvaluationFrame, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    build_lagrange_kernel_column,
+    lagrange::{
+        evaluate_lagrange_kernel_transition, get_lagrange_kernel_first_value,
+        get_lagrange_kernel_selector,
+    },
+    Air, AirContext, Assertion, DefaultEvaluationFrame, EvaluationFrame, ProofOptions, TraceInfo,
+    TraceLayout, TraceMeta, TraceMetaSchema, TransitionConstraintDegree,
 };
-use crate::{AuxTraceRandElements, FieldExtension, HashFunction};
+use crate::{AuxTraceRandElements, FieldExtension, HashFunction, Table, TraceMetaError};
 use crypto::{hashers::Blake3_256, RandomCoin};
 use math::{fields::f128::BaseElement, get_power_series, log2, polynom, FieldElement, StarkField};
-use utils::collections::{BTreeMap, Vec};
+use utils::{
+    collections::{BTreeMap, Vec},
+    ByteReader, Deserializable, Serializable, SliceReader,
+};
 
 // PERIODIC COLUMNS
 // ================================================================================================
@@ -56,6 +64,345 @@ fn get_periodic_column_polys_num_values_not_power_of_two() {
     assert_eq!(0, column_polys.len());
 }
 
+#[test]
+fn get_periodic_column_evaluations() {
+    let trace_length = 16;
+    let col1 = vec![BaseElement::ONE, BaseElement::ZERO];
+    let col2 = vec![
+        BaseElement::new(3),
+        BaseElement::ZERO,
+        BaseElement::ONE,
+        BaseElement::new(7),
+    ];
+    let air = MockAir::with_periodic_columns(vec![col1.clone(), col2.clone()], trace_length);
+    let column_polys = air.get_periodic_column_polys();
+
+    // over the trace domain, evaluations are the same as periodic column values
+    let evaluations = air.get_periodic_column_evaluations(trace_length, BaseElement::ONE);
+    assert_eq!(vec![col1, col2], evaluations);
+
+    // over a shifted domain, evaluations are the same as evaluating polynomials step by step
+    let domain_size = trace_length * 8;
+    let g = BaseElement::get_root_of_unity(log2(domain_size));
+    let evaluations = air.get_periodic_column_evaluations(domain_size, air.domain_offset());
+    for (poly, column) in column_polys.iter().zip(evaluations.iter()) {
+        assert_eq!(poly.len() * 8, column.len());
+        let num_cycles = (trace_length / poly.len()) as u64;
+        for step in 0..domain_size {
+            let x = (air.domain_offset() * g.exp((step as u64).into())).exp(num_cycles.into());
+            assert_eq!(polynom::eval(poly, x), column[step % column.len()]);
+        }
+    }
+}
+
+#[test]
+fn add_periodic_column_fn() {
+    let trace_length = 16;
+    let f = |step: usize| BaseElement::new((step * step + 1) as u128);
+
+    let mut air = MockAir::with_periodic_columns(vec![], trace_length);
+    air.context = air.context.clone().add_periodic_column_fn(4, f);
+    assert_eq!(
+        vec![(0..4).map(f).collect::<Vec<_>>()],
+        air.context().periodic_columns()
+    );
+
+    let column_polys = air.get_periodic_column_polys();
+    assert_eq!(1, column_polys.len());
+    assert_eq!(4, column_polys[0].len());
+
+    // periodic polynomials are evaluated at x^(trace_length / period)
+    let g = BaseElement::get_root_of_unity(log2(trace_length));
+    for step in 0..trace_length {
+        let x = g.exp(((step * trace_length / 4) as u128).into());
+        assert_eq!(f(step % 4), polynom::eval(&column_polys[0], x));
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "number of values in a periodic column must be a power of two, but was 3"
+)]
+fn add_periodic_column_fn_not_power_of_two() {
+    build_context::<BaseElement>(16, 4, 1).add_periodic_column_fn(3, |_| BaseElement::ONE);
+}
+
+// LAGRANGE KERNEL
+// ================================================================================================
+
+#[test]
+fn lagrange_kernel_column() {
+    let r = [3, 5, 7, 11].map(BaseElement::new);
+    let column = build_lagrange_kernel_column(&r);
+    assert_eq!(16, column.len());
+
+    // each value is a product of r_j or (1 - r_j) depending on the bits of the step
+    for (step, &value) in column.iter().enumerate() {
+        let expected = r
+            .iter()
+            .enumerate()
+            .fold(BaseElement::ONE, |acc, (j, &r_j)| {
+                if (step >> j) & 1 == 1 {
+                    acc * r_j
+                } else {
+                    acc * (BaseElement::ONE - r_j)
+                }
+            });
+        assert_eq!(expected, value);
+    }
+    assert_eq!(column[0], get_lagrange_kernel_first_value(&r));
+
+    // transition constraint is satisfied by all consecutive values in the column, but not by
+    // values which were tampered with
+    for step in 0..column.len() - 1 {
+        let selectors = (0..r.len())
+            .map(|k| get_lagrange_kernel_selector::<BaseElement>(k, step))
+            .collect::<Vec<_>>();
+        let evaluation =
+            evaluate_lagrange_kernel_transition(column[step], column[step + 1], &selectors, &r);
+        assert_eq!(BaseElement::ZERO, evaluation);
+
+        let next = column[step + 1] + BaseElement::ONE;
+        let evaluation = evaluate_lagrange_kernel_transition(column[step], next, &selectors, &r);
+        assert_ne!(BaseElement::ZERO, evaluation);
+    }
+}
+
+#[test]
+fn set_lagrange_kernel_aux_column() {
+    let context =
+        build_multi_segment_context::<BaseElement>(16, 5).set_lagrange_kernel_aux_column(1);
+    assert_eq!(Some(1), context.lagrange_kernel_aux_column());
+    assert_eq!(2, context.num_aux_transition_constraints());
+    assert_eq!(3, context.num_assertions());
+    assert_eq!(vec![1; 3], context.num_transition_exemptions());
+
+    // one selector column is added for each variable of the kernel
+    let periods = context
+        .periodic_columns()
+        .iter()
+        .map(|column| column.len())
+        .collect::<Vec<_>>();
+    assert_eq!(vec![2, 4, 8, 16], periods);
+}
+
+#[test]
+#[should_panic(
+    expected = "a Lagrange kernel column requires at least 4 random elements, but auxiliary segment 0 has 3"
+)]
+fn set_lagrange_kernel_aux_column_not_enough_rand_elements() {
+    build_multi_segment_context::<BaseElement>(16, 3).set_lagrange_kernel_aux_column(0);
+}
+
+// AUXILIARY ASSERTIONS
+// ================================================================================================
+
+#[test]
+fn aux_assertion() {
+    let options = ProofOptions::new(
+        32,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+    let layout = TraceLayout::new(4, &[2, 3], &[1, 1]);
+    let trace_info = TraceInfo::new_multi_segment(layout.clone(), 16, Vec::new());
+    let main_degrees = vec![TransitionConstraintDegree::new(2)];
+    let aux_degrees = vec![TransitionConstraintDegree::new(2)];
+    let context = AirContext::<BaseElement>::new_multi_segment(
+        trace_info,
+        main_degrees,
+        aux_degrees,
+        1,
+        2,
+        options,
+    );
+
+    // columns of the second segment are placed after the columns of the first segment
+    let value = BaseElement::new(7);
+    let a = context.aux_assertion(0, 1, 3, value);
+    let b = context.aux_assertion(1, 2, 15, value);
+    assert_eq!(Assertion::single(1, 3, value), a);
+    assert_eq!(Assertion::single(4, 15, value), b);
+
+    // global column indexes are remapped back to the original segments and columns
+    assert_eq!((0, 1), layout.get_aux_segment_column(a.column()));
+    assert_eq!((1, 2), layout.get_aux_segment_column(b.column()));
+    for segment in 0..layout.num_aux_segments() {
+        for column in 0..layout.get_aux_segment_width(segment) {
+            let global = layout.get_aux_column_index(segment, column);
+            assert_eq!((segment, column), layout.get_aux_segment_column(global));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "column index for auxiliary segment 0 must be smaller than 2, but was 2")]
+fn aux_assertion_column_out_of_bounds() {
+    let context = build_multi_segment_context::<BaseElement>(16, 5);
+    context.aux_assertion(0, 2, 0, BaseElement::ONE);
+}
+
+#[test]
+#[should_panic(expected = "auxiliary column index must be smaller than 2, but was 2")]
+fn aux_segment_column_out_of_bounds() {
+    TraceLayout::new(4, &[2], &[1]).get_aux_segment_column(2);
+}
+
+#[test]
+fn segment_boundaries_single_segment() {
+    let layout = TraceLayout::new(4, &[], &[]);
+    assert_eq!(vec![0..4], layout.segment_boundaries());
+    for column in 0..4 {
+        assert_eq!(0, layout.column_segment(column));
+    }
+}
+
+#[test]
+fn segment_boundaries_multi_segment() {
+    // the trailing empty segment is not a part of the layout
+    let layout = TraceLayout::new(4, &[2, 3, 0], &[1, 2, 0]);
+    assert_eq!(vec![0..4, 4..6, 6..9], layout.segment_boundaries());
+
+    let segments = (0..9)
+        .map(|column| layout.column_segment(column))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![0, 0, 0, 0, 1, 1, 2, 2, 2], segments);
+
+    // global column indexes of auxiliary segments are consistent with auxiliary column indexes
+    for segment in 0..layout.num_aux_segments() {
+        for column in 0..layout.get_aux_segment_width(segment) {
+            let global = layout.main_trace_width() + layout.get_aux_column_index(segment, column);
+            assert_eq!(segment + 1, layout.column_segment(global));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "column index must be smaller than 6, but was 6")]
+fn column_segment_out_of_bounds() {
+    TraceLayout::new(4, &[2], &[1]).column_segment(6);
+}
+
+#[test]
+fn empty_aux_segments() {
+    // empty auxiliary segments are skipped
+    let layout = TraceLayout::new(4, &[0, 0], &[0, 0]);
+    assert_eq!(0, layout.num_aux_segments());
+    assert_eq!(0, layout.aux_trace_width());
+    assert_eq!(TraceLayout::new(4, &[], &[]), layout);
+    assert!(!TraceInfo::new_multi_segment(layout.clone(), 16, vec![]).is_multi_segment());
+
+    let layout = TraceLayout::new(4, &[2, 0], &[1, 0]);
+    assert_eq!(1, layout.num_aux_segments());
+    assert_eq!(2, layout.aux_trace_width());
+    assert_eq!(TraceLayout::new(4, &[2], &[1]), layout);
+
+    // layouts with empty segments survive a serialization round trip
+    for layout in [TraceLayout::new(4, &[0], &[0]), layout] {
+        let bytes = layout.to_bytes();
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(layout, TraceLayout::read_from(&mut reader).unwrap());
+        assert!(!reader.has_more_bytes());
+    }
+}
+
+// DOMAIN INFO
+// ================================================================================================
+
+#[test]
+fn domain_info() {
+    let trace_length = 16;
+    let air = MockAir::with_periodic_columns(vec![], trace_length);
+    let domain_info = air.domain_info();
+
+    let g = BaseElement::get_root_of_unity(log2(trace_length));
+    assert_eq!(&g, domain_info.trace_domain_generator());
+    assert_eq!(g, air.trace_domain_generator());
+
+    let g_lde = BaseElement::get_root_of_unity(log2(air.lde_domain_size()));
+    assert_eq!(&g_lde, domain_info.lde_domain_generator());
+    assert_eq!(g_lde, air.lde_domain_generator());
+}
+
+// TRACE METADATA
+// ================================================================================================
+
+#[test]
+fn trace_meta_round_trip() {
+    let meta = TraceMeta::new(1)
+        .with_field(&[1, 2, 3])
+        .with_field(&[])
+        .with_field(&[4; 300]);
+    let trace_info = TraceInfo::with_meta(4, 16, meta.to_bytes());
+
+    let schema = TraceMetaSchema::new(1, 3);
+    let parsed = schema.parse(&trace_info).unwrap();
+    assert_eq!(meta, parsed);
+    assert_eq!(Some(&[1u8, 2, 3][..]), parsed.get_field(0));
+    assert_eq!(Some(&[][..]), parsed.get_field(1));
+    assert_eq!(Some(&[4u8; 300][..]), parsed.get_field(2));
+    assert_eq!(None, parsed.get_field(3));
+
+    // metadata with a different version or number of fields does not conform to the schema
+    assert_eq!(
+        Err(TraceMetaError::VersionMismatch(2, 1)),
+        TraceMetaSchema::new(2, 3).parse(&trace_info)
+    );
+    assert_eq!(
+        Err(TraceMetaError::NumFieldsMismatch(2, 3)),
+        TraceMetaSchema::new(1, 2).parse(&trace_info)
+    );
+
+    // opaque metadata which is not a valid serialization of trace metadata is rejected
+    let mut bytes = meta.to_bytes();
+    bytes.push(0);
+    let trace_info = TraceInfo::with_meta(4, 16, bytes);
+    assert!(matches!(
+        schema.parse(&trace_info),
+        Err(TraceMetaError::MalformedMeta(_))
+    ));
+}
+
+#[test]
+fn trace_meta_too_long() {
+    let meta = TraceMeta::new(1).with_field(&[7; 100]);
+    let trace_info = TraceInfo::with_meta(4, 16, meta.to_bytes());
+    let meta_len = trace_info.meta().len();
+    assert_eq!(104, meta_len);
+
+    assert_eq!(Ok(()), trace_info.validate_meta(meta_len));
+    assert_eq!(
+        Err(TraceMetaError::MetaTooLong(64, meta_len)),
+        trace_info.validate_meta(64)
+    );
+
+    let schema = TraceMetaSchema::new(1, 1).with_max_length(64);
+    assert_eq!(
+        Err(TraceMetaError::MetaTooLong(64, meta_len)),
+        schema.parse(&trace_info)
+    );
+}
+
+// EVALUATION FRAME
+// ================================================================================================
+
+#[test]
+fn select_frame_columns() {
+    let current = (0..6u32).map(BaseElement::from).collect::<Vec<_>>();
+    let next = (6..12u32).map(BaseElement::from).collect::<Vec<_>>();
+    let frame = DefaultEvaluationFrame::from_table(Table::from_rows(vec![current, next]));
+
+    // columns of the selected frame are indexed locally
+    let local_frame = frame.select_columns(2..5);
+    assert_eq!(&frame.current()[2..5], local_frame.current());
+    assert_eq!(&frame.next()[2..5], local_frame.next());
+    assert_eq!(frame.current()[2], local_frame.current()[0]);
+}
+
 // TRANSITION CONSTRAINTS
 // ================================================================================================
 
@@ -217,6 +564,56 @@ fn get_boundary_constraints() {
     assert_eq!(expected_cc[&7], constraint.cc().clone());
 }
 
+// COMPOSITION DEGREE
+// ================================================================================================
+
+#[test]
+fn set_composition_degree_hint() {
+    // a degree 4 constraint requires a constraint evaluation domain blowup of 4, but once divided
+    // by its divisor, it has degree 3 * 15 = 45, and thus, fits into 3 composition columns
+    let options = build_context::<BaseElement>(16, 4, 1).options;
+    let t_degrees = vec![TransitionConstraintDegree::new(4)];
+    let context = AirContext::<BaseElement>::new(TraceInfo::new(4, 16), t_degrees, 1, options);
+    assert_eq!(4, context.num_composition_columns());
+    assert_eq!(63, context.composition_degree());
+
+    let hinted = context.clone().set_composition_degree_hint(45);
+    assert_eq!(3, hinted.num_composition_columns());
+    assert_eq!(47, hinted.composition_degree());
+    assert_eq!(context.ce_domain_size(), hinted.ce_domain_size());
+
+    // hints which do not reduce the number of columns are ignored
+    let hinted = context.set_composition_degree_hint(100);
+    assert_eq!(4, hinted.num_composition_columns());
+    assert_eq!(63, hinted.composition_degree());
+}
+
+#[test]
+#[should_panic(expected = "composition degree hint is too low; expected at least 45, but was 44")]
+fn set_composition_degree_hint_too_low() {
+    let options = build_context::<BaseElement>(16, 4, 1).options;
+    let t_degrees = vec![TransitionConstraintDegree::new(4)];
+    AirContext::<BaseElement>::new(TraceInfo::new(4, 16), t_degrees, 1, options)
+        .set_composition_degree_hint(44);
+}
+
+// BLINDING ROWS
+// ================================================================================================
+
+#[test]
+fn set_num_blinding_rows() {
+    // 32 queries and an evaluation frame of 2 rows require at least 34 blinding rows
+    let context = build_context::<BaseElement>(128, 4, 1).set_num_blinding_rows(34, 2);
+    assert_eq!(34, context.num_blinding_rows());
+    assert_eq!(vec![35], context.num_transition_exemptions());
+}
+
+#[test]
+#[should_panic(expected = "number of blinding rows must be at least 34, but was 33")]
+fn set_num_blinding_rows_too_few() {
+    build_context::<BaseElement>(128, 4, 1).set_num_blinding_rows(33, 2);
+}
+
 // MOCK AIR
 // ================================================================================================
 
@@ -233,7 +630,7 @@ impl MockAir {
     ) -> Self {
         let mut result = Self::new(
             TraceInfo::with_meta(4, trace_length, vec![1]),
-            (),
+            BaseElement::ZERO,
             ProofOptions::new(
                 32,
                 8,
@@ -251,7 +648,7 @@ impl MockAir {
     pub fn with_assertions(assertions: Vec<Assertion<BaseElement>>, trace_length: usize) -> Self {
         let mut result = Self::new(
             TraceInfo::with_meta(4, trace_length, vec![assertions.len() as u8]),
-            (),
+            BaseElement::ZERO,
             ProofOptions::new(
                 32,
                 8,
@@ -269,11 +666,11 @@ impl MockAir {
 
 impl Air for MockAir {
     type BaseField = BaseElement;
-    type PublicInputs = ();
+    type PublicInputs = BaseElement;
     type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
     type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
 
-    fn new(trace_info: TraceInfo, _pub_inputs: (), _options: ProofOptions) -> Self {
+    fn new(trace_info: TraceInfo, _pub_inputs: BaseElement, _options: ProofOptions) -> Self {
         let num_assertions = trace_info.meta()[0] as usize;
         let context = build_context(trace_info.length(), trace_info.width(), num_assertions);
         MockAir {
@@ -288,7 +685,10 @@ impl Air for MockAir {
     }
 
     fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
-        self.periodic_columns.clone()
+        // columns added to the context come first, followed by explicitly provided columns
+        let mut result = self.context.periodic_columns().to_vec();
+        result.extend(self.periodic_columns.iter().cloned());
+        result
     }
 
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
@@ -299,11 +699,87 @@ impl Air for MockAir {
         &self,
         _frame: &Self::Frame<E>,
         _periodic_values: &[E],
+        _next_periodic_values: &[E],
         _result: &mut [E],
     ) {
     }
 }
 
+// DOMAIN OFFSET
+// ================================================================================================
+
+#[test]
+fn domain_offset() {
+    let options = build_context::<BaseElement>(16, 4, 1).options;
+    assert_eq!(
+        BaseElement::GENERATOR,
+        options.domain_offset::<BaseElement>()
+    );
+    assert_eq!(
+        BaseElement::GENERATOR,
+        options.to_fri_options().domain_offset::<BaseElement>()
+    );
+
+    // a custom offset is applied to both trace and FRI domains, and survives serialization
+    let options = options.with_domain_offset(5);
+    assert_eq!(BaseElement::new(5), options.domain_offset::<BaseElement>());
+    assert_eq!(
+        BaseElement::new(5),
+        options.to_fri_options().domain_offset::<BaseElement>()
+    );
+    let parsed = ProofOptions::read_from(&mut SliceReader::new(&options.to_bytes())).unwrap();
+    assert_eq!(options, parsed);
+
+    let trace_info = TraceInfo::new(4, 16);
+    let t_degrees = vec![TransitionConstraintDegree::new(2)];
+    let context = AirContext::<BaseElement>::new(trace_info, t_degrees, 1, options);
+    assert_eq!(
+        BaseElement::new(5),
+        context.options.domain_offset::<BaseElement>()
+    );
+}
+
+#[test]
+#[should_panic(expected = "domain offset cannot be an element of the LDE domain of size 128")]
+fn domain_offset_in_lde_domain() {
+    let options = build_context::<BaseElement>(16, 4, 1)
+        .options
+        .with_domain_offset(1);
+    let trace_info = TraceInfo::new(4, 16);
+    let t_degrees = vec![TransitionConstraintDegree::new(2)];
+    AirContext::<BaseElement>::new(trace_info, t_degrees, 1, options);
+}
+
+#[test]
+fn domain_offset_validity() {
+    use math::fields::f64::BaseElement as Felt64;
+
+    let options = build_context::<BaseElement>(16, 4, 1).options;
+    assert!(options.is_valid_domain_offset::<BaseElement>(128));
+    assert!(!options
+        .clone()
+        .with_domain_offset(1)
+        .is_valid_domain_offset::<BaseElement>(128));
+
+    // the modulus of the 64-bit field maps to zero in that field, but not in the 128-bit field
+    let options = options.with_domain_offset(Felt64::MODULUS);
+    assert!(options.is_valid_domain_offset::<BaseElement>(128));
+    assert!(!options.is_valid_domain_offset::<Felt64>(128));
+}
+
+#[test]
+#[should_panic(expected = "domain offset cannot be zero in the base field")]
+fn domain_offset_zero_in_base_field() {
+    use math::fields::f64::BaseElement as Felt64;
+
+    let options = build_context::<Felt64>(16, 4, 1)
+        .options
+        .with_domain_offset(Felt64::MODULUS);
+    let trace_info = TraceInfo::new(4, 16);
+    let t_degrees = vec![TransitionConstraintDegree::new(2)];
+    AirContext::<Felt64>::new(trace_info, t_degrees, 1, options);
+}
+
 // UTILITY FUNCTIONS
 // ================================================================================================
 
@@ -326,6 +802,26 @@ pub fn build_context<B: StarkField>(
     AirContext::new(trace_info, t_degrees, num_assertions, options)
 }
 
+pub fn build_multi_segment_context<B: StarkField>(
+    trace_length: usize,
+    num_aux_rand_elements: usize,
+) -> AirContext<B> {
+    let options = ProofOptions::new(
+        32,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+    let main_degrees = vec![TransitionConstraintDegree::new(2)];
+    let aux_degrees = vec![TransitionConstraintDegree::new(2)];
+    let layout = TraceLayout::new(4, &[2], &[num_aux_rand_elements]);
+    let trace_info = TraceInfo::new_multi_segment(layout, trace_length, Vec::new());
+    AirContext::new_multi_segment(trace_info, main_degrees, aux_degrees, 1, 1, options)
+}
+
 pub fn build_prng() -> RandomCoin<BaseElement, Blake3_256<BaseElement>> {
     RandomCoin::new(&[0; 32])
 }
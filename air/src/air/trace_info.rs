@@ -3,6 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::errors::TraceMetaError;
+use core::ops::Range;
 use utils::{
     collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
     DeserializationError, Serializable,
@@ -15,7 +17,7 @@ use utils::{
 /// Trace info consists of trace layout info, length, and optional custom metadata. Trace layout
 /// specifies the number of columns for all trace segments. Currently, a trace can consist of at
 /// most two segments. Metadata is just a vector of bytes and can store any values up to 64KB in
-/// size.
+/// size; [TraceMeta](crate::TraceMeta) can be used to give these bytes a versioned structure.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct TraceInfo {
     layout: TraceLayout,
@@ -128,6 +130,23 @@ impl TraceInfo {
     pub fn is_multi_segment(&self) -> bool {
         self.layout.num_aux_segments > 0
     }
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that execution trace metadata is no longer than `max_length` bytes.
+    ///
+    /// This can be used to enforce a stricter limit on the metadata than the one imposed by
+    /// [TraceInfo::MAX_META_LENGTH].
+    ///
+    /// # Errors
+    /// Returns an error if the metadata is longer than `max_length` bytes.
+    pub fn validate_meta(&self, max_length: usize) -> Result<(), TraceMetaError> {
+        if self.meta.len() > max_length {
+            return Err(TraceMetaError::MetaTooLong(max_length, self.meta.len()));
+        }
+        Ok(())
+    }
 }
 
 // TRACE LAYOUT
@@ -148,6 +167,10 @@ impl TraceInfo {
 /// The number of random elements may be different from the number of columns in a given auxiliary
 /// segment. For example, an auxiliary segment may contain just one column, but may require many
 /// random elements.
+///
+/// Columns whose values do not change between proofs (e.g., selectors or lookup tables) must be
+/// placed in the main segment, and thus, are committed to as a part of every proof. The cost of
+/// interpolating such columns can be avoided by supplying a `TracePolyCache` to the prover.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct TraceLayout {
     main_segment_width: usize,
@@ -161,6 +184,9 @@ impl TraceLayout {
     // --------------------------------------------------------------------------------------------
     /// Returns a new [TraceLayout] instantiated with the provided info.
     ///
+    /// Auxiliary segments of zero width are skipped: they are not counted towards the number of
+    /// auxiliary segments, and do not contribute any columns to the trace.
+    ///
     /// # Panics
     /// Panics if:
     /// * Width of the main trace segment is set to zero.
@@ -212,10 +238,12 @@ impl TraceLayout {
             );
         }
 
+        // empty segments can appear only at the end of the layout and do not contain any
+        // columns; thus, they are not retained
         Self {
             main_segment_width: main_width,
-            aux_segment_widths: aux_widths.to_vec(),
-            aux_segment_rands: aux_rands.to_vec(),
+            aux_segment_widths: aux_widths[..num_aux_segments].to_vec(),
+            aux_segment_rands: aux_rands[..num_aux_segments].to_vec(),
             num_aux_segments,
         }
     }
@@ -257,6 +285,93 @@ impl TraceLayout {
         // TODO: panic if segment_idx is not within num_aux_segments
         self.aux_segment_rands[segment_idx]
     }
+
+    /// Returns the index of the column at index `column_idx` of the auxiliary trace segment at
+    /// index `segment_idx` in the context of all auxiliary trace segments.
+    ///
+    /// Columns of auxiliary segments are indexed consecutively across all auxiliary segments
+    /// (e.g., this is how columns of auxiliary assertions are interpreted). Thus, the returned
+    /// index is `column_idx` plus the sum of widths of all preceding auxiliary segments.
+    ///
+    /// # Panics
+    /// Panics if `segment_idx` is not a valid auxiliary segment index, or if `column_idx` is not
+    /// smaller than the width of the specified segment.
+    pub fn get_aux_column_index(&self, segment_idx: usize, column_idx: usize) -> usize {
+        assert!(
+            segment_idx < self.num_aux_segments,
+            "auxiliary segment index must be smaller than {}, but was {}",
+            self.num_aux_segments,
+            segment_idx
+        );
+        let segment_width = self.aux_segment_widths[segment_idx];
+        assert!(
+            column_idx < segment_width,
+            "column index for auxiliary segment {} must be smaller than {}, but was {}",
+            segment_idx,
+            segment_width,
+            column_idx
+        );
+        self.aux_segment_widths[..segment_idx].iter().sum::<usize>() + column_idx
+    }
+
+    /// Returns the index of the auxiliary trace segment containing the column at index
+    /// `column_idx`, and the index of this column within the segment.
+    ///
+    /// `column_idx` is zero-based across all auxiliary trace segments; this is the inverse of
+    /// [TraceLayout::get_aux_column_index()].
+    ///
+    /// # Panics
+    /// Panics if `column_idx` is not smaller than the total width of all auxiliary segments.
+    pub fn get_aux_segment_column(&self, column_idx: usize) -> (usize, usize) {
+        let mut column = column_idx;
+        for (segment_idx, &segment_width) in self.aux_segment_widths.iter().enumerate() {
+            if column < segment_width {
+                return (segment_idx, column);
+            }
+            column -= segment_width;
+        }
+        panic!(
+            "auxiliary column index must be smaller than {}, but was {}",
+            self.aux_trace_width(),
+            column_idx
+        );
+    }
+
+    /// Returns ranges of global column indexes occupied by each segment of an execution trace.
+    ///
+    /// Global column indexes enumerate columns of the main segment first, followed by columns of
+    /// all auxiliary segments. The first range in the returned vector is for the main segment,
+    /// and the remaining ranges are for auxiliary segments in the order in which they are defined.
+    pub fn segment_boundaries(&self) -> Vec<Range<usize>> {
+        let mut result = Vec::with_capacity(self.num_segments());
+        result.push(0..self.main_segment_width);
+        let mut segment_start = self.main_segment_width;
+        for &segment_width in self.aux_segment_widths[..self.num_aux_segments].iter() {
+            result.push(segment_start..segment_start + segment_width);
+            segment_start += segment_width;
+        }
+        result
+    }
+
+    /// Returns the index of the trace segment containing the column at global index `column_idx`.
+    ///
+    /// Segment 0 is the main segment, and segment `i` for `i > 0` is the auxiliary segment at
+    /// index `i - 1`.
+    ///
+    /// # Panics
+    /// Panics if `column_idx` is not smaller than the total width of all trace segments.
+    pub fn column_segment(&self, column_idx: usize) -> usize {
+        self.segment_boundaries()
+            .iter()
+            .position(|segment| segment.contains(&column_idx))
+            .unwrap_or_else(|| {
+                panic!(
+                    "column index must be smaller than {}, but was {}",
+                    self.main_segment_width + self.aux_trace_width(),
+                    column_idx
+                )
+            })
+    }
 }
 
 impl Serializable for TraceLayout {
@@ -296,19 +411,14 @@ impl Deserializable for TraceLayout {
             ));
         }
 
-        // read and validate auxiliary trace segment widths
-        let mut was_zero_width = false;
+        // read and validate auxiliary trace segment widths; empty segments are not serialized
         let mut aux_widths = vec![0; num_aux_segments];
         for width in aux_widths.iter_mut() {
             *width = source.read_u8()? as usize;
-            if *width != 0 {
-                if was_zero_width {
-                    return Err(DeserializationError::InvalidValue(
-                        "a non-empty trace segment cannot follow an empty segment".to_string(),
-                    ));
-                }
-            } else {
-                was_zero_width = true;
+            if *width == 0 {
+                return Err(DeserializationError::InvalidValue(
+                    "auxiliary trace segment width must be greater than zero".to_string(),
+                ));
             }
         }
 
@@ -323,13 +433,9 @@ impl Deserializable for TraceLayout {
 
         // read and validate number of random elements for each auxiliary trace segment
         let mut aux_rands = vec![0; num_aux_segments];
-        for (num_rand_elements, &width) in aux_rands.iter_mut().zip(aux_widths.iter()) {
+        for num_rand_elements in aux_rands.iter_mut() {
             *num_rand_elements = source.read_u8()? as usize;
-            if width == 0 && *num_rand_elements != 0 {
-                return Err(DeserializationError::InvalidValue(
-                    "an empty trace segment cannot require random elements".to_string(),
-                ));
-            } else if width != 0 && *num_rand_elements == 0 {
+            if *num_rand_elements == 0 {
                 return Err(DeserializationError::InvalidValue(
                     "a non-empty trace segment must require at least one random element"
                         .to_string(),
@@ -0,0 +1,238 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::TraceInfo;
+use crate::errors::TraceMetaError;
+use utils::{
+    collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Serializable, SliceReader,
+};
+
+// TRACE METADATA
+// ================================================================================================
+/// Structured execution trace metadata.
+///
+/// [TraceInfo] treats metadata as an opaque vector of bytes. This struct provides a simple
+/// structure for these bytes which makes it possible to distinguish between different metadata
+/// layouts: the metadata is serialized as a version byte, followed by the number of fields, and
+/// then by the fields themselves with every field prefixed by its length.
+///
+/// The number of fields is limited to 255, and each field can be at most 65535 bytes long.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TraceMeta {
+    version: u8,
+    fields: Vec<Vec<u8>>,
+}
+
+impl TraceMeta {
+    /// Maximum number of fields in trace metadata; currently set at 255.
+    pub const MAX_FIELDS: usize = 255;
+    /// Maximum number of bytes in a single metadata field; currently set at 65535.
+    pub const MAX_FIELD_LENGTH: usize = 65535;
+
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns new trace metadata of the specified version with no fields.
+    pub fn new(version: u8) -> Self {
+        Self {
+            version,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Appends a field with the specified bytes to this metadata and returns the result.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The metadata already contains 255 fields.
+    /// * Length of the `field` is greater than 65535 bytes.
+    pub fn with_field(mut self, field: &[u8]) -> Self {
+        assert!(
+            self.fields.len() < Self::MAX_FIELDS,
+            "number of metadata fields cannot be greater than {}",
+            Self::MAX_FIELDS
+        );
+        assert!(
+            field.len() <= Self::MAX_FIELD_LENGTH,
+            "metadata field cannot be longer than {} bytes, but was {}",
+            Self::MAX_FIELD_LENGTH,
+            field.len()
+        );
+        self.fields.push(field.to_vec());
+        self
+    }
+
+    /// Parses the provided bytes into structured trace metadata.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes are not a valid serialization of [TraceMeta], including the
+    /// case when there are bytes left over after all fields have been read.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(bytes);
+        let meta = Self::read_from(&mut source)?;
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(meta)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the version of this metadata.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the number of fields in this metadata.
+    pub fn num_fields(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Returns the field at the specified index, or None if the index is out of bounds.
+    pub fn get_field(&self, index: usize) -> Option<&[u8]> {
+        self.fields.get(index).map(|field| field.as_slice())
+    }
+}
+
+impl Serializable for TraceMeta {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.version);
+        target.write_u8(self.fields.len() as u8);
+        for field in self.fields.iter() {
+            target.write_u16(field.len() as u16);
+            target.write_u8_slice(field);
+        }
+    }
+}
+
+impl Deserializable for TraceMeta {
+    /// Reads [TraceMeta] from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid [TraceMeta] struct could not be read from the specified
+    /// `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let version = source.read_u8()?;
+        let num_fields = source.read_u8()? as usize;
+        let mut fields = Vec::with_capacity(num_fields);
+        for _ in 0..num_fields {
+            let field_len = source.read_u16()? as usize;
+            let field = if field_len != 0 {
+                source.read_u8_vec(field_len)?
+            } else {
+                Vec::new()
+            };
+            fields.push(field);
+        }
+        Ok(Self { version, fields })
+    }
+}
+
+// TRACE METADATA SCHEMA
+// ================================================================================================
+/// Describes the structure of trace metadata expected by a computation.
+///
+/// A schema specifies the version and the number of fields of the metadata, and the maximum
+/// number of bytes the serialized metadata may occupy. An AIR can declare a schema via
+/// [Air::trace_meta_schema()](crate::Air::trace_meta_schema) method; in this case, the verifier
+/// rejects proofs with trace metadata which does not conform to the schema.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TraceMetaSchema {
+    version: u8,
+    num_fields: usize,
+    max_length: usize,
+}
+
+impl TraceMetaSchema {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new schema for metadata of the specified version with the specified number of
+    /// fields.
+    ///
+    /// The maximum length of metadata is set to [TraceInfo::MAX_META_LENGTH].
+    ///
+    /// # Panics
+    /// Panics if `num_fields` is greater than 255.
+    pub fn new(version: u8, num_fields: usize) -> Self {
+        assert!(
+            num_fields <= TraceMeta::MAX_FIELDS,
+            "number of metadata fields cannot be greater than {}, but was {}",
+            TraceMeta::MAX_FIELDS,
+            num_fields
+        );
+        Self {
+            version,
+            num_fields,
+            max_length: TraceInfo::MAX_META_LENGTH,
+        }
+    }
+
+    /// Sets the maximum number of bytes the serialized metadata may occupy and returns the
+    /// updated schema.
+    ///
+    /// # Panics
+    /// Panics if `max_length` is greater than [TraceInfo::MAX_META_LENGTH].
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        assert!(
+            max_length <= TraceInfo::MAX_META_LENGTH,
+            "maximum metadata length cannot be greater than {}, but was {}",
+            TraceInfo::MAX_META_LENGTH,
+            max_length
+        );
+        self.max_length = max_length;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the metadata version expected by this schema.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the number of metadata fields expected by this schema.
+    pub fn num_fields(&self) -> usize {
+        self.num_fields
+    }
+
+    /// Returns the maximum number of bytes the serialized metadata may occupy.
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    // PARSING
+    // --------------------------------------------------------------------------------------------
+
+    /// Parses metadata of the provided trace info according to this schema.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The metadata is longer than the maximum length specified by this schema.
+    /// * The metadata is not a valid serialization of [TraceMeta].
+    /// * Version or number of fields of the metadata does not match this schema.
+    pub fn parse(&self, trace_info: &TraceInfo) -> Result<TraceMeta, TraceMetaError> {
+        trace_info.validate_meta(self.max_length)?;
+        let meta = TraceMeta::from_bytes(trace_info.meta())
+            .map_err(|err| TraceMetaError::MalformedMeta(err.to_string()))?;
+        if meta.version() != self.version {
+            return Err(TraceMetaError::VersionMismatch(
+                self.version,
+                meta.version(),
+            ));
+        }
+        if meta.num_fields() != self.num_fields {
+            return Err(TraceMetaError::NumFieldsMismatch(
+                self.num_fields,
+                meta.num_fields(),
+            ));
+        }
+        Ok(meta)
+    }
+}
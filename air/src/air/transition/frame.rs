@@ -5,7 +5,8 @@
 
 use super::{super::Air, FieldElement};
 use crate::Table;
-use utils::TableReader;
+use core::ops::Range;
+use utils::{collections::Vec, TableReader};
 
 /// A set of execution trace rows required for evaluation of transition constraints.
 /// It is passed in as one of the parameters into
@@ -33,6 +34,23 @@ pub trait EvaluationFrame<E: FieldElement> {
 
     /// Returns the offsets that make up a frame
     fn offsets() -> &'static [usize];
+
+    /// Returns a frame consisting of the specified columns of this frame.
+    ///
+    /// Columns of the returned frame are indexed starting from 0. This can be used to evaluate
+    /// constraints of a sub-computation which occupies a range of trace columns (e.g., a
+    /// sub-trace of a `MultiTrace` built by the prover) against its local column indices.
+    fn select_columns(&self, columns: Range<usize>) -> Self
+    where
+        Self: Sized,
+    {
+        let table = self.to_table();
+        let rows = table
+            .rows()
+            .map(|row| row[columns.clone()].to_vec())
+            .collect::<Vec<_>>();
+        Self::from_table(Table::from_rows(rows))
+    }
 }
 
 /// Contains rows of the execution trace
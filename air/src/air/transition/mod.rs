@@ -235,6 +235,12 @@ impl<E: FieldElement> TransitionConstraintGroup<E> {
         // degree of the resulting polynomial will be exactly equal to the composition_degree.
         let target_degree = composition_degree + divisor.degree();
         let evaluation_degree = degree.get_evaluation_degree(trace_length);
+        assert!(
+            evaluation_degree <= target_degree,
+            "composition degree {} is too low to accommodate transition constraints with evaluation degree {}",
+            composition_degree,
+            evaluation_degree
+        );
         let degree_adjustment = (target_degree - evaluation_degree) as u32;
         TransitionConstraintGroup {
             degree,
@@ -315,6 +321,24 @@ impl<E: FieldElement> TransitionConstraintGroup<E> {
     }
 }
 
+// CONSTRAINT HELPERS
+// ================================================================================================
+
+/// Combines two branches of a transition constraint into a single constraint based on the value
+/// of a selector.
+///
+/// The result is computed as `selector * branch_a + (1 - selector) * branch_b`. Thus, when the
+/// selector is ONE, the result evaluates to zero only when `branch_a` evaluates to zero, and when
+/// the selector is ZERO, the result evaluates to zero only when `branch_b` evaluates to zero.
+///
+/// This helper does not enforce that the selector is binary; if the selector is taken from a
+/// trace column, a separate constraint (e.g., `selector * (selector - 1) = 0`) must be defined
+/// for it. The degree of the resulting constraint is the degree of the selector plus the larger
+/// of the degrees of the two branches.
+pub fn select_constraint<E: FieldElement>(selector: E, branch_a: E, branch_b: E) -> E {
+    selector * branch_a + (E::ONE - selector) * branch_b
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -4,6 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use core::fmt;
+use utils::string::String;
 
 // ASSERTION ERROR
 // ================================================================================================
@@ -43,3 +44,80 @@ impl fmt::Display for AssertionError {
         }
     }
 }
+
+// TRACE METADATA ERROR
+// ================================================================================================
+/// Represents an error returned when trace metadata does not conform to the expected schema.
+#[derive(Debug, PartialEq)]
+pub enum TraceMetaError {
+    /// This error occurs when trace metadata is longer than the maximum allowed length. The
+    /// values are: maximum length, and actual length.
+    MetaTooLong(usize, usize),
+    /// This error occurs when trace metadata could not be parsed into structured metadata.
+    MalformedMeta(String),
+    /// This error occurs when the version of trace metadata is different from the version
+    /// expected by the schema. The values are: expected version, and actual version.
+    VersionMismatch(u8, u8),
+    /// This error occurs when the number of fields in trace metadata is different from the number
+    /// of fields expected by the schema. The values are: expected number, and actual number.
+    NumFieldsMismatch(usize, usize),
+}
+
+impl fmt::Display for TraceMetaError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MetaTooLong(max_length, actual) => {
+                write!(f, "trace metadata cannot be longer than {} bytes, but was {}", max_length, actual)
+            }
+            Self::MalformedMeta(err_msg) => {
+                write!(f, "trace metadata is malformed: {}", err_msg)
+            }
+            Self::VersionMismatch(expected, actual) => {
+                write!(f, "expected trace metadata version {}, but was {}", expected, actual)
+            }
+            Self::NumFieldsMismatch(expected, actual) => {
+                write!(f, "expected trace metadata to have {} fields, but was {}", expected, actual)
+            }
+        }
+    }
+}
+
+// MEMBERSHIP ERROR
+// ================================================================================================
+/// Represents an error returned when a column membership proof cannot be verified.
+#[derive(Debug, PartialEq, Eq)]
+pub enum MembershipError {
+    /// This error occurs when the opened position is not in the trace domain, i.e., it is not a
+    /// multiple of the blowup factor. The values are: opened position, and blowup factor.
+    PositionNotInTraceDomain(usize, usize),
+    /// This error occurs when the opened row does not contain the expected value in the
+    /// specified column.
+    ValueMismatch(usize),
+    /// This error occurs when the first element of the Merkle path is not the hash of the opened
+    /// row.
+    RowHashMismatch,
+    /// This error occurs when the Merkle path for the opened position does not resolve to the
+    /// commitment root.
+    InvalidMerklePath,
+}
+
+impl fmt::Display for MembershipError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PositionNotInTraceDomain(position, blowup) => {
+                write!(f, "position {} is not in the trace domain for blowup factor {}", position, blowup)
+            }
+            Self::ValueMismatch(column) => {
+                write!(f, "opened row does not contain the expected value in column {}", column)
+            }
+            Self::RowHashMismatch => {
+                write!(f, "first element of the Merkle path is not the hash of the opened row")
+            }
+            Self::InvalidMerklePath => {
+                write!(f, "Merkle path does not resolve to the commitment root")
+            }
+        }
+    }
+}
@@ -37,7 +37,7 @@ extern crate alloc;
 pub mod proof;
 
 mod errors;
-pub use errors::AssertionError;
+pub use errors::{AssertionError, MembershipError, TraceMetaError};
 
 mod options;
 pub use options::{FieldExtension, HashFunction, ProofOptions};
@@ -47,8 +47,10 @@ pub use table::Table;
 
 mod air;
 pub use air::{
-    Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
+    build_lagrange_kernel_column, get_public_coin_seed, select_constraint, Air, AirContext,
+    Assertion, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
     BoundaryConstraints, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, DefaultEvaluationFrame, EvaluationFrame, TraceInfo, TraceLayout,
-    TransitionConstraintDegree, TransitionConstraintGroup, TransitionConstraints,
+    DeepCompositionCoefficients, DefaultEvaluationFrame, DomainInfo, EvaluationFrame, PublicCoin,
+    TraceInfo, TraceLayout, TraceMeta, TraceMetaSchema, TransitionConstraintDegree,
+    TransitionConstraintGroup, TransitionConstraints,
 };
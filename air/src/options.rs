@@ -3,10 +3,26 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use core::cmp;
 use fri::FriOptions;
-use math::StarkField;
+use math::{log2, StarkField};
 use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
+// CONSTANTS
+// ================================================================================================
+
+/// Grinding factor contributes to security level only if security level from queries alone is at
+/// least this many bits.
+const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
+
+/// Number of bits lost in the FRI commit phase soundness error for the list-decoding parameter
+/// m = 3, i.e., log2((m + 1/2)^7 / 3) rounded up.
+const PROVEN_FRI_COMMIT_LOSS: u32 = 12;
+
+/// Number of thousandths of a bit lost per query in the list-decoding regime for m = 3, i.e.,
+/// log2(1 + 1 / (2m)) rounded up.
+const PROVEN_QUERY_LOSS_MILLIBITS: u32 = 223;
+
 // TYPES AND INTERFACES
 // ================================================================================================
 
@@ -87,6 +103,10 @@ pub enum FieldExtension {
 /// 5. Grinding factor - higher values increase proof soundness, but also may increase proof
 ///    generation time. More precisely, proof soundness is bounded by
 ///    `num_queries * log2(blowup_factor) + grinding_factor`.
+///
+/// Additionally, the offset by which the low-degree extension domain is shifted can be set via
+/// [ProofOptions::with_domain_offset()]. This does not affect proof soundness, but must be the
+/// same for the prover and the verifier.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ProofOptions {
     num_queries: u8,
@@ -96,6 +116,7 @@ pub struct ProofOptions {
     field_extension: FieldExtension,
     fri_folding_factor: u8,
     fri_max_remainder_size: u8, // stored as power of 2
+    domain_offset: Option<u64>,
 }
 
 // PROOF OPTIONS IMPLEMENTATION
@@ -159,9 +180,28 @@ impl ProofOptions {
             field_extension,
             fri_folding_factor: fri_folding_factor as u8,
             fri_max_remainder_size: fri_max_remainder_size.trailing_zeros() as u8,
+            domain_offset: None,
         }
     }
 
+    /// Returns a new [ProofOptions] struct with the low-degree extension domain shifted by the
+    /// specified `domain_offset` instead of the primitive element of the base field.
+    ///
+    /// The offset is mapped into the base field of a computation via `B::from(domain_offset)`.
+    /// For the resulting coset to be a valid evaluation domain, the mapped offset must not be zero
+    /// (e.g., for fields smaller than 64 bits, multiples of the field modulus map to zero), and
+    /// must not be an element of the LDE domain itself; this is checked when an
+    /// [AirContext](crate::AirContext) is instantiated with these options (see also
+    /// [ProofOptions::is_valid_domain_offset()]).
+    ///
+    /// # Panics
+    /// Panics if `domain_offset` is zero.
+    pub fn with_domain_offset(mut self, domain_offset: u64) -> Self {
+        assert!(domain_offset != 0, "domain offset cannot be zero");
+        self.domain_offset = Some(domain_offset);
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -180,6 +220,10 @@ impl ProofOptions {
     /// has a direct impact on proof soundness as each query adds roughly `log2(blowup_factor)`
     /// bits of security to a proof. However, higher blowup factors also increases prover runtime,
     /// and may increase proof size.
+    ///
+    /// The same blowup factor is used for all trace segments: trace polynomials of all segments
+    /// are evaluated over a single LDE domain, so that every query opens all trace columns at the
+    /// same point, and all columns can be merged into a single DEEP composition polynomial.
     pub fn blowup_factor(&self) -> usize {
         self.blowup_factor as usize
     }
@@ -217,16 +261,136 @@ impl ProofOptions {
     /// Returns the offset by which the low-degree extension domain is shifted in relation to the
     /// trace domain.
     ///
-    /// Currently, this is hard-coded to the primitive element of the underlying base field.
+    /// Unless a custom offset was set via [ProofOptions::with_domain_offset()], this is the
+    /// primitive element of the underlying base field. A custom offset is not validated, and
+    /// thus, the returned value may be zero in `B`; use [ProofOptions::is_valid_domain_offset()]
+    /// for options which come from an untrusted source.
     pub fn domain_offset<B: StarkField>(&self) -> B {
-        B::GENERATOR
+        match self.domain_offset {
+            Some(offset) => B::from(offset),
+            None => B::GENERATOR,
+        }
+    }
+
+    /// Returns true if the domain offset of these options shifts the LDE domain of the specified
+    /// size in field `B` into a valid coset.
+    ///
+    /// This is the case when the offset mapped into `B` is neither zero nor an element of the LDE
+    /// domain itself.
+    pub fn is_valid_domain_offset<B: StarkField>(&self, lde_domain_size: usize) -> bool {
+        let offset: B = self.domain_offset();
+        offset != B::ZERO && offset.exp((lde_domain_size as u64).into()) != B::ONE
     }
 
     /// Returns options for FRI protocol instantiated with parameters from this proof options.
     pub fn to_fri_options(&self) -> FriOptions {
         let folding_factor = self.fri_folding_factor as usize;
         let max_remainder_size = 2usize.pow(self.fri_max_remainder_size as u32);
-        FriOptions::new(self.blowup_factor(), folding_factor, max_remainder_size)
+        let options = FriOptions::new(self.blowup_factor(), folding_factor, max_remainder_size);
+        match self.domain_offset {
+            Some(offset) => options.with_domain_offset(offset),
+            None => options,
+        }
+    }
+
+    // SECURITY LEVEL
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns conjectured security level (in bits) of proofs generated with these options for
+    /// a computation with the specified trace length over a base field with the specified number
+    /// of modulus bits.
+    ///
+    /// The field in which the protocol is executed is defined by the base field together with
+    /// [field_extension()](ProofOptions::field_extension). The returned value is the minimum of:
+    /// * Field security: the size of the extension field (in bits) reduced by log2 of the LDE
+    ///   domain size.
+    /// * Query security: `log2(blowup_factor)` bits per query, plus `grinding_factor` bits if
+    ///   queries alone provide at least 80 bits of security. This relies on the conjecture that
+    ///   each query contributes `log2(blowup_factor)` bits of security.
+    /// * Collision resistance of the hash function.
+    ///
+    /// This can be used to make sure that a set of options provides the desired security level
+    /// before any proofs are generated.
+    ///
+    /// # Panics
+    /// Panics if `trace_length` is not a power of two.
+    pub fn conjectured_security_bits(&self, base_field_bits: u32, trace_length: usize) -> u32 {
+        let lde_domain_bits = self.get_lde_domain_bits(trace_length);
+
+        // compute max security we can get for a given field size
+        let field_size = base_field_bits * self.field_extension().degree();
+        let field_security = field_size.saturating_sub(lde_domain_bits);
+
+        // compute security we get by executing multiple query rounds
+        let security_per_query = log2(self.blowup_factor());
+        let query_security = security_per_query * self.num_queries() as u32;
+
+        self.combine_security_bits(field_security, query_security)
+    }
+
+    /// Returns proven security level (in bits) of proofs generated with these options for a
+    /// computation with the specified trace length over a base field with the specified number
+    /// of modulus bits.
+    ///
+    /// This estimate is based on the soundness of FRI in the list-decoding regime (as analyzed
+    /// in the [ethSTARK](https://eprint.iacr.org/2021/582) paper) with the list-decoding
+    /// parameter m = 3, and rounds all intermediate values conservatively. With ρ defined as
+    /// 1 / `blowup_factor`, the returned value is the minimum of:
+    /// * Field security: the size of the extension field (in bits) reduced by
+    ///   log2(|D|<sup>2</sup> · (m + 1/2)<sup>7</sup> / (3 · ρ<sup>3/2</sup>)), where |D| is the
+    ///   size of the LDE domain.
+    /// * Query security: log2(1 / α) bits per query, where α = (1 + 1 / (2m)) · √ρ, plus
+    ///   `grinding_factor` bits if queries alone provide at least 80 bits of security.
+    /// * Collision resistance of the hash function.
+    ///
+    /// The number of queries needed to reach a given proven security level is usually 2x - 3x
+    /// higher than the number of queries needed to reach the same conjectured security level.
+    ///
+    /// # Panics
+    /// Panics if `trace_length` is not a power of two.
+    pub fn proven_security_bits(&self, base_field_bits: u32, trace_length: usize) -> u32 {
+        let lde_domain_bits = self.get_lde_domain_bits(trace_length);
+        let blowup_bits = log2(self.blowup_factor());
+
+        // compute max security we can get for a given field size
+        let field_size = base_field_bits * self.field_extension().degree();
+        let field_security = field_size.saturating_sub(
+            2 * lde_domain_bits + (3 * blowup_bits + 1) / 2 + PROVEN_FRI_COMMIT_LOSS,
+        );
+
+        // compute security we get by executing multiple query rounds; security per query is
+        // computed in thousandths of a bit to avoid rounding it down to zero for small blowups
+        let millibits_per_query = (500 * blowup_bits).saturating_sub(PROVEN_QUERY_LOSS_MILLIBITS);
+        let query_security = millibits_per_query * self.num_queries() as u32 / 1000;
+
+        self.combine_security_bits(field_security, query_security)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns log2 of the LDE domain size for a trace of the specified length.
+    fn get_lde_domain_bits(&self, trace_length: usize) -> u32 {
+        assert!(
+            trace_length.is_power_of_two(),
+            "trace length must be a power of two, but was {}",
+            trace_length
+        );
+        log2(trace_length * self.blowup_factor())
+    }
+
+    /// Combines field and query security into the overall security level, taking grinding
+    /// factor and hash function collision resistance into account.
+    fn combine_security_bits(&self, field_security: u32, mut query_security: u32) -> u32 {
+        // include grinding factor contributions only for proofs adequate security
+        if query_security >= GRINDING_CONTRIBUTION_FLOOR {
+            query_security += self.grinding_factor();
+        }
+
+        cmp::min(
+            cmp::min(field_security, query_security).saturating_sub(1),
+            self.hash_fn().collision_resistance(),
+        )
     }
 }
 
@@ -240,6 +404,8 @@ impl Serializable for ProofOptions {
         target.write(self.field_extension);
         target.write_u8(self.fri_folding_factor);
         target.write_u8(self.fri_max_remainder_size);
+        // zero is never a valid domain offset, and so it is used to encode the default offset
+        target.write_u64(self.domain_offset.unwrap_or(0));
     }
 }
 
@@ -249,7 +415,7 @@ impl Deserializable for ProofOptions {
     /// # Errors
     /// Returns an error of a valid proof options could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        Ok(ProofOptions::new(
+        let options = ProofOptions::new(
             source.read_u8()? as usize,
             source.read_u8()? as usize,
             source.read_u8()? as u32,
@@ -257,7 +423,11 @@ impl Deserializable for ProofOptions {
             FieldExtension::read_from(source)?,
             source.read_u8()? as usize,
             2usize.pow(source.read_u8()? as u32),
-        ))
+        );
+        match source.read_u64()? {
+            0 => Ok(options),
+            offset => Ok(options.with_domain_offset(offset)),
+        }
     }
 }
 
@@ -341,3 +511,70 @@ impl Deserializable for HashFunction {
         }
     }
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{FieldExtension, HashFunction, ProofOptions};
+
+    #[test]
+    fn security_bits_96() {
+        // 128-bit base field, no extension, trace length 2^20, LDE domain size 2^23
+        let options = build_options(27, 8, HashFunction::Blake3_256, FieldExtension::None);
+        let trace_length = 1 << 20;
+
+        // field: 128 - 23 = 105; queries: 3 * 27 + 16 = 97
+        assert_eq!(96, options.conjectured_security_bits(128, trace_length));
+
+        // field: 128 - (2 * 23 + 5 + 12) = 65; queries: floor(27 * 1.277) = 34
+        assert_eq!(33, options.proven_security_bits(128, trace_length));
+
+        // security is capped by collision resistance of the hash function
+        let options = build_options(27, 8, HashFunction::Blake3_192, FieldExtension::None);
+        assert_eq!(96, options.conjectured_security_bits(128, trace_length));
+    }
+
+    #[test]
+    fn security_bits_128() {
+        // 64-bit base field, cubic extension, trace length 2^20, LDE domain size 2^24
+        let options = build_options(29, 16, HashFunction::Blake3_256, FieldExtension::Cubic);
+        let trace_length = 1 << 20;
+
+        // field: 192 - 24 = 168; queries: 4 * 29 + 16 = 132; hash: 128
+        assert_eq!(128, options.conjectured_security_bits(64, trace_length));
+
+        // field: 192 - (2 * 24 + 6 + 12) = 126; queries: floor(29 * 1.777) = 51
+        assert_eq!(50, options.proven_security_bits(64, trace_length));
+
+        // field: 126; queries: floor(64 * 1.777) + 16 = 129
+        let options = build_options(64, 16, HashFunction::Blake3_256, FieldExtension::Cubic);
+        assert_eq!(125, options.proven_security_bits(64, trace_length));
+
+        // without an extension, security is bounded by the size of the base field
+        let options = build_options(29, 16, HashFunction::Blake3_256, FieldExtension::None);
+        assert_eq!(39, options.conjectured_security_bits(64, trace_length));
+        assert_eq!(0, options.proven_security_bits(64, trace_length));
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn build_options(
+        num_queries: usize,
+        blowup_factor: usize,
+        hash_fn: HashFunction,
+        field_extension: FieldExtension,
+    ) -> ProofOptions {
+        ProofOptions::new(
+            num_queries,
+            blowup_factor,
+            16,
+            hash_fn,
+            field_extension,
+            8,
+            256,
+        )
+    }
+}
@@ -0,0 +1,478 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Commitments, Context, OodFrame, Queries, StarkProof};
+use core::marker::PhantomData;
+use crypto::{ElementHasher, Hasher};
+use fri::FriProof;
+use math::{fields::f252::BaseElement as Fe, FieldElement, StarkField};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of bytes packed into a single calldata word; 31 bytes always fit into a Starknet field
+/// element.
+const BYTES_PER_WORD: usize = 31;
+
+/// Number of bytes needed to encode a Starknet field element.
+const FELT_BYTES: usize = 32;
+
+// CAIRO CALLDATA
+// ================================================================================================
+
+impl StarkProof {
+    /// Encodes this proof as a sequence of Starknet field elements (felts) which can be passed
+    /// as calldata to a Cairo verifier.
+    ///
+    /// Digests of hash function `H` and elements of field `H::BaseField` are encoded as
+    /// individual felts:
+    /// * Bytes of a digest are reversed and interpreted as an integer in big-endian byte order.
+    ///   This is the same convention as used by [Pedersen_256](crypto::hashers::Pedersen_256) to
+    ///   convert between digests and felts, and thus, a Pedersen digest is encoded as the felt
+    ///   it was computed as.
+    /// * A field element is encoded as a felt equal to its canonical integer value. Elements of
+    ///   extension fields are encoded as their base field components in serialization order.
+    ///
+    /// Lists are encoded as the number of items followed by the items. The calldata consists of
+    /// the following items (in this order):
+    /// 1. Proof context (trace info and proof options). The context contains only integers and
+    ///    the trace metadata, and thus, is encoded as a sequence of bytes: the number of bytes
+    ///    `n` followed by `ceil(n / 31)` words, each containing 31 consecutive bytes (the last
+    ///    word may contain fewer bytes) interpreted as an integer in big-endian byte order.
+    /// 2. List of commitments: commitments to trace segments, followed by the commitment to the
+    ///    constraint composition polynomial, followed by commitments to FRI layers (including
+    ///    the FRI remainder).
+    /// 3. The number of trace segments followed by trace queries for each trace segment.
+    /// 4. Constraint composition polynomial queries.
+    /// 5. Out-of-domain frame: list of trace evaluations (for each frame row, main trace
+    ///    columns followed by auxiliary trace columns), followed by the list of constraint
+    ///    composition polynomial evaluations.
+    /// 6. FRI proof: the number of FRI layers followed by queries for each layer, the list of
+    ///    FRI remainder evaluations, and the binary logarithm of the number of partitions.
+    /// 7. Proof-of-work nonce.
+    ///
+    /// Queries are encoded as the list of queried values (all values of the first query,
+    /// followed by all values of the second query etc.), followed by the number of node vectors
+    /// in the batch Merkle proof and the list of digests for each of these vectors.
+    ///
+    /// The proof can be decoded from calldata via
+    /// [from_cairo_calldata()](Self::from_cairo_calldata).
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * This proof could not be parsed into digests of `H` and elements of `H::BaseField`.
+    /// * Any of the digests or field elements does not fit into a felt.
+    pub fn to_cairo_calldata<H: ElementHasher>(&self) -> Result<Vec<Fe>, DeserializationError> {
+        let mut target = CalldataWriter::<H>::new()?;
+
+        target.write_bytes(&self.context.to_bytes());
+
+        let commitments = self.commitments.to_bytes();
+        let mut source = SliceReader::new(&commitments);
+        let num_bytes = source.read_u16()? as usize;
+        target.write_digests(&source.read_u8_vec(num_bytes)?)?;
+
+        target.write_u64(self.trace_queries.len() as u64);
+        for queries in self.trace_queries.iter() {
+            target.write_queries(&mut SliceReader::new(&queries.to_bytes()))?;
+        }
+        target.write_queries(&mut SliceReader::new(&self.constraint_queries.to_bytes()))?;
+
+        let ood_frame = self.ood_frame.to_bytes();
+        let mut source = SliceReader::new(&ood_frame);
+        for _ in 0..2 {
+            let num_bytes = source.read_u16()? as usize;
+            target.write_elements(&source.read_u8_vec(num_bytes)?)?;
+        }
+
+        let fri_proof = self.fri_proof.to_bytes();
+        let mut source = SliceReader::new(&fri_proof);
+        let num_layers = source.read_u8()?;
+        target.write_u64(num_layers as u64);
+        for _ in 0..num_layers {
+            target.write_queries(&mut source)?;
+        }
+        let num_bytes = source.read_u16()? as usize;
+        target.write_elements(&source.read_u8_vec(num_bytes)?)?;
+        target.write_u64(source.read_u8()? as u64);
+
+        target.write_u64(self.pow_nonce);
+        Ok(target.felts)
+    }
+
+    /// Returns a STARK proof decoded from the calldata produced by
+    /// [to_cairo_calldata()](Self::to_cairo_calldata) for hash function `H`.
+    ///
+    /// # Errors
+    /// Returns an error if a valid STARK proof could not be decoded from the specified calldata.
+    pub fn from_cairo_calldata<H: ElementHasher>(
+        calldata: &[Fe],
+    ) -> Result<Self, DeserializationError> {
+        let mut source = CalldataReader::<H>::new(calldata)?;
+
+        let context: Context = decode(&source.read_bytes()?)?;
+
+        let mut bytes = Vec::new();
+        write_u16_prefixed(&mut bytes, &source.read_digests()?)?;
+        let commitments: Commitments = decode(&bytes)?;
+
+        let num_trace_segments = source.read_u64()? as usize;
+        if num_trace_segments != context.trace_layout().num_segments() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected queries for {} trace segments, but was {}",
+                context.trace_layout().num_segments(),
+                num_trace_segments
+            )));
+        }
+        let mut trace_queries = Vec::with_capacity(num_trace_segments);
+        for _ in 0..num_trace_segments {
+            let mut bytes = Vec::new();
+            source.read_queries(&mut bytes)?;
+            trace_queries.push(decode::<Queries>(&bytes)?);
+        }
+
+        let mut bytes = Vec::new();
+        source.read_queries(&mut bytes)?;
+        let constraint_queries: Queries = decode(&bytes)?;
+
+        let mut bytes = Vec::new();
+        write_u16_prefixed(&mut bytes, &source.read_elements()?)?;
+        write_u16_prefixed(&mut bytes, &source.read_elements()?)?;
+        let ood_frame: OodFrame = decode(&bytes)?;
+
+        let mut bytes = Vec::new();
+        let num_layers = source.read_u8()?;
+        bytes.write_u8(num_layers);
+        for _ in 0..num_layers {
+            source.read_queries(&mut bytes)?;
+        }
+        write_u16_prefixed(&mut bytes, &source.read_elements()?)?;
+        bytes.write_u8(source.read_u8()?);
+        let fri_proof: FriProof = decode(&bytes)?;
+
+        let proof = StarkProof {
+            context,
+            commitments,
+            trace_queries,
+            constraint_queries,
+            ood_frame,
+            fri_proof,
+            pow_nonce: source.read_u64()?,
+        };
+        if source.has_more_elements() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(proof)
+    }
+}
+
+// CALLDATA WRITER
+// ================================================================================================
+
+/// Encodes components of a serialized proof into a sequence of field elements as described in
+/// [StarkProof::to_cairo_calldata()].
+struct CalldataWriter<H: ElementHasher> {
+    felts: Vec<Fe>,
+    digest_size: usize,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> CalldataWriter<H> {
+    fn new() -> Result<Self, DeserializationError> {
+        Ok(CalldataWriter {
+            felts: Vec::new(),
+            digest_size: get_digest_size::<H>()?,
+            _hasher: PhantomData,
+        })
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.felts.push(Fe::from(value));
+    }
+
+    /// Writes the number of bytes followed by the bytes packed into 31-byte big-endian words.
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.write_u64(bytes.len() as u64);
+        for chunk in bytes.chunks(BYTES_PER_WORD) {
+            let mut word = [0u8; FELT_BYTES];
+            word[FELT_BYTES - chunk.len()..].copy_from_slice(chunk);
+            self.felts
+                .push(Fe::from_bytes_be(&word).expect("31 bytes always fit into a field element"));
+        }
+    }
+
+    /// Writes the number of serialized digests followed by one felt per digest.
+    fn write_digests(&mut self, bytes: &[u8]) -> Result<(), DeserializationError> {
+        let digests = split_into_chunks(bytes, self.digest_size, "digest")?;
+        self.write_u64(digests.len() as u64);
+        for digest in digests {
+            self.felts.push(le_bytes_to_felt(digest)?);
+        }
+        Ok(())
+    }
+
+    /// Writes the number of serialized base field elements followed by one felt per element.
+    fn write_elements(&mut self, bytes: &[u8]) -> Result<(), DeserializationError> {
+        let elements = split_into_chunks(bytes, H::BaseField::ELEMENT_BYTES, "field element")?;
+        self.write_u64(elements.len() as u64);
+        for element in elements {
+            // make sure the bytes encode a valid element before converting them into a felt
+            H::BaseField::read_from(&mut SliceReader::new(element))?;
+            self.felts.push(le_bytes_to_felt(element)?);
+        }
+        Ok(())
+    }
+
+    /// Reads serialized queries from the `source` and writes query values followed by nodes of
+    /// the batch Merkle proof.
+    fn write_queries<R: ByteReader>(&mut self, source: &mut R) -> Result<(), DeserializationError> {
+        let num_value_bytes = source.read_u32()? as usize;
+        self.write_elements(&source.read_u8_vec(num_value_bytes)?)?;
+
+        let num_path_bytes = source.read_u32()? as usize;
+        let paths = source.read_u8_vec(num_path_bytes)?;
+        let mut paths = SliceReader::new(&paths);
+        let num_node_vectors = paths.read_u8()?;
+        self.write_u64(num_node_vectors as u64);
+        for _ in 0..num_node_vectors {
+            let num_nodes = paths.read_u8()? as usize;
+            self.write_digests(&paths.read_u8_vec(num_nodes * self.digest_size)?)?;
+        }
+        if paths.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(())
+    }
+}
+
+// CALLDATA READER
+// ================================================================================================
+
+/// Reads values from a sequence of field elements encoded by [StarkProof::to_cairo_calldata()]
+/// and converts them back into their serialized form.
+struct CalldataReader<'a, H: ElementHasher> {
+    source: &'a [Fe],
+    pos: usize,
+    digest_size: usize,
+    _hasher: PhantomData<H>,
+}
+
+impl<'a, H: ElementHasher> CalldataReader<'a, H> {
+    fn new(source: &'a [Fe]) -> Result<Self, DeserializationError> {
+        Ok(CalldataReader {
+            source,
+            pos: 0,
+            digest_size: get_digest_size::<H>()?,
+            _hasher: PhantomData,
+        })
+    }
+
+    fn has_more_elements(&self) -> bool {
+        self.pos < self.source.len()
+    }
+
+    /// Reads a single element and interprets it as a 64-bit unsigned integer.
+    fn read_u64(&mut self) -> Result<u64, DeserializationError> {
+        let bytes = self.read_element()?.to_bytes_be();
+        let (high, low) = bytes.split_at(FELT_BYTES - 8);
+        if high.iter().any(|&b| b != 0) {
+            return Err(DeserializationError::InvalidValue(
+                "calldata element does not encode a 64-bit integer".into(),
+            ));
+        }
+        Ok(u64::from_be_bytes(low.try_into().unwrap()))
+    }
+
+    /// Reads a single element and interprets it as an 8-bit unsigned integer.
+    fn read_u8(&mut self) -> Result<u8, DeserializationError> {
+        let value = self.read_u64()?;
+        u8::try_from(value).map_err(|_| {
+            DeserializationError::InvalidValue(format!(
+                "calldata element {} does not encode an 8-bit integer",
+                value
+            ))
+        })
+    }
+
+    /// Reads a sequence of bytes packed into 31-byte big-endian words.
+    fn read_bytes(&mut self) -> Result<Vec<u8>, DeserializationError> {
+        let num_bytes = self.read_u64()? as usize;
+        let num_words = (num_bytes + BYTES_PER_WORD - 1) / BYTES_PER_WORD;
+        if self.source.len() - self.pos < num_words {
+            return Err(DeserializationError::UnexpectedEOF);
+        }
+
+        let mut bytes = Vec::with_capacity(num_bytes);
+        for i in 0..num_words {
+            let word_len = core::cmp::min(BYTES_PER_WORD, num_bytes - i * BYTES_PER_WORD);
+            let word = self.read_element()?.to_bytes_be();
+            let (padding, value) = word.split_at(FELT_BYTES - word_len);
+            if padding.iter().any(|&b| b != 0) {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "calldata word does not fit into {} bytes",
+                    word_len
+                )));
+            }
+            bytes.extend_from_slice(value);
+        }
+        Ok(bytes)
+    }
+
+    /// Reads a list of digests and returns the digests serialized one after another.
+    fn read_digests(&mut self) -> Result<Vec<u8>, DeserializationError> {
+        let num_digests = self.read_u64()? as usize;
+        self.read_items(num_digests, self.digest_size)
+    }
+
+    /// Reads a list of base field elements and returns the elements serialized one after
+    /// another.
+    fn read_elements(&mut self) -> Result<Vec<u8>, DeserializationError> {
+        let num_elements = self.read_u64()? as usize;
+        let bytes = self.read_items(num_elements, H::BaseField::ELEMENT_BYTES)?;
+        let mut reader = SliceReader::new(&bytes);
+        H::BaseField::read_batch_from(&mut reader, num_elements)?;
+        Ok(bytes)
+    }
+
+    /// Reads queries and writes them into the `target` in their serialized form.
+    fn read_queries(&mut self, target: &mut Vec<u8>) -> Result<(), DeserializationError> {
+        let values = self.read_elements()?;
+        target.write_u32(values.len() as u32);
+        target.write_u8_slice(&values);
+
+        let mut paths = Vec::new();
+        let num_node_vectors = self.read_u8()?;
+        paths.write_u8(num_node_vectors);
+        for _ in 0..num_node_vectors {
+            let nodes = self.read_digests()?;
+            let num_nodes = u8::try_from(nodes.len() / self.digest_size).map_err(|_| {
+                DeserializationError::InvalidValue("too many nodes in a Merkle path".into())
+            })?;
+            paths.write_u8(num_nodes);
+            paths.write_u8_slice(&nodes);
+        }
+        target.write_u32(paths.len() as u32);
+        target.write_u8_slice(&paths);
+        Ok(())
+    }
+
+    /// Reads `num_items` elements and converts each of them into `item_size` bytes in
+    /// little-endian byte order.
+    fn read_items(
+        &mut self,
+        num_items: usize,
+        item_size: usize,
+    ) -> Result<Vec<u8>, DeserializationError> {
+        if self.source.len() - self.pos < num_items {
+            return Err(DeserializationError::UnexpectedEOF);
+        }
+
+        let mut bytes = Vec::with_capacity(num_items * item_size);
+        for _ in 0..num_items {
+            let mut item = self.read_element()?.to_bytes_be();
+            item.reverse();
+            let (value, padding) = item.split_at(item_size);
+            if padding.iter().any(|&b| b != 0) {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "calldata element does not fit into {} bytes",
+                    item_size
+                )));
+            }
+            bytes.extend_from_slice(value);
+        }
+        Ok(bytes)
+    }
+
+    fn read_element(&mut self) -> Result<Fe, DeserializationError> {
+        let element = *self
+            .source
+            .get(self.pos)
+            .ok_or(DeserializationError::UnexpectedEOF)?;
+        self.pos += 1;
+        Ok(element)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of bytes in a serialized digest of hash function `H`.
+///
+/// # Errors
+/// Returns an error if a digest of `H` does not fit into a felt.
+fn get_digest_size<H: Hasher>() -> Result<usize, DeserializationError> {
+    let digest_size = H::Digest::default().to_bytes().len();
+    if digest_size > FELT_BYTES {
+        return Err(DeserializationError::InvalidValue(format!(
+            "digests of {} bytes do not fit into a Starknet field element",
+            digest_size
+        )));
+    }
+    Ok(digest_size)
+}
+
+/// Splits `bytes` into chunks of `chunk_size` bytes; returns an error if the number of bytes is
+/// not a multiple of `chunk_size`.
+fn split_into_chunks<'a>(
+    bytes: &'a [u8],
+    chunk_size: usize,
+    name: &str,
+) -> Result<Vec<&'a [u8]>, DeserializationError> {
+    if bytes.len() % chunk_size != 0 {
+        return Err(DeserializationError::InvalidValue(format!(
+            "{} bytes cannot be split into {} values of {} bytes",
+            bytes.len(),
+            name,
+            chunk_size
+        )));
+    }
+    Ok(bytes.chunks(chunk_size).collect())
+}
+
+/// Interprets the provided bytes as an integer in little-endian byte order and returns a felt
+/// with this value.
+///
+/// # Errors
+/// Returns an error if the integer is not smaller than the modulus of the Starknet field.
+fn le_bytes_to_felt(bytes: &[u8]) -> Result<Fe, DeserializationError> {
+    let mut word = [0u8; FELT_BYTES];
+    for (dst, src) in word.iter_mut().rev().zip(bytes) {
+        *dst = *src;
+    }
+
+    let mut modulus = Fe::get_modulus_le_bytes();
+    modulus.reverse();
+    if word[..] >= modulus[..] {
+        return Err(DeserializationError::InvalidValue(
+            "value does not fit into a Starknet field element".into(),
+        ));
+    }
+    Fe::from_bytes_be(&word)
+}
+
+/// Writes the number of bytes as a u16 value followed by the bytes into the `target`.
+fn write_u16_prefixed(target: &mut Vec<u8>, bytes: &[u8]) -> Result<(), DeserializationError> {
+    let num_bytes = u16::try_from(bytes.len()).map_err(|_| {
+        DeserializationError::InvalidValue(format!("too many bytes in a section: {}", bytes.len()))
+    })?;
+    target.write_u16(num_bytes);
+    target.write_u8_slice(bytes);
+    Ok(())
+}
+
+/// Deserializes a value of type `T` from the provided bytes; all bytes must be consumed by the
+/// value.
+fn decode<T: Deserializable>(bytes: &[u8]) -> Result<T, DeserializationError> {
+    let mut reader = SliceReader::new(bytes);
+    let value = T::read_from(&mut reader)?;
+    if reader.has_more_bytes() {
+        return Err(DeserializationError::UnconsumedBytes);
+    }
+    Ok(value)
+}
@@ -0,0 +1,83 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::MembershipError;
+use crypto::{ElementHasher, Hasher, MerkleTree};
+use math::FieldElement;
+use utils::collections::Vec;
+
+// COLUMN MEMBERSHIP PROOF
+// ================================================================================================
+
+/// A proof that a value appears in a column of a committed trace segment.
+///
+/// The proof opens a single row of the committed segment: it contains values of all columns in
+/// the row together with a Merkle authentication path from the hash of the row to the root of
+/// the segment commitment. This is the same way trace rows are opened in STARK proofs, and thus,
+/// the proof can be checked against a trace commitment root contained in a
+/// [StarkProof](super::StarkProof).
+///
+/// Membership proofs can be verified via [verify_column_membership()] function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MembershipProof<E: FieldElement, H: Hasher> {
+    /// Index of the opened row.
+    pub position: usize,
+    /// Values of all columns of the committed segment in the opened row.
+    pub row: Vec<E>,
+    /// Merkle authentication path for the opened row; the first element of the path is the hash
+    /// of the row.
+    pub path: Vec<H::Digest>,
+}
+
+/// Checks that the `proof` attests that the specified `value` is located in the specified
+/// `column` of a trace segment committed to by the `root`.
+///
+/// The `root` is expected to be a commitment to a low-degree extension of the trace segment with
+/// the specified `blowup_factor`. Only rows of the extension which lie in the trace domain (i.e.,
+/// rows at positions which are multiples of `blowup_factor`) are trace cells; all other rows are
+/// evaluations of trace polynomials outside of the trace, and thus, opening them does not prove
+/// anything about the trace. For a commitment to the trace itself, `blowup_factor` should be 1.
+///
+/// The check passes if all of the following hold:
+/// * The opened position is a multiple of `blowup_factor`.
+/// * The opened row contains `value` at index `column`.
+/// * The first element of the Merkle path is the hash of the opened row.
+/// * The Merkle path for the opened position resolves to `root`.
+///
+/// # Errors
+/// Returns an error if any of the above conditions does not hold.
+///
+/// # Panics
+/// Panics if `blowup_factor` is zero.
+pub fn verify_column_membership<E, H>(
+    root: &H::Digest,
+    column: usize,
+    value: E,
+    blowup_factor: usize,
+    proof: &MembershipProof<E, H>,
+) -> Result<(), MembershipError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    assert!(blowup_factor > 0, "blowup factor must be greater than zero");
+    if proof.position % blowup_factor != 0 {
+        return Err(MembershipError::PositionNotInTraceDomain(
+            proof.position,
+            blowup_factor,
+        ));
+    }
+
+    if proof.row.get(column) != Some(&value) {
+        return Err(MembershipError::ValueMismatch(column));
+    }
+
+    if proof.path.len() < 2 || proof.path[0] != H::hash_elements(&proof.row) {
+        return Err(MembershipError::RowHashMismatch);
+    }
+
+    MerkleTree::<H>::verify(*root, proof.position, &proof.path)
+        .map_err(|_| MembershipError::InvalidMerklePath)
+}
@@ -6,9 +6,7 @@
 //! Contains STARK proof struct and associated components.
 
 use crate::{ProofOptions, Table, TraceInfo, TraceLayout};
-use core::cmp;
 use fri::FriProof;
-use math::log2;
 use utils::{
     collections::Vec, ByteReader, Deserializable, DeserializationError, Serializable, SliceReader,
 };
@@ -25,10 +23,13 @@ pub use queries::Queries;
 mod ood_frame;
 pub use ood_frame::OodFrame;
 
-// CONSTANTS
-// ================================================================================================
+mod membership;
+pub use membership::{verify_column_membership, MembershipProof};
+
+mod stats;
+pub use stats::{ProofSizeBreakdown, ProofStats};
 
-const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
+mod cairo;
 
 // STARK PROOF
 // ================================================================================================
@@ -99,16 +100,54 @@ impl StarkProof {
     /// security level is returned. Usually, the number of queries needed for provable security is
     /// 2x - 3x higher than the number of queries needed for conjectured security at the same
     /// security level.
+    ///
+    /// See [ProofOptions::conjectured_security_bits()] and [ProofOptions::proven_security_bits()]
+    /// for details on how security level is computed.
     pub fn security_level(&self, conjectured: bool) -> u32 {
+        let options = self.context.options();
+        let base_field_bits = self.context.num_modulus_bits();
+        let trace_length = self.context.trace_length();
         if conjectured {
-            get_conjectured_security(
-                self.context.options(),
-                self.context.num_modulus_bits(),
-                self.lde_domain_size() as u64,
-            )
+            options.conjectured_security_bits(base_field_bits, trace_length)
         } else {
-            // TODO: implement provable security estimation
-            unimplemented!("proven security estimation has not been implement yet")
+            options.proven_security_bits(base_field_bits, trace_length)
+        }
+    }
+
+    // PROOF STATS
+    // --------------------------------------------------------------------------------------------
+    /// Returns statistics describing the structure of this proof.
+    ///
+    /// The stats include trace dimensions, the number of constraint composition columns, the
+    /// number of FRI layers and queries, the conjectured security level, and sizes of individual
+    /// components of the serialized proof.
+    pub fn stats(&self) -> ProofStats {
+        let layout = self.trace_layout();
+        let options = self.options();
+
+        // OOD constraint evaluations are stored as elements of the extension field
+        let element_bytes =
+            self.context.field_modulus_bytes().len() * options.field_extension().degree() as usize;
+
+        ProofStats {
+            trace_length: self.trace_length(),
+            main_trace_width: layout.main_trace_width(),
+            aux_trace_width: layout.aux_trace_width(),
+            num_aux_segments: layout.num_aux_segments(),
+            lde_domain_size: self.lde_domain_size(),
+            num_composition_columns: self.ood_frame.num_evaluation_bytes() / element_bytes,
+            num_queries: options.num_queries(),
+            num_fri_layers: self.fri_proof.num_layers(),
+            security_level: self.security_level(true),
+            size: ProofSizeBreakdown {
+                context: self.context.to_bytes().len(),
+                commitments: self.commitments.to_bytes().len(),
+                trace_queries: self.trace_queries.to_bytes().len(),
+                constraint_queries: self.constraint_queries.to_bytes().len(),
+                ood_frame: self.ood_frame.to_bytes().len(),
+                fri_proof: self.fri_proof.to_bytes().len(),
+                pow_nonce: self.pow_nonce.to_le_bytes().len(),
+            },
         }
     }
 
@@ -164,34 +203,3 @@ impl StarkProof {
         Ok(proof)
     }
 }
-
-// HELPER FUNCTIONS
-// ================================================================================================
-
-/// Computes conjectured security level for the specified proof parameters.
-fn get_conjectured_security(
-    options: &ProofOptions,
-    base_field_bits: u32,
-    lde_domain_size: u64,
-) -> u32 {
-    // compute max security we can get for a given field size
-    let field_size = base_field_bits * options.field_extension().degree();
-    let field_security = field_size - lde_domain_size.trailing_zeros();
-
-    // compute max security we can get for a given hash function
-    let hash_fn_security = options.hash_fn().collision_resistance();
-
-    // compute security we get by executing multiple query rounds
-    let security_per_query = log2(options.blowup_factor());
-    let mut query_security = security_per_query * options.num_queries() as u32;
-
-    // include grinding factor contributions only for proofs adequate security
-    if query_security >= GRINDING_CONTRIBUTION_FLOOR {
-        query_security += options.grinding_factor();
-    }
-
-    cmp::min(
-        cmp::min(field_security, query_security) - 1,
-        hash_fn_security,
-    )
-}
@@ -70,6 +70,14 @@ impl OodFrame {
         evaluations.write_into(&mut self.evaluations)
     }
 
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of bytes used to encode constraint evaluations in this frame.
+    pub(crate) fn num_evaluation_bytes(&self) -> usize {
+        self.evaluations.len()
+    }
+
     // PARSER
     // --------------------------------------------------------------------------------------------
     /// Returns main and auxiliary (if any) trace evaluation frames and a vector of out-of-domain
@@ -126,6 +134,49 @@ impl OodFrame {
 
         Ok((main_frame, aux_frame, evaluations))
     }
+
+    // COMPACT SERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes `self` into the `target` using a compact encoding which stores base and
+    /// extension components of field elements separately.
+    ///
+    /// When the execution trace is defined over a base field but the out-of-domain point is
+    /// drawn from an extension field `E`, many extension components of the OOD evaluations may
+    /// be zero. The compact encoding writes base components of all elements densely, and then
+    /// writes only the non-zero extension components preceded by a bitmap of their positions.
+    /// For base field elements (when `E` has extension degree 1) no bitmap is written.
+    ///
+    /// # Errors
+    /// Returns an error if the internal bytes of this frame could not be parsed into elements of
+    /// type `E`.
+    pub fn write_compact_into<E: FieldElement, W: ByteWriter>(
+        &self,
+        target: &mut W,
+    ) -> Result<(), DeserializationError> {
+        write_compact_elements::<E, W>(&self.trace_states, target)?;
+        write_compact_elements::<E, W>(&self.evaluations, target)
+    }
+
+    /// Reads an out-of-domain frame serialized via [write_compact_into()](OodFrame::write_compact_into)
+    /// from the specified `source`.
+    ///
+    /// The returned frame is identical to the frame which was serialized, and can be parsed
+    /// and used in the same way as a frame read via [Deserializable::read_from()].
+    ///
+    /// # Errors
+    /// Returns an error if a valid compact OOD frame for elements of type `E` could not be read
+    /// from the specified `source`.
+    pub fn read_compact_from<E: FieldElement, R: ByteReader>(
+        source: &mut R,
+    ) -> Result<Self, DeserializationError> {
+        let trace_states = read_compact_elements::<E, R>(source)?;
+        let evaluations = read_compact_elements::<E, R>(source)?;
+        Ok(OodFrame {
+            trace_states,
+            evaluations,
+        })
+    }
 }
 
 impl Serializable for OodFrame {
@@ -161,3 +212,86 @@ impl Deserializable for OodFrame {
         })
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Parses `bytes` into elements of type `E` and writes them into the `target` using compact
+/// encoding: base components of all elements first, followed by a bitmap of non-zero extension
+/// components and the non-zero extension components themselves.
+fn write_compact_elements<E: FieldElement, W: ByteWriter>(
+    bytes: &[u8],
+    target: &mut W,
+) -> Result<(), DeserializationError> {
+    if bytes.len() % E::ELEMENT_BYTES != 0 {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of bytes ({}) does not divide into a whole number of field elements",
+            bytes.len()
+        )));
+    }
+    let num_elements = bytes.len() / E::ELEMENT_BYTES;
+    let mut reader = SliceReader::new(bytes);
+    let elements = E::read_batch_from(&mut reader, num_elements)?;
+    let components = E::as_base_elements(&elements);
+    let extension_degree = E::ELEMENT_BYTES / <E::BaseField as FieldElement>::ELEMENT_BYTES;
+
+    target.write_u16(num_elements as u16);
+
+    // write base components of all elements
+    for element in components.chunks(extension_degree) {
+        element[0].write_into(target);
+    }
+
+    // write extension components; only non-zero components are written, and their positions
+    // are recorded in a bitmap
+    if extension_degree > 1 {
+        let mut bitmap = vec![0u8; (num_elements * (extension_degree - 1) + 7) / 8];
+        let mut non_zero = Vec::new();
+        let extension_components = components
+            .chunks(extension_degree)
+            .flat_map(|element| element[1..].iter());
+        for (i, &component) in extension_components.enumerate() {
+            if component != E::BaseField::ZERO {
+                bitmap[i / 8] |= 1 << (i % 8);
+                non_zero.push(component);
+            }
+        }
+        target.write_u8_slice(&bitmap);
+        non_zero.write_into(target);
+    }
+
+    Ok(())
+}
+
+/// Reads elements of type `E` encoded via [write_compact_elements()] from the `source` and
+/// returns their standard byte representation.
+fn read_compact_elements<E: FieldElement, R: ByteReader>(
+    source: &mut R,
+) -> Result<Vec<u8>, DeserializationError> {
+    let extension_degree = E::ELEMENT_BYTES / <E::BaseField as FieldElement>::ELEMENT_BYTES;
+    let num_elements = source.read_u16()? as usize;
+    let base_components = E::BaseField::read_batch_from(source, num_elements)?;
+
+    let mut extension_components = vec![E::BaseField::ZERO; num_elements * (extension_degree - 1)];
+    if extension_degree > 1 {
+        let bitmap = source.read_u8_vec((extension_components.len() + 7) / 8)?;
+        for (i, component) in extension_components.iter_mut().enumerate() {
+            if bitmap[i / 8] & (1 << (i % 8)) != 0 {
+                *component = E::BaseField::read_from(source)?;
+            }
+        }
+    }
+
+    // assemble the elements back into their standard byte representation; this relies on the
+    // fact that an extension field element is serialized as a sequence of its base components
+    let mut result = Vec::with_capacity(num_elements * E::ELEMENT_BYTES);
+    for (i, base) in base_components.iter().enumerate() {
+        base.write_into(&mut result);
+        let start = i * (extension_degree - 1);
+        for component in extension_components[start..start + extension_degree - 1].iter() {
+            component.write_into(&mut result);
+        }
+    }
+
+    Ok(result)
+}
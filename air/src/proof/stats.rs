@@ -0,0 +1,150 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+// PROOF STATS
+// ================================================================================================
+/// Statistics describing the structure of a [StarkProof](super::StarkProof).
+///
+/// Stats are computed via [StarkProof::stats()](super::StarkProof::stats) from the proof itself,
+/// and thus, without access to the AIR of the computation. As a consequence, the number of
+/// transition constraints is not available; instead, the number of constraint composition columns
+/// is reported (this number is equal to the highest constraint degree less one, rounded up to the
+/// next power of two).
+///
+/// Stats can be serialized into a compact sequence of bytes, e.g., to be ingested by monitoring
+/// tools.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ProofStats {
+    /// Number of steps in the execution trace.
+    pub trace_length: usize,
+    /// Number of columns in the main segment of the execution trace.
+    pub main_trace_width: usize,
+    /// Number of columns in all auxiliary segments of the execution trace.
+    pub aux_trace_width: usize,
+    /// Number of auxiliary segments in the execution trace.
+    pub num_aux_segments: usize,
+    /// Size of the low-degree extension domain.
+    pub lde_domain_size: usize,
+    /// Number of columns into which the constraint composition polynomial was decomposed.
+    pub num_composition_columns: usize,
+    /// Number of queries made by the verifier (before removing duplicates).
+    pub num_queries: usize,
+    /// Number of FRI layers in the proof (not including the remainder).
+    pub num_fri_layers: usize,
+    /// Conjectured security level of the proof (in bits).
+    pub security_level: u32,
+    /// Sizes of individual components of the serialized proof.
+    pub size: ProofSizeBreakdown,
+}
+
+impl Serializable for ProofStats {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u64(self.trace_length as u64);
+        target.write_u16(self.main_trace_width as u16);
+        target.write_u16(self.aux_trace_width as u16);
+        target.write_u8(self.num_aux_segments as u8);
+        target.write_u64(self.lde_domain_size as u64);
+        target.write_u16(self.num_composition_columns as u16);
+        target.write_u16(self.num_queries as u16);
+        target.write_u8(self.num_fri_layers as u8);
+        target.write_u32(self.security_level);
+        self.size.write_into(target);
+    }
+}
+
+impl Deserializable for ProofStats {
+    /// Reads proof stats from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid ProofStats struct could not be read from the specified
+    /// `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(ProofStats {
+            trace_length: source.read_u64()? as usize,
+            main_trace_width: source.read_u16()? as usize,
+            aux_trace_width: source.read_u16()? as usize,
+            num_aux_segments: source.read_u8()? as usize,
+            lde_domain_size: source.read_u64()? as usize,
+            num_composition_columns: source.read_u16()? as usize,
+            num_queries: source.read_u16()? as usize,
+            num_fri_layers: source.read_u8()? as usize,
+            security_level: source.read_u32()?,
+            size: ProofSizeBreakdown::read_from(source)?,
+        })
+    }
+}
+
+// PROOF SIZE BREAKDOWN
+// ================================================================================================
+/// Sizes (in bytes) of individual components of a serialized [StarkProof](super::StarkProof).
+///
+/// The sizes include all metadata written by the serializers of the respective components, and
+/// thus, [total()](ProofSizeBreakdown::total) is equal to the length of the vector returned by
+/// [StarkProof::to_bytes()](super::StarkProof::to_bytes).
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct ProofSizeBreakdown {
+    /// Size of the proof context.
+    pub context: usize,
+    /// Size of all commitments (trace, constraint, and FRI layer commitments).
+    pub commitments: usize,
+    /// Size of trace queries across all trace segments.
+    pub trace_queries: usize,
+    /// Size of constraint composition queries.
+    pub constraint_queries: usize,
+    /// Size of the out-of-domain frame.
+    pub ood_frame: usize,
+    /// Size of the FRI proof.
+    pub fri_proof: usize,
+    /// Size of the proof-of-work nonce.
+    pub pow_nonce: usize,
+}
+
+impl ProofSizeBreakdown {
+    /// Returns the total size of the serialized proof in bytes.
+    pub fn total(&self) -> usize {
+        self.context
+            + self.commitments
+            + self.trace_queries
+            + self.constraint_queries
+            + self.ood_frame
+            + self.fri_proof
+            + self.pow_nonce
+    }
+}
+
+impl Serializable for ProofSizeBreakdown {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.context as u32);
+        target.write_u32(self.commitments as u32);
+        target.write_u32(self.trace_queries as u32);
+        target.write_u32(self.constraint_queries as u32);
+        target.write_u32(self.ood_frame as u32);
+        target.write_u32(self.fri_proof as u32);
+        target.write_u32(self.pow_nonce as u32);
+    }
+}
+
+impl Deserializable for ProofSizeBreakdown {
+    /// Reads a proof size breakdown from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid ProofSizeBreakdown struct could not be read from the specified
+    /// `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(ProofSizeBreakdown {
+            context: source.read_u32()? as usize,
+            commitments: source.read_u32()? as usize,
+            trace_queries: source.read_u32()? as usize,
+            constraint_queries: source.read_u32()? as usize,
+            ood_frame: source.read_u32()? as usize,
+            fri_proof: source.read_u32()? as usize,
+            pow_nonce: source.read_u32()? as usize,
+        })
+    }
+}
@@ -2,25 +2,38 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
-use math::fields::f128;
-use rand_utils::rand_value;
+use criterion::{
+    black_box, criterion_group, criterion_main, measurement::WallTime, BatchSize, BenchmarkGroup,
+    BenchmarkId, Criterion,
+};
+use math::fields::{f128, f252, f64::BaseElement};
+use rand_utils::{rand_value, rand_vector};
 use winter_crypto::{
-    hashers::{Blake3_256, Blake2s_256, Rp62_248, Rp64_256, Sha3_256},
-    Hasher,
+    bench_hasher,
+    hashers::{
+        Blake2s_256, Blake3_192, Blake3_256, Monolith, Pedersen_256, Rp62_248, Rp64_256, Rpo256,
+        Sha3_256, Tip5,
+    },
+    ElementHasher, Hasher,
 };
 
 type Blake3 = Blake3_256<f128::BaseElement>;
 type Blake3Digest = <Blake3 as Hasher>::Digest;
 
-type Blake2s = Blake3_256<f128::BaseElement>;
-type Blake2sDigest = <Blake3 as Hasher>::Digest;
+type Blake2s = Blake2s_256<f128::BaseElement>;
+type Blake2sDigest = <Blake2s as Hasher>::Digest;
 
 type Sha3 = Sha3_256<f128::BaseElement>;
 type Sha3Digest = <Sha3 as Hasher>::Digest;
 
 type Rp62_248Digest = <Rp62_248 as Hasher>::Digest;
 type Rp64_256Digest = <Rp64_256 as Hasher>::Digest;
+type Rpo64 = Rpo256<BaseElement>;
+type Rpo64Digest = <Rpo64 as Hasher>::Digest;
+type Tip5_64 = Tip5<BaseElement>;
+type Tip5_64Digest = <Tip5_64 as Hasher>::Digest;
+type Monolith64 = Monolith<BaseElement>;
+type Monolith64Digest = <Monolith64 as Hasher>::Digest;
 
 fn blake3(c: &mut Criterion) {
     let v: [Blake3Digest; 2] = [Blake3::hash(&[1u8]), Blake3::hash(&[2u8])];
@@ -122,5 +135,115 @@ fn rescue256(c: &mut Criterion) {
     });
 }
 
-criterion_group!(hash_group, blake3, sha3, rescue248, rescue256);
+fn rpo256(c: &mut Criterion) {
+    let v: [Rpo64Digest; 2] = [Rpo64::hash(&[1u8]), Rpo64::hash(&[2u8])];
+    c.bench_function("hash_rpo256 (cached)", |bench| {
+        bench.iter(|| Rpo64::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_rpo256 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Rpo64::hash(&rand_value::<u64>().to_le_bytes()),
+                    Rpo64::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Rpo64::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn tip5_320(c: &mut Criterion) {
+    let v: [Tip5_64Digest; 2] = [Tip5_64::hash(&[1u8]), Tip5_64::hash(&[2u8])];
+    c.bench_function("hash_tip5_320 (cached)", |bench| {
+        bench.iter(|| Tip5_64::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_tip5_320 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Tip5_64::hash(&rand_value::<u64>().to_le_bytes()),
+                    Tip5_64::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Tip5_64::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn monolith64(c: &mut Criterion) {
+    let v: [Monolith64Digest; 2] = [Monolith64::hash(&[1u8]), Monolith64::hash(&[2u8])];
+    c.bench_function("hash_monolith_64 (cached)", |bench| {
+        bench.iter(|| Monolith64::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_monolith_64 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Monolith64::hash(&rand_value::<u64>().to_le_bytes()),
+                    Monolith64::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Monolith64::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn pedersen_elements(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hash_pedersen_256_elements");
+    for &num_elements in [8, 64, 512].iter() {
+        let elements: Vec<f252::BaseElement> = rand_vector(num_elements);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(num_elements),
+            &elements,
+            |bench, elements| bench.iter(|| Pedersen_256::hash_elements(black_box(elements))),
+        );
+    }
+    group.finish();
+}
+
+fn compare_hashers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hasher_comparison");
+    compare::<Blake3_256<f128::BaseElement>>(&mut group, "blake3_256");
+    compare::<Blake3_192<f128::BaseElement>>(&mut group, "blake3_192");
+    compare::<Blake2s>(&mut group, "blake2s_256");
+    compare::<Sha3>(&mut group, "sha3_256");
+    compare::<Pedersen_256<f252::BaseElement>>(&mut group, "pedersen_256");
+    compare::<Rp62_248>(&mut group, "rp62_248");
+    compare::<Rp64_256>(&mut group, "rp64_256");
+    compare::<Rpo64>(&mut group, "rpo256");
+    compare::<Tip5_64>(&mut group, "tip5_320");
+    compare::<Monolith64>(&mut group, "monolith_64");
+    group.finish();
+}
+
+/// Measures merge and element hashing time of `H` using the same procedure as [bench_hasher()].
+fn compare<H: ElementHasher>(group: &mut BenchmarkGroup<WallTime>, name: &str) {
+    group.bench_function(BenchmarkId::new("merge", name), |b| {
+        b.iter_custom(|iters| bench_hasher::<H>(iters as usize, 0).merge_time)
+    });
+    group.bench_function(BenchmarkId::new("hash_elements", name), |b| {
+        b.iter_custom(|iters| bench_hasher::<H>(0, iters as usize).element_hash_time)
+    });
+}
+
+criterion_group!(
+    hash_group,
+    blake3,
+    blake2s,
+    sha3,
+    rescue248,
+    rescue256,
+    rpo256,
+    tip5_320,
+    monolith64,
+    pedersen_elements,
+    compare_hashers
+);
 criterion_main!(hash_group);
@@ -0,0 +1,107 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{errors::MerkleTreeError, hash::Hasher, MerkleTree};
+use core::fmt::Debug;
+use utils::collections::Vec;
+
+// VECTOR COMMITMENT
+// ================================================================================================
+
+/// A vector commitment scheme over digests produced by a hash function `H`.
+///
+/// A vector commitment allows a prover to commit to an ordered list of leaves (digests), and to
+/// later open the commitment at individual positions. Openings can be checked by a verifier
+/// against the commitment without access to the full list of leaves.
+///
+/// [MerkleTree] is the default implementation of this trait. Other schemes (e.g., Merkle trees
+/// for which the top few levels are sent to the verifier in full) can be plugged in by
+/// implementing this trait.
+///
+/// Note that trace and FRI layer commitments are not yet built via this trait: openings of these
+/// commitments are serialized into STARK proofs as batch Merkle proofs, and thus, they use
+/// [MerkleTree] directly.
+pub trait VectorCommitment<H: Hasher>: Sized {
+    /// Commitment to the entire vector of leaves; this is what the verifier receives during the
+    /// commit phase of the protocol.
+    type Commitment: Clone + Debug + PartialEq;
+
+    /// Proof that a leaf is located at a given position in the committed vector.
+    type Opening: Clone;
+
+    /// Errors which can occur when building, opening, or verifying a commitment.
+    type Error: Debug;
+
+    /// Commits to the provided leaves and returns the resulting vector commitment.
+    ///
+    /// # Errors
+    /// Returns an error if a commitment to the specified leaves could not be built.
+    fn commit(leaves: Vec<H::Digest>) -> Result<Self, Self::Error>;
+
+    /// Returns the commitment to the vector of leaves.
+    fn commitment(&self) -> Self::Commitment;
+
+    /// Returns the number of leaves in the committed vector.
+    fn num_leaves(&self) -> usize;
+
+    /// Returns a leaf at the specified `index` together with an opening proof for this leaf.
+    ///
+    /// # Errors
+    /// Returns an error if the specified index is greater than or equal to the number of leaves.
+    fn open(&self, index: usize) -> Result<(H::Digest, Self::Opening), Self::Error>;
+
+    /// Checks whether the `opening` proves that `leaf` is located at the specified `index` in the
+    /// vector committed to by `commitment`.
+    ///
+    /// # Errors
+    /// Returns an error if the `opening` is not valid for the specified leaf and position.
+    fn verify_opening(
+        commitment: &Self::Commitment,
+        index: usize,
+        leaf: H::Digest,
+        opening: &Self::Opening,
+    ) -> Result<(), Self::Error>;
+}
+
+// MERKLE TREE IMPLEMENTATION
+// ================================================================================================
+
+/// Implements a vector commitment where the commitment is the root of a Merkle tree, and an
+/// opening is a Merkle path as returned by [MerkleTree::prove()] (i.e., the leaf is the first
+/// element of the path).
+impl<H: Hasher> VectorCommitment<H> for MerkleTree<H> {
+    type Commitment = H::Digest;
+    type Opening = Vec<H::Digest>;
+    type Error = MerkleTreeError;
+
+    fn commit(leaves: Vec<H::Digest>) -> Result<Self, Self::Error> {
+        MerkleTree::new(leaves)
+    }
+
+    fn commitment(&self) -> Self::Commitment {
+        *self.root()
+    }
+
+    fn num_leaves(&self) -> usize {
+        self.leaves().len()
+    }
+
+    fn open(&self, index: usize) -> Result<(H::Digest, Self::Opening), Self::Error> {
+        let path = self.prove(index)?;
+        Ok((path[0], path))
+    }
+
+    fn verify_opening(
+        commitment: &Self::Commitment,
+        index: usize,
+        leaf: H::Digest,
+        opening: &Self::Opening,
+    ) -> Result<(), Self::Error> {
+        if opening.len() < 2 || opening[0] != leaf {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        MerkleTree::<H>::verify(*commitment, index, opening)
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Digest, ElementHasher, Hasher};
+use core::{fmt, mem, ptr};
+use std::time::{Duration, Instant};
+use utils::collections::Vec;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of base field elements hashed together by a single element hash in [bench_hasher()].
+pub const ELEMENTS_PER_HASH: usize = 8;
+
+// BENCHMARK
+// ================================================================================================
+
+/// Measures the performance of the hash function `H` on the current machine.
+///
+/// The benchmark runs `num_merges` sequential invocations of [Hasher::merge()] (the operation
+/// used to build Merkle trees), followed by `num_element_hashes` invocations of
+/// [ElementHasher::hash_elements()] over [ELEMENTS_PER_HASH] base field elements (the operation
+/// used to hash rows of the execution trace). Each invocation depends on the result of the
+/// previous one so that the calls cannot be optimized away or executed out of order.
+///
+/// This function is intended for comparing hash functions against each other to help pick one
+/// for a specific computation; for statistically robust measurements use the criterion benchmarks
+/// of this crate, which are built on top of this function.
+pub fn bench_hasher<H: ElementHasher>(num_merges: usize, num_element_hashes: usize) -> BenchResult {
+    // measure time needed to merge digests
+    let mut digests = [H::hash(&[0u8; 32]), H::hash(&[1u8; 32])];
+    let now = Instant::now();
+    for _ in 0..num_merges {
+        digests[0] = H::merge(black_box(&digests));
+    }
+    let merge_time = now.elapsed();
+    black_box(digests);
+
+    // measure time needed to hash field elements
+    let mut elements = (0..ELEMENTS_PER_HASH as u32)
+        .map(H::BaseField::from)
+        .collect::<Vec<_>>();
+    let now = Instant::now();
+    for _ in 0..num_element_hashes {
+        let digest = H::hash_elements(black_box(&elements));
+        elements[0] += H::BaseField::from(digest.as_bytes()[0]);
+    }
+    let element_hash_time = now.elapsed();
+    black_box(elements);
+
+    BenchResult {
+        num_merges,
+        merge_time,
+        num_element_hashes,
+        element_hash_time,
+    }
+}
+
+// BENCHMARK RESULT
+// ================================================================================================
+
+/// Timings collected by [bench_hasher()] for a single hash function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchResult {
+    /// Number of 2-to-1 digest merges performed.
+    pub num_merges: usize,
+    /// Total time spent merging digests.
+    pub merge_time: Duration,
+    /// Number of element hashes performed; each hash is computed over [ELEMENTS_PER_HASH]
+    /// base field elements.
+    pub num_element_hashes: usize,
+    /// Total time spent hashing field elements.
+    pub element_hash_time: Duration,
+}
+
+impl BenchResult {
+    /// Returns the number of 2-to-1 digest merges performed per second.
+    pub fn merges_per_second(&self) -> f64 {
+        throughput(self.num_merges, self.merge_time)
+    }
+
+    /// Returns the number of element hashes performed per second.
+    pub fn element_hashes_per_second(&self) -> f64 {
+        throughput(self.num_element_hashes, self.element_hash_time)
+    }
+}
+
+impl fmt::Display for BenchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:.0} merges/s, {:.0} element hashes/s",
+            self.merges_per_second(),
+            self.element_hashes_per_second()
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Prevents the compiler from optimizing away computations which produce or consume `value`.
+///
+/// This is the same implementation as `criterion::black_box()` uses on stable Rust;
+/// `core::hint::black_box()` is not available in Rust 1.60.
+fn black_box<T>(value: T) -> T {
+    // the value is read from a valid reference, and the original is forgotten so that it is not
+    // dropped twice
+    unsafe {
+        let result = ptr::read_volatile(&value);
+        mem::forget(value);
+        result
+    }
+}
+
+fn throughput(num_operations: usize, time: Duration) -> f64 {
+    if num_operations == 0 {
+        0.0
+    } else {
+        num_operations as f64 / time.as_secs_f64()
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{bench_hasher, BenchResult};
+    use crate::{
+        hashers::{
+            Blake2s_256, Blake3_192, Blake3_256, Monolith, Pedersen_256, Rp62_248, Rp64_256,
+            Rpo256, Sha3_256, Tip5,
+        },
+        ElementHasher,
+    };
+    use math::fields::{f128, f252, f64};
+
+    #[test]
+    fn bench_all_hashers() {
+        check_bench::<Blake3_256<f128::BaseElement>>();
+        check_bench::<Blake3_192<f128::BaseElement>>();
+        check_bench::<Blake2s_256<f128::BaseElement>>();
+        check_bench::<Sha3_256<f128::BaseElement>>();
+        check_bench::<Pedersen_256<f252::BaseElement>>();
+        check_bench::<Rp62_248>();
+        check_bench::<Rp64_256>();
+        check_bench::<Rpo256<f64::BaseElement>>();
+        check_bench::<Tip5<f64::BaseElement>>();
+        check_bench::<Monolith<f64::BaseElement>>();
+    }
+
+    #[test]
+    fn bench_result_throughput() {
+        let result = bench_hasher::<Blake3_256<f128::BaseElement>>(0, 0);
+        assert_eq!(0.0, result.merges_per_second());
+        assert_eq!(0.0, result.element_hashes_per_second());
+    }
+
+    fn check_bench<H: ElementHasher>() {
+        let result: BenchResult = bench_hasher::<H>(16, 8);
+        assert_eq!(16, result.num_merges);
+        assert_eq!(8, result.num_element_hashes);
+        assert!(result.merges_per_second() > 0.0);
+        assert!(result.element_hashes_per_second() > 0.0);
+    }
+}
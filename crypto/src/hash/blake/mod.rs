@@ -4,91 +4,54 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{ByteDigest, ElementHasher, Hasher};
-use core::{convert::TryInto, fmt::Debug, marker::PhantomData};
+use core::{fmt::Debug, marker::PhantomData};
 use math::{FieldElement, StarkField};
 use utils::ByteWriter;
 
 #[cfg(test)]
 mod tests;
 
-// BLAKE3 256-BIT OUTPUT
+// BLAKE3 N-BYTE OUTPUT
 // ================================================================================================
 
-/// Implementation of the [Hasher](super::Hasher) trait for BLAKE3 hash function with 256-bit
+/// Implementation of the [Hasher](super::Hasher) trait for BLAKE3 hash function with `N`-byte
 /// output.
+///
+/// Digests are the first `N` bytes of the 256-bit BLAKE3 output, and thus, [Blake3_256] and
+/// [Blake3_192] are instantiations of this hasher with `N` set to 32 and 24 respectively. For an
+/// `N`-byte digest, collision resistance of the hash function is at most 4 * `N` bits.
+///
+/// # Panics
+/// Hashing methods panic if `N` is zero or greater than 32.
 #[derive(Debug, PartialEq, Eq)]
-pub struct Blake3_256<B: StarkField>(PhantomData<B>);
+pub struct Blake3<B: StarkField, const N: usize>(PhantomData<B>);
 
-impl<B: StarkField> Hasher for Blake3_256<B> {
-    type Digest = ByteDigest<32>;
+/// BLAKE3 hash function with 256-bit output.
+pub type Blake3_256<B> = Blake3<B, 32>;
 
-    fn hash(bytes: &[u8]) -> Self::Digest {
-        ByteDigest(*blake3::hash(bytes).as_bytes())
-    }
-
-    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
-        ByteDigest(blake3::hash(ByteDigest::digests_as_bytes(values)).into())
-    }
+/// BLAKE3 hash function with 192-bit output.
+pub type Blake3_192<B> = Blake3<B, 24>;
 
-    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
-        let mut data = [0; 40];
-        data[..32].copy_from_slice(&seed.0);
-        data[32..].copy_from_slice(&value.to_le_bytes());
-        ByteDigest(*blake3::hash(&data).as_bytes())
-    }
-}
-
-impl<B: StarkField> ElementHasher for Blake3_256<B> {
-    type BaseField = B;
-
-    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
-        if B::IS_CANONICAL {
-            // when element's internal and canonical representations are the same, we can hash
-            // element bytes directly
-            let bytes = E::elements_as_bytes(elements);
-            ByteDigest(*blake3::hash(bytes).as_bytes())
-        } else {
-            // when elements' internal and canonical representations differ, we need to serialize
-            // them before hashing
-            let mut hasher = BlakeHasher::new();
-            hasher.write(elements);
-            ByteDigest(hasher.finalize())
-        }
-    }
-}
-
-// BLAKE3 192-BIT OUTPUT
-// ================================================================================================
-
-/// Implementation of the [Hasher](super::Hasher) trait for BLAKE3 hash function with 192-bit
-/// output.
-#[derive(Debug, PartialEq, Eq)]
-pub struct Blake3_192<B: StarkField>(PhantomData<B>);
-
-impl<B: StarkField> Hasher for Blake3_192<B> {
-    type Digest = ByteDigest<24>;
+impl<B: StarkField, const N: usize> Hasher for Blake3<B, N> {
+    type Digest = ByteDigest<N>;
 
     fn hash(bytes: &[u8]) -> Self::Digest {
-        let result = blake3::hash(bytes);
-        ByteDigest(result.as_bytes()[..24].try_into().unwrap())
+        ByteDigest::truncated(blake3::hash(bytes).as_bytes())
     }
 
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
-        let result = blake3::hash(ByteDigest::digests_as_bytes(values));
-        ByteDigest(result.as_bytes()[..24].try_into().unwrap())
+        ByteDigest::truncated(blake3::hash(ByteDigest::digests_as_bytes(values)).as_bytes())
     }
 
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
-        let mut data = [0; 32];
-        data[..24].copy_from_slice(&seed.0);
-        data[24..].copy_from_slice(&value.to_le_bytes());
-
-        let result = blake3::hash(&data);
-        ByteDigest(result.as_bytes()[..24].try_into().unwrap())
+        let mut data = [0; 40];
+        data[..N].copy_from_slice(&seed.0);
+        data[N..N + 8].copy_from_slice(&value.to_le_bytes());
+        ByteDigest::truncated(blake3::hash(&data[..N + 8]).as_bytes())
     }
 }
 
-impl<B: StarkField> ElementHasher for Blake3_192<B> {
+impl<B: StarkField, const N: usize> ElementHasher for Blake3<B, N> {
     type BaseField = B;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
@@ -96,15 +59,13 @@ impl<B: StarkField> ElementHasher for Blake3_192<B> {
             // when element's internal and canonical representations are the same, we can hash
             // element bytes directly
             let bytes = E::elements_as_bytes(elements);
-            let result = blake3::hash(bytes);
-            ByteDigest(result.as_bytes()[..24].try_into().unwrap())
+            ByteDigest::truncated(blake3::hash(bytes).as_bytes())
         } else {
             // when elements' internal and canonical representations differ, we need to serialize
             // them before hashing
             let mut hasher = BlakeHasher::new();
             hasher.write(elements);
-            let result = hasher.finalize();
-            ByteDigest(result[..24].try_into().unwrap())
+            ByteDigest::truncated(&hasher.finalize())
         }
     }
 }
@@ -3,9 +3,74 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{Blake3_256, ElementHasher, Hasher};
-use math::{fields::f62::BaseElement, FieldElement};
-use rand_utils::rand_array;
+use super::{Blake3, Blake3_192, Blake3_256, ElementHasher, Hasher};
+use math::{
+    fields::{f128, f62::BaseElement},
+    FieldElement,
+};
+use rand_utils::{rand_array, rand_vector};
+use utils::{collections::Vec, Serializable};
+
+// KNOWN-ANSWER TESTS
+// ================================================================================================
+
+/// BLAKE3 digest of an empty input, as given by the BLAKE3 reference implementation.
+const EMPTY_DIGEST: [u8; 32] = [
+    0xaf, 0x13, 0x49, 0xb9, 0xf5, 0xf9, 0xa1, 0xa6, 0xa0, 0x40, 0x4d, 0xea, 0x36, 0xdc, 0xc9, 0x49,
+    0x9b, 0xcb, 0x25, 0xc9, 0xad, 0xc1, 0x12, 0xb7, 0xcc, 0x9a, 0x93, 0xca, 0xe4, 0x1f, 0x32, 0x62,
+];
+
+/// BLAKE3 digest of "abc", as given by the BLAKE3 reference implementation.
+const ABC_DIGEST: [u8; 32] = [
+    0x64, 0x37, 0xb3, 0xac, 0x38, 0x46, 0x51, 0x33, 0xff, 0xb6, 0x3b, 0x75, 0x27, 0x3a, 0x8d, 0xb5,
+    0x48, 0xc5, 0x58, 0x46, 0x5d, 0x79, 0xdb, 0x03, 0xfd, 0x35, 0x9c, 0x6c, 0xd5, 0xbd, 0x9d, 0x85,
+];
+
+#[test]
+fn hash_known_answers() {
+    assert_eq!(EMPTY_DIGEST, Blake3_256::<BaseElement>::hash(&[]).0);
+    assert_eq!(ABC_DIGEST, Blake3_256::<BaseElement>::hash(b"abc").0);
+
+    // 192-bit output is a truncation of the 256-bit output
+    assert_eq!(EMPTY_DIGEST[..24], Blake3_192::<BaseElement>::hash(&[]).0);
+    assert_eq!(ABC_DIGEST[..24], Blake3_192::<BaseElement>::hash(b"abc").0);
+}
+
+#[test]
+fn merge_is_hash_of_concatenation() {
+    let d1 = Blake3_256::<BaseElement>::hash(b"abc");
+    let d2 = Blake3_256::<BaseElement>::hash(&[]);
+    let mut bytes = d1.0.to_vec();
+    bytes.extend_from_slice(&d2.0);
+    assert_eq!(
+        Blake3_256::<BaseElement>::hash(&bytes),
+        Blake3_256::merge(&[d1, d2])
+    );
+}
+
+#[test]
+fn hash_elements_is_hash_of_serialized_elements() {
+    // elements with internal representation different from canonical representation
+    let elements: Vec<BaseElement> = rand_vector(7);
+    assert_eq!(
+        Blake3_256::<BaseElement>::hash(&elements.to_bytes()),
+        Blake3_256::hash_elements(&elements)
+    );
+    assert_eq!(
+        Blake3_192::<BaseElement>::hash(&elements.to_bytes()),
+        Blake3_192::hash_elements(&elements)
+    );
+
+    // elements with canonical internal representation are hashed directly
+    let elements: Vec<f128::BaseElement> = rand_vector(7);
+    assert_eq!(
+        Blake3_256::<f128::BaseElement>::hash(&elements.to_bytes()),
+        Blake3_256::hash_elements(&elements)
+    );
+}
+
+// PADDING TESTS
+// ================================================================================================
 
 #[test]
 fn hash_padding() {
@@ -28,3 +93,17 @@ fn hash_elements_padding() {
     let r2 = Blake3_256::hash_elements(&e2);
     assert_ne!(r1, r2);
 }
+
+#[test]
+fn generic_digest_size() {
+    let bytes = [1_u8, 2, 3];
+    let elements: [BaseElement; 4] = rand_array();
+
+    // shorter digests are prefixes of the 256-bit digest
+    let digest = Blake3_256::<BaseElement>::hash(&bytes);
+    assert_eq!(digest.0[..16], Blake3::<BaseElement, 16>::hash(&bytes).0);
+    assert_eq!(digest.0[..24], Blake3_192::<BaseElement>::hash(&bytes).0);
+
+    let digest = Blake3_256::hash_elements(&elements);
+    assert_eq!(digest.0[..16], Blake3::<_, 16>::hash_elements(&elements).0);
+}
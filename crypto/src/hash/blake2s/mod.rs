@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{ByteDigest, ElementHasher, Hasher};
-use blake2::blake2s::{blake2s, Blake2s};
+use blake2::blake2s::{blake2s, Blake2s as Blake2sState};
 use core::{convert::TryInto, fmt::Debug, marker::PhantomData};
 use math::{FieldElement, StarkField};
 use utils::ByteWriter;
@@ -19,84 +19,47 @@ fn blake2s_hash(bytes: &[u8]) -> [u8; 32] {
         .expect("slice with incorrect length");
 }
 
-// BLAKE2s 256-BIT OUTPUT
+// BLAKE2s N-BYTE OUTPUT
 // ================================================================================================
 
-/// Implementation of the [Hasher](super::Hasher) trait for BLAKE2s hash function with 256-bit
+/// Implementation of the [Hasher](super::Hasher) trait for BLAKE2s hash function with `N`-byte
 /// output.
+///
+/// Digests are the first `N` bytes of the 256-bit BLAKE2s output, and thus, [Blake2s_256] and
+/// [Blake2s_192] are instantiations of this hasher with `N` set to 32 and 24 respectively. For an
+/// `N`-byte digest, collision resistance of the hash function is at most 4 * `N` bits.
+///
+/// # Panics
+/// Hashing methods panic if `N` is zero or greater than 32.
 #[derive(Debug, PartialEq, Eq)]
-pub struct Blake2s_256<B: StarkField>(PhantomData<B>);
+pub struct Blake2s<B: StarkField, const N: usize>(PhantomData<B>);
 
-impl<B: StarkField> Hasher for Blake2s_256<B> {
-    type Digest = ByteDigest<32>;
+/// BLAKE2s hash function with 256-bit output.
+pub type Blake2s_256<B> = Blake2s<B, 32>;
 
-    fn hash(bytes: &[u8]) -> Self::Digest {
-        ByteDigest(blake2s_hash(bytes))
-    }
-
-    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
-        ByteDigest(blake2s_hash(ByteDigest::digests_as_bytes(values)).into())
-    }
+/// BLAKE2s hash function with 192-bit output.
+pub type Blake2s_192<B> = Blake2s<B, 24>;
 
-    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
-        let mut data = [0; 40];
-        data[..32].copy_from_slice(&seed.0);
-        data[32..].copy_from_slice(&value.to_le_bytes());
-        ByteDigest(blake2s_hash(&data))
-    }
-}
-
-impl<B: StarkField> ElementHasher for Blake2s_256<B> {
-    type BaseField = B;
-
-    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
-        if B::IS_CANONICAL {
-            // when element's internal and canonical representations are the same, we can hash
-            // element bytes directly
-            let bytes = E::elements_as_bytes(elements);
-            ByteDigest(blake2s_hash(bytes))
-        } else {
-            // when elements' internal and canonical representations differ, we need to serialize
-            // them before hashing
-            let mut hasher = Blake2sHasher::new();
-            hasher.write(elements);
-            ByteDigest(hasher.finalize())
-        }
-    }
-}
-
-// BLAKE2s 192-BIT OUTPUT
-// ================================================================================================
-
-/// Implementation of the [Hasher](super::Hasher) trait for BLAKE2s hash function with 192-bit
-/// output.
-#[derive(Debug, PartialEq, Eq)]
-pub struct Blake2s_192<B: StarkField>(PhantomData<B>);
-
-impl<B: StarkField> Hasher for Blake2s_192<B> {
-    type Digest = ByteDigest<24>;
+impl<B: StarkField, const N: usize> Hasher for Blake2s<B, N> {
+    type Digest = ByteDigest<N>;
 
     fn hash(bytes: &[u8]) -> Self::Digest {
-        let result = blake2s_hash(bytes);
-        ByteDigest(result[..24].try_into().unwrap())
+        ByteDigest::truncated(&blake2s_hash(bytes))
     }
 
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
-        let result = blake2s_hash(ByteDigest::digests_as_bytes(values));
-        ByteDigest(result[..24].try_into().unwrap())
+        ByteDigest::truncated(&blake2s_hash(ByteDigest::digests_as_bytes(values)))
     }
 
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
-        let mut data = [0; 32];
-        data[..24].copy_from_slice(&seed.0);
-        data[24..].copy_from_slice(&value.to_le_bytes());
-
-        let result = blake2s_hash(&data);
-        ByteDigest(result[..24].try_into().unwrap())
+        let mut data = [0; 40];
+        data[..N].copy_from_slice(&seed.0);
+        data[N..N + 8].copy_from_slice(&value.to_le_bytes());
+        ByteDigest::truncated(&blake2s_hash(&data[..N + 8]))
     }
 }
 
-impl<B: StarkField> ElementHasher for Blake2s_192<B> {
+impl<B: StarkField, const N: usize> ElementHasher for Blake2s<B, N> {
     type BaseField = B;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
@@ -104,15 +67,13 @@ impl<B: StarkField> ElementHasher for Blake2s_192<B> {
             // when element's internal and canonical representations are the same, we can hash
             // element bytes directly
             let bytes = E::elements_as_bytes(elements);
-            let result = blake2s_hash(bytes);
-            ByteDigest(result[..24].try_into().unwrap())
+            ByteDigest::truncated(&blake2s_hash(bytes))
         } else {
             // when elements' internal and canonical representations differ, we need to serialize
             // them before hashing
             let mut hasher = Blake2sHasher::new();
             hasher.write(elements);
-            let result = hasher.finalize();
-            ByteDigest(result[..24].try_into().unwrap())
+            ByteDigest::truncated(&hasher.finalize())
         }
     }
 }
@@ -121,11 +82,11 @@ impl<B: StarkField> ElementHasher for Blake2s_192<B> {
 // ================================================================================================
 
 /// Wrapper around BLAKE2s hasher to implement [ByteWriter] trait for it.
-struct Blake2sHasher(Blake2s);
+struct Blake2sHasher(Blake2sState);
 
 impl Blake2sHasher {
     pub fn new() -> Self {
-        Self(Blake2s::new(32))
+        Self(Blake2sState::new(32))
     }
 
     pub fn finalize(&self) -> [u8; 32] {
@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{Blake2s_256, ElementHasher, Hasher};
+use super::{Blake2s, Blake2s_192, Blake2s_256, ElementHasher, Hasher};
 use math::{fields::f62::BaseElement, FieldElement};
 use rand_utils::rand_array;
 
@@ -28,3 +28,17 @@ fn hash_elements_padding() {
     let r2 = Blake2s_256::hash_elements(&e2);
     assert_ne!(r1, r2);
 }
+
+#[test]
+fn generic_digest_size() {
+    let bytes = [1_u8, 2, 3];
+    let elements: [BaseElement; 4] = rand_array();
+
+    // shorter digests are prefixes of the 256-bit digest
+    let digest = Blake2s_256::<BaseElement>::hash(&bytes);
+    assert_eq!(digest.0[..16], Blake2s::<BaseElement, 16>::hash(&bytes).0);
+    assert_eq!(digest.0[..24], Blake2s_192::<BaseElement>::hash(&bytes).0);
+
+    let digest = Blake2s_256::hash_elements(&elements);
+    assert_eq!(digest.0[..16], Blake2s::<_, 16>::hash_elements(&elements).0);
+}
@@ -4,23 +4,40 @@
 // LICENSE file in the root directory of this source tree.
 
 use core::{fmt::Debug, slice};
-use math::{FieldElement, StarkField};
+use math::{ExtensionOf, FieldElement, StarkField};
 use utils::{ByteReader, Deserializable, DeserializationError, Serializable};
 
 mod blake;
-pub use blake::{Blake3_192, Blake3_256};
+pub use blake::{Blake3, Blake3_192, Blake3_256};
 
 mod blake2s;
-pub use blake2s::{Blake2s_256};
+pub use blake2s::{Blake2s, Blake2s_192, Blake2s_256};
+
+mod monolith;
+pub use monolith::Monolith;
 
 mod pedersen;
 pub use pedersen::{Pedersen_256};
 
+mod poseidon2;
+pub use poseidon2::Poseidon2_256;
+
 mod sha;
 pub use sha::Sha3_256;
 
 mod rescue;
-pub use rescue::{Rp62_248, Rp64_256};
+pub use rescue::{Rp62_248, Rp64_256, Rpo256};
+
+mod tip5;
+pub use tip5::Tip5;
+
+mod truncated;
+pub use truncated::TruncatedDigest;
+
+#[cfg(feature = "std")]
+mod bench;
+#[cfg(feature = "std")]
+pub use bench::{bench_hasher, BenchResult, ELEMENTS_PER_HASH};
 
 // HASHER TRAITS
 // ================================================================================================
@@ -58,6 +75,18 @@ pub trait ElementHasher: Hasher {
     fn hash_elements<E>(elements: &[E]) -> Self::Digest
     where
         E: FieldElement<BaseField = Self::BaseField>;
+
+    /// Returns a hash of the provided extension field elements.
+    ///
+    /// The elements are hashed as a sequence of their base field coordinates, with coordinates
+    /// of each element laid out in order before those of the next element. Thus, the result is
+    /// the same as hashing the base field elements obtained by flattening `elements`.
+    fn hash_extension_elements<E>(elements: &[E]) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<Self::BaseField>,
+    {
+        Self::hash_elements(E::as_base_elements(elements))
+    }
 }
 
 // DIGEST TRAIT
@@ -79,7 +108,11 @@ pub trait Digest:
 // BYTE DIGEST
 // ================================================================================================
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// A digest represented by an array of `N` bytes.
+///
+/// Digests are ordered lexicographically by their bytes, and thus, can be sorted or used as keys
+/// in ordered and hashed collections.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct ByteDigest<const N: usize>(pub [u8; N]);
 
 impl<const N: usize> ByteDigest<N> {
@@ -100,6 +133,22 @@ impl<const N: usize> ByteDigest<N> {
         let len = digests.len() * N;
         unsafe { slice::from_raw_parts(p as *const u8, len) }
     }
+
+    /// Returns a digest consisting of the first `N` bytes of the provided 32 bytes.
+    ///
+    /// # Panics
+    /// Panics if `N` is zero or greater than 32.
+    #[inline(always)]
+    pub(crate) fn truncated(bytes: &[u8; 32]) -> Self {
+        assert!(
+            N > 0 && N <= 32,
+            "truncated digest size must be between 1 and 32 bytes, but was {}",
+            N
+        );
+        let mut result = [0; N];
+        result.copy_from_slice(&bytes[..N]);
+        ByteDigest(result)
+    }
 }
 
 impl<const N: usize> Digest for ByteDigest<N> {
@@ -131,6 +180,7 @@ impl<const N: usize> Deserializable for ByteDigest<N> {
 #[cfg(test)]
 mod tests {
     use super::{ByteDigest, Digest};
+    use utils::collections::{BTreeSet, Vec};
 
     #[test]
     fn byte_digest_as_bytes() {
@@ -142,4 +192,21 @@ mod tests {
         expected[31] = 0;
         assert_eq!(expected, d.as_bytes());
     }
+
+    #[test]
+    fn byte_digest_ordering() {
+        let mut set = BTreeSet::new();
+        set.insert(ByteDigest::new([2_u8, 0, 0, 0]));
+        set.insert(ByteDigest::new([1_u8, 255, 255, 255]));
+        set.insert(ByteDigest::new([2_u8, 0, 0, 1]));
+        set.insert(ByteDigest::new([0_u8, 0, 0, 0]));
+        set.insert(ByteDigest::new([1_u8, 255, 255, 255]));
+
+        // duplicates are removed and digests are iterated in lexicographic order
+        let digests = set.into_iter().map(|d| d.0).collect::<Vec<_>>();
+        assert_eq!(
+            vec![[0, 0, 0, 0], [1, 255, 255, 255], [2, 0, 0, 0], [2, 0, 0, 1]],
+            digests
+        );
+    }
 }
@@ -0,0 +1,474 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{rescue::ElementDigest, Digest, ElementHasher, Hasher};
+use core::convert::TryInto;
+use core::marker::PhantomData;
+use core::ops::Range;
+use math::{fields::f64::BaseElement, FieldElement, StarkField};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+/// the remaining 4 elements are reserved for capacity.
+const STATE_WIDTH: usize = 12;
+
+/// The rate portion of the state is located in elements 0 through 7.
+const RATE_RANGE: Range<usize> = 0..8;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+const INPUT1_RANGE: Range<usize> = 0..4;
+const INPUT2_RANGE: Range<usize> = 4..8;
+
+/// The capacity portion of the state is located in elements 8, 9, 10, and 11.
+const CAPACITY_RANGE: Range<usize> = 8..12;
+
+/// The output of the hash function is a digest which consists of 4 field elements or 32 bytes.
+///
+/// The digest is returned from state elements 0, 1, 2, and 3 (the first four elements of the
+/// rate portion).
+const DIGEST_RANGE: Range<usize> = 0..4;
+
+/// The number of rounds is set to 6 to target 128-bit security level.
+const NUM_ROUNDS: usize = 6;
+
+/// The number of state elements to which the Bars layer is applied.
+const NUM_BARS: usize = 4;
+
+/// The number of bytes packed into a single field element when hashing a byte string; every
+/// 7-byte chunk is guaranteed to map to some field element.
+const BINARY_CHUNK_SIZE: usize = 7;
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of [Hasher] trait for Monolith hash function with 256-bit output.
+///
+/// The hash function is implemented according to the Monolith
+/// [specifications](https://eprint.iacr.org/2023/1025.pdf) and its permutation is compatible with
+/// the reference implementation of Monolith-64. Currently, the function is implemented only for
+/// the 64-bit field with modulus 2^64 - 2^32 + 1 (i.e., for `B` = `f64::BaseElement`).
+///
+/// The parameters used to instantiate the function are:
+/// * Field: 64-bit prime field with modulus 2^64 - 2^32 + 1.
+/// * State width: 12 field elements.
+/// * Capacity size: 4 field elements.
+/// * Number of rounds: 6.
+/// * Bars: 8-bit lookup table applied to each byte of the first 4 state elements.
+/// * Bricks: Feistel Type-3 layer `x_i <- x_i + x_{i-1}^2`.
+/// * Concrete: multiplication by a circulant MDS matrix followed by round constant addition.
+///
+/// The above parameters target 128-bit security level. The digest consists of four field elements
+/// and it can be serialized into 32 bytes (256 bits).
+///
+/// Unlike algebraic hash functions such as [Rpo256](super::Rpo256), Monolith relies on a lookup
+/// based S-Box which requires no field multiplications; this makes plain (non-circuit) evaluation
+/// of the function very fast, while keeping it efficient to arithmetize.
+///
+/// ## Sponge layout
+/// The first 8 elements of the state (elements 0 through 7) are used as rate and the remaining
+/// 4 elements (elements 8, 9, 10, and 11) are reserved for capacity. The output of the hash
+/// function comes from the first four elements of the rate portion of the state (elements 0, 1,
+/// 2, and 3).
+///
+/// ## Padding
+/// The sponge is used in overwrite mode and follows the same padding rule as
+/// [Rpo256](super::Rpo256):
+/// * If the number of elements to be hashed is a multiple of the rate width, no padding is
+///   applied and the capacity portion of the state is initialized to all zeros.
+/// * Otherwise, the first capacity element is set to ONE, and the input is padded with a single
+///   ONE element followed by as many ZERO elements as needed to fill up the rate.
+///
+/// ## Hash output consistency
+/// Functions [hash_elements()](Monolith::hash_elements), [merge()](Monolith::merge), and
+/// [merge_with_int()](Monolith::merge_with_int) are internally consistent. For example, merging
+/// two digests using [merge()](Monolith::merge) will produce the same result as hashing 8
+/// elements which make up these digests using [hash_elements()](Monolith::hash_elements)
+/// function. However, as with [Rpo256](super::Rpo256), [hash()](Monolith::hash) function is not
+/// consistent with the functions mentioned above.
+pub struct Monolith<B: StarkField>(PhantomData<B>);
+
+impl Hasher for Monolith<BaseElement> {
+    type Digest = ElementDigest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // compute the number of elements required to represent the string; we will be processing
+        // the string in 7-byte chunks, thus the number of elements will be equal to the number
+        // of such chunks (including a potential partial chunk at the end).
+        let num_elements = if bytes.len() % BINARY_CHUNK_SIZE == 0 {
+            bytes.len() / BINARY_CHUNK_SIZE
+        } else {
+            bytes.len() / BINARY_CHUNK_SIZE + 1
+        };
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to ONE if the number of elements is not a multiple of RATE_WIDTH.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        if num_elements % RATE_WIDTH != 0 {
+            state[CAPACITY_RANGE.start] = BaseElement::ONE;
+        }
+
+        // break the string into 7-byte chunks, convert each chunk into a field element, and
+        // absorb the element into the rate portion of the state.
+        let mut i = 0;
+        let mut buf = [0_u8; 8];
+        for chunk in bytes.chunks(BINARY_CHUNK_SIZE) {
+            if i < num_elements - 1 {
+                buf[..BINARY_CHUNK_SIZE].copy_from_slice(chunk);
+            } else {
+                // if we are dealing with the last chunk, it may be smaller than 7 bytes long, so
+                // we need to handle it slightly differently. we also append a byte with value 1
+                // to the end of the string; this pads the string in such a way that adding
+                // trailing zeros results in different hash
+                let chunk_len = chunk.len();
+                buf = [0_u8; 8];
+                buf[..chunk_len].copy_from_slice(chunk);
+                buf[chunk_len] = 1;
+            }
+
+            // convert the bytes into a field element and absorb it into the rate portion of the
+            // state (overwriting the previous value); if the rate is filled up, apply the Monolith
+            // permutation and start absorbing again from zero index.
+            state[RATE_RANGE.start + i] = BaseElement::new(u64::from_le_bytes(buf));
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), pad the rate with ONE followed
+        // by ZEROs and apply the Monolith permutation.
+        if i > 0 {
+            Self::pad_and_apply_permutation(&mut state, i);
+        }
+
+        // return the first 4 elements of the rate as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // initialize the state by copying the digest elements into the rate portion of the state
+        // (8 total elements); the capacity is left as all zeros because the input fills up the
+        // rate exactly and no padding is required.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[RATE_RANGE].copy_from_slice(Self::Digest::digests_as_elements(values));
+
+        // apply the Monolith permutation and return the first four elements of the rate
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // initialize the state as follows:
+        // - seed is copied into the first 4 elements of the rate portion of the state.
+        // - if the value fits into a single field element, copy it into the fifth rate element
+        //   and pad the remaining rate elements with ONE followed by ZEROs.
+        // - if the value doesn't fit into a single field element, split it into two field
+        //   elements, copy them into rate elements 5 and 6, and pad the remaining rate elements
+        //   with ONE followed by ZEROs.
+        // - in both cases, set the first capacity element to ONE since the input is not a
+        //   multiple of the rate width.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[INPUT1_RANGE].copy_from_slice(seed.as_elements());
+        state[INPUT2_RANGE.start] = BaseElement::new(value);
+        if value < BaseElement::MODULUS {
+            state[INPUT2_RANGE.start + 1] = BaseElement::ONE;
+        } else {
+            state[INPUT2_RANGE.start + 1] = BaseElement::new(value / BaseElement::MODULUS);
+            state[INPUT2_RANGE.start + 2] = BaseElement::ONE;
+        }
+        state[CAPACITY_RANGE.start] = BaseElement::ONE;
+
+        // apply the Monolith permutation and return the first four elements of the rate
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+impl ElementHasher for Monolith<BaseElement> {
+    type BaseField = BaseElement;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // convert the elements into a list of base field elements
+        let elements = E::as_base_elements(elements);
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to ONE if the number of elements is not a multiple of RATE_WIDTH.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        if elements.len() % RATE_WIDTH != 0 {
+            state[CAPACITY_RANGE.start] = BaseElement::ONE;
+        }
+
+        // absorb elements into the state one by one (overwriting the rate elements) until the rate
+        // portion of the state is filled up; then apply the Monolith permutation and start
+        // absorbing again; repeat until all elements have been absorbed
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[RATE_RANGE.start + i] = element;
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), pad the rate with ONE followed
+        // by ZEROs and apply the Monolith permutation.
+        if i > 0 {
+            Self::pad_and_apply_permutation(&mut state, i);
+        }
+
+        // return the first 4 elements of the rate as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Monolith<BaseElement> {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of rounds is set to 6 to target 128-bit security level.
+    pub const NUM_ROUNDS: usize = NUM_ROUNDS;
+
+    /// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+    /// the remaining 4 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 0 through 7 (inclusive).
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 8, 9, 10, and 11.
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 0, 1, 2, and 3.
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// Lookup table applied to each byte of a state element in the Bars layer.
+    pub const LOOKUP_TABLE: [u8; 256] = LOOKUP_TABLE;
+
+    /// First row of the circulant MDS matrix used in the Concrete layer.
+    pub const MDS_FIRST_ROW: [BaseElement; STATE_WIDTH] = MDS_FIRST_ROW;
+
+    /// Round constants added to the hasher state at the end of all but the last Monolith round.
+    pub const ROUND_CONSTANTS: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS - 1] = ROUND_CONSTANTS;
+
+    // MONOLITH PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies Monolith permutation to the provided state.
+    ///
+    /// The permutation consists of an initial Concrete layer (without round constants) followed
+    /// by [NUM_ROUNDS](Self::NUM_ROUNDS) rounds; no round constants are added in the last round.
+    pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
+        Self::apply_concrete(state);
+        for i in 0..NUM_ROUNDS {
+            Self::apply_round(state, i);
+        }
+    }
+
+    /// Monolith round function.
+    #[inline(always)]
+    pub fn apply_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        Self::apply_bars(state);
+        Self::apply_bricks(state);
+        Self::apply_concrete(state);
+        if round < NUM_ROUNDS - 1 {
+            Self::add_constants(state, &ROUND_CONSTANTS[round]);
+        }
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Pads the rate portion of the state starting at rate element `num_absorbed` with ONE
+    /// followed by ZEROs, and applies the Monolith permutation to the state.
+    #[inline(always)]
+    fn pad_and_apply_permutation(state: &mut [BaseElement; STATE_WIDTH], num_absorbed: usize) {
+        debug_assert!(num_absorbed > 0 && num_absorbed < RATE_WIDTH);
+        state[RATE_RANGE.start + num_absorbed] = BaseElement::ONE;
+        state[RATE_RANGE.start + num_absorbed + 1..RATE_RANGE.end].fill(BaseElement::ZERO);
+        Self::apply_permutation(state);
+    }
+
+    #[inline(always)]
+    fn apply_bars(state: &mut [BaseElement; STATE_WIDTH]) {
+        state[..NUM_BARS].iter_mut().for_each(|v| *v = bar(*v));
+    }
+
+    #[inline(always)]
+    fn apply_bricks(state: &mut [BaseElement; STATE_WIDTH]) {
+        // iterate in reverse so that each element is updated using the original value of the
+        // preceding element
+        for i in (1..STATE_WIDTH).rev() {
+            state[i] += state[i - 1].square();
+        }
+    }
+
+    #[inline(always)]
+    fn apply_concrete(state: &mut [BaseElement; STATE_WIDTH]) {
+        let mut result = [BaseElement::ZERO; STATE_WIDTH];
+        for (i, r) in result.iter_mut().enumerate() {
+            for (j, &s) in state.iter().enumerate() {
+                *r += MDS_FIRST_ROW[(STATE_WIDTH + j - i) % STATE_WIDTH] * s;
+            }
+        }
+        *state = result
+    }
+
+    #[inline(always)]
+    fn add_constants(state: &mut [BaseElement; STATE_WIDTH], ark: &[BaseElement; STATE_WIDTH]) {
+        state.iter_mut().zip(ark).for_each(|(s, &k)| *s += k);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Splits the canonical representation of the element into 8 bytes, applies the lookup table to
+/// each byte, and recombines the bytes into a field element.
+///
+/// The lookup table is designed so that the result is always smaller than the field modulus, and
+/// thus, is a valid field element.
+#[inline(always)]
+fn bar(value: BaseElement) -> BaseElement {
+    let mut bytes = value.as_int().to_le_bytes();
+    bytes
+        .iter_mut()
+        .for_each(|b| *b = LOOKUP_TABLE[*b as usize]);
+    BaseElement::new(u64::from_le_bytes(bytes))
+}
+
+// CONSTANTS
+// ================================================================================================
+
+/// Monolith lookup table used in the Bars layer.
+///
+/// The table is defined by the 8-bit chi-like map
+/// `S(y) = (y ^ ((!y <<< 1) & (y <<< 2) & (y <<< 3))) <<< 1`, where `<<<` denotes a rotation to
+/// the left within a byte.
+const LOOKUP_TABLE: [u8; 256] = [
+    0, 2, 4, 22, 8, 10, 44, 46, 16, 18, 20, 6, 88, 90, 92, 94, 32, 34, 36, 54, 40, 42, 12, 14, 176,
+    178, 180, 166, 184, 186, 188, 190, 64, 66, 68, 86, 72, 74, 108, 110, 80, 82, 84, 70, 24, 26,
+    28, 30, 97, 99, 101, 119, 105, 107, 77, 79, 113, 115, 117, 103, 121, 123, 125, 127, 128, 130,
+    132, 150, 136, 138, 172, 174, 144, 146, 148, 134, 216, 218, 220, 222, 160, 162, 164, 182, 168,
+    170, 140, 142, 48, 50, 52, 38, 56, 58, 60, 62, 194, 192, 198, 212, 202, 200, 238, 236, 210,
+    208, 214, 196, 154, 152, 158, 156, 226, 224, 230, 244, 234, 232, 206, 204, 242, 240, 246, 228,
+    250, 248, 254, 252, 1, 11, 5, 23, 9, 3, 45, 47, 17, 27, 21, 7, 89, 83, 93, 95, 33, 43, 37, 55,
+    41, 35, 13, 15, 177, 187, 181, 167, 185, 179, 189, 191, 65, 75, 69, 87, 73, 67, 109, 111, 81,
+    91, 85, 71, 25, 19, 29, 31, 96, 106, 100, 118, 104, 98, 76, 78, 112, 122, 116, 102, 120, 114,
+    124, 126, 133, 139, 129, 151, 141, 131, 169, 175, 149, 155, 145, 135, 221, 211, 217, 223, 165,
+    171, 161, 183, 173, 163, 137, 143, 53, 59, 49, 39, 61, 51, 57, 63, 197, 203, 193, 215, 205,
+    195, 233, 239, 213, 219, 209, 199, 157, 147, 153, 159, 229, 235, 225, 247, 237, 227, 201, 207,
+    245, 251, 241, 231, 253, 243, 249, 255,
+];
+
+/// First row of the Monolith MDS matrix.
+///
+/// The matrix is circulant; each subsequent row is the previous row rotated one element to the
+/// right. This is the same matrix as the one used by [Rpo256](super::Rpo256).
+const MDS_FIRST_ROW: [BaseElement; STATE_WIDTH] = [
+    BaseElement::new(7),
+    BaseElement::new(23),
+    BaseElement::new(8),
+    BaseElement::new(26),
+    BaseElement::new(13),
+    BaseElement::new(10),
+    BaseElement::new(9),
+    BaseElement::new(7),
+    BaseElement::new(6),
+    BaseElement::new(22),
+    BaseElement::new(21),
+    BaseElement::new(8),
+];
+
+/// Round constants added to the hasher state at the end of the first 5 Monolith rounds.
+///
+/// The constants are generated by seeding SHAKE128 with the string "Monolith", followed by the
+/// state width and the number of rounds (one byte each), the field modulus (8 bytes, little
+/// endian), and the bit sizes of the 8 lookup limbs (one byte each); the output is then read in
+/// 8-byte little-endian chunks, and the chunks which are not smaller than the field modulus are
+/// discarded.
+const ROUND_CONSTANTS: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS - 1] = [
+    [
+        BaseElement::new(13596126580325903823),
+        BaseElement::new(5676126986831820406),
+        BaseElement::new(11349149288412960427),
+        BaseElement::new(3368797843020733411),
+        BaseElement::new(16240671731749717664),
+        BaseElement::new(9273190757374900239),
+        BaseElement::new(14446552112110239438),
+        BaseElement::new(4033077683985131644),
+        BaseElement::new(4291229347329361293),
+        BaseElement::new(13231607645683636062),
+        BaseElement::new(1383651072186713277),
+        BaseElement::new(8898815177417587567),
+    ],
+    [
+        BaseElement::new(2383619671172821638),
+        BaseElement::new(6065528368924797662),
+        BaseElement::new(16737578966352303081),
+        BaseElement::new(2661700069680749654),
+        BaseElement::new(7414030722730336790),
+        BaseElement::new(18124970299993404776),
+        BaseElement::new(9169923000283400738),
+        BaseElement::new(15832813151034110977),
+        BaseElement::new(16245117847613094506),
+        BaseElement::new(11056181639108379773),
+        BaseElement::new(10546400734398052938),
+        BaseElement::new(8443860941261719174),
+    ],
+    [
+        BaseElement::new(15799082741422909885),
+        BaseElement::new(13421235861052008152),
+        BaseElement::new(15448208253823605561),
+        BaseElement::new(2540286744040770964),
+        BaseElement::new(2895626806801935918),
+        BaseElement::new(8644593510196221619),
+        BaseElement::new(17722491003064835823),
+        BaseElement::new(5166255496419771636),
+        BaseElement::new(1015740739405252346),
+        BaseElement::new(4400043467547597488),
+        BaseElement::new(5176473243271652644),
+        BaseElement::new(4517904634837939508),
+    ],
+    [
+        BaseElement::new(18341030605319882173),
+        BaseElement::new(13366339881666916534),
+        BaseElement::new(6291492342503367536),
+        BaseElement::new(10004214885638819819),
+        BaseElement::new(4748655089269860551),
+        BaseElement::new(1520762444865670308),
+        BaseElement::new(8393589389936386108),
+        BaseElement::new(11025183333304586284),
+        BaseElement::new(5993305003203422738),
+        BaseElement::new(458912836931247573),
+        BaseElement::new(5947003897778655410),
+        BaseElement::new(17184667486285295106),
+    ],
+    [
+        BaseElement::new(15710528677110011358),
+        BaseElement::new(8929476121507374707),
+        BaseElement::new(2351989866172789037),
+        BaseElement::new(11264145846854799752),
+        BaseElement::new(14924075362538455764),
+        BaseElement::new(10107004551857451916),
+        BaseElement::new(18325221206052792232),
+        BaseElement::new(16751515052585522105),
+        BaseElement::new(15305034267720085905),
+        BaseElement::new(15639149412312342017),
+        BaseElement::new(14624541102106656564),
+        BaseElement::new(3542311898554959098),
+    ],
+];
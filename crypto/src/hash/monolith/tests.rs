@@ -0,0 +1,263 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    bar, BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, Monolith, StarkField,
+    LOOKUP_TABLE, NUM_BARS, NUM_ROUNDS, ROUND_CONSTANTS, STATE_WIDTH,
+};
+use core::convert::TryInto;
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake128,
+};
+
+use rand_utils::{rand_array, rand_value};
+
+type Monolith64 = Monolith<BaseElement>;
+
+#[test]
+fn test_lookup_table() {
+    // the table must be a permutation of bytes defined by the chi-like map of the Bars layer
+    let rotl = |y: u8, n: u32| y.rotate_left(n);
+    let mut seen = [false; 256];
+    for (y, &s) in LOOKUP_TABLE.iter().enumerate() {
+        let y = y as u8;
+        let expected = rotl(y ^ (!rotl(y, 1) & rotl(y, 2) & rotl(y, 3)), 1);
+        assert_eq!(expected, s);
+        assert!(!seen[s as usize]);
+        seen[s as usize] = true;
+    }
+}
+
+#[test]
+fn test_bar() {
+    assert_eq!(BaseElement::ZERO, bar(BaseElement::ZERO));
+    assert_eq!(BaseElement::new(2), bar(BaseElement::ONE));
+    assert_eq!(BaseElement::new(0xffffffff00000000), bar(-BaseElement::ONE));
+
+    // the result must always be a valid field element
+    for _ in 0..1000 {
+        let e: BaseElement = rand_value();
+        let bytes = e.as_int().to_le_bytes().map(|b| LOOKUP_TABLE[b as usize]);
+        assert!(u64::from_le_bytes(bytes) < BaseElement::MODULUS);
+        assert_eq!(BaseElement::new(u64::from_le_bytes(bytes)), bar(e));
+    }
+}
+
+#[test]
+fn test_round_constants() {
+    let mut shake = Shake128::default();
+    shake.update(b"Monolith");
+    shake.update(&[STATE_WIDTH as u8, NUM_ROUNDS as u8]);
+    shake.update(&BaseElement::MODULUS.to_le_bytes());
+    shake.update(&[8; 8]);
+    let mut reader = shake.finalize_xof();
+
+    for round_constants in ROUND_CONSTANTS.iter() {
+        for &constant in round_constants.iter() {
+            let expected = loop {
+                let mut buf = [0_u8; 8];
+                reader.read(&mut buf);
+                let value = u64::from_le_bytes(buf);
+                if value < BaseElement::MODULUS {
+                    break value;
+                }
+            };
+            assert_eq!(expected, constant.as_int());
+        }
+    }
+}
+
+#[test]
+fn test_bars_and_bricks() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut expected = state;
+    expected[..NUM_BARS].iter_mut().for_each(|v| *v = bar(*v));
+    let mut actual = state;
+    Monolith64::apply_bars(&mut actual);
+    assert_eq!(expected, actual);
+
+    let mut expected = state;
+    for i in 1..STATE_WIDTH {
+        expected[i] = state[i] + state[i - 1].square();
+    }
+    let mut actual = state;
+    Monolith64::apply_bricks(&mut actual);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn apply_permutation() {
+    let mut state: [BaseElement; STATE_WIDTH] = (0..STATE_WIDTH as u64)
+        .map(BaseElement::new)
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+
+    Monolith64::apply_permutation(&mut state);
+
+    // expected values are the known-answer test of the reference implementation of Monolith-64
+    // for input [0, 1, ..., 11]
+    let expected = vec![
+        BaseElement::new(5867581605548782913),
+        BaseElement::new(588867029099903233),
+        BaseElement::new(6043817495575026667),
+        BaseElement::new(805786589926590032),
+        BaseElement::new(9919982299747097782),
+        BaseElement::new(6718641691835914685),
+        BaseElement::new(7951881005429661950),
+        BaseElement::new(15453177927755089358),
+        BaseElement::new(974633365445157727),
+        BaseElement::new(9654662171963364206),
+        BaseElement::new(6281307445101925412),
+        BaseElement::new(13745376999934453119),
+    ];
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn hash_elements_test_vectors() {
+    // the sponge construction is not a part of the Monolith reference implementation, and thus,
+    // there are no reference vectors for it; these are regression values computed by hashing
+    // sequences [0, 1, ..., n - 1] for n in [1, 7, 8, 9, 16, 17] with an independent model of the
+    // sponge construction described above built on top of the reference permutation. The inputs
+    // cover lengths which are shorter than, equal to, and longer than the rate width.
+    let lengths = [1_u64, 7, 8, 9, 16, 17];
+    let expected = [
+        [
+            BaseElement::new(1694235209307113441),
+            BaseElement::new(16100732432802374008),
+            BaseElement::new(9690506322890284553),
+            BaseElement::new(11835531059887141894),
+        ],
+        [
+            BaseElement::new(11827066999930371761),
+            BaseElement::new(17564257659950822964),
+            BaseElement::new(3248845422673071252),
+            BaseElement::new(4029948895160336413),
+        ],
+        [
+            BaseElement::new(8597293992452543654),
+            BaseElement::new(13251886779405042379),
+            BaseElement::new(6979248705951146223),
+            BaseElement::new(15358842403937303290),
+        ],
+        [
+            BaseElement::new(6966726154539205739),
+            BaseElement::new(3737058470224585986),
+            BaseElement::new(965437426095220861),
+            BaseElement::new(5662401132617390561),
+        ],
+        [
+            BaseElement::new(28217501033776848),
+            BaseElement::new(8722274869521667005),
+            BaseElement::new(7369810671744834784),
+            BaseElement::new(15011791226684973851),
+        ],
+        [
+            BaseElement::new(1740870836518972660),
+            BaseElement::new(5826027739170664),
+            BaseElement::new(8282038334837748731),
+            BaseElement::new(12543345573035916653),
+        ],
+    ];
+
+    for (&n, expected) in lengths.iter().zip(expected) {
+        let elements = (0..n).map(BaseElement::new).collect::<Vec<_>>();
+        let result = Monolith64::hash_elements(&elements);
+        assert_eq!(ElementDigest::new(expected), result);
+    }
+}
+
+#[test]
+fn merge_test_vector() {
+    // merging two digests is the same as hashing their 8 elements, and thus, this is the regression
+    // value for n = 8 above
+    let elements = (0..8).map(BaseElement::new).collect::<Vec<_>>();
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    let expected = [
+        BaseElement::new(8597293992452543654),
+        BaseElement::new(13251886779405042379),
+        BaseElement::new(6979248705951146223),
+        BaseElement::new(15358842403937303290),
+    ];
+
+    assert_eq!(ElementDigest::new(expected), Monolith64::merge(&digests));
+}
+
+#[test]
+fn hash_elements_vs_merge() {
+    let elements: [BaseElement; 8] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    let m_result = Monolith64::merge(&digests);
+    let h_result = Monolith64::hash_elements(&elements);
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = Monolith64::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = Monolith64::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = Monolith64::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = Monolith64::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Monolith64::hash(&[1_u8, 2, 3]);
+    let r2 = Monolith64::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = Monolith64::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = Monolith64::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with multiple zeros
+    let r1 = Monolith64::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0]);
+    let r2 = Monolith64::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0, 0, 0]);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    // appending a zero element should result in a different hash
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = Monolith64::hash_elements(&e1);
+    let r2 = Monolith64::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
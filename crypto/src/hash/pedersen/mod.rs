@@ -60,37 +60,40 @@ impl<B: StarkField> ElementHasher for Pedersen_256<B> {
     type BaseField = B;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
-
-        let len = Fe::from(elements.len());
-        let montgomery = Fe::from_bytes_be(&[0x00u8,0x40,0x00,0x00,0x00,0x00,0x00,0x01,
-                                             0x10u8,0x00,0x00,0x00,0x00,0x00,0x01,0x21,
-                                             0x00u8,0x00,0x00,0x00,0x00,0x00,0x00,0x00,
-                                             0x00u8,0x00,0x00,0x00,0x00,0x00,0x00,0x00]).unwrap();
-
-        let elements_as_chunks = E::elements_as_bytes(elements).chunks(E::ELEMENT_BYTES);
-        
-        let mut data: Vec<u8> = vec![0; elements.len()*E::ELEMENT_BYTES];
-
-        for (dst, src) in data.chunks_mut(E::ELEMENT_BYTES).zip(elements_as_chunks) {
-
-            let element = {
-                let mut chunk = [0u8; 32];
-                write_be_bytes(&src, &mut chunk);
-                Fe::from_bytes_be(&chunk).unwrap()
-            } * montgomery;
-
-            dst.copy_from_slice(&element.to_bytes_be());
-
+        // elements are serialized in a single pass: each base field element is converted into
+        // Montgomery form and written into the buffer in little-endian byte order, which is the
+        // layout expected by pedersen_hash()
+        let montgomery = montgomery_factor();
+        let bytes = E::elements_as_bytes(elements);
+        let mut data: Vec<u8> = vec![0; bytes.len() / B::ELEMENT_BYTES * 32];
+        for (dst, src) in data.chunks_mut(32).zip(bytes.chunks(B::ELEMENT_BYTES)) {
+            let element = to_montgomery(src, &montgomery);
+            write_be_bytes(&element.to_bytes_be(), dst.try_into().unwrap());
         }
 
-        let mut data_endian: Vec<u8> = vec![0; elements.len()*E::ELEMENT_BYTES];
-        for (dst, src) in data_endian.chunks_mut(32).zip(data.chunks(32)) {
+        ByteDigest(pedersen_hash(&data))
+    }
+}
 
-            write_be_bytes(&src, dst.try_into().unwrap()); 
-        }
+// HELPER FUNCTIONS
+// ================================================================================================
 
-        ByteDigest(pedersen_hash(data_endian.as_slice()))
-    }
+/// Returns the factor by which a little-endian element needs to be multiplied to obtain its
+/// Montgomery form.
+fn montgomery_factor() -> Fe {
+    Fe::from_bytes_be(&[
+        0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+        0x21, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00,
+    ])
+    .unwrap()
+}
+
+/// Converts a little-endian encoded element into Montgomery form.
+fn to_montgomery(bytes: &[u8], montgomery: &Fe) -> Fe {
+    let mut chunk = [0u8; 32];
+    write_be_bytes(bytes, &mut chunk);
+    Fe::from_bytes_be(&chunk).unwrap() * *montgomery
 }
 
 fn write_be_bytes(value: &[u8], out: &mut [u8; 32]) {
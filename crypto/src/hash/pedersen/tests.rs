@@ -2,7 +2,9 @@
 use crate::hash::pedersen::pedersen_hash;
 use super::{Pedersen_256, ElementHasher, Hasher};
 use math::{fields::f252::BaseElement, FieldElement};
-use rand_utils::rand_array;
+use rand_utils::{rand_array, rand_vector};
+use starknet_ff::FieldElement as Fe;
+use utils::collections::Vec;
 
 #[test]
 fn hash_padding() {
@@ -55,4 +57,53 @@ fn test_pedersen_hash() {
              0xFFu8, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x03];
     let digest = pedersen_hash(&e);
     println!("test_pedersen_hash {:?}", digest);
-}
\ No newline at end of file
+}
+
+#[test]
+fn hash_elements_matches_two_pass_serialization() {
+    for num_elements in [0, 1, 2, 7, 8, 64, 255] {
+        let elements: Vec<BaseElement> = rand_vector(num_elements);
+        assert_eq!(
+            hash_elements_two_pass(&elements),
+            Pedersen_256::hash_elements(&elements).0
+        );
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes element hash using the original serialization procedure, which converted elements
+/// into Montgomery form in one buffer and reversed their byte order in another.
+fn hash_elements_two_pass(elements: &[BaseElement]) -> [u8; 32] {
+    let montgomery = Fe::from_bytes_be(&[
+        0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+        0x21, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00,
+    ])
+    .unwrap();
+
+    let mut data: Vec<u8> = vec![0; elements.len() * BaseElement::ELEMENT_BYTES];
+    let elements_as_chunks =
+        BaseElement::elements_as_bytes(elements).chunks(BaseElement::ELEMENT_BYTES);
+    for (dst, src) in data
+        .chunks_mut(BaseElement::ELEMENT_BYTES)
+        .zip(elements_as_chunks)
+    {
+        let mut chunk = [0u8; 32];
+        for (s, d) in src.iter().rev().zip(chunk.iter_mut()) {
+            *d = *s;
+        }
+        let element = Fe::from_bytes_be(&chunk).unwrap() * montgomery;
+        dst.copy_from_slice(&element.to_bytes_be());
+    }
+
+    let mut data_endian: Vec<u8> = vec![0; data.len()];
+    for (dst, src) in data_endian.chunks_mut(32).zip(data.chunks(32)) {
+        for (s, d) in src.iter().rev().zip(dst.iter_mut()) {
+            *d = *s;
+        }
+    }
+
+    pedersen_hash(&data_endian)
+}
@@ -0,0 +1,502 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::super::{rescue::ElementDigest, ElementHasher, Hasher};
+use super::Poseidon2_256;
+use core::convert::TryInto;
+use core::ops::Range;
+use math::{fields::f64::BaseElement, FieldElement, StarkField};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+/// the remaining 4 elements are reserved for capacity.
+const STATE_WIDTH: usize = 12;
+
+/// The rate portion of the state is located in elements 0 through 7.
+const RATE_RANGE: Range<usize> = 0..8;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+const INPUT1_RANGE: Range<usize> = 0..4;
+const INPUT2_RANGE: Range<usize> = 4..8;
+
+/// The capacity portion of the state is located in elements 8, 9, 10, and 11.
+const CAPACITY_RANGE: Range<usize> = 8..12;
+
+/// The output of the hash function is a digest which consists of 4 field elements or 32 bytes.
+///
+/// The digest is returned from state elements 0, 1, 2, and 3 (the first four elements of the
+/// rate portion).
+const DIGEST_RANGE: Range<usize> = 0..4;
+
+/// The number of full (external) rounds; half of them are applied before the partial rounds and
+/// the other half after.
+const NUM_FULL_ROUNDS: usize = 8;
+
+/// The number of partial (internal) rounds.
+const NUM_PARTIAL_ROUNDS: usize = 22;
+
+/// The number of bytes packed into a single field element when hashing a byte string; every
+/// 7-byte chunk is guaranteed to map to some field element.
+const BINARY_CHUNK_SIZE: usize = 7;
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+impl Hasher for Poseidon2_256<BaseElement> {
+    type Digest = ElementDigest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // compute the number of elements required to represent the string; we will be processing
+        // the string in 7-byte chunks, thus the number of elements will be equal to the number
+        // of such chunks (including a potential partial chunk at the end).
+        let num_elements = if bytes.len() % BINARY_CHUNK_SIZE == 0 {
+            bytes.len() / BINARY_CHUNK_SIZE
+        } else {
+            bytes.len() / BINARY_CHUNK_SIZE + 1
+        };
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to ONE if the number of elements is not a multiple of RATE_WIDTH.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        if num_elements % RATE_WIDTH != 0 {
+            state[CAPACITY_RANGE.start] = BaseElement::ONE;
+        }
+
+        // break the string into 7-byte chunks, convert each chunk into a field element, and
+        // absorb the element into the rate portion of the state.
+        let mut i = 0;
+        let mut buf = [0_u8; 8];
+        for chunk in bytes.chunks(BINARY_CHUNK_SIZE) {
+            if i < num_elements - 1 {
+                buf[..BINARY_CHUNK_SIZE].copy_from_slice(chunk);
+            } else {
+                // if we are dealing with the last chunk, it may be smaller than 7 bytes long, so
+                // we need to handle it slightly differently. we also append a byte with value 1
+                // to the end of the string; this pads the string in such a way that adding
+                // trailing zeros results in different hash
+                let chunk_len = chunk.len();
+                buf = [0_u8; 8];
+                buf[..chunk_len].copy_from_slice(chunk);
+                buf[chunk_len] = 1;
+            }
+
+            // convert the bytes into a field element and absorb it into the rate portion of the
+            // state (overwriting the previous value); if the rate is filled up, apply the
+            // Poseidon2 permutation and start absorbing again from zero index.
+            state[RATE_RANGE.start + i] = BaseElement::new(u64::from_le_bytes(buf));
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), pad the rate with ONE followed
+        // by ZEROs and apply the Poseidon2 permutation.
+        if i > 0 {
+            Self::pad_and_apply_permutation(&mut state, i);
+        }
+
+        // return the first 4 elements of the rate as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // initialize the state by copying the digest elements into the rate portion of the state
+        // (8 total elements); the capacity is left as all zeros because the input fills up the
+        // rate exactly and no padding is required.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[RATE_RANGE].copy_from_slice(Self::Digest::digests_as_elements(values));
+
+        // apply the Poseidon2 permutation and return the first four elements of the rate
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // initialize the state as follows:
+        // - seed is copied into the first 4 elements of the rate portion of the state.
+        // - if the value fits into a single field element, copy it into the fifth rate element
+        //   and pad the remaining rate elements with ONE followed by ZEROs.
+        // - if the value doesn't fit into a single field element, split it into two field
+        //   elements, copy them into rate elements 5 and 6, and pad the remaining rate elements
+        //   with ONE followed by ZEROs.
+        // - in both cases, set the first capacity element to ONE since the input is not a
+        //   multiple of the rate width.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[INPUT1_RANGE].copy_from_slice(seed.as_elements());
+        state[INPUT2_RANGE.start] = BaseElement::new(value);
+        if value < BaseElement::MODULUS {
+            state[INPUT2_RANGE.start + 1] = BaseElement::ONE;
+        } else {
+            state[INPUT2_RANGE.start + 1] = BaseElement::new(value / BaseElement::MODULUS);
+            state[INPUT2_RANGE.start + 2] = BaseElement::ONE;
+        }
+        state[CAPACITY_RANGE.start] = BaseElement::ONE;
+
+        // apply the Poseidon2 permutation and return the first four elements of the rate
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+impl ElementHasher for Poseidon2_256<BaseElement> {
+    type BaseField = BaseElement;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // convert the elements into a list of base field elements
+        let elements = E::as_base_elements(elements);
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to ONE if the number of elements is not a multiple of RATE_WIDTH.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        if elements.len() % RATE_WIDTH != 0 {
+            state[CAPACITY_RANGE.start] = BaseElement::ONE;
+        }
+
+        // absorb elements into the state one by one (overwriting the rate elements) until the rate
+        // portion of the state is filled up; then apply the Poseidon2 permutation and start
+        // absorbing again; repeat until all elements have been absorbed
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[RATE_RANGE.start + i] = element;
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), pad the rate with ONE followed
+        // by ZEROs and apply the Poseidon2 permutation.
+        if i > 0 {
+            Self::pad_and_apply_permutation(&mut state, i);
+        }
+
+        // return the first 4 elements of the rate as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Poseidon2_256<BaseElement> {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of full (external) rounds.
+    pub const NUM_FULL_ROUNDS: usize = NUM_FULL_ROUNDS;
+
+    /// The number of partial (internal) rounds.
+    pub const NUM_PARTIAL_ROUNDS: usize = NUM_PARTIAL_ROUNDS;
+
+    /// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+    /// the remaining 4 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 0 through 7 (inclusive).
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 8, 9, 10, and 11.
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 0, 1, 2, and 3.
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// Round constants added to the entire state in the full rounds.
+    pub const EXTERNAL_ROUND_CONSTANTS: [[BaseElement; STATE_WIDTH]; NUM_FULL_ROUNDS] =
+        EXTERNAL_ROUND_CONSTANTS;
+
+    /// Round constants added to the first state element in the partial rounds.
+    pub const INTERNAL_ROUND_CONSTANTS: [BaseElement; NUM_PARTIAL_ROUNDS] =
+        INTERNAL_ROUND_CONSTANTS;
+
+    /// Diagonal `d` of the internal matrix `1 + diag(d)` (i.e., the diagonal of the matrix minus
+    /// one), where `1` is the matrix with all entries set to one.
+    pub const INTERNAL_MATRIX_DIAG: [BaseElement; STATE_WIDTH] = INTERNAL_MATRIX_DIAG;
+
+    // POSEIDON2 PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies Poseidon2 permutation to the provided state.
+    ///
+    /// The permutation consists of an initial multiplication by the external matrix, followed by
+    /// half of the full rounds, all of the partial rounds, and the remaining full rounds.
+    pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
+        Self::apply_external_matrix(state);
+        for ark in EXTERNAL_ROUND_CONSTANTS[..NUM_FULL_ROUNDS / 2].iter() {
+            Self::apply_full_round(state, ark);
+        }
+        for &ark in INTERNAL_ROUND_CONSTANTS.iter() {
+            Self::apply_partial_round(state, ark);
+        }
+        for ark in EXTERNAL_ROUND_CONSTANTS[NUM_FULL_ROUNDS / 2..].iter() {
+            Self::apply_full_round(state, ark);
+        }
+    }
+
+    /// Poseidon2 full (external) round function.
+    #[inline(always)]
+    pub fn apply_full_round(
+        state: &mut [BaseElement; STATE_WIDTH],
+        ark: &[BaseElement; STATE_WIDTH],
+    ) {
+        state
+            .iter_mut()
+            .zip(ark)
+            .for_each(|(s, &k)| *s = sbox(*s + k));
+        Self::apply_external_matrix(state);
+    }
+
+    /// Poseidon2 partial (internal) round function.
+    #[inline(always)]
+    pub fn apply_partial_round(state: &mut [BaseElement; STATE_WIDTH], ark: BaseElement) {
+        state[0] = sbox(state[0] + ark);
+        Self::apply_internal_matrix(state);
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Pads the rate portion of the state starting at rate element `num_absorbed` with ONE
+    /// followed by ZEROs, and applies the Poseidon2 permutation to the state.
+    #[inline(always)]
+    fn pad_and_apply_permutation(state: &mut [BaseElement; STATE_WIDTH], num_absorbed: usize) {
+        debug_assert!(num_absorbed > 0 && num_absorbed < RATE_WIDTH);
+        state[RATE_RANGE.start + num_absorbed] = BaseElement::ONE;
+        state[RATE_RANGE.start + num_absorbed + 1..RATE_RANGE.end].fill(BaseElement::ZERO);
+        Self::apply_permutation(state);
+    }
+
+    /// Multiplies the state by the external matrix `circ(2 * M4, M4, M4)`, where `M4` is the 4x4
+    /// MDS matrix from the Poseidon2 specification.
+    #[inline(always)]
+    fn apply_external_matrix(state: &mut [BaseElement; STATE_WIDTH]) {
+        for chunk in state.chunks_exact_mut(4) {
+            apply_m4(chunk.try_into().unwrap());
+        }
+
+        let mut sums = [BaseElement::ZERO; 4];
+        for chunk in state.chunks_exact(4) {
+            sums.iter_mut().zip(chunk).for_each(|(s, &v)| *s += v);
+        }
+        for chunk in state.chunks_exact_mut(4) {
+            chunk.iter_mut().zip(sums).for_each(|(v, s)| *v += s);
+        }
+    }
+
+    /// Multiplies the state by the internal matrix `1 + diag(d)`.
+    #[inline(always)]
+    fn apply_internal_matrix(state: &mut [BaseElement; STATE_WIDTH]) {
+        let sum = state.iter().fold(BaseElement::ZERO, |acc, &v| acc + v);
+        state
+            .iter_mut()
+            .zip(INTERNAL_MATRIX_DIAG)
+            .for_each(|(v, d)| *v = *v * d + sum);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes x^7.
+#[inline(always)]
+fn sbox(x: BaseElement) -> BaseElement {
+    let x2 = x.square();
+    let x4 = x2.square();
+    x4 * x2 * x
+}
+
+/// Multiplies the provided 4 elements by the matrix
+/// `[[5, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]]` using 8 additions and 4
+/// doublings.
+#[inline(always)]
+fn apply_m4(x: &mut [BaseElement; 4]) {
+    let t0 = x[0] + x[1];
+    let t1 = x[2] + x[3];
+    let t2 = x[1].double() + t1;
+    let t3 = x[3].double() + t0;
+    let t4 = t1.double().double() + t3;
+    let t5 = t0.double().double() + t2;
+    let t6 = t3 + t5;
+    let t7 = t2 + t4;
+    *x = [t6, t5, t7, t4];
+}
+
+// CONSTANTS
+// ================================================================================================
+
+/// Diagonal of the internal matrix minus one; these are the values used by the reference
+/// instance of Poseidon2 over the 64-bit field with state width 12.
+const INTERNAL_MATRIX_DIAG: [BaseElement; STATE_WIDTH] = [
+    BaseElement::new(14102670999874605824),
+    BaseElement::new(15585654191999307702),
+    BaseElement::new(940187017142450255),
+    BaseElement::new(8747386241522630711),
+    BaseElement::new(6750641561540124747),
+    BaseElement::new(7440998025584530007),
+    BaseElement::new(6136358134615751536),
+    BaseElement::new(12413576830284969611),
+    BaseElement::new(11675438539028694709),
+    BaseElement::new(17580553691069642926),
+    BaseElement::new(892707462476851331),
+    BaseElement::new(15167485180850043744),
+];
+
+/// Round constants added to the hasher state in the first and the last 4 full rounds.
+///
+/// The constants are generated using the Grain LFSR initialized for a prime field, x^alpha S-Box,
+/// 64-bit field size, state width 12, 8 full rounds, and 22 partial rounds. Same as in the
+/// reference implementation, constants are drawn in the order in which they are used: 12 for
+/// each of the first 4 full rounds, one for each partial round (see [INTERNAL_ROUND_CONSTANTS]),
+/// and 12 for each of the last 4 full rounds.
+const EXTERNAL_ROUND_CONSTANTS: [[BaseElement; STATE_WIDTH]; NUM_FULL_ROUNDS] = [
+    [
+        BaseElement::new(1431286215153372998),
+        BaseElement::new(3509349009260703107),
+        BaseElement::new(2289575380984896342),
+        BaseElement::new(10625215922958251110),
+        BaseElement::new(17137022507167291684),
+        BaseElement::new(17143426961497010024),
+        BaseElement::new(9589775313463224365),
+        BaseElement::new(7736066733515538648),
+        BaseElement::new(2217569167061322248),
+        BaseElement::new(10394930802584583083),
+        BaseElement::new(4612393375016695705),
+        BaseElement::new(5332470884919453534),
+    ],
+    [
+        BaseElement::new(8724526834049581439),
+        BaseElement::new(17673787971454860688),
+        BaseElement::new(2519987773101056005),
+        BaseElement::new(7999687124137420323),
+        BaseElement::new(18312454652563306701),
+        BaseElement::new(15136091233824155669),
+        BaseElement::new(1257110570403430003),
+        BaseElement::new(5665449074466664773),
+        BaseElement::new(16178737609685266571),
+        BaseElement::new(52855143527893348),
+        BaseElement::new(8084454992943870230),
+        BaseElement::new(2597062441266647183),
+    ],
+    [
+        BaseElement::new(3342624911463171251),
+        BaseElement::new(6781356195391537436),
+        BaseElement::new(4697929572322733707),
+        BaseElement::new(4179687232228901671),
+        BaseElement::new(17841073646522133059),
+        BaseElement::new(18340176721233187897),
+        BaseElement::new(13152929999122219197),
+        BaseElement::new(6306257051437840427),
+        BaseElement::new(4974451914008050921),
+        BaseElement::new(11258703678970285201),
+        BaseElement::new(581736081259960204),
+        BaseElement::new(18323286026903235604),
+    ],
+    [
+        BaseElement::new(10250026231324330997),
+        BaseElement::new(13321947507807660157),
+        BaseElement::new(13020725208899496943),
+        BaseElement::new(11416990495425192684),
+        BaseElement::new(7221795794796219413),
+        BaseElement::new(2607917872900632985),
+        BaseElement::new(2591896057192169329),
+        BaseElement::new(10485489452304998145),
+        BaseElement::new(9480186048908910015),
+        BaseElement::new(2645141845409940474),
+        BaseElement::new(16242299839765162610),
+        BaseElement::new(12203738590896308135),
+    ],
+    [
+        BaseElement::new(14306783492963476045),
+        BaseElement::new(12653264875831356889),
+        BaseElement::new(10887434669785806501),
+        BaseElement::new(7221072982690633460),
+        BaseElement::new(9953585853856674407),
+        BaseElement::new(13497620366078753434),
+        BaseElement::new(18140292631504202243),
+        BaseElement::new(17311934738088402529),
+        BaseElement::new(6686302214424395771),
+        BaseElement::new(11193071888943695519),
+        BaseElement::new(10233795775801758543),
+        BaseElement::new(3362219552562939863),
+    ],
+    [
+        BaseElement::new(8595401306696186761),
+        BaseElement::new(7753411262943026561),
+        BaseElement::new(12415218859476220947),
+        BaseElement::new(12517451587026875834),
+        BaseElement::new(3257008032900598499),
+        BaseElement::new(2187469039578904770),
+        BaseElement::new(657675168296710415),
+        BaseElement::new(8659969869470208989),
+        BaseElement::new(12526098871288378639),
+        BaseElement::new(12525853395769009329),
+        BaseElement::new(15388161689979551704),
+        BaseElement::new(7880966905416338909),
+    ],
+    [
+        BaseElement::new(2911694411222711481),
+        BaseElement::new(6420652251792580406),
+        BaseElement::new(323544930728360053),
+        BaseElement::new(11718666476052241225),
+        BaseElement::new(2449132068789045592),
+        BaseElement::new(17993014181992530560),
+        BaseElement::new(15161788952257357966),
+        BaseElement::new(3788504801066818367),
+        BaseElement::new(1282111773460545571),
+        BaseElement::new(8849495164481705550),
+        BaseElement::new(8380852402060721190),
+        BaseElement::new(2161980224591127360),
+    ],
+    [
+        BaseElement::new(2440151485689245146),
+        BaseElement::new(17521895002090134367),
+        BaseElement::new(13821005335130766955),
+        BaseElement::new(17513705631114265826),
+        BaseElement::new(17068447856797239529),
+        BaseElement::new(17964439003977043993),
+        BaseElement::new(5685000919538239429),
+        BaseElement::new(11615940660682589106),
+        BaseElement::new(2522854885180605258),
+        BaseElement::new(12584118968072796115),
+        BaseElement::new(17841258728624635591),
+        BaseElement::new(10821564568873127316),
+    ],
+];
+
+/// Round constants added to the first state element in the partial rounds.
+const INTERNAL_ROUND_CONSTANTS: [BaseElement; NUM_PARTIAL_ROUNDS] = [
+    BaseElement::new(5395176197344543510),
+    BaseElement::new(17941136338888340715),
+    BaseElement::new(7559392505546762987),
+    BaseElement::new(549633128904721280),
+    BaseElement::new(15658455328409267684),
+    BaseElement::new(10078371877170729592),
+    BaseElement::new(2349868247408080783),
+    BaseElement::new(13105911261634181239),
+    BaseElement::new(12868653202234053626),
+    BaseElement::new(9471330315555975806),
+    BaseElement::new(4580289636625406680),
+    BaseElement::new(13222733136951421572),
+    BaseElement::new(4555032575628627551),
+    BaseElement::new(7619130111929922899),
+    BaseElement::new(4547848507246491777),
+    BaseElement::new(5662043532568004632),
+    BaseElement::new(15723873049665279492),
+    BaseElement::new(13585630674756818185),
+    BaseElement::new(6990417929677264473),
+    BaseElement::new(6373257983538884779),
+    BaseElement::new(1005856792729125863),
+    BaseElement::new(17850970025369572891),
+];
@@ -0,0 +1,242 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    super::grain::Grain, apply_m4, BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher,
+    Poseidon2_256, StarkField, EXTERNAL_ROUND_CONSTANTS, INTERNAL_MATRIX_DIAG,
+    INTERNAL_ROUND_CONSTANTS, NUM_FULL_ROUNDS, NUM_PARTIAL_ROUNDS, STATE_WIDTH,
+};
+use core::convert::TryInto;
+use rand_utils::{rand_array, rand_value};
+use utils::collections::Vec;
+
+type Poseidon2 = Poseidon2_256<BaseElement>;
+
+#[test]
+fn test_round_constants() {
+    let mut grain = Grain::new(
+        64,
+        STATE_WIDTH as u32,
+        NUM_FULL_ROUNDS as u32,
+        NUM_PARTIAL_ROUNDS as u32,
+    );
+    let modulus = [BaseElement::MODULUS, 0, 0, 0];
+    let mut draw = |count: usize| {
+        grain
+            .draw_elements(count, 64, modulus)
+            .into_iter()
+            .map(|value| value[0])
+            .collect::<Vec<_>>()
+    };
+
+    // constants are drawn in the order in which they are used: a full set of constants for each
+    // of the first 4 full rounds, a single constant for each partial round, and a full set of
+    // constants for each of the last 4 full rounds
+    let half_full_rounds = NUM_FULL_ROUNDS / 2;
+    let first_rounds = draw(half_full_rounds * STATE_WIDTH);
+    let partial_rounds = draw(NUM_PARTIAL_ROUNDS);
+    let last_rounds = draw(half_full_rounds * STATE_WIDTH);
+
+    let full_rounds = first_rounds
+        .chunks(STATE_WIDTH)
+        .chain(last_rounds.chunks(STATE_WIDTH));
+    for (expected, actual) in full_rounds.zip(EXTERNAL_ROUND_CONSTANTS.iter()) {
+        let actual = actual.iter().map(|v| v.as_int()).collect::<Vec<_>>();
+        assert_eq!(expected.to_vec(), actual);
+    }
+
+    let actual = INTERNAL_ROUND_CONSTANTS
+        .iter()
+        .map(|v| v.as_int())
+        .collect::<Vec<_>>();
+    assert_eq!(partial_rounds, actual);
+}
+
+#[test]
+fn test_m4() {
+    let m4 = [[5_u64, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]];
+    let state: [BaseElement; 4] = rand_array();
+
+    let mut expected = [BaseElement::ZERO; 4];
+    for (e, row) in expected.iter_mut().zip(m4.iter()) {
+        for (&m, &s) in row.iter().zip(state.iter()) {
+            *e += BaseElement::new(m) * s;
+        }
+    }
+
+    let mut actual = state;
+    apply_m4(&mut actual);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_internal_matrix() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    // the internal matrix is the all-ones matrix plus diag(d)
+    let sum = state.iter().fold(BaseElement::ZERO, |acc, &v| acc + v);
+    let mut expected = [BaseElement::ZERO; STATE_WIDTH];
+    for (i, e) in expected.iter_mut().enumerate() {
+        *e = sum + INTERNAL_MATRIX_DIAG[i] * state[i];
+    }
+
+    let mut actual = state;
+    Poseidon2::apply_internal_matrix(&mut actual);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn apply_permutation() {
+    let mut state: [BaseElement; STATE_WIDTH] = (0..STATE_WIDTH as u64)
+        .map(BaseElement::new)
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+
+    Poseidon2::apply_permutation(&mut state);
+
+    // expected values are the known-answer test vector for the Poseidon2 instance over this field
+    // with state width 12 from the reference implementation of Poseidon2 by the authors of the
+    // specification (https://github.com/HorizenLabs/poseidon2); the input is [0, 1, ..., 11]
+    let expected = vec![
+        BaseElement::new(0x01eaef96bdf1c0c1),
+        BaseElement::new(0x1f0d2cc525b2540c),
+        BaseElement::new(0x6282c1dfe1e0358d),
+        BaseElement::new(0xe780d721f698e1e6),
+        BaseElement::new(0x280c0b6f753d833b),
+        BaseElement::new(0x1b942dd5023156ab),
+        BaseElement::new(0x43f0df3fcccb8398),
+        BaseElement::new(0xe8e8190585489025),
+        BaseElement::new(0x56bdbf72f77ada22),
+        BaseElement::new(0x7911c32bf9dcd705),
+        BaseElement::new(0xec467926508fbe67),
+        BaseElement::new(0x6a50450ddf85a6ed),
+    ];
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn hash_elements_test_vectors() {
+    // the sponge construction is not a part of the Poseidon2 reference implementation, and thus,
+    // there are no reference vectors for it; these are regression values computed by hashing
+    // sequences [0, 1, ..., n - 1] for n in [1, 7, 8, 9, 16, 17] with an independent model of the
+    // sponge construction described above built on top of the reference permutation. The inputs
+    // cover lengths which are shorter than, equal to, and longer than the rate width.
+    let lengths = [1_u64, 7, 8, 9, 16, 17];
+    let expected = [
+        [
+            BaseElement::new(11442475158863280612),
+            BaseElement::new(13532250414393217426),
+            BaseElement::new(10413042623013900764),
+            BaseElement::new(12860685102692376665),
+        ],
+        [
+            BaseElement::new(3196320971582987164),
+            BaseElement::new(2966086678504750914),
+            BaseElement::new(12638476847791458910),
+            BaseElement::new(18344336851263143099),
+        ],
+        [
+            BaseElement::new(18243748776347319819),
+            BaseElement::new(10674975148410631354),
+            BaseElement::new(14220205444945226431),
+            BaseElement::new(7294857961130422363),
+        ],
+        [
+            BaseElement::new(18129979791803329453),
+            BaseElement::new(4229574819321889095),
+            BaseElement::new(10245889443111769803),
+            BaseElement::new(11361210546558374148),
+        ],
+        [
+            BaseElement::new(5817670271457267695),
+            BaseElement::new(7536224371597466508),
+            BaseElement::new(9353932130366981279),
+            BaseElement::new(14095307802353385839),
+        ],
+        [
+            BaseElement::new(4243100507137258571),
+            BaseElement::new(15664194758571377390),
+            BaseElement::new(8326711616213832221),
+            BaseElement::new(16276677847839482059),
+        ],
+    ];
+
+    for (&n, expected) in lengths.iter().zip(expected) {
+        let elements = (0..n).map(BaseElement::new).collect::<Vec<_>>();
+        let result = Poseidon2::hash_elements(&elements);
+        assert_eq!(ElementDigest::new(expected), result);
+    }
+}
+
+#[test]
+fn hash_elements_vs_merge() {
+    let elements: [BaseElement; 8] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    let m_result = Poseidon2::merge(&digests);
+    let h_result = Poseidon2::hash_elements(&elements);
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = Poseidon2::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = Poseidon2::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = Poseidon2::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = Poseidon2::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Poseidon2::hash(&[1_u8, 2, 3]);
+    let r2 = Poseidon2::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = Poseidon2::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = Poseidon2::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with multiple zeros
+    let r1 = Poseidon2::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0]);
+    let r2 = Poseidon2::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0, 0, 0]);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    // appending a zero element should result in a different hash
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = Poseidon2::hash_elements(&e1);
+    let r2 = Poseidon2::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
@@ -0,0 +1,102 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use utils::collections::Vec;
+
+// GRAIN LFSR
+// ================================================================================================
+
+/// Grain LFSR used by the reference implementation of Poseidon2 to generate round constants.
+///
+/// The LFSR is initialized with an 80-bit state which encodes the parameters of the hash function
+/// and is then clocked 160 times; afterwards, output bits are produced in pairs, and the second
+/// bit of a pair is output only if the first bit is set.
+pub struct Grain {
+    state: [bool; 80],
+    position: usize,
+}
+
+impl Grain {
+    /// Returns a new LFSR initialized for a prime field of the specified size, x^alpha S-Box,
+    /// and the specified state width and round numbers.
+    pub fn new(field_bits: u32, state_width: u32, num_full: u32, num_partial: u32) -> Self {
+        // field type (1 = prime field) and S-Box type (0 = x^alpha) followed by the parameters;
+        // the remaining 30 bits are set to 1
+        let fields = [
+            (1, 2),
+            (0, 4),
+            (field_bits, 12),
+            (state_width, 12),
+            (num_full, 10),
+            (num_partial, 10),
+        ];
+        let mut state = [true; 80];
+        let mut i = 0;
+        for (value, num_bits) in fields {
+            for j in (0..num_bits).rev() {
+                state[i] = (value >> j) & 1 == 1;
+                i += 1;
+            }
+        }
+
+        let mut grain = Grain { state, position: 0 };
+        for _ in 0..160 {
+            grain.clock();
+        }
+        grain
+    }
+
+    /// Returns `count` field elements in little-endian 64-bit limbs; each element is sampled by
+    /// reading `field_bits` output bits (most significant bit first) and discarding values which
+    /// are not smaller than the modulus.
+    pub fn draw_elements(
+        &mut self,
+        count: usize,
+        field_bits: u32,
+        modulus: [u64; 4],
+    ) -> Vec<[u64; 4]> {
+        let mut result = Vec::with_capacity(count);
+        while result.len() < count {
+            let mut value = [0_u64; 4];
+            for _ in 0..field_bits {
+                let bit = self.next_bit() as u64;
+                for k in (1..4).rev() {
+                    value[k] = (value[k] << 1) | (value[k - 1] >> 63);
+                }
+                value[0] = (value[0] << 1) | bit;
+            }
+
+            let is_valid = value
+                .iter()
+                .rev()
+                .zip(modulus.iter().rev())
+                .find(|(v, m)| v != m);
+            if let Some((v, m)) = is_valid {
+                if v < m {
+                    result.push(value);
+                }
+            }
+        }
+        result
+    }
+
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let first = self.clock();
+            let second = self.clock();
+            if first {
+                return second;
+            }
+        }
+    }
+
+    fn clock(&mut self) -> bool {
+        let bit = |i: usize| self.state[(self.position + i) % 80];
+        let new_bit = bit(62) ^ bit(51) ^ bit(38) ^ bit(23) ^ bit(13) ^ bit(0);
+        self.state[self.position] = new_bit;
+        self.position = (self.position + 1) % 80;
+        new_bit
+    }
+}
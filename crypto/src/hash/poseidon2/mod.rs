@@ -0,0 +1,59 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::marker::PhantomData;
+use math::StarkField;
+
+mod f64;
+
+#[cfg(test)]
+mod grain;
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of [Hasher](super::Hasher) trait for Poseidon2 hash function with 256-bit
+/// output.
+///
+/// The hash function is implemented according to the Poseidon2
+/// [specifications](https://eprint.iacr.org/2023/323.pdf). Compared to Poseidon, the external
+/// rounds use a cheap matrix built from a fixed 4x4 MDS matrix, and the internal rounds use a
+/// matrix of the form `1 + diag(d)`, which can be applied with a linear number of operations.
+/// This makes the function cheaper both to evaluate and to arithmetize, and thus, well suited
+/// for recursive proof verification.
+///
+/// The function is currently instantiated only for the 64-bit field with modulus
+/// 2^64 - 2^32 + 1 (`B` = `f64::BaseElement`): state width is 12 elements (8 rate, 4 capacity),
+/// S-Box is x^7, there are 8 external and 22 internal rounds; the digest consists of 4 field
+/// elements and can be serialized into 32 bytes (256 bits).
+///
+/// Round constants are generated using the Grain LFSR in the same way as in the reference
+/// implementation of Poseidon2, and the diagonal of the internal matrix is the one of the
+/// reference instance with state width 12. Thus, the permutation is the same as the reference
+/// Poseidon2 instance for this field with state width 12.
+///
+/// ## Sponge layout
+/// The rate portion of the state is located at the beginning of the state, and the capacity
+/// portion is located at the end of the state. The output of the hash function comes from the
+/// first elements of the rate portion of the state.
+///
+/// ## Padding
+/// The sponge is used in overwrite mode and follows the same padding rule as
+/// [Rpo256](super::Rpo256):
+/// * If the number of elements to be hashed is a multiple of the rate width, no padding is
+///   applied and the capacity portion of the state is initialized to all zeros.
+/// * Otherwise, the first capacity element is set to ONE, and the input is padded with a single
+///   ONE element followed by as many ZERO elements as needed to fill up the rate.
+///
+/// ## Hash output consistency
+/// Functions [hash_elements()](super::ElementHasher::hash_elements),
+/// [merge()](super::Hasher::merge), and [merge_with_int()](super::Hasher::merge_with_int) are
+/// internally consistent. For example, merging two digests using
+/// [merge()](super::Hasher::merge) will produce the same result as hashing the elements which
+/// make up these digests using [hash_elements()](super::ElementHasher::hash_elements) function.
+/// However, as with [Rpo256](super::Rpo256), [hash()](super::Hasher::hash) function is not
+/// consistent with the functions mentioned above.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Poseidon2_256<B: StarkField>(PhantomData<B>);
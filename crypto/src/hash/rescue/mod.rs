@@ -9,8 +9,12 @@ mod rp62_248;
 pub use rp62_248::Rp62_248;
 
 mod rp64_256;
+pub(super) use rp64_256::ElementDigest;
 pub use rp64_256::Rp64_256;
 
+mod rpo;
+pub use rpo::Rpo256;
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -8,6 +8,7 @@ use super::{
     INV_ALPHA, STATE_WIDTH,
 };
 use core::convert::TryInto;
+use math::fields::QuadExtension;
 
 use rand_utils::{rand_array, rand_value};
 
@@ -154,3 +155,17 @@ fn hash_elements_padding() {
     let r2 = Rp64_256::hash_elements(&e2);
     assert_ne!(r1, r2);
 }
+
+#[test]
+fn hash_extension_elements() {
+    let base: [BaseElement; 6] = rand_array();
+    let elements = [
+        QuadExtension::new(base[0], base[1]),
+        QuadExtension::new(base[2], base[3]),
+        QuadExtension::new(base[4], base[5]),
+    ];
+
+    let expected = Rp64_256::hash_elements(&base);
+    assert_eq!(expected, Rp64_256::hash_extension_elements(&elements));
+    assert_eq!(expected, Rp64_256::hash_elements(&elements));
+}
@@ -0,0 +1,237 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, Rpo256, StarkField, ALPHA,
+    INV_ALPHA, INV_MDS, MDS, STATE_WIDTH,
+};
+use core::convert::TryInto;
+
+use rand_utils::{rand_array, rand_value};
+
+type Rpo = Rpo256<BaseElement>;
+
+#[test]
+fn test_alphas() {
+    let e: BaseElement = rand_value();
+    let e_exp = e.exp(ALPHA.into());
+    assert_eq!(e, e_exp.exp(INV_ALPHA));
+}
+
+#[test]
+fn test_sbox() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut expected = state;
+    expected.iter_mut().for_each(|v| *v = v.exp(ALPHA));
+
+    let mut actual = state;
+    Rpo::apply_sbox(&mut actual);
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_inv_sbox() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut expected = state;
+    expected.iter_mut().for_each(|v| *v = v.exp(INV_ALPHA));
+
+    let mut actual = state;
+    Rpo::apply_inv_sbox(&mut actual);
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_inv_mds() {
+    for (i, mds_row) in MDS.iter().enumerate() {
+        for j in 0..STATE_WIDTH {
+            let mut result = BaseElement::ZERO;
+            for (k, &m) in mds_row.iter().enumerate() {
+                result += m * INV_MDS[k][j];
+            }
+            if i == j {
+                assert_eq!(BaseElement::ONE, result);
+            } else {
+                assert_eq!(BaseElement::ZERO, result);
+            }
+        }
+    }
+}
+
+#[test]
+fn apply_permutation() {
+    let mut state: [BaseElement; STATE_WIDTH] = [
+        BaseElement::new(0),
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+        BaseElement::new(4),
+        BaseElement::new(5),
+        BaseElement::new(6),
+        BaseElement::new(7),
+        BaseElement::new(8),
+        BaseElement::new(9),
+        BaseElement::new(10),
+        BaseElement::new(11),
+    ];
+
+    Rpo::apply_permutation(&mut state);
+
+    // expected values are obtained by executing a reference implementation of the RPO
+    // specification
+    let expected = vec![
+        BaseElement::new(15056646954853821376),
+        BaseElement::new(594518210294093573),
+        BaseElement::new(10395398226526937664),
+        BaseElement::new(3903707756219396109),
+        BaseElement::new(7670128982698747483),
+        BaseElement::new(4249514323476682720),
+        BaseElement::new(16506822133651532340),
+        BaseElement::new(10593868791806571942),
+        BaseElement::new(9413309068803954142),
+        BaseElement::new(15946782832277734471),
+        BaseElement::new(7904287043744270535),
+        BaseElement::new(16548919317472389167),
+    ];
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn hash_elements_test_vectors() {
+    // expected values are obtained by hashing sequences [0, 1, ..., n - 1] for n in
+    // [1, 7, 8, 9, 16, 17] with a reference implementation of the RPO specification; these cover
+    // inputs which are shorter than, equal to, and longer than the rate width.
+    let lengths = [1_u64, 7, 8, 9, 16, 17];
+    let expected = [
+        [
+            BaseElement::new(1502364727743950833),
+            BaseElement::new(5880949717274681448),
+            BaseElement::new(162790463902224431),
+            BaseElement::new(6901340476773664264),
+        ],
+        [
+            BaseElement::new(162696376578462826),
+            BaseElement::new(4991300494838863586),
+            BaseElement::new(660346084748120605),
+            BaseElement::new(13179389528641752698),
+        ],
+        [
+            BaseElement::new(2242391899857912644),
+            BaseElement::new(12689382052053305418),
+            BaseElement::new(235236990017815546),
+            BaseElement::new(5046143039268215739),
+        ],
+        [
+            BaseElement::new(9585630502158073976),
+            BaseElement::new(1310051013427303477),
+            BaseElement::new(7491921222636097758),
+            BaseElement::new(9417501558995216762),
+        ],
+        [
+            BaseElement::new(4935426252518736883),
+            BaseElement::new(12584230452580950419),
+            BaseElement::new(8762518969632303998),
+            BaseElement::new(18159875708229758073),
+        ],
+        [
+            BaseElement::new(14871230873837295931),
+            BaseElement::new(11225255908868362971),
+            BaseElement::new(18100987641405432308),
+            BaseElement::new(1559244340089644233),
+        ],
+    ];
+
+    for (&n, expected) in lengths.iter().zip(expected) {
+        let elements = (0..n).map(BaseElement::new).collect::<Vec<_>>();
+        let result = Rpo::hash_elements(&elements);
+        assert_eq!(ElementDigest::new(expected), result);
+    }
+}
+
+#[test]
+fn hash_elements_vs_merge() {
+    let elements: [BaseElement; 8] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    let m_result = Rpo::merge(&digests);
+    let h_result = Rpo::hash_elements(&elements);
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = Rpo::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = Rpo::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = Rpo::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = Rpo::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Rpo::hash(&[1_u8, 2, 3]);
+    let r2 = Rpo::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with bigger inputs
+    let r1 = Rpo::hash(&[1_u8, 2, 3, 4, 5, 6]);
+    let r2 = Rpo::hash(&[1_u8, 2, 3, 4, 5, 6, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = Rpo::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = Rpo::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with multiple zeros
+    let r1 = Rpo::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0]);
+    let r2 = Rpo::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0, 0, 0]);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    // appending a zero element should result in a different hash
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = Rpo::hash_elements(&e1);
+    let r2 = Rpo::hash_elements(&e2);
+    assert_ne!(r1, r2);
+
+    // padding a partial block with ONE followed by ZEROs should differ from hashing the padded
+    // block directly, since the latter is a full block and sets no capacity flag
+    let mut e3 = [BaseElement::ZERO; 8];
+    e3[..2].copy_from_slice(&e1);
+    e3[2] = BaseElement::ONE;
+    let r3 = Rpo::hash_elements(&e3);
+    assert_ne!(r1, r3);
+}
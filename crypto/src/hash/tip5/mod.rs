@@ -0,0 +1,485 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Digest, ElementHasher, Hasher};
+use core::convert::TryInto;
+use core::marker::PhantomData;
+use core::ops::Range;
+use math::{fields::f64::BaseElement, FieldElement, StarkField};
+
+mod digest;
+pub use digest::ElementDigest;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Sponge state is set to 16 field elements or 1024 bytes; 10 elements are reserved for rate and
+/// the remaining 6 elements are reserved for capacity.
+const STATE_WIDTH: usize = 16;
+
+/// The rate portion of the state is located in elements 0 through 9.
+const RATE_RANGE: Range<usize> = 0..10;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+const INPUT1_RANGE: Range<usize> = 0..5;
+const INPUT2_RANGE: Range<usize> = 5..10;
+
+/// The capacity portion of the state is located in elements 10 through 15.
+const CAPACITY_RANGE: Range<usize> = 10..16;
+
+/// The output of the hash function is a digest which consists of 5 field elements or 40 bytes.
+///
+/// The digest is returned from state elements 0, 1, 2, 3, and 4 (the first five elements of the
+/// rate portion).
+const DIGEST_RANGE: Range<usize> = 0..5;
+const DIGEST_SIZE: usize = DIGEST_RANGE.end - DIGEST_RANGE.start;
+
+/// The number of rounds is set to 5 to target 128-bit security level.
+const NUM_ROUNDS: usize = 5;
+
+/// The number of state elements to which the split-and-lookup S-Box is applied; the power map
+/// S-Box is applied to the remaining elements of the state.
+const NUM_SPLIT_AND_LOOKUP: usize = 4;
+
+/// The number of bytes packed into a single field element when hashing a byte string; every
+/// 7-byte chunk is guaranteed to map to some field element.
+const BINARY_CHUNK_SIZE: usize = 7;
+
+/// Montgomery radix R = 2^64 mod p, and its inverse.
+///
+/// The lookup tables are applied to bytes of the Montgomery representation of field elements
+/// (this is how the reference implementation stores elements internally). Since field elements
+/// in this crate are not stored in Montgomery form, we convert to and from this form explicitly.
+const MONTGOMERY_R: BaseElement = BaseElement::new(4294967295);
+const MONTGOMERY_R_INV: BaseElement = BaseElement::new(18446744065119617025);
+
+/// Power map S-Box degree.
+///
+/// The constant is defined for tests only because the exponentiation in the code is unrolled
+/// for efficiency reasons.
+#[cfg(test)]
+const ALPHA: u64 = 7;
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of [Hasher] trait for Tip5 hash function with 320-bit output.
+///
+/// The hash function is implemented according to the Tip5
+/// [specifications](https://eprint.iacr.org/2023/107.pdf) and is compatible with the Triton VM
+/// reference implementation. Currently, the function is implemented only for the 64-bit field
+/// with modulus 2^64 - 2^32 + 1 (i.e., for `B` = `f64::BaseElement`).
+///
+/// The parameters used to instantiate the function are:
+/// * Field: 64-bit prime field with modulus 2^64 - 2^32 + 1.
+/// * State width: 16 field elements.
+/// * Capacity size: 6 field elements.
+/// * Number of rounds: 5.
+/// * S-Box: split-and-lookup map (8-bit lookup table) for the first 4 state elements, and power
+///   map of degree 7 for the remaining 12 state elements.
+///
+/// The above parameters target 128-bit security level. The digest consists of five field elements
+/// and it can be serialized into 40 bytes (320 bits).
+///
+/// ## Sponge layout
+/// The first 10 elements of the state (elements 0 through 9) are used as rate and the remaining
+/// 6 elements (elements 10 through 15) are reserved for capacity. The output of the hash function
+/// comes from the first five elements of the rate portion of the state (elements 0 through 4).
+///
+/// ## Padding and domain separation
+/// The sponge is used in overwrite mode. Following the reference implementation, two domains are
+/// distinguished:
+/// * Fixed-length inputs (used by [merge()](Tip5::merge)): the capacity portion of the state
+///   is initialized to all ONEs, and the 10 input elements fill up the rate exactly; no padding
+///   is applied.
+/// * Variable-length inputs (used by [hash_elements()](Tip5::hash_elements),
+///   [hash()](Tip5::hash), and [merge_with_int()](Tip5::merge_with_int)): the capacity
+///   portion of the state is initialized to all ZEROs, and the input is always padded with a
+///   single ONE element followed by as many ZERO elements as needed to fill up the rate.
+///
+/// This means that, unlike for [Rpo256](super::Rpo256), merging two digests using
+/// [merge()](Tip5::merge) produces a different result than hashing the 10 elements which
+/// make up these digests using [hash_elements()](Tip5::hash_elements) function.
+///
+/// [merge_with_int()](Tip5::merge_with_int) is consistent with
+/// [hash_elements()](Tip5::hash_elements): the result is the same as hashing the elements
+/// of the seed followed by the value (split into two elements if it does not fit into one).
+pub struct Tip5<B: StarkField>(PhantomData<B>);
+
+impl Hasher for Tip5<BaseElement> {
+    type Digest = ElementDigest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // compute the number of elements required to represent the string; we will be processing
+        // the string in 7-byte chunks, thus the number of elements will be equal to the number
+        // of such chunks (including a potential partial chunk at the end).
+        let num_elements = if bytes.len() % BINARY_CHUNK_SIZE == 0 {
+            bytes.len() / BINARY_CHUNK_SIZE
+        } else {
+            bytes.len() / BINARY_CHUNK_SIZE + 1
+        };
+
+        // initialize the state to all zeros as this is a variable-length input
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+
+        // break the string into 7-byte chunks, convert each chunk into a field element, and
+        // absorb the element into the rate portion of the state.
+        let mut i = 0;
+        let mut buf = [0_u8; 8];
+        for chunk in bytes.chunks(BINARY_CHUNK_SIZE) {
+            if i < num_elements - 1 {
+                buf[..BINARY_CHUNK_SIZE].copy_from_slice(chunk);
+            } else {
+                // if we are dealing with the last chunk, it may be smaller than 7 bytes long, so
+                // we need to handle it slightly differently. we also append a byte with value 1
+                // to the end of the string; this pads the string in such a way that adding
+                // trailing zeros results in different hash
+                let chunk_len = chunk.len();
+                buf = [0_u8; 8];
+                buf[..chunk_len].copy_from_slice(chunk);
+                buf[chunk_len] = 1;
+            }
+
+            // convert the bytes into a field element and absorb it into the rate portion of the
+            // state (overwriting the previous value); if the rate is filled up, apply the Tip5
+            // permutation and start absorbing again from zero index.
+            state[RATE_RANGE.start + i] = BaseElement::new(u64::from_le_bytes(buf));
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // pad the rate with ONE followed by ZEROs and apply the Tip5 permutation
+        Self::pad_and_apply_permutation(&mut state, i);
+
+        // return the first 5 elements of the rate as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // initialize the state by copying the digest elements into the rate portion of the state
+        // (10 total elements); the capacity is set to all ONEs because this is a fixed-length
+        // input.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[RATE_RANGE].copy_from_slice(Self::Digest::digests_as_elements(values));
+        state[CAPACITY_RANGE].fill(BaseElement::ONE);
+
+        // apply the Tip5 permutation and return the first five elements of the rate
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // initialize the state as follows:
+        // - seed is copied into the first 5 elements of the rate portion of the state.
+        // - if the value fits into a single field element, copy it into the sixth rate element
+        //   and pad the remaining rate elements with ONE followed by ZEROs.
+        // - if the value doesn't fit into a single field element, split it into two field
+        //   elements, copy them into rate elements 6 and 7, and pad the remaining rate elements
+        //   with ONE followed by ZEROs.
+        // - in both cases, the capacity is left as all zeros since this is a variable-length
+        //   input.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[INPUT1_RANGE].copy_from_slice(seed.as_elements());
+        state[INPUT2_RANGE.start] = BaseElement::new(value);
+        if value < BaseElement::MODULUS {
+            state[INPUT2_RANGE.start + 1] = BaseElement::ONE;
+        } else {
+            state[INPUT2_RANGE.start + 1] = BaseElement::new(value / BaseElement::MODULUS);
+            state[INPUT2_RANGE.start + 2] = BaseElement::ONE;
+        }
+
+        // apply the Tip5 permutation and return the first five elements of the rate
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+impl ElementHasher for Tip5<BaseElement> {
+    type BaseField = BaseElement;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // convert the elements into a list of base field elements
+        let elements = E::as_base_elements(elements);
+
+        // initialize the state to all zeros as this is a variable-length input
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+
+        // absorb elements into the state one by one (overwriting the rate elements) until the rate
+        // portion of the state is filled up; then apply the Tip5 permutation and start absorbing
+        // again; repeat until all elements have been absorbed
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[RATE_RANGE.start + i] = element;
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // pad the rate with ONE followed by ZEROs and apply the Tip5 permutation
+        Self::pad_and_apply_permutation(&mut state, i);
+
+        // return the first 5 elements of the rate as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Tip5<BaseElement> {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of rounds is set to 5 to target 128-bit security level.
+    pub const NUM_ROUNDS: usize = NUM_ROUNDS;
+
+    /// Sponge state is set to 16 field elements or 1024 bytes; 10 elements are reserved for rate
+    /// and the remaining 6 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 0 through 9 (inclusive).
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 10 through 15 (inclusive).
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 0 through 4 (inclusive).
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// Lookup table used in the split-and-lookup part of the S-Box layer.
+    pub const LOOKUP_TABLE: [u8; 256] = LOOKUP_TABLE;
+
+    /// First column of the circulant MDS matrix used for computing the linear layer.
+    pub const MDS_FIRST_COLUMN: [BaseElement; STATE_WIDTH] = MDS_FIRST_COLUMN;
+
+    /// Round constants added to the hasher state at the end of each Tip5 round.
+    pub const ROUND_CONSTANTS: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS] = ROUND_CONSTANTS;
+
+    // TIP5 PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies Tip5 permutation to the provided state.
+    pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
+        for i in 0..NUM_ROUNDS {
+            Self::apply_round(state, i);
+        }
+    }
+
+    /// Tip5 round function.
+    #[inline(always)]
+    pub fn apply_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        Self::apply_sbox(state);
+        Self::apply_mds(state);
+        Self::add_constants(state, &ROUND_CONSTANTS[round]);
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Pads the rate portion of the state starting at rate element `num_absorbed` with ONE
+    /// followed by ZEROs, and applies the Tip5 permutation to the state.
+    #[inline(always)]
+    fn pad_and_apply_permutation(state: &mut [BaseElement; STATE_WIDTH], num_absorbed: usize) {
+        debug_assert!(num_absorbed < RATE_WIDTH);
+        state[RATE_RANGE.start + num_absorbed] = BaseElement::ONE;
+        state[RATE_RANGE.start + num_absorbed + 1..RATE_RANGE.end].fill(BaseElement::ZERO);
+        Self::apply_permutation(state);
+    }
+
+    #[inline(always)]
+    fn apply_sbox(state: &mut [BaseElement; STATE_WIDTH]) {
+        // apply split-and-lookup map to the first elements of the state
+        state[..NUM_SPLIT_AND_LOOKUP]
+            .iter_mut()
+            .for_each(|v| *v = split_and_lookup(*v));
+
+        // apply power map to the remaining elements of the state
+        state[NUM_SPLIT_AND_LOOKUP..].iter_mut().for_each(|v| {
+            let t2 = v.square();
+            let t4 = t2.square();
+            *v *= t2 * t4;
+        });
+    }
+
+    #[inline(always)]
+    fn apply_mds(state: &mut [BaseElement; STATE_WIDTH]) {
+        let mut result = [BaseElement::ZERO; STATE_WIDTH];
+        for (i, r) in result.iter_mut().enumerate() {
+            for (j, &s) in state.iter().enumerate() {
+                *r += MDS_FIRST_COLUMN[(STATE_WIDTH + i - j) % STATE_WIDTH] * s;
+            }
+        }
+        *state = result
+    }
+
+    #[inline(always)]
+    fn add_constants(state: &mut [BaseElement; STATE_WIDTH], ark: &[BaseElement; STATE_WIDTH]) {
+        state.iter_mut().zip(ark).for_each(|(s, &k)| *s += k);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Splits the Montgomery representation of the element into 8 bytes, applies the lookup table to
+/// each byte, and recombines the bytes into a field element.
+///
+/// The lookup table maps 0 to 0 and 255 to 255, and thus, the result is guaranteed to be a valid
+/// field element in Montgomery form.
+#[inline(always)]
+fn split_and_lookup(value: BaseElement) -> BaseElement {
+    let mut bytes = (value * MONTGOMERY_R).as_int().to_le_bytes();
+    bytes
+        .iter_mut()
+        .for_each(|b| *b = LOOKUP_TABLE[*b as usize]);
+    BaseElement::new(u64::from_le_bytes(bytes)) * MONTGOMERY_R_INV
+}
+
+// CONSTANTS
+// ================================================================================================
+
+/// Tip5 lookup table used in the split-and-lookup part of the S-Box layer.
+///
+/// The table is the offset Fermat cube map over bytes: `L(x) = (x + 1)^3 mod 257 - 1`.
+const LOOKUP_TABLE: [u8; 256] = [
+    0, 7, 26, 63, 124, 215, 85, 254, 214, 228, 45, 185, 140, 173, 33, 240, 29, 177, 176, 32, 8,
+    110, 87, 202, 204, 99, 150, 106, 230, 14, 235, 128, 213, 239, 212, 138, 23, 130, 208, 6, 44,
+    71, 93, 116, 146, 189, 251, 81, 199, 97, 38, 28, 73, 179, 95, 84, 152, 48, 35, 119, 49, 88,
+    242, 3, 148, 169, 72, 120, 62, 161, 166, 83, 175, 191, 137, 19, 100, 129, 112, 55, 221, 102,
+    218, 61, 151, 237, 68, 164, 17, 147, 46, 234, 203, 216, 22, 141, 65, 57, 123, 12, 244, 54, 219,
+    231, 96, 77, 180, 154, 5, 253, 133, 165, 98, 195, 205, 134, 245, 30, 9, 188, 59, 142, 186, 197,
+    181, 144, 92, 31, 224, 163, 111, 74, 58, 69, 113, 196, 67, 246, 225, 10, 121, 50, 60, 157, 90,
+    122, 2, 250, 101, 75, 178, 159, 24, 36, 201, 11, 243, 132, 198, 190, 114, 233, 39, 52, 21, 209,
+    108, 238, 91, 187, 18, 104, 194, 37, 153, 34, 200, 143, 126, 155, 236, 118, 64, 80, 172, 89,
+    94, 193, 135, 183, 86, 107, 252, 13, 167, 206, 136, 220, 207, 103, 171, 160, 76, 182, 227, 217,
+    158, 56, 174, 4, 66, 109, 139, 162, 184, 211, 249, 47, 125, 232, 117, 43, 16, 42, 127, 20, 241,
+    25, 149, 105, 156, 51, 53, 168, 145, 247, 223, 79, 78, 226, 15, 222, 82, 115, 70, 210, 27, 41,
+    1, 170, 40, 131, 192, 229, 248, 255,
+];
+
+/// First column of the Tip5 MDS matrix.
+///
+/// The matrix is circulant; each subsequent column is the previous column rotated one element
+/// down.
+const MDS_FIRST_COLUMN: [BaseElement; STATE_WIDTH] = [
+    BaseElement::new(61402),
+    BaseElement::new(1108),
+    BaseElement::new(28750),
+    BaseElement::new(33823),
+    BaseElement::new(7454),
+    BaseElement::new(43244),
+    BaseElement::new(53865),
+    BaseElement::new(12034),
+    BaseElement::new(56951),
+    BaseElement::new(27521),
+    BaseElement::new(41351),
+    BaseElement::new(40901),
+    BaseElement::new(12021),
+    BaseElement::new(59689),
+    BaseElement::new(26798),
+    BaseElement::new(17845),
+];
+
+/// Round constants added to the hasher state at the end of each Tip5 round.
+const ROUND_CONSTANTS: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS] = [
+    [
+        BaseElement::new(13630775303355457758),
+        BaseElement::new(16896927574093233874),
+        BaseElement::new(10379449653650130495),
+        BaseElement::new(1965408364413093495),
+        BaseElement::new(15232538947090185111),
+        BaseElement::new(15892634398091747074),
+        BaseElement::new(3989134140024871768),
+        BaseElement::new(2851411912127730865),
+        BaseElement::new(8709136439293758776),
+        BaseElement::new(3694858669662939734),
+        BaseElement::new(12692440244315327141),
+        BaseElement::new(10722316166358076749),
+        BaseElement::new(12745429320441639448),
+        BaseElement::new(17932424223723990421),
+        BaseElement::new(7558102534867937463),
+        BaseElement::new(15551047435855531404),
+    ],
+    [
+        BaseElement::new(17532528648579384106),
+        BaseElement::new(5216785850422679555),
+        BaseElement::new(15418071332095031847),
+        BaseElement::new(11921929762955146258),
+        BaseElement::new(9738718993677019874),
+        BaseElement::new(3464580399432997147),
+        BaseElement::new(13408434769117164050),
+        BaseElement::new(264428218649616431),
+        BaseElement::new(4436247869008081381),
+        BaseElement::new(4063129435850804221),
+        BaseElement::new(2865073155741120117),
+        BaseElement::new(5749834437609765994),
+        BaseElement::new(6804196764189408435),
+        BaseElement::new(17060469201292988508),
+        BaseElement::new(9475383556737206708),
+        BaseElement::new(12876344085611465020),
+    ],
+    [
+        BaseElement::new(13835756199368269249),
+        BaseElement::new(1648753455944344172),
+        BaseElement::new(9836124473569258483),
+        BaseElement::new(12867641597107932229),
+        BaseElement::new(11254152636692960595),
+        BaseElement::new(16550832737139861108),
+        BaseElement::new(11861573970480733262),
+        BaseElement::new(1256660473588673495),
+        BaseElement::new(13879506000676455136),
+        BaseElement::new(10564103842682358721),
+        BaseElement::new(16142842524796397521),
+        BaseElement::new(3287098591948630584),
+        BaseElement::new(685911471061284805),
+        BaseElement::new(5285298776918878023),
+        BaseElement::new(18310953571768047354),
+        BaseElement::new(3142266350630002035),
+    ],
+    [
+        BaseElement::new(549990724933663297),
+        BaseElement::new(4901984846118077401),
+        BaseElement::new(11458643033696775769),
+        BaseElement::new(8706785264119212710),
+        BaseElement::new(12521758138015724072),
+        BaseElement::new(11877914062416978196),
+        BaseElement::new(11333318251134523752),
+        BaseElement::new(3933899631278608623),
+        BaseElement::new(16635128972021157924),
+        BaseElement::new(10291337173108950450),
+        BaseElement::new(4142107155024199350),
+        BaseElement::new(16973934533787743537),
+        BaseElement::new(11068111539125175221),
+        BaseElement::new(17546769694830203606),
+        BaseElement::new(5315217744825068993),
+        BaseElement::new(4609594252909613081),
+    ],
+    [
+        BaseElement::new(3350107164315270407),
+        BaseElement::new(17715942834299349177),
+        BaseElement::new(9600609149219873996),
+        BaseElement::new(12894357635820003949),
+        BaseElement::new(4597649658040514631),
+        BaseElement::new(7735563950920491847),
+        BaseElement::new(1663379455870887181),
+        BaseElement::new(13889298103638829706),
+        BaseElement::new(7375530351220884434),
+        BaseElement::new(3502022433285269151),
+        BaseElement::new(9231805330431056952),
+        BaseElement::new(9252272755288523725),
+        BaseElement::new(10014268662326746219),
+        BaseElement::new(15565031632950843234),
+        BaseElement::new(1209725273521819323),
+        BaseElement::new(6024642864597845108),
+    ],
+];
@@ -0,0 +1,265 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    split_and_lookup, BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, StarkField,
+    Tip5, ALPHA, LOOKUP_TABLE, MONTGOMERY_R, MONTGOMERY_R_INV, NUM_SPLIT_AND_LOOKUP, STATE_WIDTH,
+};
+use core::convert::TryInto;
+
+use rand_utils::{rand_array, rand_value};
+
+#[test]
+fn test_lookup_table() {
+    // the table must be a permutation of bytes defined by L(x) = (x + 1)^3 mod 257 - 1
+    let mut seen = [false; 256];
+    for (x, &y) in LOOKUP_TABLE.iter().enumerate() {
+        let expected = ((x as u64 + 1).pow(3) % 257 - 1) as u8;
+        assert_eq!(expected, y);
+        assert!(!seen[y as usize]);
+        seen[y as usize] = true;
+    }
+}
+
+#[test]
+fn test_montgomery_constants() {
+    assert_eq!(BaseElement::ONE, MONTGOMERY_R * MONTGOMERY_R_INV);
+    assert_eq!(BaseElement::new(u64::MAX) + BaseElement::ONE, MONTGOMERY_R);
+}
+
+#[test]
+fn test_split_and_lookup() {
+    // zero is mapped to zero and -1 is mapped to -1 since the lookup table fixes bytes 0 and 255
+    assert_eq!(BaseElement::ZERO, split_and_lookup(BaseElement::ZERO));
+    assert_eq!(-BaseElement::ONE, split_and_lookup(-BaseElement::ONE));
+
+    // the result must always be a valid field element
+    for _ in 0..1000 {
+        let e: BaseElement = rand_value();
+        assert!(split_and_lookup(e).as_int() < BaseElement::MODULUS);
+    }
+}
+
+#[test]
+fn test_sbox() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut expected = state;
+    expected[..NUM_SPLIT_AND_LOOKUP]
+        .iter_mut()
+        .for_each(|v| *v = split_and_lookup(*v));
+    expected[NUM_SPLIT_AND_LOOKUP..]
+        .iter_mut()
+        .for_each(|v| *v = v.exp(ALPHA));
+
+    let mut actual = state;
+    Tip5::apply_sbox(&mut actual);
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn apply_permutation() {
+    let mut state: [BaseElement; STATE_WIDTH] = [
+        BaseElement::new(0),
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+        BaseElement::new(4),
+        BaseElement::new(5),
+        BaseElement::new(6),
+        BaseElement::new(7),
+        BaseElement::new(8),
+        BaseElement::new(9),
+        BaseElement::new(10),
+        BaseElement::new(11),
+        BaseElement::new(12),
+        BaseElement::new(13),
+        BaseElement::new(14),
+        BaseElement::new(15),
+    ];
+
+    Tip5::apply_permutation(&mut state);
+
+    // expected values are obtained by executing a reference implementation of the Tip5
+    // specification
+    let expected = vec![
+        BaseElement::new(14273019456630489802),
+        BaseElement::new(12225354657803044645),
+        BaseElement::new(18223679466392555512),
+        BaseElement::new(4879234115918641111),
+        BaseElement::new(198243361942729835),
+        BaseElement::new(6697571774370475124),
+        BaseElement::new(3935892719377798608),
+        BaseElement::new(2781322532457452310),
+        BaseElement::new(7475933807446249354),
+        BaseElement::new(7334965145562953054),
+        BaseElement::new(1275437117587945070),
+        BaseElement::new(2445375571864276273),
+        BaseElement::new(17005006372293520413),
+        BaseElement::new(9537835648539327419),
+        BaseElement::new(12703602725074524970),
+        BaseElement::new(5428520427373770602),
+    ];
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn hash_elements_test_vectors() {
+    // expected values are obtained by hashing sequences [0, 1, ..., n - 1] for n in
+    // [1, 9, 10, 11, 20, 21] with a reference implementation of the Tip5 specification; these
+    // cover inputs which are shorter than, equal to, and longer than the rate width.
+    let lengths = [1_u64, 9, 10, 11, 20, 21];
+    let expected = [
+        [
+            BaseElement::new(4843866011885844809),
+            BaseElement::new(16618866032559590857),
+            BaseElement::new(18247689143239181392),
+            BaseElement::new(7637465675240023996),
+            BaseElement::new(9104890367162237026),
+        ],
+        [
+            BaseElement::new(5188069162914592397),
+            BaseElement::new(852189275605886954),
+            BaseElement::new(1770154650497175879),
+            BaseElement::new(10044069521465249269),
+            BaseElement::new(15310276722084590255),
+        ],
+        [
+            BaseElement::new(11390788208692602429),
+            BaseElement::new(6957282862762085915),
+            BaseElement::new(1981796760358476339),
+            BaseElement::new(12105030651631844013),
+            BaseElement::new(12902609297038505194),
+        ],
+        [
+            BaseElement::new(7526065621963615182),
+            BaseElement::new(16903862215725836028),
+            BaseElement::new(8157482418627423091),
+            BaseElement::new(7458995957627234180),
+            BaseElement::new(5913482034288186032),
+        ],
+        [
+            BaseElement::new(14872239546964970853),
+            BaseElement::new(16820838656552620920),
+            BaseElement::new(9692282728457704207),
+            BaseElement::new(8736222862981639500),
+            BaseElement::new(2929917713051936136),
+        ],
+        [
+            BaseElement::new(8110436661106556374),
+            BaseElement::new(16436980442114399603),
+            BaseElement::new(10092420481792118268),
+            BaseElement::new(10725001856152099581),
+            BaseElement::new(2919168321789993406),
+        ],
+    ];
+
+    for (&n, expected) in lengths.iter().zip(expected) {
+        let elements = (0..n).map(BaseElement::new).collect::<Vec<_>>();
+        let result = Tip5::hash_elements(&elements);
+        assert_eq!(ElementDigest::new(expected), result);
+    }
+}
+
+#[test]
+fn merge_test_vector() {
+    // expected value is obtained by hashing [0, 1, ..., 9] as a fixed-length input with a
+    // reference implementation of the Tip5 specification
+    let elements = (0..10).map(BaseElement::new).collect::<Vec<_>>();
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..5].try_into().unwrap()),
+        ElementDigest::new(elements[5..].try_into().unwrap()),
+    ];
+
+    let expected = [
+        BaseElement::new(3110372704410120700),
+        BaseElement::new(8302474967766940368),
+        BaseElement::new(7132587465497701049),
+        BaseElement::new(4643011738479212626),
+        BaseElement::new(8384034896017378691),
+    ];
+
+    assert_eq!(ElementDigest::new(expected), Tip5::merge(&digests));
+}
+
+#[test]
+fn hash_elements_vs_merge() {
+    let elements: [BaseElement; 10] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..5].try_into().unwrap()),
+        ElementDigest::new(elements[5..].try_into().unwrap()),
+    ];
+
+    // merge() uses the fixed-length domain while hash_elements() uses the variable-length
+    // domain, and thus, the results must differ
+    let m_result = Tip5::merge(&digests);
+    let h_result = Tip5::hash_elements(&elements);
+    assert_ne!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = Tip5::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = Tip5::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = Tip5::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = Tip5::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Tip5::hash(&[1_u8, 2, 3]);
+    let r2 = Tip5::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = Tip5::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = Tip5::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with multiple zeros
+    let r1 = Tip5::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0]);
+    let r2 = Tip5::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0, 0, 0]);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    // appending a zero element should result in a different hash
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = Tip5::hash_elements(&e1);
+    let r2 = Tip5::hash_elements(&e2);
+    assert_ne!(r1, r2);
+
+    // a full rate block is still padded, so it must hash differently from the same block
+    // followed by the explicit padding
+    let e3: [BaseElement; 10] = rand_array();
+    let mut e4 = e3.to_vec();
+    e4.push(BaseElement::ONE);
+    assert_ne!(Tip5::hash_elements(&e3), Tip5::hash_elements(&e4));
+}
@@ -0,0 +1,76 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ByteDigest, Digest, ElementHasher, Hasher};
+use core::marker::PhantomData;
+use math::FieldElement;
+
+#[cfg(test)]
+mod tests;
+
+// TRUNCATED DIGEST
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait which truncates digests of the inner
+/// hasher `H` to their first `N` bytes.
+///
+/// Truncated digests reduce the size of Merkle authentication paths (and thus, the size of
+/// STARK proofs) at the expense of security. For an `N`-byte digest, collision resistance of
+/// the hash function is at most 4 * `N` bits (e.g., 64 bits for 16-byte digests and 80 bits
+/// for 20-byte digests), while second preimage resistance is at most 8 * `N` bits. Thus,
+/// truncated digests should be used only when the target security level of a proof is low
+/// enough, and when the security argument for the protocol does not rely on collision
+/// resistance beyond this level.
+///
+/// All hashing methods truncate consistently:
+/// * [hash()](Hasher::hash) and [hash_elements()](ElementHasher::hash_elements) truncate the
+///   digest computed by the inner hasher.
+/// * [merge()](Hasher::merge) hashes the concatenation of the two truncated digests with the
+///   inner hasher, and truncates the result.
+/// * [merge_with_int()](Hasher::merge_with_int) hashes the truncated seed concatenated with the
+///   little-endian bytes of the value, and truncates the result.
+///
+/// `N` must not exceed the size of the digest produced by `H`; otherwise, the truncated digest
+/// would be padded with zeros.
+///
+/// # Panics
+/// Hashing methods panic if `N` is zero or greater than 32.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TruncatedDigest<H: Hasher, const N: usize>(PhantomData<H>);
+
+impl<H: Hasher, const N: usize> Hasher for TruncatedDigest<H, N> {
+    type Digest = ByteDigest<N>;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        truncate(H::hash(bytes))
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        truncate(H::hash(ByteDigest::digests_as_bytes(values)))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 40];
+        data[..N].copy_from_slice(&seed.0);
+        data[N..N + 8].copy_from_slice(&value.to_le_bytes());
+        truncate(H::hash(&data[..N + 8]))
+    }
+}
+
+impl<H: ElementHasher, const N: usize> ElementHasher for TruncatedDigest<H, N> {
+    type BaseField = H::BaseField;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        truncate(H::hash_elements(elements))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the first `N` bytes of the provided digest.
+fn truncate<D: Digest, const N: usize>(digest: D) -> ByteDigest<N> {
+    ByteDigest::truncated(&digest.as_bytes())
+}
@@ -0,0 +1,50 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ElementHasher, Hasher, TruncatedDigest};
+use crate::{hash::Blake2s_256, MerkleTree};
+use math::fields::f128::BaseElement;
+use utils::collections::Vec;
+
+type Blake2s_128 = TruncatedDigest<Blake2s_256<BaseElement>, 16>;
+
+#[test]
+fn truncated_hash_consistency() {
+    let bytes = [1_u8, 2, 3, 4, 5];
+    let full = Blake2s_256::<BaseElement>::hash(&bytes);
+    assert_eq!(full.0[..16], Blake2s_128::hash(&bytes).0);
+
+    let elements = [BaseElement::new(1), BaseElement::new(2)];
+    let full = Blake2s_256::<BaseElement>::hash_elements(&elements);
+    assert_eq!(full.0[..16], Blake2s_128::hash_elements(&elements).0);
+
+    // merging truncated digests is the same as hashing their concatenation
+    let d1 = Blake2s_128::hash(&[1_u8]);
+    let d2 = Blake2s_128::hash(&[2_u8]);
+    let mut data = d1.0.to_vec();
+    data.extend_from_slice(&d2.0);
+    assert_eq!(Blake2s_128::hash(&data), Blake2s_128::merge(&[d1, d2]));
+
+    // merging with an integer is the same as hashing the seed followed by the integer bytes
+    let mut data = d1.0.to_vec();
+    data.extend_from_slice(&42_u64.to_le_bytes());
+    assert_eq!(
+        Blake2s_128::hash(&data),
+        Blake2s_128::merge_with_int(d1, 42)
+    );
+}
+
+#[test]
+fn truncated_merkle_path() {
+    let leaves = (0..8_u8)
+        .map(|i| Blake2s_128::hash(&[i]))
+        .collect::<Vec<_>>();
+    let tree = MerkleTree::<Blake2s_128>::new(leaves).unwrap();
+
+    let proof = tree.prove(5).unwrap();
+    assert!(proof.iter().all(|node| node.0.len() == 16));
+    assert!(MerkleTree::<Blake2s_128>::verify(*tree.root(), 5, &proof).is_ok());
+    assert!(MerkleTree::<Blake2s_128>::verify(*tree.root(), 4, &proof).is_err());
+}
@@ -11,7 +11,8 @@
 //! * **Merkle trees** - which are used as a commitment scheme in the STARK protocol. The
 //!   [MerkleTree] implementation supports concurrent tree construction as well as compact
 //!   aggregation of Merkle paths implemented using a variation of the
-//!   [Octopus](https://eprint.iacr.org/2017/933) algorithm.
+//!   [Octopus](https://eprint.iacr.org/2017/933) algorithm. Merkle trees implement the
+//!   [VectorCommitment] trait, which allows plugging in other vector commitment schemes.
 //! * **PRNG** - which is used to generate pseudo-random elements in a finite field. The
 //!   [RandomCoin] implementation uses a cryptographic hash function to generate pseudo-random
 //!   elements form a seed.
@@ -24,26 +25,39 @@ extern crate alloc;
 
 pub mod hash;
 pub use hash::{Digest, ElementHasher, Hasher};
+#[cfg(feature = "std")]
+pub use hash::{bench_hasher, BenchResult};
 pub mod hashers {
     //! Contains implementations of currently supported hash functions.
 
+    pub use super::hash::Blake2s;
+    pub use super::hash::Blake3;
     pub use super::hash::Blake3_192;
     pub use super::hash::Blake3_256;
+    pub use super::hash::Blake2s_192;
     pub use super::hash::Blake2s_256;
+    pub use super::hash::Monolith;
     pub use super::hash::Pedersen_256;
+    pub use super::hash::Poseidon2_256;
     pub use super::hash::Rp62_248;
     pub use super::hash::Rp64_256;
+    pub use super::hash::Rpo256;
     pub use super::hash::Sha3_256;
+    pub use super::hash::Tip5;
+    pub use super::hash::TruncatedDigest;
 }
 
 mod merkle;
 pub use merkle::{build_merkle_nodes, BatchMerkleProof, MerkleTree};
 
+mod commitment;
+pub use commitment::VectorCommitment;
+
 #[cfg(feature = "concurrent")]
 pub use merkle::concurrent;
 
 mod random;
-pub use random::RandomCoin;
+pub use random::{RandomCoin, TranscriptEvent};
 
 mod errors;
 pub use errors::{MerkleTreeError, RandomCoinError};
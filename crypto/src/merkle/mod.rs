@@ -147,6 +147,42 @@ impl<H: Hasher> MerkleTree<H> {
         &self.leaves
     }
 
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Replaces the leaf at the specified `index` with the provided `leaf` and updates the root
+    /// of the tree.
+    ///
+    /// Only the nodes on the path from the updated leaf to the root are recomputed; thus, the
+    /// update requires `log2(n)` hash operations, where `n` is the number of leaves in the tree.
+    ///
+    /// # Errors
+    /// Returns an error if the specified index is greater than or equal to the number of leaves
+    /// in the tree.
+    pub fn update_leaf(&mut self, index: usize, leaf: H::Digest) -> Result<(), MerkleTreeError> {
+        if index >= self.leaves.len() {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(
+                self.leaves.len(),
+                index,
+            ));
+        }
+        self.leaves[index] = leaf;
+
+        // recompute the parent of the updated leaf
+        let mut index = (index + self.nodes.len()) >> 1;
+        let left = (index << 1) - self.nodes.len();
+        self.nodes[index] = H::merge(&[self.leaves[left], self.leaves[left + 1]]);
+
+        // recompute all other nodes on the path to the root
+        while index > 1 {
+            index >>= 1;
+            let left = index << 1;
+            self.nodes[index] = H::merge(&[self.nodes[left], self.nodes[left + 1]]);
+        }
+
+        Ok(())
+    }
+
     // PROVING METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -301,6 +337,22 @@ impl<H: Hasher> MerkleTree<H> {
         }
         Ok(())
     }
+
+    /// Checks whether all of the specified Merkle paths are valid.
+    ///
+    /// Each entry in `paths` is a tuple `(root, index, leaf, path)`, where `path` is a Merkle
+    /// path in the format returned by [MerkleTree::prove()] (i.e., the leaf is the first element
+    /// of the path). The paths may belong to different trees (e.g., trace, constraint, and FRI
+    /// layer trees of a proof), as long as all trees were built using the same hash function.
+    ///
+    /// Returns true if for every entry, the first element of `path` is equal to `leaf`, and
+    /// `path` resolves to `root` at the specified `index`; returns false otherwise. Returns true
+    /// if `paths` is empty.
+    pub fn verify_many_paths(paths: &[(H::Digest, usize, H::Digest, &[H::Digest])]) -> bool {
+        paths.iter().all(|(root, index, leaf, path)| {
+            path.len() >= 2 && path[0] == *leaf && Self::verify(*root, *index, path).is_ok()
+        })
+    }
 }
 
 // HELPER FUNCTIONS
@@ -4,6 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::*;
+use crate::VectorCommitment;
 use math::fields::f128::BaseElement;
 use proptest::prelude::*;
 
@@ -232,6 +233,115 @@ fn verify_batch() {
     assert!(MerkleTree::verify_batch(tree.root(), &[0, 1, 2, 3, 4, 5, 6, 7], &proof).is_ok());
 }
 
+#[test]
+fn verify_many_paths() {
+    let leaves4 = Digest256::bytes_as_digests(&LEAVES4).to_vec();
+    let tree4 = MerkleTree::<Blake2s_256>::new(leaves4.clone()).unwrap();
+    let leaves8 = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree8 = MerkleTree::<Blake2s_256>::new(leaves8.clone()).unwrap();
+
+    let path4_1 = tree4.prove(1).unwrap();
+    let path4_2 = tree4.prove(2).unwrap();
+    let path8_6 = tree8.prove(6).unwrap();
+
+    // a mixed set of valid paths from two different trees
+    let paths = [
+        (*tree4.root(), 1, leaves4[1], path4_1.as_slice()),
+        (*tree8.root(), 6, leaves8[6], path8_6.as_slice()),
+        (*tree4.root(), 2, leaves4[2], path4_2.as_slice()),
+    ];
+    assert!(MerkleTree::<Blake2s_256>::verify_many_paths(&paths));
+    assert!(MerkleTree::<Blake2s_256>::verify_many_paths(&[]));
+
+    // a path checked against the wrong root
+    let mut invalid = paths;
+    invalid[1].0 = *tree4.root();
+    assert!(!MerkleTree::<Blake2s_256>::verify_many_paths(&invalid));
+
+    // a path checked against the wrong index
+    let mut invalid = paths;
+    invalid[0].1 = 0;
+    assert!(!MerkleTree::<Blake2s_256>::verify_many_paths(&invalid));
+
+    // a path which does not start with the specified leaf
+    let mut invalid = paths;
+    invalid[2].2 = leaves4[3];
+    assert!(!MerkleTree::<Blake2s_256>::verify_many_paths(&invalid));
+}
+
+#[test]
+fn merkle_paths_with_generic_digest_sizes() {
+    check_merkle_paths::<crate::hash::Blake2s<BaseElement, 16>>();
+    check_merkle_paths::<crate::hash::Blake2s<BaseElement, 24>>();
+    check_merkle_paths::<crate::hash::Blake2s<BaseElement, 32>>();
+    check_merkle_paths::<crate::hash::Blake3<BaseElement, 16>>();
+    check_merkle_paths::<crate::hash::Blake3<BaseElement, 24>>();
+    check_merkle_paths::<crate::hash::Blake3<BaseElement, 32>>();
+}
+
+#[test]
+fn update_leaf() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let new_leaf = Blake2s_256::hash(&[1, 2, 3]);
+
+    // updating any single leaf results in the same tree as building it from scratch
+    for index in 0..leaves.len() {
+        let mut tree = MerkleTree::<Blake2s_256>::new(leaves.clone()).unwrap();
+        tree.update_leaf(index, new_leaf).unwrap();
+
+        let mut new_leaves = leaves.clone();
+        new_leaves[index] = new_leaf;
+        let expected = MerkleTree::<Blake2s_256>::new(new_leaves).unwrap();
+
+        assert_eq!(expected.root(), tree.root());
+        assert_eq!(expected.leaves(), tree.leaves());
+        for i in 0..leaves.len() {
+            assert_eq!(expected.prove(i).unwrap(), tree.prove(i).unwrap());
+        }
+    }
+
+    // updating a leaf which is not in the tree should fail
+    let mut tree = MerkleTree::<Blake2s_256>::new(leaves.clone()).unwrap();
+    assert_eq!(
+        Err(MerkleTreeError::LeafIndexOutOfBounds(8, 8)),
+        tree.update_leaf(8, new_leaf)
+    );
+    assert_eq!(leaves, tree.leaves());
+}
+
+#[test]
+fn vector_commitment_merkle_tree() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    check_vector_commitment::<MerkleTree<Blake2s_256>>(leaves);
+}
+
+#[test]
+fn vector_commitment_merkle_cap() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    check_vector_commitment::<MerkleCap<Blake2s_256>>(leaves);
+
+    // the commitment consists of the nodes at the cap level of the underlying tree
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let cap = MerkleCap::<Blake2s_256>::commit(leaves.clone()).unwrap();
+    let tree = MerkleTree::<Blake2s_256>::new(leaves).unwrap();
+    let commitment = cap.commitment();
+    assert_eq!(1 << MERKLE_CAP_HEIGHT, commitment.len());
+    assert_eq!(
+        *tree.root(),
+        hash_2x1(
+            hash_2x1(commitment[0], commitment[1]),
+            hash_2x1(commitment[2], commitment[3])
+        )
+    );
+
+    // openings are shorter than Merkle paths by the height of the cap
+    let (_, opening) = cap.open(5).unwrap();
+    assert_eq!(
+        tree.prove(5).unwrap().len() - MERKLE_CAP_HEIGHT,
+        opening.len()
+    );
+}
+
 proptest! {
     #[test]
     fn prove_n_verify(tree in random_blake3_merkle_tree(128),
@@ -273,6 +383,28 @@ proptest! {
 
 // HELPER FUNCTIONS
 // --------------------------------------------------------------------------------------------
+fn check_merkle_paths<H: Hasher>() {
+    let leaves = (0..8u8).map(|i| H::hash(&[i])).collect::<Vec<_>>();
+    let tree = MerkleTree::<H>::new(leaves.clone()).unwrap();
+
+    // individual paths
+    for (i, leaf) in leaves.iter().enumerate() {
+        let proof = tree.prove(i).unwrap();
+        assert_eq!(*leaf, proof[0]);
+        assert!(MerkleTree::<H>::verify(*tree.root(), i, &proof).is_ok());
+    }
+
+    // batch paths
+    let indexes = [1, 2, 6];
+    let proof = tree.prove_batch(&indexes).unwrap();
+    assert!(MerkleTree::<H>::verify_batch(tree.root(), &indexes, &proof).is_ok());
+
+    // a path for a different leaf should not verify
+    let mut proof = tree.prove(3).unwrap();
+    proof[0] = H::hash(&[42]);
+    assert!(MerkleTree::<H>::verify(*tree.root(), 3, &proof).is_err());
+}
+
 fn hash_2x1(v1: Digest256, v2: Digest256) -> Digest256 {
     Blake2s_256::merge(&[v1, v2])
 }
@@ -285,3 +417,88 @@ pub fn random_blake3_merkle_tree(
         MerkleTree::<Blake2s_256>::new(leaves).unwrap()
     })
 }
+
+fn check_vector_commitment<V: VectorCommitment<Blake2s_256>>(leaves: Vec<Digest256>) {
+    let vc = V::commit(leaves.clone()).unwrap();
+    assert_eq!(leaves.len(), vc.num_leaves());
+    let commitment = vc.commitment();
+
+    for (i, &leaf) in leaves.iter().enumerate() {
+        let (opened_leaf, opening) = vc.open(i).unwrap();
+        assert_eq!(leaf, opened_leaf);
+        assert!(V::verify_opening(&commitment, i, leaf, &opening).is_ok());
+
+        // wrong leaf and wrong index should be rejected
+        let other = (i + 1) % leaves.len();
+        assert!(V::verify_opening(&commitment, i, leaves[other], &opening).is_err());
+        assert!(V::verify_opening(&commitment, other, leaf, &opening).is_err());
+    }
+
+    assert!(vc.open(leaves.len()).is_err());
+}
+
+// MERKLE CAP
+// --------------------------------------------------------------------------------------------
+
+const MERKLE_CAP_HEIGHT: usize = 2;
+
+/// A Merkle tree for which all nodes at depth [MERKLE_CAP_HEIGHT] are used as the commitment;
+/// this shortens each opening by [MERKLE_CAP_HEIGHT] nodes.
+struct MerkleCap<H: Hasher> {
+    tree: MerkleTree<H>,
+}
+
+impl<H: Hasher> VectorCommitment<H> for MerkleCap<H> {
+    type Commitment = Vec<H::Digest>;
+    type Opening = Vec<H::Digest>;
+    type Error = MerkleTreeError;
+
+    fn commit(leaves: Vec<H::Digest>) -> Result<Self, Self::Error> {
+        let tree = MerkleTree::new(leaves)?;
+        assert!(tree.depth() > MERKLE_CAP_HEIGHT);
+        Ok(Self { tree })
+    }
+
+    fn commitment(&self) -> Self::Commitment {
+        let cap_size = 1 << MERKLE_CAP_HEIGHT;
+        self.tree.nodes[cap_size..cap_size * 2].to_vec()
+    }
+
+    fn num_leaves(&self) -> usize {
+        self.tree.leaves().len()
+    }
+
+    fn open(&self, index: usize) -> Result<(H::Digest, Self::Opening), Self::Error> {
+        let mut path = self.tree.prove(index)?;
+        path.truncate(path.len() - MERKLE_CAP_HEIGHT);
+        Ok((path[0], path))
+    }
+
+    fn verify_opening(
+        commitment: &Self::Commitment,
+        index: usize,
+        leaf: H::Digest,
+        opening: &Self::Opening,
+    ) -> Result<(), Self::Error> {
+        if commitment.len() != 1 << MERKLE_CAP_HEIGHT || opening.len() < 2 || opening[0] != leaf {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+
+        let r = index & 1;
+        let mut v = H::merge(&[opening[r], opening[1 - r]]);
+        let mut index = index >> 1;
+        for &p in opening.iter().skip(2) {
+            v = if index & 1 == 0 {
+                H::merge(&[v, p])
+            } else {
+                H::merge(&[p, v])
+            };
+            index >>= 1;
+        }
+
+        match commitment.get(index) {
+            Some(&node) if node == v => Ok(()),
+            _ => Err(MerkleTreeError::InvalidProof),
+        }
+    }
+}
@@ -3,10 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{errors::RandomCoinError, Digest, Hasher};
+use crate::{errors::RandomCoinError, Digest, ElementHasher, Hasher};
 use core::{convert::TryInto, marker::PhantomData};
 use math::{FieldElement, StarkField};
-use utils::collections::Vec;
+use utils::{collections::Vec, Serializable};
 
 // RANDOM COIN
 // ================================================================================================
@@ -22,7 +22,9 @@ use utils::collections::Vec;
 ///   time, the `seed` is set to a hash of the provided bytes, and the `counter` is set to 0.
 /// - To draw the next element, we increment the `counter` and compute hash(`seed` || `counter`).
 ///   If the resulting value is a valid field element, we return the result; otherwise we try
-///   again until a valid element is found or the number of allowed tries is exceeded.
+///   again until a valid element is found or the number of allowed tries is exceeded. Elements
+///   which do not fit into a single digest are assembled from several consecutive digests, one
+///   per base field element.
 /// - We can also re-seed the coin with a new value. During the reseeding procedure, the
 ///   seed is set to hash(`old_seed` || `new_seed`), and the counter is reset to 0.
 ///
@@ -56,6 +58,14 @@ use utils::collections::Vec;
 /// let e2 = coin2.draw::<BaseElement>().unwrap();;
 /// assert_ne!(e1, e2);
 /// ```
+///
+/// # Transcript
+/// When `transcript` feature is enabled, the coin records every operation which changes its
+/// state (instantiation, reseeding, and drawing of elements or integers) together with the
+/// resulting seed or drawn values. The recorded events can be retrieved via
+/// [RandomCoin::transcript()], and the transcripts of the prover and the verifier can be compared
+/// to find the first operation at which they diverge. When the feature is disabled, nothing is
+/// recorded.
 pub struct RandomCoin<B, H>
 where
     B: StarkField,
@@ -63,6 +73,8 @@ where
 {
     pub seed: H::Digest,
     pub counter: u64,
+    #[cfg(feature = "transcript")]
+    transcript: Vec<TranscriptEvent>,
     _base_field: PhantomData<B>,
 }
 
@@ -72,11 +84,34 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
     /// Returns a new random coin instantiated with the provided `seed`.
     pub fn new(seed: &[u8]) -> Self {
         let seed = H::hash(seed);
-        RandomCoin {
-            seed,
-            counter: 0,
-            _base_field: PhantomData,
-        }
+        Self::with_seed(seed)
+    }
+
+    /// Returns a new random coin instantiated with a hash of the provided `seed` elements.
+    ///
+    /// Unlike [RandomCoin::new()], the seed is hashed via
+    /// [ElementHasher::hash_elements()] rather than as a sequence of bytes, which makes the
+    /// initial state of the coin cheaper to compute with algebraic hash functions.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{RandomCoin, hashers::Blake3_256};
+    /// # use math::{fields::f128::BaseElement, FieldElement};
+    /// let seed = [BaseElement::ONE, BaseElement::new(2)];
+    /// let mut coin1 = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::from_elements(&seed);
+    /// let mut coin2 = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::from_elements(&seed);
+    ///
+    /// // should draw the same elements for the same seed
+    /// let e1 = coin1.draw::<BaseElement>().unwrap();
+    /// let e2 = coin2.draw::<BaseElement>().unwrap();
+    /// assert_eq!(e1, e2);
+    /// ```
+    pub fn from_elements(seed: &[B]) -> Self
+    where
+        H: ElementHasher<BaseField = B>,
+    {
+        let seed = H::hash_elements(seed);
+        Self::with_seed(seed)
     }
 
     // RESEEDING
@@ -105,6 +140,7 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
     pub fn reseed(&mut self, data: H::Digest) {
         self.seed = H::merge(&[self.seed, data]);
         self.counter = 0;
+        self.record(|coin| TranscriptEvent::Reseed(coin.seed.to_bytes()));
     }
 
     /// Reseeds the coin with the specified value by setting the new seed to hash(`seed` ||
@@ -131,6 +167,7 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
     pub fn reseed_with_int(&mut self, value: u64) {
         self.seed = H::merge_with_int(self.seed, value);
         self.counter = 0;
+        self.record(|coin| TranscriptEvent::Reseed(coin.seed.to_bytes()));
     }
 
     // PUBLIC ACCESSORS
@@ -168,6 +205,24 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
         seed_head.trailing_zeros()
     }
 
+    /// Returns the events recorded by this coin since its instantiation.
+    ///
+    /// Events are recorded only when `transcript` feature is enabled; otherwise, the returned
+    /// slice is always empty.
+    #[cfg(feature = "transcript")]
+    pub fn transcript(&self) -> &[TranscriptEvent] {
+        &self.transcript
+    }
+
+    /// Returns the events recorded by this coin since its instantiation.
+    ///
+    /// Events are recorded only when `transcript` feature is enabled; otherwise, the returned
+    /// slice is always empty.
+    #[cfg(not(feature = "transcript"))]
+    pub fn transcript(&self) -> &[TranscriptEvent] {
+        &[]
+    }
+
     // DRAW METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -181,13 +236,13 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
         E: FieldElement<BaseField = B>,
     {
         for _ in 0..1000 {
-            // get the next pseudo-random value and take the first ELEMENT_BYTES from it
-            let value = self.next();
-            let bytes = &value.as_bytes()[..E::ELEMENT_BYTES as usize];
+            // get the next ELEMENT_BYTES of pseudo-random bytes
+            let bytes = self.next_element_bytes::<E>();
 
             // check if the bytes can be converted into a valid field element; if they can,
             // return; otherwise try again
-            if let Some(element) = E::from_random_bytes(bytes) {
+            if let Some(element) = E::from_random_bytes(&bytes) {
+                self.record(|_| TranscriptEvent::DrawElement(element.to_bytes()));
                 return Ok(element);
             }
         }
@@ -293,15 +348,83 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
             ));
         }
 
+        self.record(|_| TranscriptEvent::DrawIntegers(values.clone()));
         Ok(values)
     }
 
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns a new random coin with the specified initial seed.
+    fn with_seed(seed: H::Digest) -> Self {
+        let mut coin = RandomCoin {
+            seed,
+            counter: 0,
+            #[cfg(feature = "transcript")]
+            transcript: Vec::new(),
+            _base_field: PhantomData,
+        };
+        coin.record(|coin| TranscriptEvent::Init(coin.seed.to_bytes()));
+        coin
+    }
+
+    /// Appends the event built by `event` to the transcript of this coin; when `transcript`
+    /// feature is disabled, this is a no-op and `event` is never invoked.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    fn record<F: FnOnce(&Self) -> TranscriptEvent>(&mut self, event: F) {
+        #[cfg(feature = "transcript")]
+        {
+            let event = event(self);
+            self.transcript.push(event);
+        }
+    }
+
     /// Updates the state by incrementing the counter and returns hash(seed || counter)
     fn next(&mut self) -> H::Digest {
         self.counter += 1;
         H::merge_with_int(self.seed, self.counter)
     }
+
+    /// Returns the next `E::ELEMENT_BYTES` pseudo-random bytes.
+    ///
+    /// If an element of `E` fits into a single digest, the bytes are taken from the next digest.
+    /// Otherwise (e.g., for extensions of a 252-bit field), the bytes are assembled from as many
+    /// consecutive digests as there are base field elements in `E`, taking the first
+    /// `B::ELEMENT_BYTES` bytes from each digest.
+    fn next_element_bytes<E: FieldElement<BaseField = B>>(&mut self) -> Vec<u8> {
+        let element_bytes = E::ELEMENT_BYTES;
+        let value = self.next().as_bytes();
+        if element_bytes <= value.len() {
+            return value[..element_bytes].to_vec();
+        }
+
+        let base_bytes = B::ELEMENT_BYTES;
+        let mut result = Vec::with_capacity(element_bytes);
+        result.extend_from_slice(&value[..base_bytes]);
+        while result.len() < element_bytes {
+            result.extend_from_slice(&self.next().as_bytes()[..base_bytes]);
+        }
+        result
+    }
+}
+
+// TRANSCRIPT EVENT
+// ================================================================================================
+
+/// An operation performed on a [RandomCoin] recorded in the transcript of the coin.
+///
+/// Seeds are recorded as raw bytes of the digest, and drawn field elements are recorded in their
+/// serialized form; this makes it possible to compare transcripts of coins instantiated with
+/// different field extensions without knowing the concrete types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptEvent {
+    /// The coin was instantiated with the specified seed.
+    Init(Vec<u8>),
+    /// The coin was reseeded; contains the resulting seed.
+    Reseed(Vec<u8>),
+    /// A field element was drawn from the coin; contains the serialized element.
+    DrawElement(Vec<u8>),
+    /// A set of unique integers was drawn from the coin.
+    DrawIntegers(Vec<usize>),
 }
@@ -0,0 +1,43 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use examples::{fibonacci, Example};
+use std::time::Duration;
+use winterfell::{FieldExtension, HashFunction, ProofOptions};
+
+const SIZES: [usize; 3] = [16_384, 65_536, 262_144];
+
+fn divisor_cache(c: &mut Criterion) {
+    let mut group = c.benchmark_group("divisor_cache");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    let options = ProofOptions::new(
+        32,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+
+    for &size in SIZES.iter() {
+        let fib = fibonacci::fib2::FibExample::new(size, options.clone());
+        group.bench_function(BenchmarkId::new("uncached", size), |bench| {
+            bench.iter(|| fib.prove());
+        });
+
+        let fib = fibonacci::fib2::FibExample::new(size, options.clone()).with_divisor_cache();
+        group.bench_function(BenchmarkId::new("cached", size), |bench| {
+            bench.iter(|| fib.prove());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(divisor_cache_group, divisor_cache);
+criterion_main!(divisor_cache_group);
@@ -0,0 +1,41 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use examples::{rescue_raps, Example};
+use std::time::Duration;
+use winterfell::{FieldExtension, HashFunction, ProofOptions};
+
+// Rescue RAPs example has a relatively wide execution trace, and thus, constraint evaluation
+// accounts for a large portion of proof generation time; running this benchmark with and without
+// `concurrent` feature shows the effect of evaluating constraints in parallel.
+const SIZES: [usize; 2] = [256, 1024];
+
+fn rescue_raps(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rescue_raps");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(25));
+
+    let options = ProofOptions::new(
+        32,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+
+    for &size in SIZES.iter() {
+        let resc = rescue_raps::RescueRapsExample::new(size, options.clone());
+        group.bench_function(BenchmarkId::from_parameter(size), |bench| {
+            bench.iter(|| resc.prove());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(rescue_raps_group, rescue_raps);
+criterion_main!(rescue_raps_group);
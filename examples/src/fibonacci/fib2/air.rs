@@ -45,6 +45,7 @@ impl Air for FibAir {
         &self,
         frame: &Self::Frame<E>,
         _periodic_values: &[E],
+        _next_periodic_values: &[E],
         result: &mut [E],
     ) {
         let current = frame.current();
@@ -9,7 +9,8 @@ use log::debug;
 use std::time::Instant;
 use winterfell::{
     math::{fields::f128::BaseElement, log2, FieldElement},
-    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+    Air, DivisorCache, ProofOptions, Prover, ProverObserver, StarkProof, Trace, TraceInfo,
+    TraceTable, VerifierError,
 };
 
 mod air;
@@ -40,6 +41,7 @@ pub struct FibExample {
     options: ProofOptions,
     sequence_length: usize,
     result: BaseElement,
+    divisor_cache: Option<DivisorCache<BaseElement>>,
 }
 
 impl FibExample {
@@ -62,8 +64,18 @@ impl FibExample {
             options,
             sequence_length,
             result,
+            divisor_cache: None,
         }
     }
+
+    /// Precomputes transition constraint divisors for this example so that they are reused by
+    /// all subsequent invocations of [prove()](Example::prove).
+    pub fn with_divisor_cache(mut self) -> Self {
+        let trace_info = TraceInfo::new(TRACE_WIDTH, self.sequence_length / 2);
+        let air = FibAir::new(trace_info, self.result, self.options.clone());
+        self.divisor_cache = Some(DivisorCache::new(&air));
+        self
+    }
 }
 
 // EXAMPLE IMPLEMENTATION
@@ -78,7 +90,10 @@ impl Example for FibExample {
         );
 
         // create a prover
-        let prover = FibProver::new(self.options.clone());
+        let prover = match self.divisor_cache {
+            Some(ref cache) => FibProver::with_divisor_cache(self.options.clone(), cache),
+            None => FibProver::new(self.options.clone()),
+        };
 
         // generate execution trace
         let now = Instant::now();
@@ -4,19 +4,45 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    BaseElement, FibAir, FieldElement, ProofOptions, Prover, Trace, TraceTable, TRACE_WIDTH,
+    BaseElement, DivisorCache, FibAir, FieldElement, ProofOptions, Prover, ProverObserver, Trace,
+    TraceTable, TRACE_WIDTH,
 };
 
 // FIBONACCI PROVER
 // ================================================================================================
 
-pub struct FibProver {
+pub struct FibProver<'a> {
     options: ProofOptions,
+    divisor_cache: Option<&'a DivisorCache<BaseElement>>,
+    observer: Option<&'a dyn ProverObserver>,
 }
 
-impl FibProver {
+impl<'a> FibProver<'a> {
     pub fn new(options: ProofOptions) -> Self {
-        Self { options }
+        Self {
+            options,
+            divisor_cache: None,
+            observer: None,
+        }
+    }
+
+    /// Returns a prover which reuses the specified divisor cache across all generated proofs.
+    pub fn with_divisor_cache(options: ProofOptions, cache: &'a DivisorCache<BaseElement>) -> Self {
+        Self {
+            options,
+            divisor_cache: Some(cache),
+            observer: None,
+        }
+    }
+
+    /// Returns a prover which notifies the specified observer about the progress of proof
+    /// generation.
+    pub fn with_observer(options: ProofOptions, observer: &'a dyn ProverObserver) -> Self {
+        Self {
+            options,
+            divisor_cache: None,
+            observer: Some(observer),
+        }
     }
 
     /// Builds an execution trace for computing a Fibonacci sequence of the specified length such
@@ -43,7 +69,7 @@ impl FibProver {
     }
 }
 
-impl Prover for FibProver {
+impl<'a> Prover for FibProver<'a> {
     type BaseField = BaseElement;
     type Air = FibAir;
     type Trace = TraceTable<BaseElement>;
@@ -56,4 +82,12 @@ impl Prover for FibProver {
     fn options(&self) -> &ProofOptions {
         &self.options
     }
+
+    fn divisor_cache(&self) -> Option<&DivisorCache<BaseElement>> {
+        self.divisor_cache
+    }
+
+    fn observer(&self) -> &dyn ProverObserver {
+        self.observer.unwrap_or(&())
+    }
 }
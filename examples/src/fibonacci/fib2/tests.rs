@@ -22,3 +22,738 @@ fn fib2_test_basic_proof_verification_fail() {
     let fib = Box::new(super::FibExample::new(16, build_proof_options(false)));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib2_test_prove_and_verify() {
+    use super::{prover::FibProver, BaseElement, FieldElement};
+    use crate::{utils::prove_and_verify, Example};
+
+    let fib = super::FibExample::new(16, build_proof_options(false));
+    let prover = FibProver::new(build_proof_options(false));
+    let proof = prove_and_verify(&prover, prover.build_trace(16), fib.result).unwrap();
+    assert!(fib.verify(proof).is_ok());
+
+    // a proof for a different result must be rejected
+    let result = fib.result + BaseElement::ONE;
+    assert!(prove_and_verify(&prover, prover.build_trace(16), result).is_err());
+}
+
+#[test]
+fn fib2_test_proof_verification_with_seed() {
+    use super::{air::FibAir, prover::FibProver, BaseElement, Prover, Trace};
+
+    let prover = FibProver::new(build_proof_options(false));
+    let seed1 = [BaseElement::new(1), BaseElement::new(2)];
+    let seed2 = [BaseElement::new(3), BaseElement::new(4)];
+
+    let trace = prover.build_trace(16);
+    let result = trace.get(1, trace.length() - 1);
+    let proof1 = prover.prove_with_seed(trace, &seed1).unwrap();
+    let proof2 = prover
+        .prove_with_seed(prover.build_trace(16), &seed2)
+        .unwrap();
+
+    // proofs bound to different seeds must differ
+    assert_ne!(proof1.to_bytes(), proof2.to_bytes());
+
+    // a proof must verify only against the seed it was generated with
+    assert!(winterfell::verify_with_seed::<FibAir>(proof1.clone(), result, &seed1).is_ok());
+    assert!(winterfell::verify_with_seed::<FibAir>(proof1.clone(), result, &seed2).is_err());
+    assert!(winterfell::verify::<FibAir>(proof1, result).is_err());
+    assert!(winterfell::verify_with_seed::<FibAir>(proof2, result, &seed2).is_ok());
+}
+
+#[test]
+fn fib2_test_proof_generation_with_deadline() {
+    use super::{air::FibAir, prover::FibProver, Prover, Trace};
+    use std::time::{Duration, Instant};
+    use winterfell::{ProverError, ProverPhase};
+
+    let prover = FibProver::new(build_proof_options(false));
+
+    // a deadline in the past aborts proof generation before the trace is extended
+    let deadline = Instant::now() - Duration::from_secs(1);
+    let result = prover.prove_with_deadline(prover.build_trace(16), deadline);
+    assert_eq!(
+        Err(ProverError::DeadlineExceeded(ProverPhase::TraceLde)),
+        result.map(|_| ())
+    );
+
+    // a generous deadline has no effect on the generated proof
+    let trace = prover.build_trace(16);
+    let result = trace.get(1, trace.length() - 1);
+    let deadline = Instant::now() + Duration::from_secs(600);
+    let proof = prover.prove_with_deadline(trace, deadline).unwrap();
+    assert_eq!(
+        prover.prove(prover.build_trace(16)).unwrap().to_bytes(),
+        proof.to_bytes()
+    );
+    assert!(winterfell::verify::<FibAir>(proof, result).is_ok());
+}
+
+#[test]
+fn fib2_test_proof_verification_with_divisor_cache() {
+    use crate::Example;
+
+    let fib = super::FibExample::new(16, build_proof_options(false));
+    let fib_cached = super::FibExample::new(16, build_proof_options(false)).with_divisor_cache();
+
+    // using cached divisors must not change the proof
+    let proof = fib.prove();
+    let cached_proof = fib_cached.prove();
+    assert_eq!(proof.to_bytes(), cached_proof.to_bytes());
+    assert!(fib_cached.verify(cached_proof).is_ok());
+}
+
+#[test]
+fn fib2_test_proof_generation_with_observer() {
+    use super::{air::FibAir, prover::FibProver};
+    use std::cell::RefCell;
+    use winterfell::ProverObserver;
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        TraceCommitted(usize),
+        ConstraintsEvaluated,
+        FriLayer(usize, usize),
+        ProofComplete,
+    }
+
+    #[derive(Default)]
+    struct RecordingObserver(RefCell<Vec<Event>>);
+
+    impl ProverObserver for RecordingObserver {
+        fn on_trace_committed(&self, segment_idx: usize) {
+            self.0.borrow_mut().push(Event::TraceCommitted(segment_idx));
+        }
+
+        fn on_constraints_evaluated(&self) {
+            self.0.borrow_mut().push(Event::ConstraintsEvaluated);
+        }
+
+        fn on_fri_layer(&self, layer_idx: usize, domain_size: usize) {
+            self.0
+                .borrow_mut()
+                .push(Event::FriLayer(layer_idx, domain_size));
+        }
+
+        fn on_proof_complete(&self) {
+            self.0.borrow_mut().push(Event::ProofComplete);
+        }
+    }
+
+    let options = build_proof_options(false);
+    let observer = RecordingObserver::default();
+    let prover = FibProver::with_observer(options.clone(), &observer);
+    let (proof, result) = prove_fib(&prover, 256);
+    let lde_domain_size = proof.lde_domain_size();
+    assert!(winterfell::verify::<FibAir>(proof, result).is_ok());
+
+    // with a blowup factor of 8 and folding factor of 4, the LDE domain of 1024 elements is
+    // folded once before the remainder fits into 256 elements
+    let fri_options = options.to_fri_options();
+    let num_fri_layers = fri_options.num_fri_layers(lde_domain_size) + 1;
+    assert_eq!(2, num_fri_layers);
+    let mut expected = vec![Event::TraceCommitted(0), Event::ConstraintsEvaluated];
+    let mut domain_size = lde_domain_size;
+    for layer_idx in 0..num_fri_layers {
+        expected.push(Event::FriLayer(layer_idx, domain_size));
+        domain_size /= fri_options.folding_factor();
+    }
+    expected.push(Event::ProofComplete);
+    assert_eq!(expected, observer.0.into_inner());
+}
+
+#[test]
+fn fib2_test_ood_evaluations() {
+    use super::{air::FibAir, prover::FibProver, BaseElement, FieldElement, Prover, Trace};
+    use winterfell::{
+        crypto::hashers::Blake3_256,
+        math::{fft, polynom},
+        Air, DefaultEvaluationFrame, EvaluationFrame, Table,
+    };
+
+    let options = build_proof_options(false);
+    let prover = FibProver::new(options.clone());
+    let trace = prover.build_trace(64);
+    let result = trace.get(1, trace.length() - 1);
+    let air = FibAir::new(trace.get_info(), result, options);
+
+    let ood = prover
+        .build_ood_evaluations::<BaseElement, Blake3_256<BaseElement>>(prover.build_trace(64))
+        .unwrap();
+
+    // the evaluations are the same as the ones included into the proof
+    let proof = prover.prove(prover.build_trace(64)).unwrap();
+    let (main_frame, aux_frame, evaluations) = proof
+        .ood_frame
+        .parse::<BaseElement, DefaultEvaluationFrame<_>, DefaultEvaluationFrame<_>>(
+            air.trace_layout().main_trace_width(),
+            0,
+            2,
+            ood.constraint_evaluations.len(),
+        )
+        .unwrap();
+    assert!(aux_frame.is_none());
+    assert_eq!(ood.trace_states[0], main_frame.current());
+    assert_eq!(ood.trace_states[1], main_frame.next());
+    assert_eq!(ood.constraint_evaluations, evaluations);
+
+    // the trace states are evaluations of the trace polynomials at z and z * g
+    let inv_twiddles = fft::get_inv_twiddles::<BaseElement>(trace.length());
+    let trace_polys = trace
+        .main_segment()
+        .columns()
+        .map(|column| {
+            let mut poly = column.to_vec();
+            fft::interpolate_poly(&mut poly, &inv_twiddles);
+            poly
+        })
+        .collect::<Vec<_>>();
+    let g = air.trace_domain_generator();
+    let evaluate_frame = |x: BaseElement| {
+        let current = trace_polys.iter().map(|p| polynom::eval(p, x)).collect();
+        let next = trace_polys
+            .iter()
+            .map(|p| polynom::eval(p, x * g))
+            .collect();
+        DefaultEvaluationFrame::<BaseElement>::from_table(Table::from_rows(vec![current, next]))
+    };
+    let z_frame = evaluate_frame(ood.z);
+    assert_eq!(ood.trace_states[0], z_frame.current());
+    assert_eq!(ood.trace_states[1], z_frame.next());
+
+    // transition constraints of degree 1 vanish over all but the last step of the trace domain,
+    // and thus, they are equal to the divisor multiplied by a constant; so, dividing constraint
+    // evaluations at z by the divisor must give the same result as at any other point
+    let n = trace.length() as u64;
+    let last_step = g.exp(n - 1);
+    let quotients = |x: BaseElement| {
+        let mut evaluations = [BaseElement::ZERO; 2];
+        air.evaluate_transition(&evaluate_frame(x), &[], &[], &mut evaluations);
+        let divisor = (x.exp(n) - BaseElement::ONE) / (x - last_step);
+        evaluations.map(|e| e / divisor)
+    };
+    assert_eq!(quotients(ood.z), quotients(ood.z + BaseElement::ONE));
+}
+
+#[test]
+fn fib2_test_proof_verification_with_compact_ood_frame() {
+    use crate::Example;
+    use winterfell::{
+        math::fields::{f128::BaseElement, QuadExtension},
+        OodFrame, SliceReader,
+    };
+
+    let fib = super::FibExample::new(16, build_proof_options(true));
+    let mut proof = fib.prove();
+
+    // round-trip the OOD frame through the compact encoding
+    let mut bytes = Vec::new();
+    proof
+        .ood_frame
+        .write_compact_into::<QuadExtension<BaseElement>, _>(&mut bytes)
+        .unwrap();
+    let mut reader = SliceReader::new(&bytes);
+    let ood_frame =
+        OodFrame::read_compact_from::<QuadExtension<BaseElement>, _>(&mut reader).unwrap();
+    assert_eq!(proof.ood_frame, ood_frame);
+
+    proof.ood_frame = ood_frame;
+    assert!(fib.verify(proof).is_ok());
+}
+
+#[test]
+fn fib2_test_proof_stats() {
+    use crate::Example;
+    use winterfell::{Deserializable, ProofStats, Serializable, SliceReader};
+
+    // use a long enough sequence for the proof to contain at least one FRI layer
+    let fib = super::FibExample::new(1024, build_proof_options(false));
+    let proof = fib.prove();
+    let stats = proof.stats();
+
+    assert_eq!(512, stats.trace_length);
+    assert_eq!(super::TRACE_WIDTH, stats.main_trace_width);
+    assert_eq!(0, stats.aux_trace_width);
+    assert_eq!(0, stats.num_aux_segments);
+    assert_eq!(proof.lde_domain_size(), stats.lde_domain_size);
+    assert_eq!(
+        stats.trace_length * proof.options().blowup_factor(),
+        stats.lde_domain_size
+    );
+    // all Fibonacci constraints are of degree 1, and thus, fit into a single composition column
+    assert_eq!(1, stats.num_composition_columns);
+    assert_eq!(proof.options().num_queries(), stats.num_queries);
+    assert_eq!(proof.fri_proof.num_layers(), stats.num_fri_layers);
+    assert_eq!(
+        proof
+            .options()
+            .to_fri_options()
+            .num_fri_layers(stats.lde_domain_size),
+        stats.num_fri_layers
+    );
+    assert!(stats.num_fri_layers > 0);
+    assert_eq!(proof.security_level(true), stats.security_level);
+
+    // all components must be accounted for in the size breakdown
+    let size = stats.size;
+    for component in [
+        size.context,
+        size.commitments,
+        size.trace_queries,
+        size.constraint_queries,
+        size.ood_frame,
+        size.fri_proof,
+    ] {
+        assert!(component > 0);
+    }
+    assert_eq!(8, size.pow_nonce);
+    assert_eq!(proof.to_bytes().len(), size.total());
+
+    // stats must survive a serialization round-trip
+    let bytes = stats.to_bytes();
+    let mut reader = SliceReader::new(&bytes);
+    assert_eq!(stats, ProofStats::read_from(&mut reader).unwrap());
+}
+
+#[test]
+fn fib2_test_proof_verification_with_wrong_extension() {
+    use super::{air::FibAir, prover::FibProver, BaseElement, FieldElement, ProofOptions};
+    use winterfell::{
+        Air, AirContext, Assertion, DefaultEvaluationFrame, FieldExtension, TraceInfo,
+        VerifierError,
+    };
+
+    /// Fibonacci AIR which always uses cubic field extension, regardless of the proof options it
+    /// is instantiated with.
+    struct CubicFibAir(FibAir);
+
+    impl Air for CubicFibAir {
+        type BaseField = BaseElement;
+        type PublicInputs = BaseElement;
+        type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
+        type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
+
+        fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+            let fri_options = options.to_fri_options();
+            let options = ProofOptions::new(
+                options.num_queries(),
+                options.blowup_factor(),
+                options.grinding_factor(),
+                options.hash_fn(),
+                FieldExtension::Cubic,
+                fri_options.folding_factor(),
+                fri_options.max_remainder_size(),
+            );
+            Self(FibAir::new(trace_info, pub_inputs, options))
+        }
+
+        fn context(&self) -> &AirContext<Self::BaseField> {
+            self.0.context()
+        }
+
+        fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+            &self,
+            frame: &Self::Frame<E>,
+            periodic_values: &[E],
+            next_periodic_values: &[E],
+            result: &mut [E],
+        ) {
+            self.0
+                .evaluate_transition(frame, periodic_values, next_periodic_values, result)
+        }
+
+        fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+            self.0.get_assertions()
+        }
+    }
+
+    // generate a proof using quadratic extension
+    let (proof, result) = prove_fib(&FibProver::new(build_proof_options(true)), 16);
+    assert_eq!(FieldExtension::Quadratic, proof.options().field_extension());
+
+    assert!(winterfell::verify::<FibAir>(proof.clone(), result).is_ok());
+    assert_eq!(
+        Err(VerifierError::ExtensionDegreeMismatch(2, 3)),
+        winterfell::verify::<CubicFibAir>(proof, result)
+    );
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn fib2_test_constraint_subset_verification() {
+    use super::{air::FibAir, prover::FibProver, BaseElement, FieldElement, ProofOptions};
+    use winterfell::{
+        Air, AirContext, Assertion, DefaultEvaluationFrame, TraceInfo, VerifierError,
+    };
+
+    /// Fibonacci AIR in which the second transition constraint is defined incorrectly; the first
+    /// transition constraint is delegated to [FibAir].
+    struct BrokenFibAir(FibAir);
+
+    impl Air for BrokenFibAir {
+        type BaseField = BaseElement;
+        type PublicInputs = BaseElement;
+        type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
+        type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
+
+        fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+            Self(FibAir::new(trace_info, pub_inputs, options))
+        }
+
+        fn context(&self) -> &AirContext<Self::BaseField> {
+            self.0.context()
+        }
+
+        fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+            &self,
+            frame: &Self::Frame<E>,
+            periodic_values: &[E],
+            next_periodic_values: &[E],
+            result: &mut [E],
+        ) {
+            self.0
+                .evaluate_transition(frame, periodic_values, next_periodic_values, result);
+
+            // s_{1, i+1} = s_{1, i} + s_{0, i} does not hold for a Fibonacci sequence
+            let current = frame.current();
+            let next = frame.next();
+            result[1] = next[1] - (current[1] + current[0]);
+        }
+
+        fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+            self.0.get_assertions()
+        }
+    }
+
+    let (proof, result) = prove_fib(&FibProver::new(build_proof_options(false)), 16);
+
+    // the full verification fails without pointing to the broken constraint
+    assert_eq!(
+        Err(VerifierError::InconsistentOodConstraintEvaluations),
+        winterfell::verify::<BrokenFibAir>(proof.clone(), result)
+    );
+
+    // isolating the satisfied constraint passes, while isolating the broken one fails
+    assert!(
+        winterfell::verify_constraint_subset::<BrokenFibAir>(proof.clone(), result, &[0]).is_ok()
+    );
+    assert_eq!(
+        Err(VerifierError::TransitionConstraintNotSatisfied(1, 0)),
+        winterfell::verify_constraint_subset::<BrokenFibAir>(proof.clone(), result, &[0, 1])
+    );
+    assert_eq!(
+        Err(VerifierError::TransitionConstraintNotSatisfied(1, 0)),
+        winterfell::verify_constraint_subset::<BrokenFibAir>(proof, result, &[1])
+    );
+
+    // traces with more steps than there are queries in the proof cannot be reconstructed
+    let (proof, result) = prove_fib(&FibProver::new(build_proof_options(false)), 128);
+    assert_eq!(28, proof.options().num_queries());
+    assert_eq!(
+        Err(VerifierError::NotEnoughTraceQueries(64, 28)),
+        winterfell::verify_constraint_subset::<FibAir>(proof, result, &[0])
+    );
+}
+
+#[test]
+fn fib2_test_stacked_proof_verification() {
+    use super::{prover::FibProver, BaseElement, FieldElement};
+    use stacked::{StackedFibAir, StackedFibProver};
+    use winterfell::{MultiTrace, Prover};
+
+    let fib = FibProver::new(build_proof_options(false));
+    let trace = MultiTrace::new(vec![fib.build_trace(16), fib.build_trace(16)]);
+    assert_eq!(0..2, trace.main_columns(0));
+    assert_eq!(2..4, trace.main_columns(1));
+
+    let prover = StackedFibProver::new(build_proof_options(false));
+    let results = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+
+    assert!(winterfell::verify::<StackedFibAir>(proof.clone(), results.clone()).is_ok());
+
+    // the proof must not verify if the result of any one of the instances is wrong
+    let mut wrong_results = results;
+    wrong_results.0[1] += BaseElement::ONE;
+    assert!(winterfell::verify::<StackedFibAir>(proof, wrong_results).is_err());
+}
+
+#[test]
+fn fib2_test_sparse_trace_proof_verification() {
+    use super::BaseElement;
+    use selector::{SelectorFibAir, SelectorFibProver};
+    use winterfell::{FieldElement, Prover, TraceColumn};
+
+    let prover = SelectorFibProver::new(build_proof_options(false));
+    let trace = prover.build_trace(64);
+    match trace.get_column(2) {
+        TraceColumn::Sparse(column) => assert_eq!(1, column.num_nonzero()),
+        TraceColumn::Dense(_) => panic!("selector column must be stored sparsely"),
+    }
+
+    let result = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    assert!(winterfell::verify::<SelectorFibAir>(proof.clone(), result).is_ok());
+    assert!(winterfell::verify::<SelectorFibAir>(proof, result + BaseElement::ONE).is_err());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds a trace for a Fibonacci sequence of the specified length, and returns a proof generated
+/// for it by the specified prover together with the last term of the sequence.
+fn prove_fib(
+    prover: &super::prover::FibProver,
+    sequence_length: usize,
+) -> (super::StarkProof, super::BaseElement) {
+    use super::{Prover, Trace};
+
+    let trace = prover.build_trace(sequence_length);
+    let result = trace.get(1, trace.length() - 1);
+    (prover.prove(trace).unwrap(), result)
+}
+
+// STACKED FIBONACCI
+// ================================================================================================
+
+mod stacked {
+    use super::super::{
+        air::FibAir, BaseElement, FieldElement, ProofOptions, TraceTable, TRACE_WIDTH,
+    };
+    use winterfell::{
+        Air, AirContext, Assertion, DefaultEvaluationFrame, EvaluationFrame, MultiTrace, Prover,
+        PublicCoin, Trace, TraceInfo, TransitionConstraintDegree,
+    };
+
+    /// Results of the stacked Fibonacci computations, one per computation.
+    #[derive(Clone)]
+    pub struct StackedFibInputs(pub Vec<BaseElement>);
+
+    impl PublicCoin for StackedFibInputs {
+        type BaseField = BaseElement;
+
+        fn to_elements(&self) -> Vec<BaseElement> {
+            self.0.clone()
+        }
+    }
+
+    /// AIR for several independent Fibonacci computations stacked side by side; constraints of
+    /// each computation are delegated to [FibAir] evaluated against the columns of that
+    /// computation.
+    pub struct StackedFibAir {
+        context: AirContext<BaseElement>,
+        instances: Vec<FibAir>,
+    }
+
+    impl Air for StackedFibAir {
+        type BaseField = BaseElement;
+        type PublicInputs = StackedFibInputs;
+        type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
+        type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
+
+        fn new(trace_info: TraceInfo, pub_inputs: StackedFibInputs, options: ProofOptions) -> Self {
+            assert_eq!(TRACE_WIDTH * pub_inputs.0.len(), trace_info.width());
+            let instance_info = TraceInfo::new(TRACE_WIDTH, trace_info.length());
+            let instances = pub_inputs
+                .0
+                .into_iter()
+                .map(|result| FibAir::new(instance_info.clone(), result, options.clone()))
+                .collect::<Vec<_>>();
+
+            let degrees = vec![TransitionConstraintDegree::new(1); TRACE_WIDTH * instances.len()];
+            let num_assertions = 3 * instances.len();
+            StackedFibAir {
+                context: AirContext::new(trace_info, degrees, num_assertions, options),
+                instances,
+            }
+        }
+
+        fn context(&self) -> &AirContext<Self::BaseField> {
+            &self.context
+        }
+
+        fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+            &self,
+            frame: &Self::Frame<E>,
+            periodic_values: &[E],
+            next_periodic_values: &[E],
+            result: &mut [E],
+        ) {
+            let mut result_offset = 0;
+            for (i, instance) in self.instances.iter().enumerate() {
+                let local_frame = frame.select_columns(i * TRACE_WIDTH..(i + 1) * TRACE_WIDTH);
+
+                let num_constraints = instance.context().num_main_transition_constraints();
+                instance.evaluate_transition(
+                    &local_frame,
+                    periodic_values,
+                    next_periodic_values,
+                    &mut result[result_offset..result_offset + num_constraints],
+                );
+                result_offset += num_constraints;
+            }
+        }
+
+        fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+            let mut result = Vec::new();
+            for (i, instance) in self.instances.iter().enumerate() {
+                let columns = (i * TRACE_WIDTH..(i + 1) * TRACE_WIDTH).collect::<Vec<_>>();
+                for assertion in instance.get_assertions() {
+                    result.push(assertion.remap_column(&columns));
+                }
+            }
+            result
+        }
+    }
+
+    pub struct StackedFibProver {
+        options: ProofOptions,
+    }
+
+    impl StackedFibProver {
+        pub fn new(options: ProofOptions) -> Self {
+            Self { options }
+        }
+    }
+
+    impl Prover for StackedFibProver {
+        type BaseField = BaseElement;
+        type Air = StackedFibAir;
+        type Trace = MultiTrace<TraceTable<BaseElement>>;
+
+        fn get_pub_inputs(&self, trace: &Self::Trace) -> StackedFibInputs {
+            StackedFibInputs(
+                (0..trace.num_traces())
+                    .map(|i| {
+                        let instance = trace.get_trace(i);
+                        instance.get(1, instance.length() - 1)
+                    })
+                    .collect(),
+            )
+        }
+
+        fn options(&self) -> &ProofOptions {
+            &self.options
+        }
+    }
+}
+
+// FIBONACCI WITH SELECTOR
+// ================================================================================================
+
+mod selector {
+    use super::super::{
+        air::FibAir, prover::FibProver, BaseElement, FieldElement, ProofOptions, TRACE_WIDTH,
+    };
+    use winterfell::{
+        Air, AirContext, Assertion, DefaultEvaluationFrame, EvaluationFrame, Prover, SparseColumn,
+        SparseTrace, Table, Trace, TraceInfo, TransitionConstraintDegree,
+    };
+
+    /// AIR for a Fibonacci computation with an additional one-hot selector column which marks
+    /// the row containing the result. The constraints of the Fibonacci computation are delegated
+    /// to [FibAir].
+    pub struct SelectorFibAir {
+        context: AirContext<BaseElement>,
+        inner: FibAir,
+    }
+
+    impl Air for SelectorFibAir {
+        type BaseField = BaseElement;
+        type PublicInputs = BaseElement;
+        type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
+        type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
+
+        fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+            assert_eq!(TRACE_WIDTH + 1, trace_info.width());
+            let degrees = vec![
+                TransitionConstraintDegree::new(1),
+                TransitionConstraintDegree::new(1),
+                TransitionConstraintDegree::new(2),
+            ];
+            let inner_info = TraceInfo::new(TRACE_WIDTH, trace_info.length());
+            SelectorFibAir {
+                context: AirContext::new(trace_info, degrees, 5, options.clone()),
+                inner: FibAir::new(inner_info, pub_inputs, options),
+            }
+        }
+
+        fn context(&self) -> &AirContext<Self::BaseField> {
+            &self.context
+        }
+
+        fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+            &self,
+            frame: &Self::Frame<E>,
+            periodic_values: &[E],
+            next_periodic_values: &[E],
+            result: &mut [E],
+        ) {
+            let fib_frame = DefaultEvaluationFrame::from_table(Table::from_rows(vec![
+                frame.current()[..TRACE_WIDTH].to_vec(),
+                frame.next()[..TRACE_WIDTH].to_vec(),
+            ]));
+            self.inner.evaluate_transition(
+                &fib_frame,
+                periodic_values,
+                next_periodic_values,
+                &mut result[..TRACE_WIDTH],
+            );
+
+            // the selector is binary
+            let selector = frame.current()[TRACE_WIDTH];
+            result[TRACE_WIDTH] = selector * (selector - E::ONE);
+        }
+
+        fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+            // the selector is cleared in the first row and set in the last row
+            let last_step = self.trace_length() - 1;
+            let mut result = self.inner.get_assertions();
+            result.push(Assertion::single(TRACE_WIDTH, 0, BaseElement::ZERO));
+            result.push(Assertion::single(TRACE_WIDTH, last_step, BaseElement::ONE));
+            result
+        }
+    }
+
+    pub struct SelectorFibProver {
+        options: ProofOptions,
+    }
+
+    impl SelectorFibProver {
+        pub fn new(options: ProofOptions) -> Self {
+            Self { options }
+        }
+
+        /// Builds a Fibonacci trace for a sequence of the specified length, and appends to it a
+        /// sparsely-stored selector column which is set only in the last row.
+        pub fn build_trace(&self, sequence_length: usize) -> SparseTrace<BaseElement> {
+            let fib = FibProver::new(self.options.clone()).build_trace(sequence_length);
+            let trace_length = fib.length();
+            let selector = SparseColumn::with_entries(
+                trace_length,
+                vec![(trace_length - 1, BaseElement::ONE)],
+            );
+            SparseTrace::init(vec![
+                fib.get_column(0).to_vec().into(),
+                fib.get_column(1).to_vec().into(),
+                selector.into(),
+            ])
+        }
+    }
+
+    impl Prover for SelectorFibProver {
+        type BaseField = BaseElement;
+        type Air = SelectorFibAir;
+        type Trace = SparseTrace<BaseElement>;
+
+        fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+            trace.get(1, trace.length() - 1)
+        }
+
+        fn options(&self) -> &ProofOptions {
+            &self.options
+        }
+    }
+}
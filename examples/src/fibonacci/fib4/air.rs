@@ -0,0 +1,83 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, FieldElement, TRACE_WIDTH};
+use crate::utils::are_equal;
+use winterfell::{
+    Air, AirContext, Assertion, DefaultEvaluationFrame, ProofOptions, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// FIBONACCI AIR
+// ================================================================================================
+
+pub struct Fib4Air {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for Fib4Air {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+    type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
+    type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: Self::BaseField, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        Fib4Air {
+            context: AirContext::new(trace_info, degrees, 5, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &Self::Frame<E>,
+        _periodic_values: &[E],
+        _next_periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        // expected state width is 4 field elements
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // constraints of Fibonacci sequence (4 registers, 4 terms per step):
+        // s_{0, i+1} = s_{2, i} + s_{3, i}
+        // s_{1, i+1} = s_{3, i} + s_{0, i+1}
+        // s_{2, i+1} = s_{0, i+1} + s_{1, i+1}
+        // s_{3, i+1} = s_{1, i+1} + s_{2, i+1}
+        result[0] = are_equal(next[0], current[2] + current[3]);
+        result[1] = are_equal(next[1], current[3] + next[0]);
+        result[2] = are_equal(next[2], next[0] + next[1]);
+        result[3] = are_equal(next[3], next[1] + next[2]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // a valid Fibonacci sequence should start with 1, 1, 2, 3 and terminate with the
+        // expected result
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::new(1)),
+            Assertion::single(1, 0, BaseElement::new(1)),
+            Assertion::single(2, 0, BaseElement::new(2)),
+            Assertion::single(3, 0, BaseElement::new(3)),
+            Assertion::single(3, last_step, self.result),
+        ]
+    }
+}
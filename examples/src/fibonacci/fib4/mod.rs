@@ -0,0 +1,106 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::utils::compute_fib_term;
+use crate::{Example, ExampleOptions};
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    math::{fields::f128::BaseElement, log2, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+};
+
+mod air;
+use air::Fib4Air;
+
+mod prover;
+use prover::Fib4Prover;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+const TRACE_WIDTH: usize = 4;
+
+// FIBONACCI EXAMPLE
+// ================================================================================================
+
+pub fn get_example(options: ExampleOptions, sequence_length: usize) -> Box<dyn Example> {
+    Box::new(Fib4Example::new(
+        sequence_length,
+        options.to_proof_options(28, 8),
+    ))
+}
+
+pub struct Fib4Example {
+    options: ProofOptions,
+    sequence_length: usize,
+    result: BaseElement,
+}
+
+impl Fib4Example {
+    pub fn new(sequence_length: usize, options: ProofOptions) -> Fib4Example {
+        assert!(
+            sequence_length.is_power_of_two(),
+            "sequence length must be a power of 2"
+        );
+
+        // compute Fibonacci sequence
+        let now = Instant::now();
+        let result = compute_fib_term(sequence_length);
+        debug!(
+            "Computed Fibonacci sequence up to {}th term in {} ms",
+            sequence_length,
+            now.elapsed().as_millis()
+        );
+
+        Fib4Example {
+            options,
+            sequence_length,
+            result,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl Example for Fib4Example {
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for computing Fibonacci sequence (4 terms per step) up to {}th term\n\
+            ---------------------",
+            self.sequence_length
+        );
+
+        // create a prover
+        let prover = Fib4Prover::new(self.options.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(self.sequence_length);
+        let trace_width = trace.width();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace_width,
+            log2(trace_length),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        winterfell::verify::<Fib4Air>(proof, self.result)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        winterfell::verify::<Fib4Air>(proof, self.result + BaseElement::ONE)
+    }
+}
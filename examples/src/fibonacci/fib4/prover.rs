@@ -0,0 +1,63 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, Fib4Air, FieldElement, ProofOptions, Prover, Trace, TraceTable, TRACE_WIDTH,
+};
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+pub struct Fib4Prover {
+    options: ProofOptions,
+}
+
+impl Fib4Prover {
+    pub fn new(options: ProofOptions) -> Self {
+        Self { options }
+    }
+
+    /// Builds an execution trace for computing a Fibonacci sequence of the specified length such
+    /// that each row advances the sequence by 4 terms.
+    pub fn build_trace(&self, length: usize) -> TraceTable<BaseElement> {
+        assert!(
+            length.is_power_of_two(),
+            "sequence length must be a power of 2"
+        );
+
+        let mut trace = TraceTable::new(TRACE_WIDTH, length / 4);
+        trace.fill(
+            |state| {
+                state[0] = BaseElement::ONE;
+                state[1] = BaseElement::ONE;
+                state[2] = state[0] + state[1];
+                state[3] = state[1] + state[2];
+            },
+            |_, state| {
+                state[0] = state[2] + state[3];
+                state[1] = state[3] + state[0];
+                state[2] = state[0] + state[1];
+                state[3] = state[1] + state[2];
+            },
+        );
+
+        trace
+    }
+}
+
+impl Prover for Fib4Prover {
+    type BaseField = BaseElement;
+    type Air = Fib4Air;
+    type Trace = TraceTable<BaseElement>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        let last_step = trace.length() - 1;
+        trace.get(3, last_step)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
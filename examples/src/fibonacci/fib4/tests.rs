@@ -0,0 +1,38 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::super::utils::build_proof_options;
+
+#[test]
+fn fib4_test_basic_proof_verification() {
+    let fib = Box::new(super::Fib4Example::new(64, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib4_test_basic_proof_verification_extension() {
+    let fib = Box::new(super::Fib4Example::new(64, build_proof_options(true)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib4_test_basic_proof_verification_fail() {
+    let fib = Box::new(super::Fib4Example::new(64, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification_fail(fib);
+}
+
+#[test]
+fn fib4_test_prove_and_verify() {
+    use super::{super::utils::compute_fib_term, prover::Fib4Prover, BaseElement, FieldElement};
+    use crate::utils::prove_and_verify;
+
+    let prover = Fib4Prover::new(build_proof_options(false));
+    let result = compute_fib_term(64);
+    assert!(prove_and_verify(&prover, prover.build_trace(64), result).is_ok());
+
+    // a proof for a different result must be rejected
+    let result = result + BaseElement::ONE;
+    assert!(prove_and_verify(&prover, prover.build_trace(64), result).is_err());
+}
@@ -46,6 +46,7 @@ impl Air for Fib8Air {
         &self,
         frame: &Self::Frame<E>,
         _periodic_values: &[E],
+        _next_periodic_values: &[E],
         result: &mut [E],
     ) {
         let current = frame.current();
@@ -4,8 +4,10 @@
 // LICENSE file in the root directory of this source tree.
 
 pub mod fib2;
+pub mod fib4;
 pub mod fib8;
 pub mod mulfib2;
+pub mod mulfib4;
 pub mod mulfib8;
 
 pub mod utils;
@@ -48,6 +48,7 @@ impl Air for MulFib2Air {
         &self,
         frame: &Self::Frame<E>,
         _periodic_values: &[E],
+        _next_periodic_values: &[E],
         result: &mut [E],
     ) {
         let current = frame.current();
@@ -54,6 +54,7 @@ impl Air for MulFib8Air {
         &self,
         frame: &Self::Frame<E>,
         _periodic_values: &[E],
+        _next_periodic_values: &[E],
         result: &mut [E],
     ) {
         let current = frame.current();
@@ -22,3 +22,14 @@ fn mulfib8_test_basic_proof_verification_fail() {
     let fib = Box::new(super::MulFib8Example::new(64, build_proof_options(false)));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn mulfib8_test_prove_and_verify() {
+    use super::prover::MulFib8Prover;
+    use crate::{utils::prove_and_verify, Example};
+
+    let fib = super::MulFib8Example::new(64, build_proof_options(false));
+    let prover = MulFib8Prover::new(build_proof_options(false));
+    let proof = prove_and_verify(&prover, prover.build_trace(64), fib.result).unwrap();
+    assert!(fib.verify(proof).is_ok());
+}
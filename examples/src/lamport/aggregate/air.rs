@@ -9,8 +9,8 @@ use super::{
 use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
 use winterfell::{
     math::{fields::f128::BaseElement, FieldElement},
-    Air, AirContext, Assertion, ByteWriter, DefaultEvaluationFrame, ProofOptions, Serializable,
-    TraceInfo, TransitionConstraintDegree,
+    Air, AirContext, Assertion, DefaultEvaluationFrame, ProofOptions, PublicCoin, TraceInfo,
+    TransitionConstraintDegree,
 };
 
 // CONSTANTS
@@ -26,10 +26,14 @@ pub struct PublicInputs {
     pub messages: Vec<[BaseElement; 2]>,
 }
 
-impl Serializable for PublicInputs {
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write(&self.pub_keys);
-        target.write(&self.messages);
+impl PublicCoin for PublicInputs {
+    type BaseField = BaseElement;
+
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = Vec::with_capacity((self.pub_keys.len() + self.messages.len()) * 2);
+        result.extend(self.pub_keys.iter().flatten());
+        result.extend(self.messages.iter().flatten());
+        result
     }
 }
 
@@ -98,6 +102,7 @@ impl Air for LamportAggregateAir {
         &self,
         frame: &Self::Frame<E>,
         periodic_values: &[E],
+        _next_periodic_values: &[E],
         result: &mut [E],
     ) {
         let current = frame.current();
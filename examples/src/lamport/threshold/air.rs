@@ -10,8 +10,8 @@ use super::{
 use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
 use winterfell::{
     math::{fields::f128::BaseElement, log2, FieldElement, StarkField},
-    Air, AirContext, Assertion, ByteWriter, DefaultEvaluationFrame, ProofOptions, Serializable,
-    TraceInfo, TransitionConstraintDegree,
+    Air, AirContext, Assertion, DefaultEvaluationFrame, ProofOptions, PublicCoin, TraceInfo,
+    TransitionConstraintDegree,
 };
 
 // CONSTANTS
@@ -29,12 +29,15 @@ pub struct PublicInputs {
     pub message: [BaseElement; 2],
 }
 
-impl Serializable for PublicInputs {
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write(&self.pub_key_root[..]);
-        target.write_u32(self.num_pub_keys as u32);
-        target.write_u32(self.num_signatures as u32);
-        target.write(&self.message[..]);
+impl PublicCoin for PublicInputs {
+    type BaseField = BaseElement;
+
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = self.pub_key_root.to_vec();
+        result.push(BaseElement::new(self.num_pub_keys as u128));
+        result.push(BaseElement::new(self.num_signatures as u128));
+        result.extend_from_slice(&self.message);
+        result
     }
 }
 
@@ -109,6 +112,7 @@ impl Air for LamportThresholdAir {
         &self,
         frame: &Self::Frame<E>,
         periodic_values: &[E],
+        _next_periodic_values: &[E],
         result: &mut [E],
     ) {
         let current = frame.current();
@@ -12,6 +12,7 @@ pub mod custom_frames;
 pub mod fibonacci;
 #[cfg(feature = "std")]
 pub mod lamport;
+pub mod logup;
 #[cfg(feature = "std")]
 pub mod merkle;
 pub mod rescue;
@@ -104,6 +105,12 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "1048576")]
         sequence_length: usize,
     },
+    /// Compute a Fibonacci sequence using trace table with 4 registers
+    Fib4 {
+        /// Length of Fibonacci sequence; must be a power of two
+        #[structopt(short = "n", default_value = "1048576")]
+        sequence_length: usize,
+    },
     /// Compute a Fibonacci sequence using trace table with 8 registers
     Fib8 {
         /// Length of Fibonacci sequence; must be a power of two
@@ -116,6 +123,12 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "1048576")]
         sequence_length: usize,
     },
+    /// Compute a multiplicative Fibonacci sequence using trace table with 4 registers
+    Mulfib4 {
+        /// Length of Fibonacci sequence; must be a power of two
+        #[structopt(short = "n", default_value = "1048576")]
+        sequence_length: usize,
+    },
     /// Compute a multiplicative Fibonacci sequence using trace table with 8 registers
     Mulfib8 {
         /// Length of Fibonacci sequence; must be a power of two
@@ -147,6 +160,12 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "1024")]
         chain_length: usize,
     },
+    /// Prove that a sequence of values lies in a range using a LogUp lookup argument
+    Logup {
+        /// Length of the execution trace; must be a power of two
+        #[structopt(short = "n", default_value = "1024")]
+        trace_length: usize,
+    },
     /// Compute a root of a Merkle path using Rescue hash function
     #[cfg(feature = "std")]
     Merkle {
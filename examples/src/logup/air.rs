@@ -0,0 +1,188 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, FieldElement, ProofOptions, AUX_TRACE_WIDTH, FLAG_COL, KERNEL_COL, LOGUP_COL,
+    MULTIPLICITY_COL, SUM_COL, TABLE_COL, TABLE_EVAL_COL, TRACE_WIDTH, VALUE_COL,
+};
+use crate::utils::are_equal;
+use winterfell::{
+    math::{log2, ExtensionOf},
+    Air, AirContext, Assertion, AuxTraceRandElements, DefaultEvaluationFrame, PublicCoin,
+    TraceInfo, TransitionConstraintDegree,
+};
+
+// LOGUP AIR
+// ================================================================================================
+
+pub struct PublicInputs {
+    pub values_sum: BaseElement,
+}
+
+impl PublicCoin for PublicInputs {
+    type BaseField = BaseElement;
+
+    fn to_elements(&self) -> Vec<BaseElement> {
+        vec![self.values_sum]
+    }
+}
+
+pub struct LogUpAir {
+    context: AirContext<BaseElement>,
+    values_sum: BaseElement,
+}
+
+impl Air for LogUpAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+    type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
+    type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let main_degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        let aux_degrees = vec![
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(2),
+        ];
+        assert_eq!(TRACE_WIDTH + AUX_TRACE_WIDTH, trace_info.width());
+        LogUpAir {
+            context: AirContext::new_multi_segment(
+                trace_info,
+                main_degrees,
+                aux_degrees,
+                6,
+                4,
+                options,
+            )
+            .set_lagrange_kernel_aux_column(KERNEL_COL),
+            values_sum: pub_inputs.values_sum,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &Self::Frame<E>,
+        _periodic_values: &[E],
+        _next_periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // all steps after the first one perform a lookup
+        result[0] = are_equal(next[FLAG_COL], E::ONE);
+
+        // the sum of looked up values is accumulated as the values are looked up
+        result[1] = are_equal(next[SUM_COL], current[SUM_COL] + next[VALUE_COL]);
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &Self::Frame<F>,
+        aux_frame: &Self::AuxFrame<E>,
+        _periodic_values: &[F],
+        _next_periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        let main_next = main_frame.next();
+
+        let aux_current = aux_frame.current();
+        let aux_next = aux_frame.next();
+
+        let alpha = aux_rand_elements.get_segment_elements(0)[self.num_vars()];
+
+        // enforce that the LogUp column is updated by m / (alpha - t) - f / (alpha - v), where
+        // the values are taken from the next row of the main trace; the denominators are moved
+        // to the other side of the equation to keep the constraint polynomial
+        let table = E::from(main_next[TABLE_COL]);
+        let value = E::from(main_next[VALUE_COL]);
+        let table_denom = alpha - table;
+        let value_denom = alpha - value;
+        result[0] = are_equal(
+            (aux_next[LOGUP_COL] - aux_current[LOGUP_COL]) * table_denom * value_denom,
+            value_denom.mul_base(main_next[MULTIPLICITY_COL])
+                - table_denom.mul_base(main_next[FLAG_COL]),
+        );
+
+        // enforce that the table evaluation column accumulates the inner product of the table
+        // and the Lagrange kernel columns
+        result[1] = are_equal(
+            aux_next[TABLE_EVAL_COL],
+            aux_current[TABLE_EVAL_COL] + aux_next[KERNEL_COL] * table,
+        );
+
+        // the last slot of the result is reserved for the Lagrange kernel constraint which is
+        // evaluated by the framework
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            // the first step does not perform a lookup and does not provide a table value
+            Assertion::single(TABLE_COL, 0, BaseElement::ZERO),
+            Assertion::single(MULTIPLICITY_COL, 0, BaseElement::ZERO),
+            Assertion::single(VALUE_COL, 0, BaseElement::ZERO),
+            Assertion::single(FLAG_COL, 0, BaseElement::ZERO),
+            // the sum of all looked up values must match the public input
+            Assertion::single(SUM_COL, 0, BaseElement::ZERO),
+            Assertion::single(SUM_COL, last_step, self.values_sum),
+        ]
+    }
+
+    fn get_aux_assertions<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        let last_step = self.trace_length() - 1;
+        let r = &aux_rand_elements.get_segment_elements(0)[..self.num_vars()];
+        vec![
+            // the LogUp sum must be zero, meaning that all looked up values are in the table
+            Assertion::single(LOGUP_COL, 0, E::ZERO),
+            Assertion::single(LOGUP_COL, last_step, E::ZERO),
+            // the inner product of the table and kernel columns is the evaluation of the
+            // multilinear extension of the table column at r; since the table value at step 0
+            // is asserted to be zero, the first value of the running product must be zero too
+            Assertion::single(TABLE_EVAL_COL, 0, E::ZERO),
+            Assertion::single(TABLE_EVAL_COL, last_step, get_table_evaluation(r)),
+        ]
+    }
+}
+
+impl LogUpAir {
+    /// Returns the number of variables of the Lagrange kernel, i.e., log2 of the trace length.
+    fn num_vars(&self) -> usize {
+        log2(self.trace_length()) as usize
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the evaluation of the multilinear extension of the column [0, 1, ..., 2^k - 1] at
+/// point r, where k is the length of r.
+fn get_table_evaluation<E: FieldElement>(r: &[E]) -> E {
+    let mut result = E::ZERO;
+    let mut power_of_two = E::ONE;
+    for &r_j in r.iter() {
+        result += power_of_two * r_j;
+        power_of_two = power_of_two.double();
+    }
+    result
+}
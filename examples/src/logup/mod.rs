@@ -0,0 +1,141 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Example, ExampleOptions};
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    math::{fields::f128::BaseElement, log2, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+mod trace;
+use trace::LogUpTrace;
+
+mod air;
+use air::{LogUpAir, PublicInputs};
+
+mod prover;
+use prover::LogUpProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Main trace column holding table values; the value at step i is expected to be i.
+const TABLE_COL: usize = 0;
+/// Main trace column holding the number of times each table value was looked up.
+const MULTIPLICITY_COL: usize = 1;
+/// Main trace column holding looked up values.
+const VALUE_COL: usize = 2;
+/// Main trace column which is set to ONE at all steps which perform a lookup.
+const FLAG_COL: usize = 3;
+/// Main trace column holding a running sum of looked up values.
+const SUM_COL: usize = 4;
+
+const TRACE_WIDTH: usize = 5;
+
+/// Auxiliary trace column holding a running sum of the LogUp argument.
+const LOGUP_COL: usize = 0;
+/// Auxiliary trace column designated as a Lagrange kernel column.
+const KERNEL_COL: usize = 1;
+/// Auxiliary trace column holding a running inner product of the table and the kernel columns.
+const TABLE_EVAL_COL: usize = 2;
+
+const AUX_TRACE_WIDTH: usize = 3;
+
+// LOGUP LOOKUP EXAMPLE
+// ================================================================================================
+
+pub fn get_example(options: ExampleOptions, trace_length: usize) -> Box<dyn Example> {
+    Box::new(LogUpExample::new(
+        trace_length,
+        options.to_proof_options(42, 8),
+    ))
+}
+
+/// Proves that all values in a sequence belong to the range [1, n), where n is the length of the
+/// execution trace.
+///
+/// The lookup is proven using a LogUp argument against a table column containing values
+/// [0, n) (the value at step 0 has zero multiplicity and is never looked up). Instead of being
+/// constrained step by step, the table column is bound to its expected values via a single
+/// claim about its multilinear extension: for a random point r, the inner product of the
+/// table column with a Lagrange kernel column for r must be equal to sum(2^j * r_j).
+pub struct LogUpExample {
+    options: ProofOptions,
+    values: Vec<BaseElement>,
+    values_sum: BaseElement,
+}
+
+impl LogUpExample {
+    pub fn new(trace_length: usize, options: ProofOptions) -> LogUpExample {
+        assert!(
+            trace_length.is_power_of_two(),
+            "trace length must a power of 2"
+        );
+        assert!(trace_length >= 8, "trace length must be at least 8");
+
+        // build a sequence of values in the range [1, trace_length); the first step of the trace
+        // does not perform a lookup, and thus, there are trace_length - 1 values
+        let range = trace_length - 1;
+        let values = (0..range)
+            .map(|i| BaseElement::new((1 + (i * i + 3 * i) % range) as u128))
+            .collect::<Vec<_>>();
+        let values_sum = values.iter().fold(BaseElement::ZERO, |acc, &v| acc + v);
+
+        LogUpExample {
+            options,
+            values,
+            values_sum,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl Example for LogUpExample {
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for looking up {} values using LogUp\n\
+            ---------------------",
+            self.values.len()
+        );
+
+        // create a prover
+        let prover = LogUpProver::new(self.options.clone());
+
+        // generate the execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.values);
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.main_trace_width(),
+            log2(trace_length),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            values_sum: self.values_sum,
+        };
+        winterfell::verify::<LogUpAir>(proof, pub_inputs)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            values_sum: self.values_sum + BaseElement::ONE,
+        };
+        winterfell::verify::<LogUpAir>(proof, pub_inputs)
+    }
+}
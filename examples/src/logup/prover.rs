@@ -0,0 +1,72 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, FieldElement, LogUpAir, LogUpTrace, ProofOptions, Prover, PublicInputs, Trace,
+    FLAG_COL, MULTIPLICITY_COL, SUM_COL, TABLE_COL, TRACE_WIDTH, VALUE_COL,
+};
+use winterfell::math::StarkField;
+
+// LOGUP PROVER
+// ================================================================================================
+
+pub struct LogUpProver {
+    options: ProofOptions,
+}
+
+impl LogUpProver {
+    pub fn new(options: ProofOptions) -> Self {
+        Self { options }
+    }
+
+    /// Builds an execution trace for looking up the specified values in a table of values
+    /// [0, n), where n is the length of the trace. The trace is one step longer than the number
+    /// of values, since the first step of the trace does not perform a lookup.
+    pub fn build_trace(&self, values: &[BaseElement]) -> LogUpTrace {
+        let trace_length = values.len() + 1;
+        assert!(
+            trace_length.is_power_of_two(),
+            "number of values must be one less than a power of 2"
+        );
+
+        let mut columns = vec![vec![BaseElement::ZERO; trace_length]; TRACE_WIDTH];
+        for (i, &value) in values.iter().enumerate() {
+            let step = i + 1;
+            let table_idx = value.as_int() as usize;
+            assert!(
+                table_idx > 0 && table_idx < trace_length,
+                "value {} is not in the table",
+                value
+            );
+
+            columns[VALUE_COL][step] = value;
+            columns[FLAG_COL][step] = BaseElement::ONE;
+            columns[MULTIPLICITY_COL][table_idx] += BaseElement::ONE;
+            columns[SUM_COL][step] = columns[SUM_COL][step - 1] + value;
+        }
+        for (step, table_value) in columns[TABLE_COL].iter_mut().enumerate() {
+            *table_value = BaseElement::new(step as u128);
+        }
+
+        LogUpTrace::new(columns)
+    }
+}
+
+impl Prover for LogUpProver {
+    type BaseField = BaseElement;
+    type Air = LogUpAir;
+    type Trace = LogUpTrace;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let last_step = trace.length() - 1;
+        PublicInputs {
+            values_sum: trace.get(SUM_COL, last_step),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
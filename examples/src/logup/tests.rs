@@ -0,0 +1,33 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use winterfell::{FieldExtension, HashFunction, ProofOptions};
+
+#[test]
+fn logup_test_basic_proof_verification() {
+    let logup_eg = Box::new(super::LogUpExample::new(64, build_options(false)));
+    crate::tests::test_basic_proof_verification(logup_eg);
+}
+
+#[test]
+fn logup_test_basic_proof_verification_extension() {
+    let logup_eg = Box::new(super::LogUpExample::new(64, build_options(true)));
+    crate::tests::test_basic_proof_verification(logup_eg);
+}
+
+#[test]
+fn logup_test_basic_proof_verification_fail() {
+    let logup_eg = Box::new(super::LogUpExample::new(64, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(logup_eg);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, HashFunction::Blake3_256, extension, 4, 256)
+}
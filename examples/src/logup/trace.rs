@@ -0,0 +1,112 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, FieldElement, AUX_TRACE_WIDTH, FLAG_COL, MULTIPLICITY_COL, TABLE_COL, TRACE_WIDTH,
+    VALUE_COL,
+};
+use winterfell::{build_lagrange_kernel_column, math::log2, Matrix, Trace, TraceLayout};
+
+// LOGUP TRACE
+// ================================================================================================
+/// An execution trace for the LogUp example.
+///
+/// The trace consists of a main segment with [TRACE_WIDTH] columns, and a single auxiliary
+/// segment with [AUX_TRACE_WIDTH] columns. The auxiliary segment requires log2(n) + 1 random
+/// elements, where n is the length of the trace: the first log2(n) elements define the point
+/// for the Lagrange kernel column, and the last element is used by the LogUp argument.
+pub struct LogUpTrace {
+    layout: TraceLayout,
+    trace: Matrix<BaseElement>,
+    meta: Vec<u8>,
+}
+
+impl LogUpTrace {
+    /// Returns a new trace with the main segment consisting of the specified columns.
+    pub fn new(columns: Vec<Vec<BaseElement>>) -> Self {
+        assert_eq!(TRACE_WIDTH, columns.len());
+        let trace = Matrix::new(columns);
+        let num_rand_elements = log2(trace.num_rows()) as usize + 1;
+        let layout = TraceLayout::new(TRACE_WIDTH, &[AUX_TRACE_WIDTH], &[num_rand_elements]);
+        LogUpTrace {
+            layout,
+            trace,
+            meta: Vec::new(),
+        }
+    }
+
+    /// Returns the value of the main segment column at the specified step.
+    pub fn get(&self, column: usize, step: usize) -> BaseElement {
+        self.trace.get(column, step)
+    }
+}
+
+// TRACE TRAIT IMPLEMENTATION
+// ================================================================================================
+
+impl Trace for LogUpTrace {
+    type BaseField = BaseElement;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.trace.num_rows()
+    }
+
+    fn meta(&self) -> &[u8] {
+        &self.meta
+    }
+
+    fn main_segment(&self) -> &Matrix<BaseElement> {
+        &self.trace
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[Matrix<E>],
+        rand_elements: &[E],
+    ) -> Option<Matrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // there is only one auxiliary segment in this example
+        if !aux_segments.is_empty() {
+            return None;
+        }
+
+        let num_vars = log2(self.length()) as usize;
+        let (r, alpha) = rand_elements.split_at(num_vars);
+        let alpha = alpha[0];
+
+        let table = self.trace.get_column(TABLE_COL);
+        let multiplicities = self.trace.get_column(MULTIPLICITY_COL);
+        let values = self.trace.get_column(VALUE_COL);
+        let flags = self.trace.get_column(FLAG_COL);
+
+        // the Lagrange kernel column is fully defined by the random point r
+        let kernel = build_lagrange_kernel_column(r);
+
+        // the LogUp column accumulates m_i / (alpha - t_i) - f_i / (alpha - v_i) starting with
+        // the second step, and the table evaluation column accumulates c_i * t_i
+        let mut logup = vec![E::ZERO; self.length()];
+        let mut table_eval = vec![E::ZERO; self.length()];
+        table_eval[0] = kernel[0] * E::from(table[0]);
+        for i in 1..self.length() {
+            let table_denom = alpha - E::from(table[i]);
+            let value_denom = alpha - E::from(values[i]);
+            logup[i] = logup[i - 1] + E::from(multiplicities[i]) * table_denom.inv()
+                - E::from(flags[i]) * value_denom.inv();
+            table_eval[i] = table_eval[i - 1] + kernel[i] * E::from(table[i]);
+        }
+
+        let mut result = vec![Vec::new(); AUX_TRACE_WIDTH];
+        result[super::LOGUP_COL] = logup;
+        result[super::KERNEL_COL] = kernel;
+        result[super::TABLE_EVAL_COL] = table_eval;
+        Some(Matrix::new(result))
+    }
+}
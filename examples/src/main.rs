@@ -9,7 +9,7 @@ use std::time::Instant;
 use structopt::StructOpt;
 use winterfell::StarkProof;
 
-use examples::{custom_frames, fibonacci, rescue, vdf, ExampleOptions, ExampleType};
+use examples::{custom_frames, fibonacci, logup, rescue, vdf, ExampleOptions, ExampleType};
 #[cfg(feature = "std")]
 use examples::{lamport, merkle, rescue_raps};
 
@@ -33,12 +33,18 @@ fn main() {
         ExampleType::Fib { sequence_length } => {
             fibonacci::fib2::get_example(options, sequence_length)
         }
+        ExampleType::Fib4 { sequence_length } => {
+            fibonacci::fib4::get_example(options, sequence_length)
+        }
         ExampleType::Fib8 { sequence_length } => {
             fibonacci::fib8::get_example(options, sequence_length)
         }
         ExampleType::Mulfib { sequence_length } => {
             fibonacci::mulfib2::get_example(options, sequence_length)
         }
+        ExampleType::Mulfib4 { sequence_length } => {
+            fibonacci::mulfib4::get_example(options, sequence_length)
+        }
         ExampleType::Mulfib8 { sequence_length } => {
             fibonacci::mulfib8::get_example(options, sequence_length)
         }
@@ -47,6 +53,7 @@ fn main() {
         ExampleType::Rescue { chain_length } => rescue::get_example(options, chain_length),
         #[cfg(feature = "std")]
         ExampleType::RescueRaps { chain_length } => rescue_raps::get_example(options, chain_length),
+        ExampleType::Logup { trace_length } => logup::get_example(options, trace_length),
         #[cfg(feature = "std")]
         ExampleType::Merkle { tree_depth } => merkle::get_example(options, tree_depth),
         #[cfg(feature = "std")]
@@ -6,8 +6,8 @@
 use super::{rescue, BaseElement, FieldElement, HASH_CYCLE_LEN, HASH_STATE_WIDTH, TRACE_WIDTH};
 use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
 use winterfell::{
-    Air, AirContext, Assertion, ByteWriter, DefaultEvaluationFrame, ProofOptions, Serializable,
-    TraceInfo, TransitionConstraintDegree,
+    Air, AirContext, Assertion, DefaultEvaluationFrame, ProofOptions, PublicCoin, TraceInfo,
+    TransitionConstraintDegree,
 };
 
 // MERKLE PATH VERIFICATION AIR
@@ -17,9 +17,11 @@ pub struct PublicInputs {
     pub tree_root: [BaseElement; 2],
 }
 
-impl Serializable for PublicInputs {
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write(&self.tree_root[..]);
+impl PublicCoin for PublicInputs {
+    type BaseField = BaseElement;
+
+    fn to_elements(&self) -> Vec<BaseElement> {
+        self.tree_root.to_vec()
     }
 }
 
@@ -61,6 +63,7 @@ impl Air for MerkleAir {
         &self,
         frame: &Self::Frame<E>,
         periodic_values: &[E],
+        _next_periodic_values: &[E],
         result: &mut [E],
     ) {
         let current = frame.current();
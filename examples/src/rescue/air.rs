@@ -6,7 +6,7 @@
 use super::{rescue, BaseElement, FieldElement, ProofOptions, CYCLE_LENGTH, TRACE_WIDTH};
 use crate::utils::{are_equal, is_zero, not, EvaluationResult};
 use winterfell::{
-    Air, AirContext, Assertion, ByteWriter, DefaultEvaluationFrame, Serializable, TraceInfo,
+    Air, AirContext, Assertion, DefaultEvaluationFrame, PublicCoin, TraceInfo,
     TransitionConstraintDegree,
 };
 
@@ -41,10 +41,13 @@ pub struct PublicInputs {
     pub result: [BaseElement; 2],
 }
 
-impl Serializable for PublicInputs {
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write(&self.seed[..]);
-        target.write(&self.result[..]);
+impl PublicCoin for PublicInputs {
+    type BaseField = BaseElement;
+
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = self.seed.to_vec();
+        result.extend_from_slice(&self.result);
+        result
     }
 }
 
@@ -85,6 +88,7 @@ impl Air for RescueAir {
         &self,
         frame: &Self::Frame<E>,
         periodic_values: &[E],
+        _next_periodic_values: &[E],
         result: &mut [E],
     ) {
         let current = frame.current();
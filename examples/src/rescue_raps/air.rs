@@ -9,8 +9,8 @@ use super::{
 };
 use crate::utils::{are_equal, not, EvaluationResult};
 use winterfell::{
-    Air, AirContext, Assertion, AuxTraceRandElements, ByteWriter, DefaultEvaluationFrame,
-    Serializable, TraceInfo, TransitionConstraintDegree,
+    Air, AirContext, Assertion, AuxTraceRandElements, DefaultEvaluationFrame, PublicCoin,
+    TraceInfo, TransitionConstraintDegree,
 };
 
 // CONSTANTS
@@ -43,9 +43,11 @@ pub struct PublicInputs {
     pub result: [[BaseElement; 2]; 2],
 }
 
-impl Serializable for PublicInputs {
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write(&self.result[..]);
+impl PublicCoin for PublicInputs {
+    type BaseField = BaseElement;
+
+    fn to_elements(&self) -> Vec<BaseElement> {
+        self.result.iter().flatten().copied().collect()
     }
 }
 
@@ -92,6 +94,7 @@ impl Air for RescueRapsAir {
         &self,
         frame: &Self::Frame<E>,
         periodic_values: &[E],
+        _next_periodic_values: &[E],
         result: &mut [E],
     ) {
         let current = frame.current();
@@ -157,6 +160,7 @@ impl Air for RescueRapsAir {
         main_frame: &Self::Frame<F>,
         aux_frame: &Self::AuxFrame<E>,
         periodic_values: &[F],
+        _next_periodic_values: &[F],
         aux_rand_elements: &AuxTraceRandElements<E>,
         result: &mut [E],
     ) where
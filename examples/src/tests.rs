@@ -15,3 +15,424 @@ pub fn test_basic_proof_verification_fail(e: Box<dyn Example>) {
     let verified = e.verify_with_wrong_inputs(proof);
     assert!(verified.is_err());
 }
+
+// BRANCHING CONSTRAINTS
+// ================================================================================================
+
+#[test]
+fn select_constraint_proof_verification() {
+    use crate::fibonacci::utils::build_proof_options;
+    use branching::{BranchingAir, BranchingProver};
+    use winterfell::{
+        math::{fields::f128::BaseElement, FieldElement},
+        Prover,
+    };
+
+    let prover = BranchingProver::new(build_proof_options(false));
+    let trace = prover.build_trace(32);
+    let result = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+
+    assert!(winterfell::verify::<BranchingAir>(proof.clone(), result).is_ok());
+    assert!(winterfell::verify::<BranchingAir>(proof, result + BaseElement::ONE).is_err());
+}
+
+mod branching {
+    use crate::utils::{are_equal, is_binary};
+    use winterfell::{
+        math::{fields::f128::BaseElement, FieldElement},
+        select_constraint, Air, AirContext, Assertion, DefaultEvaluationFrame, EvaluationFrame,
+        ProofOptions, Prover, Trace, TraceInfo, TraceTable, TransitionConstraintDegree,
+    };
+
+    /// AIR for a counter which, depending on the value of a selector column, either increments
+    /// its value or resets it to zero at every step.
+    pub struct BranchingAir {
+        context: AirContext<BaseElement>,
+        result: BaseElement,
+    }
+
+    impl Air for BranchingAir {
+        type BaseField = BaseElement;
+        type PublicInputs = BaseElement;
+        type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
+        type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
+
+        fn new(trace_info: TraceInfo, result: BaseElement, options: ProofOptions) -> Self {
+            let degrees = vec![
+                TransitionConstraintDegree::new(2),
+                TransitionConstraintDegree::new(2),
+            ];
+            BranchingAir {
+                context: AirContext::new(trace_info, degrees, 2, options),
+                result,
+            }
+        }
+
+        fn context(&self) -> &AirContext<Self::BaseField> {
+            &self.context
+        }
+
+        fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+            &self,
+            frame: &Self::Frame<E>,
+            _periodic_values: &[E],
+            _next_periodic_values: &[E],
+            result: &mut [E],
+        ) {
+            let current = frame.current();
+            let next = frame.next();
+
+            // the selector must be binary; when it is ONE, the counter is incremented, and when
+            // it is ZERO, the counter is reset
+            result[0] = is_binary(current[1]);
+            result[1] =
+                select_constraint(current[1], are_equal(next[0], current[0] + E::ONE), next[0]);
+        }
+
+        fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+            let last_step = self.trace_length() - 1;
+            vec![
+                Assertion::single(0, 0, BaseElement::ZERO),
+                Assertion::single(0, last_step, self.result),
+            ]
+        }
+    }
+
+    pub struct BranchingProver {
+        options: ProofOptions,
+    }
+
+    impl BranchingProver {
+        pub fn new(options: ProofOptions) -> Self {
+            Self { options }
+        }
+
+        /// Builds a trace in which the counter is reset at every fifth step.
+        pub fn build_trace(&self, length: usize) -> TraceTable<BaseElement> {
+            let selector = |step: usize| {
+                if step % 5 == 4 {
+                    BaseElement::ZERO
+                } else {
+                    BaseElement::ONE
+                }
+            };
+
+            let mut trace = TraceTable::new(2, length);
+            trace.fill(
+                |state| {
+                    state[0] = BaseElement::ZERO;
+                    state[1] = selector(0);
+                },
+                |step, state| {
+                    state[0] = if state[1] == BaseElement::ONE {
+                        state[0] + BaseElement::ONE
+                    } else {
+                        BaseElement::ZERO
+                    };
+                    state[1] = selector(step + 1);
+                },
+            );
+            trace
+        }
+    }
+
+    impl Prover for BranchingProver {
+        type BaseField = BaseElement;
+        type Air = BranchingAir;
+        type Trace = TraceTable<BaseElement>;
+
+        fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+            trace.get(0, trace.length() - 1)
+        }
+
+        fn options(&self) -> &ProofOptions {
+            &self.options
+        }
+    }
+}
+
+// MULTI-ROUND RANDOMIZED AIR
+// ================================================================================================
+
+#[test]
+fn two_round_rap_proof_verification() {
+    use crate::fibonacci::utils::build_proof_options;
+    use two_round_rap::{TwoRoundAir, TwoRoundProver};
+    use winterfell::{
+        math::{fields::f128::BaseElement, FieldElement},
+        Prover,
+    };
+
+    let prover = TwoRoundProver::new(build_proof_options(false));
+    let trace = prover.build_trace(64);
+    let result = BaseElement::new(63);
+    let proof = prover.prove(trace).unwrap();
+
+    assert!(winterfell::verify::<TwoRoundAir>(proof.clone(), result).is_ok());
+    assert!(winterfell::verify::<TwoRoundAir>(proof, result + BaseElement::ONE).is_err());
+}
+
+#[test]
+fn empty_aux_segment_proof_verification() {
+    use crate::fibonacci::utils::build_proof_options;
+    use two_round_rap::{TwoRoundAir, TwoRoundProver};
+    use winterfell::{
+        math::{fields::f128::BaseElement, FieldElement},
+        Prover, StarkProof, Trace,
+    };
+
+    // both auxiliary segments are declared, but neither contains any columns
+    let prover = TwoRoundProver::new(build_proof_options(false));
+    let trace = prover.build_trace_with_empty_aux_segments(64);
+    assert_eq!(0, trace.layout().num_aux_segments());
+    assert_eq!(0, trace.layout().aux_trace_width());
+
+    let result = BaseElement::new(63);
+    let proof = prover.prove(trace).unwrap();
+    assert!(!proof.get_trace_info().is_multi_segment());
+
+    // the proof survives a serialization round trip
+    let proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert!(winterfell::verify::<TwoRoundAir>(proof.clone(), result).is_ok());
+    assert!(winterfell::verify::<TwoRoundAir>(proof, result + BaseElement::ONE).is_err());
+}
+
+mod two_round_rap {
+    use crate::utils::are_equal;
+    use winterfell::{
+        math::{fields::f128::BaseElement, ExtensionOf, FieldElement},
+        Air, AirContext, Assertion, AuxTraceRandElements, DefaultEvaluationFrame, EvaluationFrame,
+        Matrix, ProofOptions, Prover, Trace, TraceInfo, TraceLayout, TransitionConstraintDegree,
+    };
+
+    /// AIR with two auxiliary trace segments built in two consecutive rounds of interaction.
+    ///
+    /// The main segment contains a column counting up from zero and a column counting down to
+    /// zero. In the first round, the prover receives a random element α and builds a grand
+    /// product column p which accumulates (α + up) / (α + down). In the second round, the prover
+    /// receives a random element β and builds a column s which accumulates β * p. Constraints
+    /// over s are expressed in terms of the current value of p and thus use both α and β.
+    ///
+    /// If the auxiliary segments of the trace are empty, only the constraints over the main
+    /// segment are enforced.
+    pub struct TwoRoundAir {
+        context: AirContext<BaseElement>,
+        result: BaseElement,
+    }
+
+    impl Air for TwoRoundAir {
+        type BaseField = BaseElement;
+        type PublicInputs = BaseElement;
+        type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
+        type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
+
+        fn new(trace_info: TraceInfo, result: BaseElement, options: ProofOptions) -> Self {
+            let main_degrees = vec![
+                TransitionConstraintDegree::new(1),
+                TransitionConstraintDegree::new(1),
+            ];
+            let (aux_degrees, num_aux_assertions) = if trace_info.is_multi_segment() {
+                let aux_degrees = vec![
+                    TransitionConstraintDegree::new(2),
+                    TransitionConstraintDegree::new(2),
+                ];
+                (aux_degrees, 2)
+            } else {
+                (Vec::new(), 0)
+            };
+            TwoRoundAir {
+                context: AirContext::new_multi_segment(
+                    trace_info,
+                    main_degrees,
+                    aux_degrees,
+                    3,
+                    num_aux_assertions,
+                    options,
+                ),
+                result,
+            }
+        }
+
+        fn context(&self) -> &AirContext<Self::BaseField> {
+            &self.context
+        }
+
+        fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+            &self,
+            frame: &Self::Frame<E>,
+            _periodic_values: &[E],
+            _next_periodic_values: &[E],
+            result: &mut [E],
+        ) {
+            let current = frame.current();
+            let next = frame.next();
+
+            result[0] = are_equal(next[0], current[0] + E::ONE);
+            result[1] = are_equal(next[1], current[1] - E::ONE);
+        }
+
+        fn evaluate_aux_transition<F, E>(
+            &self,
+            main_frame: &Self::Frame<F>,
+            aux_frame: &Self::AuxFrame<E>,
+            _periodic_values: &[F],
+            _next_periodic_values: &[F],
+            aux_rand_elements: &AuxTraceRandElements<E>,
+            result: &mut [E],
+        ) where
+            F: FieldElement<BaseField = Self::BaseField>,
+            E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+        {
+            let main_current = main_frame.current();
+            let aux_current = aux_frame.current();
+            let aux_next = aux_frame.next();
+
+            let alpha = aux_rand_elements.get_segment_elements(0)[0];
+            let beta = aux_rand_elements.get_segment_elements(1)[0];
+
+            let up = alpha + E::from(main_current[0]);
+            let down = alpha + E::from(main_current[1]);
+
+            // first round: p' * (α + down) = p * (α + up)
+            result[0] = are_equal(aux_next[0] * down, aux_current[0] * up);
+
+            // second round: s' = s + β * p', where p' is expressed via p and α
+            result[1] = are_equal(
+                aux_next[1] * down,
+                aux_current[1] * down + beta * aux_current[0] * up,
+            );
+        }
+
+        fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+            let last_step = self.trace_length() - 1;
+            vec![
+                Assertion::single(0, 0, BaseElement::ZERO),
+                Assertion::single(0, last_step, self.result),
+                Assertion::single(1, last_step, BaseElement::ZERO),
+            ]
+        }
+
+        fn get_aux_assertions<E: FieldElement<BaseField = Self::BaseField>>(
+            &self,
+            _aux_rand_elements: &AuxTraceRandElements<E>,
+        ) -> Vec<Assertion<E>> {
+            if !self.trace_info().is_multi_segment() {
+                return Vec::new();
+            }
+            vec![
+                Assertion::single(0, 0, E::ONE),
+                Assertion::single(1, 0, E::ZERO),
+            ]
+        }
+    }
+
+    /// Execution trace with two auxiliary segments of one column each.
+    pub struct TwoRoundTrace {
+        layout: TraceLayout,
+        trace: Matrix<BaseElement>,
+        meta: Vec<u8>,
+    }
+
+    impl Trace for TwoRoundTrace {
+        type BaseField = BaseElement;
+
+        fn layout(&self) -> &TraceLayout {
+            &self.layout
+        }
+
+        fn length(&self) -> usize {
+            self.trace.num_rows()
+        }
+
+        fn meta(&self) -> &[u8] {
+            &self.meta
+        }
+
+        fn main_segment(&self) -> &Matrix<BaseElement> {
+            &self.trace
+        }
+
+        fn build_aux_segment<E>(
+            &mut self,
+            aux_segments: &[Matrix<E>],
+            rand_elements: &[E],
+        ) -> Option<Matrix<E>>
+        where
+            E: FieldElement<BaseField = Self::BaseField>,
+        {
+            let length = self.length();
+            let mut column = vec![E::ZERO; length];
+            match aux_segments.len() {
+                0 => {
+                    // first round: grand product of (α + up) / (α + down)
+                    let alpha = rand_elements[0];
+                    column[0] = E::ONE;
+                    for i in 0..length - 1 {
+                        let up = alpha + E::from(self.trace.get(0, i));
+                        let down = alpha + E::from(self.trace.get(1, i));
+                        column[i + 1] = column[i] * up / down;
+                    }
+                }
+                1 => {
+                    // second round: running sum of β * p, where p was committed in the first round
+                    let beta = rand_elements[0];
+                    let p = aux_segments[0].get_column(0);
+                    for i in 0..length - 1 {
+                        column[i + 1] = column[i] + beta * p[i + 1];
+                    }
+                }
+                _ => return None,
+            }
+            Some(Matrix::new(vec![column]))
+        }
+    }
+
+    pub struct TwoRoundProver {
+        options: ProofOptions,
+    }
+
+    impl TwoRoundProver {
+        pub fn new(options: ProofOptions) -> Self {
+            Self { options }
+        }
+
+        /// Builds a trace with one column counting up from zero and another column counting
+        /// down to zero.
+        pub fn build_trace(&self, length: usize) -> TwoRoundTrace {
+            build_trace(length, TraceLayout::new(2, &[1, 1], &[1, 1]))
+        }
+
+        /// Builds the same trace as [TwoRoundProver::build_trace()], but declares both auxiliary
+        /// segments with zero width.
+        pub fn build_trace_with_empty_aux_segments(&self, length: usize) -> TwoRoundTrace {
+            build_trace(length, TraceLayout::new(2, &[0, 0], &[0, 0]))
+        }
+    }
+
+    impl Prover for TwoRoundProver {
+        type BaseField = BaseElement;
+        type Air = TwoRoundAir;
+        type Trace = TwoRoundTrace;
+
+        fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+            trace.main_segment().get(0, trace.length() - 1)
+        }
+
+        fn options(&self) -> &ProofOptions {
+            &self.options
+        }
+    }
+
+    fn build_trace(length: usize, layout: TraceLayout) -> TwoRoundTrace {
+        let up = (0..length)
+            .map(|i| BaseElement::new(i as u128))
+            .collect::<Vec<_>>();
+        let down = up.iter().rev().copied().collect();
+        TwoRoundTrace {
+            layout,
+            trace: Matrix::new(vec![up, down]),
+            meta: Vec::new(),
+        }
+    }
+}
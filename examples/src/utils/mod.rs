@@ -4,9 +4,11 @@
 // LICENSE file in the root directory of this source tree.
 
 use core::ops::Range;
+use log::debug;
+use std::time::Instant;
 use winterfell::{
     math::{fields::f128::BaseElement, FieldElement, StarkField},
-    Trace, TraceTable,
+    Air, Prover, StarkProof, Trace, TraceTable, VerifierError,
 };
 
 pub mod rescue;
@@ -54,6 +56,41 @@ impl<E: FieldElement> EvaluationResult<E> for Vec<E> {
     }
 }
 
+// PROVING HELPERS
+// ================================================================================================
+
+/// Generates a proof for the provided trace using the specified prover, verifies it against the
+/// specified public inputs, and returns the verified proof.
+///
+/// Proof generation and verification times are logged in the same way as by the examples runner.
+///
+/// # Errors
+/// Returns an error if the generated proof is not valid for the specified public inputs.
+///
+/// # Panics
+/// Panics if the prover fails to generate a proof for the provided trace.
+pub fn prove_and_verify<P: Prover>(
+    prover: &P,
+    trace: P::Trace,
+    pub_inputs: <P::Air as Air>::PublicInputs,
+) -> Result<StarkProof, VerifierError> {
+    let now = Instant::now();
+    let proof = prover.prove(trace).unwrap();
+    debug!(
+        "---------------------\nProof generated in {} ms",
+        now.elapsed().as_millis()
+    );
+
+    let now = Instant::now();
+    winterfell::verify::<P::Air>(proof.clone(), pub_inputs)?;
+    debug!(
+        "Proof verified in {:.1} ms",
+        now.elapsed().as_micros() as f64 / 1000f64
+    );
+
+    Ok(proof)
+}
+
 // OTHER FUNCTIONS
 // ================================================================================================
 
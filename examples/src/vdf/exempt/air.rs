@@ -5,7 +5,7 @@
 
 use super::{BaseElement, FieldElement, ProofOptions, ALPHA, FORTY_TWO};
 use winterfell::{
-    Air, AirContext, Assertion, ByteWriter, DefaultEvaluationFrame, Serializable, TraceInfo,
+    Air, AirContext, Assertion, DefaultEvaluationFrame, PublicCoin, TraceInfo,
     TransitionConstraintDegree,
 };
 
@@ -18,10 +18,11 @@ pub struct VdfInputs {
     pub result: BaseElement,
 }
 
-impl Serializable for VdfInputs {
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write(self.seed);
-        target.write(self.result);
+impl PublicCoin for VdfInputs {
+    type BaseField = BaseElement;
+
+    fn to_elements(&self) -> Vec<BaseElement> {
+        vec![self.seed, self.result]
     }
 }
 
@@ -57,6 +58,7 @@ impl Air for VdfAir {
         &self,
         frame: &Self::Frame<E>,
         _periodic_values: &[E],
+        _next_periodic_values: &[E],
         result: &mut [E],
     ) {
         let current_state = frame.current()[0];
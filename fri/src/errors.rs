@@ -19,12 +19,23 @@ pub enum VerifierError {
     /// Folding factor specified for the protocol is not supported. Currently, supported folding
     /// factors are: 4, 8, and 16.
     UnsupportedFoldingFactor(usize),
+    /// Number of externally supplied α values does not match the number of FRI layer commitments.
+    NumLayerAlphasMismatch(usize, usize),
+    /// Number of streamed FRI layer openings does not match the number of FRI layers.
+    NumLayerOpeningsMismatch(usize, usize),
     /// Number of query positions does not match the number of provided evaluations.
     NumPositionEvaluationMismatch(usize, usize),
     /// Evaluations at queried positions did not match layer commitment made by the prover.
     LayerCommitmentMismatch,
-    /// Degree-respecting projection was not performed correctly at one of the layers.
-    InvalidLayerFolding(usize),
+    /// Evaluations opened for the same position more than once within a FRI layer were not the
+    /// same; `position` is the first position with inconsistent openings.
+    DuplicateQueryMismatch(usize),
+    /// Evaluation at a queried position did not match the value implied by the previous layer.
+    /// For the first layer, this means that the provided evaluation did not match the value
+    /// committed to by the prover; for subsequent layers, this means that degree-respecting
+    /// projection was not performed correctly. `position` is the first failing query position in
+    /// the domain of `layer`.
+    QueryMismatch { position: usize, layer: usize },
     /// Failed to construct a Merkle tree out of FRI remainder values.
     RemainderTreeConstructionFailed(String),
     /// FRI remainder did not match the commitment.
@@ -49,6 +60,12 @@ impl fmt::Display for VerifierError {
             Self::UnsupportedFoldingFactor(value) => {
                 write!(f, "folding factor {} is not currently supported", value)
             }
+            Self::NumLayerAlphasMismatch(num_layers, num_alphas) => {
+                write!(f, "expected {} FRI layer alphas, but {} were provided", num_layers, num_alphas)
+            }
+            Self::NumLayerOpeningsMismatch(num_layers, num_openings) => {
+                write!(f, "expected {} FRI layer openings, but {} were provided", num_layers, num_openings)
+            }
             Self::NumPositionEvaluationMismatch(num_positions, num_evaluations) => write!(f,
                 "the number of query positions must be the same as the number of polynomial evaluations, but {} and {} were provided",
                 num_positions, num_evaluations
@@ -56,8 +73,11 @@ impl fmt::Display for VerifierError {
             Self::LayerCommitmentMismatch => {
                 write!(f, "FRI queries did not match layer commitment made by the prover")
             }
-            Self::InvalidLayerFolding(layer) => {
-                write!(f, "degree-respecting projection is not consistent at layer {}", layer)
+            Self::DuplicateQueryMismatch(position) => {
+                write!(f, "FRI queries opened more than once at position {} are not the same", position)
+            }
+            Self::QueryMismatch { position, layer } => {
+                write!(f, "FRI query at position {} is not consistent at layer {}", position, layer)
             }
             Self::RemainderTreeConstructionFailed(err_msg) => {
                 write!(f, "FRI remainder Merkle tree could not be constructed: {}", err_msg)
@@ -79,7 +79,7 @@ mod options;
 pub use options::FriOptions;
 
 mod proof;
-pub use proof::FriProof;
+pub use proof::{FriProof, FriProofSummary};
 
 mod errors;
 pub use errors::VerifierError;
@@ -13,7 +13,9 @@ use math::StarkField;
 pub struct FriOptions {
     folding_factor: usize,
     max_remainder_size: usize,
+    max_remainder_degree: Option<usize>,
     blowup_factor: usize,
+    domain_offset: Option<u64>,
 }
 
 impl FriOptions {
@@ -48,18 +50,51 @@ impl FriOptions {
         FriOptions {
             folding_factor,
             max_remainder_size,
+            max_remainder_degree: None,
             blowup_factor,
+            domain_offset: None,
         }
     }
 
+    /// Returns a new [FriOptions] struct with the degree of the remainder polynomial capped at
+    /// `max_remainder_degree`.
+    ///
+    /// When the cap is set, FRI layers are added not only until the size of the remainder layer
+    /// falls under `max_remainder_size`, but also until the degree of the polynomial implied by
+    /// the remainder does not exceed `max_remainder_degree`. This gives finer control over the
+    /// cost of the last FRI layer than `max_remainder_size` alone. The cap is honored as long as
+    /// the remainder layer can still be folded by the `folding_factor`; that is, the remainder
+    /// never becomes smaller than `folding_factor` elements.
+    pub fn with_max_remainder_degree(mut self, max_remainder_degree: usize) -> Self {
+        self.max_remainder_degree = Some(max_remainder_degree);
+        self
+    }
+
+    /// Returns a new [FriOptions] struct with the evaluation domain shifted by the specified
+    /// `domain_offset` instead of the primitive element of the base field.
+    ///
+    /// The offset is mapped into the base field via `B::from(domain_offset)`, and must be the
+    /// same offset as the one used to build the evaluations committed to in the first FRI layer.
+    ///
+    /// # Panics
+    /// Panics if `domain_offset` is zero.
+    pub fn with_domain_offset(mut self, domain_offset: u64) -> Self {
+        assert!(domain_offset != 0, "domain offset cannot be zero");
+        self.domain_offset = Some(domain_offset);
+        self
+    }
+
     /// Returns the offset by which the evaluation domain is shifted.
     ///
     /// The domain is shifted by multiplying every element in the domain by this offset.
     ///
-    /// Currently, the offset is hard-coded to be the primitive element in the field specified by
-    /// type parameter `B`.
+    /// Unless a custom offset was set via [FriOptions::with_domain_offset()], the offset is the
+    /// primitive element in the field specified by type parameter `B`.
     pub fn domain_offset<B: StarkField>(&self) -> B {
-        B::GENERATOR
+        match self.domain_offset {
+            Some(offset) => B::from(offset),
+            None => B::GENERATOR,
+        }
     }
 
     /// Returns the factor by which the degree of a polynomial is reduced with each FRI layer.
@@ -78,6 +113,14 @@ impl FriOptions {
         self.max_remainder_size
     }
 
+    /// Returns maximum allowed degree of the remainder polynomial, if one was set.
+    ///
+    /// In combination with `folding_factor` and `max_remainder_size` this property defines how
+    /// many FRI layers are needed for an evaluation domain of a given size.
+    pub fn max_remainder_degree(&self) -> Option<usize> {
+        self.max_remainder_degree
+    }
+
     /// Returns a blowup factor of the evaluation domain.
     ///
     /// Specifically, if the polynomial for which the FRI protocol is executed is of degree `d`
@@ -91,11 +134,11 @@ impl FriOptions {
     ///
     /// The remainder layer (the last FRI layer) is not included in the returned value.
     ///
-    /// The number of layers for a given domain size is defined by the `folding_factor` and
-    /// `max_remainder_size` settings.
+    /// The number of layers for a given domain size is defined by the `folding_factor`,
+    /// `max_remainder_size`, and `max_remainder_degree` settings.
     pub fn num_fri_layers(&self, mut domain_size: usize) -> usize {
         let mut result = 0;
-        while domain_size > self.max_remainder_size {
+        while self.needs_folding(domain_size) {
             domain_size /= self.folding_factor;
             result += 1;
         }
@@ -105,12 +148,30 @@ impl FriOptions {
     /// Computes and returns the size of the remainder layer (the last FRI layer) for a domain of
     /// the specified size.
     ///
-    /// The size of the remainder layer for a given domain size is defined by the `folding_factor`
-    /// and `max_remainder_size` settings.
+    /// The size of the remainder layer for a given domain size is defined by the `folding_factor`,
+    /// `max_remainder_size`, and `max_remainder_degree` settings.
     pub fn fri_remainder_size(&self, mut domain_size: usize) -> usize {
-        while domain_size > self.max_remainder_size {
+        while self.needs_folding(domain_size) {
             domain_size /= self.folding_factor;
         }
         domain_size
     }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if a layer evaluated over a domain of the specified size needs to be folded
+    /// further before it can become the remainder layer.
+    fn needs_folding(&self, domain_size: usize) -> bool {
+        if domain_size > self.max_remainder_size {
+            return true;
+        }
+        match self.max_remainder_degree {
+            Some(max_degree) => {
+                domain_size / self.blowup_factor > max_degree + 1
+                    && domain_size / self.folding_factor >= self.folding_factor
+            }
+            None => false,
+        }
+    }
 }
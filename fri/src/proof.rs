@@ -3,6 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::{utils::interpolate_remainder, FriOptions};
+use core::fmt;
 use crypto::{BatchMerkleProof, ElementHasher, Hasher};
 use math::{log2, FieldElement};
 use utils::{
@@ -25,6 +27,10 @@ use utils::{
 /// paths for these evaluations (the Merkle paths are compressed into a batch Merkle proof). The
 /// remainder is a list of field elements.
 ///
+/// Query positions are deduplicated for every layer, and the batch Merkle proof of a layer
+/// includes nodes shared by several paths (or computable from other paths) only once. Thus,
+/// proofs are already compact and no separate compaction pass is needed.
+///
 /// All values in a proof are stored as vectors of bytes. Thus, the values must be parsed before
 /// they can be returned to the user. To do this, [parse_layers()](FriProof::parse_layers())
 /// and [parse_remainder()](FriProof::parse_remainder()) methods can be used.
@@ -103,6 +109,37 @@ impl FriProof {
             .fold(self.remainder.len() + 3, |acc, layer| acc + layer.size())
     }
 
+    /// Returns a structural summary of this proof.
+    ///
+    /// The summary is computed from the lengths of the byte vectors in this proof, and thus,
+    /// no field elements or Merkle paths are deserialized. Field element type `E` and `options`
+    /// are needed only to convert byte lengths into element counts and to derive the maximum
+    /// degree of the remainder polynomial.
+    ///
+    /// The maximum degree of the remainder polynomial is the degree implied by the number of
+    /// remainder evaluations and the blowup factor; if `options` specify `max_remainder_degree`,
+    /// the degree is further limited by it (same as during proof verification).
+    pub fn summary<E: FieldElement>(&self, options: &FriOptions) -> FriProofSummary {
+        let remainder_size = self.num_remainder_elements::<E>();
+        let mut remainder_max_degree = (remainder_size / options.blowup_factor()).saturating_sub(1);
+        if let Some(degree_cap) = options.max_remainder_degree() {
+            remainder_max_degree = remainder_max_degree.min(degree_cap);
+        }
+
+        FriProofSummary {
+            num_layers: self.num_layers(),
+            layer_evaluations: self
+                .layers
+                .iter()
+                .map(|layer| layer.num_values::<E>())
+                .collect(),
+            remainder_size,
+            remainder_max_degree,
+            num_partitions: self.num_partitions(),
+            size: self.size(),
+        }
+    }
+
     // PARSING
     // --------------------------------------------------------------------------------------------
 
@@ -191,6 +228,23 @@ impl FriProof {
         }
         Ok(remainder)
     }
+
+    /// Returns coefficients of the remainder polynomial (the polynomial implied by the last FRI
+    /// layer) parsed from this proof.
+    ///
+    /// Only the remainder section of this proof is parsed, and the proof is not verified. The
+    /// polynomial is interpolated from the remainder values in the same way as the FRI verifier
+    /// does it when checking the degree of the remainder: the remainder domain is not shifted by
+    /// the domain offset, and thus, the degree of the returned polynomial is the same as the
+    /// degree of the polynomial implied by the last FRI layer.
+    ///
+    /// # Errors
+    /// Returns an error if the remainder values could not be parsed from this proof (see
+    /// [parse_remainder()](FriProof::parse_remainder()) for details).
+    pub fn remainder_poly<E: FieldElement>(&self) -> Result<Vec<E>, DeserializationError> {
+        let remainder = self.parse_remainder()?;
+        Ok(interpolate_remainder(remainder))
+    }
 }
 
 // SERIALIZATION / DESERIALIZATION
@@ -239,6 +293,46 @@ impl Deserializable for FriProof {
     }
 }
 
+// FRI PROOF SUMMARY
+// ================================================================================================
+
+/// Structural summary of a [FriProof] intended for debugging and logging.
+///
+/// A summary can be obtained via [FriProof::summary()] method, and can be printed via its
+/// [Display](fmt::Display) implementation.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FriProofSummary {
+    /// Number of FRI layers in the proof (not including the remainder).
+    pub num_layers: usize,
+    /// Number of queried evaluations included in each FRI layer.
+    pub layer_evaluations: Vec<usize>,
+    /// Number of evaluations in the remainder (the last FRI layer).
+    pub remainder_size: usize,
+    /// Maximum degree of the remainder polynomial implied by the remainder evaluations and
+    /// limited by the `max_remainder_degree` option (if set).
+    pub remainder_max_degree: usize,
+    /// Number of partitions used during proof generation.
+    pub num_partitions: usize,
+    /// Total size of the serialized proof in bytes.
+    pub size: usize,
+}
+
+impl fmt::Display for FriProofSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "FRI proof ({} bytes):", self.size)?;
+        writeln!(f, "  layers: {}", self.num_layers)?;
+        for (i, num_evaluations) in self.layer_evaluations.iter().enumerate() {
+            writeln!(f, "    layer {}: {} evaluations", i, num_evaluations)?;
+        }
+        writeln!(
+            f,
+            "  remainder: {} evaluations (max degree {})",
+            self.remainder_size, self.remainder_max_degree
+        )?;
+        write!(f, "  partitions: {}", self.num_partitions)
+    }
+}
+
 // FRI PROOF LAYER
 // ================================================================================================
 
@@ -282,6 +376,14 @@ impl FriProofLayer {
         self.values.len() + 4 + self.paths.len() + 4
     }
 
+    /// Returns the number of queried evaluations in this layer.
+    ///
+    /// The number of evaluations is computed by dividing the number of value bytes by the size
+    /// of the field element specified by `E` type parameter.
+    pub fn num_values<E: FieldElement>(&self) -> usize {
+        self.values.len() / E::ELEMENT_BYTES
+    }
+
     // PARSING
     // --------------------------------------------------------------------------------------------
     /// Decomposes this layer into a combination of query values and corresponding Merkle
@@ -47,7 +47,8 @@ mod tests;
 /// function, the prover repeatedly applies a degree-respecting projection (DRP) to `evaluations`
 /// (see [folding](crate::folding)). With every application of the DRP, the degree of the function
 /// *f* (and size of the domain over which it is evaluated) is reduced by the `folding_factor`
-/// until the remaining evaluations fit into a vector of at most `max_remainder_size` elements.
+/// until the remaining evaluations fit into a vector of at most `max_remainder_size` elements
+/// (and, if `max_remainder_degree` is set, until the degree of *f* does not exceed it).
 ///
 /// At each layer of reduction, the prover commits to the current set of evaluations. This is done
 /// by building a Merkle tree from the evaluations and sending the root of the tree to the verifier
@@ -63,6 +64,13 @@ mod tests;
 /// sends it to the prover. In the non-interactive version, α is pseudo-randomly generated based
 /// on the values the prover has written into the channel up to that point.
 ///
+/// Since α values are always obtained via [ProverChannel::draw_fri_alpha()], the caller fully
+/// controls where they come from: [DefaultProverChannel] derives them from layer commitments
+/// using a public coin, but a custom channel can also supply them externally (e.g., from a
+/// transcript shared with an outer protocol). In the latter case, the proof should be verified
+/// using [FriVerifier::new_with_alphas()](crate::FriVerifier::new_with_alphas()) with the same
+/// α values.
+///
 /// The prover keeps all FRI layers (consisting of evaluations and corresponding Merkle trees) in
 /// its internal state.
 ///
@@ -97,6 +105,8 @@ where
     _channel: PhantomData<C>,
 }
 
+// TODO: abstract over VectorCommitment once FRI proof layers can carry openings other than batch
+// Merkle proofs
 struct FriLayer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher> {
     tree: MerkleTree<H>,
     evaluations: Vec<E>,
@@ -163,7 +173,27 @@ where
     ///
     /// # Panics
     /// Panics if the prover state is dirty (the vector of layers is not empty).
-    pub fn build_layers(&mut self, channel: &mut C, mut evaluations: Vec<E>) {
+    pub fn build_layers(&mut self, channel: &mut C, evaluations: Vec<E>) {
+        self.build_layers_with_observer(channel, evaluations, |_, _| {});
+    }
+
+    /// Executes the commit phase of the FRI protocol in the same way as
+    /// [build_layers()](FriProver::build_layers), and invokes `on_layer` as soon as each of the
+    /// layers (including the remainder layer) has been built.
+    ///
+    /// `on_layer` receives the index of the layer and the size of the domain over which the
+    /// layer was evaluated; this can be used to report progress of the commit phase.
+    ///
+    /// # Panics
+    /// Panics if the prover state is dirty (the vector of layers is not empty).
+    pub fn build_layers_with_observer<F>(
+        &mut self,
+        channel: &mut C,
+        mut evaluations: Vec<E>,
+        mut on_layer: F,
+    ) where
+        F: FnMut(usize, usize),
+    {
         assert!(
             self.layers.is_empty(),
             "a prior proof generation request has not been completed yet"
@@ -171,7 +201,8 @@ where
 
         // reduce the degree by folding_factor at each iteration until the remaining polynomial
         // is small enough; + 1 is for the remainder
-        for _ in 0..self.options.num_fri_layers(evaluations.len()) + 1 {
+        for layer_idx in 0..self.options.num_fri_layers(evaluations.len()) + 1 {
+            let domain_size = evaluations.len();
             match self.folding_factor() {
                 2 => self.build_layer::<2>(channel, &mut evaluations),
                 4 => self.build_layer::<4>(channel, &mut evaluations),
@@ -179,6 +210,7 @@ where
                 16 => self.build_layer::<16>(channel, &mut evaluations),
                 _ => unimplemented!("folding factor {} is not supported", self.folding_factor()),
             }
+            on_layer(layer_idx, domain_size);
         }
 
         // make sure remainder length does not exceed max allowed value
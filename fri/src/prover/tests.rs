@@ -3,14 +3,19 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{DefaultProverChannel, FriProver};
+use super::{DefaultProverChannel, FriProver, ProverChannel};
 use crate::{
-    verifier::{DefaultVerifierChannel, FriVerifier},
+    folding::fold_positions,
+    utils::hash_values,
+    verifier::{DefaultVerifierChannel, FriVerifier, VerifierChannel},
     FriOptions, FriProof, VerifierError,
 };
-use crypto::{hashers::Blake2s_256, Hasher, RandomCoin};
-use math::{fft, fields::f128::BaseElement, FieldElement};
-use utils::{collections::Vec, Deserializable, Serializable, SliceReader};
+use crypto::{hashers::Blake2s_256, BatchMerkleProof, Hasher, MerkleTree, RandomCoin};
+use math::{fft, fields::f128::BaseElement, log2, polynom, FieldElement, StarkField};
+use utils::{
+    collections::Vec, flatten_vector_elements, string::ToString, Deserializable, Serializable,
+    SliceReader,
+};
 
 type Blake2s = Blake2s_256<BaseElement>;
 
@@ -45,9 +50,660 @@ fn fri_folding_4() {
     )
 }
 
+#[test]
+fn fri_layer_observer() {
+    let trace_length = 1 << 12;
+    let options = FriOptions::new(8, 4, 256);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, options.blowup_factor());
+
+    // the observer is invoked once per layer with the size of the domain of that layer
+    let mut layers = Vec::new();
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers_with_observer(&mut channel, evaluations, |idx, domain_size| {
+        layers.push((idx, domain_size))
+    });
+    assert_eq!(prover.num_layers(), layers.len());
+    assert_eq!(
+        vec![(0, 32768), (1, 8192), (2, 2048), (3, 512), (4, 128)],
+        layers
+    );
+
+    // observing layers does not affect the commitments written into the channel
+    let mut expected_channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, options.blowup_factor());
+    let mut expected_prover = FriProver::new(options);
+    expected_prover.build_layers(&mut expected_channel, evaluations);
+    assert_eq!(
+        expected_channel.layer_commitments(),
+        channel.layer_commitments()
+    );
+}
+
+#[test]
+fn fri_remainder_degree_cap() {
+    let trace_length_e = 10;
+    let lde_blowup_e = 3;
+    let folding_factor_e = 2;
+    for max_remainder_degree in [3, 7, 15] {
+        fri_prove_verify_with_degree_cap(
+            trace_length_e,
+            lde_blowup_e,
+            folding_factor_e,
+            max_remainder_degree,
+        )
+    }
+}
+
+#[test]
+fn fri_remainder_degree_cap_exceeded() {
+    // with folding factor 16 and blowup factor 2, the remainder cannot be folded below 32
+    // elements; the implied remainder degree (15) exceeds the cap and the proof is rejected
+    let trace_length = 1 << 8;
+    let options = FriOptions::new(2, 16, 512).with_max_remainder_degree(0);
+    let domain_size = trace_length * options.blowup_factor();
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, options.blowup_factor());
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert_eq!(Err(VerifierError::RemainderDegreeMismatch(0)), result);
+}
+
+#[test]
+fn fri_query_mismatch() {
+    let trace_length = 1 << 10;
+    let options = FriOptions::new(8, 4, 32);
+    let domain_size = trace_length * options.blowup_factor();
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut evaluations = build_evaluations(trace_length, options.blowup_factor());
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+
+    // corrupt the evaluation at one of the queried positions
+    let corrupted_position = positions[3];
+    evaluations[corrupted_position] += BaseElement::ONE;
+
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert_eq!(
+        Err(VerifierError::QueryMismatch {
+            position: corrupted_position,
+            layer: 0
+        }),
+        result
+    );
+}
+
+#[test]
+fn fri_query_positions_replay() {
+    let trace_length = 1 << 10;
+    let options = FriOptions::new(8, 4, 32);
+    let domain_size = trace_length * options.blowup_factor();
+    let num_queries = 32;
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, options.blowup_factor());
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+
+    // replaying the verifier with the same coin seed as the one used by the prover results in
+    // the same query positions every time
+    let replay = |seed: &[u8]| {
+        let mut channel = DefaultVerifierChannel::<BaseElement, Blake2s>::new(
+            proof.clone(),
+            commitments.clone(),
+            domain_size,
+            options.folding_factor(),
+        )
+        .unwrap();
+        let mut coin = RandomCoin::<BaseElement, Blake2s>::new(seed);
+        FriVerifier::new(&mut channel, &mut coin, options.clone(), trace_length - 1).unwrap();
+        channel
+            .draw_query_positions(&mut coin, num_queries)
+            .unwrap()
+    };
+    assert_eq!(positions, replay(&[]));
+    assert_eq!(positions, replay(&[]));
+
+    // a different seed results in different query positions
+    assert_ne!(positions, replay(&[1]));
+
+    // the replayed positions can be used to verify the proof
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &replay(&[]),
+        &options,
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn fri_external_alphas() {
+    let trace_length = 1 << 10;
+    let options = FriOptions::new(8, 4, 32);
+    let domain_size = trace_length * options.blowup_factor();
+    let num_layers = options.num_fri_layers(domain_size) + 1;
+
+    // build the proof using fixed alphas instead of the ones drawn from a public coin
+    let alphas = (0..num_layers as u128)
+        .map(|i| BaseElement::new(i + 3))
+        .collect::<Vec<_>>();
+    let mut channel = FixedAlphaProverChannel::new(alphas.clone());
+    let evaluations = build_evaluations(trace_length, options.blowup_factor());
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = vec![1, 13, 130, 517, 1024, 4095, 7777];
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.commitments;
+    assert_eq!(num_layers, commitments.len());
+
+    let queried_evaluations = positions
+        .iter()
+        .map(|&p| evaluations[p])
+        .collect::<Vec<_>>();
+    let verify = |alphas: Vec<BaseElement>| {
+        let mut channel = DefaultVerifierChannel::<BaseElement, Blake2s>::new(
+            proof.clone(),
+            commitments.clone(),
+            domain_size,
+            options.folding_factor(),
+        )
+        .unwrap();
+        let verifier = FriVerifier::<BaseElement, BaseElement, _, Blake2s>::new_with_alphas(
+            &mut channel,
+            alphas,
+            options.clone(),
+            trace_length - 1,
+        )?;
+        verifier.verify(&mut channel, &queried_evaluations, &positions)
+    };
+
+    // the proof is accepted when the verifier is given the same alphas
+    assert_eq!(Ok(()), verify(alphas.clone()));
+
+    // the proof is rejected when the verifier is given different alphas
+    let mut wrong_alphas = alphas.clone();
+    wrong_alphas[1] += BaseElement::ONE;
+    assert!(verify(wrong_alphas).is_err());
+
+    // the number of alphas must match the number of layer commitments
+    assert_eq!(
+        Err(VerifierError::NumLayerAlphasMismatch(
+            num_layers,
+            num_layers - 1
+        )),
+        verify(alphas[..num_layers - 1].to_vec())
+    );
+}
+
+#[test]
+fn fri_streaming_verification() {
+    let trace_length = 1 << 10;
+    let options = FriOptions::new(8, 4, 32);
+    let domain_size = trace_length * options.blowup_factor();
+    let num_layers = options.num_fri_layers(domain_size);
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, options.blowup_factor());
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+
+    let remainder = proof.parse_remainder::<BaseElement>().unwrap();
+    let layer_openings = || {
+        let (layer_queries, layer_proofs) = proof
+            .clone()
+            .parse_layers::<Blake2s, BaseElement>(domain_size, options.folding_factor())
+            .unwrap();
+        layer_queries
+            .into_iter()
+            .zip(layer_proofs)
+            .collect::<Vec<_>>()
+    };
+
+    let verify_batch = |evaluations: &[BaseElement], max_degree: usize| {
+        verify_proof(
+            proof.clone(),
+            commitments.clone(),
+            evaluations,
+            max_degree,
+            domain_size,
+            &positions,
+            &options,
+        )
+    };
+    let verify_streaming =
+        |evaluations: &[BaseElement],
+         max_degree: usize,
+         openings: Vec<(Vec<BaseElement>, BatchMerkleProof<Blake2s>)>| {
+            let mut channel = DefaultVerifierChannel::<BaseElement, Blake2s>::new(
+                proof.clone(),
+                commitments.clone(),
+                domain_size,
+                options.folding_factor(),
+            )
+            .unwrap();
+            let mut coin = RandomCoin::<BaseElement, Blake2s>::new(&[]);
+            let verifier = FriVerifier::new(&mut channel, &mut coin, options.clone(), max_degree)?;
+            let queried_evaluations = positions
+                .iter()
+                .map(|&p| evaluations[p])
+                .collect::<Vec<_>>();
+            verifier.verify_streaming(
+                openings,
+                remainder.clone(),
+                &queried_evaluations,
+                &positions,
+            )
+        };
+
+    // a valid proof is accepted by both verifiers
+    let max_degree = trace_length - 1;
+    assert_eq!(Ok(()), verify_batch(&evaluations, max_degree));
+    assert_eq!(
+        verify_batch(&evaluations, max_degree),
+        verify_streaming(&evaluations, max_degree, layer_openings())
+    );
+
+    // a proof for an invalid degree is rejected by both verifiers with the same error
+    let result = verify_batch(&evaluations, max_degree - 1);
+    assert!(result.is_err());
+    assert_eq!(
+        result,
+        verify_streaming(&evaluations, max_degree - 1, layer_openings())
+    );
+
+    // a corrupted evaluation is rejected by both verifiers with the same error
+    let mut corrupted_evaluations = evaluations.clone();
+    corrupted_evaluations[positions[3]] += BaseElement::ONE;
+    let result = verify_batch(&corrupted_evaluations, max_degree);
+    assert!(result.is_err());
+    assert_eq!(
+        result,
+        verify_streaming(&corrupted_evaluations, max_degree, layer_openings())
+    );
+
+    // a corrupted layer opening is rejected by the streaming verifier
+    let mut corrupted_openings = layer_openings();
+    corrupted_openings[1].0[2] += BaseElement::ONE;
+    assert_eq!(
+        Err(VerifierError::LayerCommitmentMismatch),
+        verify_streaming(&evaluations, max_degree, corrupted_openings)
+    );
+
+    // the number of streamed openings must match the number of FRI layers
+    let mut missing_openings = layer_openings();
+    missing_openings.pop();
+    assert_eq!(
+        Err(VerifierError::NumLayerOpeningsMismatch(
+            num_layers,
+            num_layers - 1
+        )),
+        verify_streaming(&evaluations, max_degree, missing_openings)
+    );
+
+    let mut extra_openings = layer_openings();
+    extra_openings.push(layer_openings().remove(0));
+    assert_eq!(
+        Err(VerifierError::NumLayerOpeningsMismatch(
+            num_layers,
+            num_layers + 1
+        )),
+        verify_streaming(&evaluations, max_degree, extra_openings)
+    );
+}
+
+// PROOF SUMMARY TEST
+// ================================================================================================
+
+#[test]
+fn fri_proof_summary() {
+    let trace_length = 1 << 10;
+    let options = FriOptions::new(8, 4, 32);
+    let domain_size = trace_length * options.blowup_factor();
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, options.blowup_factor());
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations);
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    let summary = proof.summary::<BaseElement>(&options);
+    assert_eq!(options.num_fri_layers(domain_size), summary.num_layers);
+    assert_eq!(summary.num_layers, summary.layer_evaluations.len());
+    for &num_evaluations in summary.layer_evaluations.iter() {
+        assert!(num_evaluations > 0);
+        assert_eq!(0, num_evaluations % options.folding_factor());
+    }
+    assert_eq!(
+        options.fri_remainder_size(domain_size),
+        summary.remainder_size
+    );
+    assert_eq!(
+        summary.remainder_size / options.blowup_factor() - 1,
+        summary.remainder_max_degree
+    );
+    assert_eq!(proof.size(), summary.size);
+    assert!(summary.to_string().contains("layers: 4"));
+}
+
+#[test]
+fn fri_proof_summary_with_degree_cap() {
+    // with folding factor 16 and blowup factor 2, the remainder cannot be folded below 32
+    // elements; the summary reports the degree allowed by the cap rather than the implied one
+    let trace_length = 1 << 8;
+    let options = FriOptions::new(2, 16, 512).with_max_remainder_degree(0);
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, options.blowup_factor());
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations);
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    let summary = proof.summary::<BaseElement>(&options);
+    assert_eq!(32, summary.remainder_size);
+    assert_eq!(0, summary.remainder_max_degree);
+
+    // without the cap, the degree is implied by the size of the remainder
+    let options = FriOptions::new(2, 16, 512);
+    let summary = proof.summary::<BaseElement>(&options);
+    assert_eq!(15, summary.remainder_max_degree);
+}
+
+#[test]
+fn fri_remainder_poly() {
+    let trace_length = 1 << 10;
+    let options = FriOptions::new(8, 4, 32);
+    let domain_size = trace_length * options.blowup_factor();
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, options.blowup_factor());
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    // the degree of the remainder polynomial is bounded by the size of the remainder
+    let remainder = proof.parse_remainder::<BaseElement>().unwrap();
+    let poly = proof.remainder_poly::<BaseElement>().unwrap();
+    assert_eq!(options.fri_remainder_size(domain_size), poly.len());
+    assert!(polynom::degree_of(&poly) < options.max_remainder_size());
+    assert!(polynom::degree_of(&poly) < remainder.len() / options.blowup_factor());
+
+    // the remainder polynomial evaluates to the remainder values over the (unshifted)
+    // remainder domain
+    let g = BaseElement::get_root_of_unity(log2(remainder.len()));
+    for (i, &value) in remainder.iter().enumerate() {
+        assert_eq!(value, polynom::eval(&poly, g.exp((i as u64).into())));
+    }
+
+    // the verifier accepts the remainder
+    let commitments = channel.layer_commitments().to_vec();
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+}
+
+#[test]
+fn fri_proof_layers_share_path_nodes() {
+    let trace_length = 1 << 10;
+    let options = FriOptions::new(8, 4, 32);
+    let folding_factor = options.folding_factor();
+    let mut domain_size = trace_length * options.blowup_factor();
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, options.blowup_factor());
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations);
+    let mut positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+
+    // Merkle paths within each layer are aggregated into batch proofs in which nodes shared by
+    // several paths (or computable from other paths) are included only once
+    let (_, layer_proofs) = proof
+        .parse_layers::<Blake2s, BaseElement>(domain_size, folding_factor)
+        .unwrap();
+    for (layer_proof, commitment) in layer_proofs.iter().zip(commitments.iter()) {
+        positions = fold_positions(&positions, domain_size, folding_factor);
+        domain_size /= folding_factor;
+
+        let paths = layer_proof.into_paths(&positions).unwrap();
+        let num_path_nodes: usize = paths.iter().map(|path| path.len() - 1).sum();
+        let num_proof_nodes: usize = layer_proof.nodes.iter().map(|nodes| nodes.len()).sum();
+        assert!(num_proof_nodes < num_path_nodes);
+
+        assert_eq!(*commitment, layer_proof.get_root(&positions).unwrap());
+        for (path, &position) in paths.iter().zip(positions.iter()) {
+            assert!(MerkleTree::<Blake2s>::verify(*commitment, position, path).is_ok());
+        }
+    }
+}
+
+// VERIFIER CHANNEL TESTS
+// ================================================================================================
+
+#[test]
+fn read_layer_queries_with_duplicates() {
+    let layer = (0..16u128)
+        .map(|i| [4 * i, 4 * i + 1, 4 * i + 2, 4 * i + 3].map(BaseElement::new))
+        .collect::<Vec<_>>();
+    let tree = MerkleTree::<Blake2s>::new(hash_values::<Blake2s, BaseElement, 4>(&layer)).unwrap();
+    let root = *tree.root();
+
+    // positions 3 and 9 are queried twice, but are opened only once
+    let positions = [3, 9, 3, 12, 9];
+    let proof = tree.prove_batch(&[3, 9, 12]).unwrap();
+    let mut channel = LayerChannel::new(vec![layer[3], layer[9], layer[12]], proof.clone());
+    let queries = channel.read_layer_queries::<4>(&positions, &root).unwrap();
+    let expected = positions.iter().map(|&p| layer[p]).collect::<Vec<_>>();
+    assert_eq!(expected, queries);
+
+    // positions 3 and 9 are opened as many times as they are queried, and the openings are the same
+    let openings = positions.iter().map(|&p| layer[p]).collect::<Vec<_>>();
+    let mut channel = LayerChannel::new(openings.clone(), proof.clone());
+    let queries = channel.read_layer_queries::<4>(&positions, &root).unwrap();
+    assert_eq!(expected, queries);
+
+    // the second opening of position 9 is different from the first one
+    let mut invalid_openings = openings.clone();
+    invalid_openings[4][1] += BaseElement::ONE;
+    let mut channel = LayerChannel::new(invalid_openings, proof.clone());
+    let result = channel.read_layer_queries::<4>(&positions, &root);
+    assert_eq!(Err(VerifierError::DuplicateQueryMismatch(9)), result);
+
+    // an opening which does not match the commitment
+    let mut invalid_openings = openings;
+    invalid_openings[1][0] += BaseElement::ONE;
+    invalid_openings[4][0] += BaseElement::ONE;
+    let mut channel = LayerChannel::new(invalid_openings, proof);
+    let result = channel.read_layer_queries::<4>(&positions, &root);
+    assert_eq!(Err(VerifierError::LayerCommitmentMismatch), result);
+}
+
 // TEST UTILS
 // ================================================================================================
 
+/// Verifier channel which contains openings for a single FRI layer.
+struct LayerChannel {
+    layer_queries: Vec<BaseElement>,
+    layer_proof: Option<BatchMerkleProof<Blake2s>>,
+}
+
+impl LayerChannel {
+    fn new(layer_queries: Vec<[BaseElement; 4]>, layer_proof: BatchMerkleProof<Blake2s>) -> Self {
+        Self {
+            layer_queries: flatten_vector_elements(layer_queries),
+            layer_proof: Some(layer_proof),
+        }
+    }
+}
+
+impl VerifierChannel<BaseElement> for LayerChannel {
+    type Hasher = Blake2s;
+
+    fn read_fri_num_partitions(&self) -> usize {
+        1
+    }
+
+    fn read_fri_layer_commitments(&mut self) -> Vec<<Blake2s as Hasher>::Digest> {
+        Vec::new()
+    }
+
+    fn take_next_fri_layer_queries(&mut self) -> Vec<BaseElement> {
+        core::mem::take(&mut self.layer_queries)
+    }
+
+    fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<Blake2s> {
+        self.layer_proof.take().expect("no layer proof")
+    }
+
+    fn take_fri_remainder(&mut self) -> Vec<BaseElement> {
+        Vec::new()
+    }
+}
+
+/// Prover channel which returns a pre-defined sequence of alphas and records layer commitments.
+struct FixedAlphaProverChannel {
+    alphas: Vec<BaseElement>,
+    commitments: Vec<<Blake2s as Hasher>::Digest>,
+}
+
+impl FixedAlphaProverChannel {
+    fn new(alphas: Vec<BaseElement>) -> Self {
+        Self {
+            alphas,
+            commitments: Vec::new(),
+        }
+    }
+}
+
+impl ProverChannel<BaseElement> for FixedAlphaProverChannel {
+    type Hasher = Blake2s;
+
+    fn commit_fri_layer(&mut self, layer_root: <Blake2s as Hasher>::Digest) {
+        self.commitments.push(layer_root);
+    }
+
+    fn draw_fri_alpha(&mut self) -> BaseElement {
+        self.alphas[self.commitments.len() - 1]
+    }
+}
+
+pub fn fri_prove_verify(
+    trace_length_e: usize,
+    lde_blowup_e: usize,
+    folding_factor_e: usize,
+    max_remainder_size_e: usize,
+) {
+    let options = FriOptions::new(
+        1 << lde_blowup_e,
+        1 << folding_factor_e,
+        1 << max_remainder_size_e,
+    );
+    prove_verify(1 << trace_length_e, options);
+}
+
+pub fn fri_prove_verify_with_degree_cap(
+    trace_length_e: usize,
+    lde_blowup_e: usize,
+    folding_factor_e: usize,
+    max_remainder_degree: usize,
+) {
+    let trace_length = 1 << trace_length_e;
+    let lde_blowup = 1 << lde_blowup_e;
+    let options = FriOptions::new(lde_blowup, 1 << folding_factor_e, trace_length * lde_blowup)
+        .with_max_remainder_degree(max_remainder_degree);
+
+    // the degree of the remainder must not exceed the cap
+    let domain_size = trace_length * lde_blowup;
+    let remainder_size = options.fri_remainder_size(domain_size);
+    assert!(remainder_size / lde_blowup - 1 <= max_remainder_degree);
+
+    prove_verify(trace_length, options);
+}
+
+fn prove_verify(trace_length: usize, options: FriOptions) {
+    let domain_size = trace_length * options.blowup_factor();
+
+    // instantiate the prover and generate the proof
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, options.blowup_factor());
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    // make sure the proof can be verified
+    let commitments = channel.layer_commitments().to_vec();
+    let max_degree = trace_length - 1;
+    let result = verify_proof(
+        proof.clone(),
+        commitments.clone(),
+        &evaluations,
+        max_degree,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+    // make sure proof fails for invalid degree
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        max_degree - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert!(result.is_err());
+}
+
 pub fn build_prover_channel(
     trace_length: usize,
     options: &FriOptions,
@@ -93,7 +749,7 @@ pub fn verify_proof(
     )
     .unwrap();
     let mut coin = RandomCoin::<BaseElement, Blake2s>::new(&[]);
-    let verifier = FriVerifier::new(&mut channel, &mut coin, options.clone(), max_degree).unwrap();
+    let verifier = FriVerifier::new(&mut channel, &mut coin, options.clone(), max_degree)?;
     let queried_evaluations = positions
         .iter()
         .map(|&p| evaluations[p])
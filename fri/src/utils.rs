@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use crypto::ElementHasher;
-use math::FieldElement;
+use math::{fft, FieldElement};
 use utils::{collections::Vec, iter_mut, uninit_vector};
 
 #[cfg(feature = "concurrent")]
@@ -49,3 +49,15 @@ where
     });
     result
 }
+
+/// Interpolates a polynomial from the remainder (last FRI layer) evaluations.
+///
+/// The domain offset is not applied here: the degree of the polynomial does not change as long
+/// as we interpolate over a coset of the original domain. Thus, if `remainder` contains
+/// evaluations of f(x) over the domain shifted by `offset`, the returned coefficients are those
+/// of f(offset * x).
+pub fn interpolate_remainder<E: FieldElement>(mut remainder: Vec<E>) -> Vec<E> {
+    let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(remainder.len());
+    fft::interpolate_poly(&mut remainder, &inv_twiddles);
+    remainder
+}
@@ -4,7 +4,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{utils::hash_values, FriProof, VerifierError};
-use crypto::{BatchMerkleProof, ElementHasher, Hasher, MerkleTree};
+use core::mem;
+use crypto::{BatchMerkleProof, ElementHasher, Hasher, MerkleTree, RandomCoin, RandomCoinError};
 use math::FieldElement;
 use utils::{collections::Vec, group_vector_elements, transpose_slice, DeserializationError};
 
@@ -73,21 +74,66 @@ pub trait VerifierChannel<E: FieldElement> {
     ///
     /// This also checks if the values are valid against the provided FRI layer commitment.
     ///
+    /// Positions may contain duplicates (e.g., when several top-level queries fold into the same
+    /// position of a lower layer). In such a case, each distinct position is verified against the
+    /// commitment only once, and the returned vector contains a copy of the query values for each
+    /// of the specified positions. The channel may contain either a single opening per distinct
+    /// position, or an opening for each of the specified positions; in the latter case, openings
+    /// at the same position must be identical.
+    ///
     /// # Errors
-    /// Returns an error if query values did not match layer commitment.
+    /// Returns an error if:
+    /// * Query values did not match layer commitment.
+    /// * Query values opened at the same position more than once were not the same.
     fn read_layer_queries<const N: usize>(
         &mut self,
         positions: &[usize],
         commitment: &<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest,
     ) -> Result<Vec<[E; N]>, VerifierError> {
-        let layer_proof = self.take_next_fri_layer_proof();
-        MerkleTree::<Self::Hasher>::verify_batch(commitment, positions, &layer_proof)
-            .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
+        let mut layer_proof = self.take_next_fri_layer_proof();
+        let layer_queries: Vec<[E; N]> = group_vector_elements(self.take_next_fri_layer_queries());
+
+        // map each position to the index of its first occurrence in the list of distinct positions
+        let mut unique_positions = Vec::with_capacity(positions.len());
+        let mut position_map = Vec::with_capacity(positions.len());
+        for &position in positions {
+            match unique_positions.iter().position(|&p| p == position) {
+                Some(idx) => position_map.push(idx),
+                None => {
+                    position_map.push(unique_positions.len());
+                    unique_positions.push(position);
+                }
+            }
+        }
 
-        // TODO: make sure layer queries hash into leaves of layer proof
+        // select a single opening for each distinct position; if the channel contains an opening
+        // for each of the specified positions, make sure duplicated openings are consistent
+        let unique_queries = if layer_queries.len() == unique_positions.len() {
+            layer_queries
+        } else if layer_queries.len() == positions.len() {
+            let mut unique_queries: Vec<[E; N]> = Vec::with_capacity(unique_positions.len());
+            for (i, (&idx, query)) in position_map.iter().zip(layer_queries).enumerate() {
+                if idx == unique_queries.len() {
+                    unique_queries.push(query);
+                } else if unique_queries[idx] != query {
+                    return Err(VerifierError::DuplicateQueryMismatch(positions[i]));
+                }
+            }
+            unique_queries
+        } else {
+            return Err(VerifierError::LayerCommitmentMismatch);
+        };
 
-        let layer_queries = self.take_next_fri_layer_queries();
-        Ok(group_vector_elements(layer_queries))
+        // make sure layer queries hash into leaves of the layer proof, and that the proof is valid
+        // against the layer commitment
+        layer_proof.leaves = hash_values::<Self::Hasher, E, N>(&unique_queries);
+        MerkleTree::<Self::Hasher>::verify_batch(commitment, &unique_positions, &layer_proof)
+            .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
+
+        Ok(position_map
+            .into_iter()
+            .map(|idx| unique_queries[idx])
+            .collect())
     }
 
     /// Returns FRI remainder values (last FRI layer) read from this channel.
@@ -136,6 +182,7 @@ pub struct DefaultVerifierChannel<E: FieldElement, H: ElementHasher<BaseField =
     layer_queries: Vec<Vec<E>>,
     remainder: Vec<E>,
     num_partitions: usize,
+    domain_size: usize,
 }
 
 impl<E, H> DefaultVerifierChannel<E, H>
@@ -165,8 +212,36 @@ where
             layer_queries,
             remainder,
             num_partitions,
+            domain_size,
         })
     }
+
+    /// Draws a set of positions at which the polynomial evaluations committed at the first FRI
+    /// layer should be queried.
+    ///
+    /// The positions are drawn from the specified `public_coin` in the same way as they are drawn
+    /// by [DefaultProverChannel::draw_query_positions()](crate::DefaultProverChannel). Thus, if
+    /// the coin is in the same state as the coin of the prover was when the proof was generated
+    /// (e.g., this is the case for the coin passed to [FriVerifier::new()](crate::FriVerifier)
+    /// together with this channel), the returned positions are exactly the positions queried
+    /// by the proof. This can be used to replay the queries of a proof (e.g., to build test
+    /// vectors).
+    ///
+    /// This draws positions for a standalone FRI proof only. When FRI is a part of a STARK proof,
+    /// the coin is also reseeded with the proof-of-work nonce of the STARK proof before query
+    /// positions are drawn, and the positions are drawn from the LDE domain of the trace; such
+    /// positions should be drawn via `query_positions()` function of the verifier crate instead.
+    ///
+    /// # Errors
+    /// Returns an error if `num_queries` unique positions could not be drawn from the domain of
+    /// the first FRI layer.
+    pub fn draw_query_positions(
+        &self,
+        public_coin: &mut RandomCoin<E::BaseField, H>,
+        num_queries: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        public_coin.draw_integers(num_queries, self.domain_size)
+    }
 }
 
 impl<E, H> VerifierChannel<E> for DefaultVerifierChannel<E, H>
@@ -196,3 +271,126 @@ where
         self.remainder.clone()
     }
 }
+
+// STREAMING VERIFIER CHANNEL
+// ================================================================================================
+
+/// A verifier channel which reads FRI layer openings from an iterator one layer at a time.
+///
+/// This channel is used internally by [FriVerifier::verify_streaming()](super::FriVerifier) to
+/// run the query phase of the protocol against openings which arrive incrementally. At most one
+/// layer opening is held by the channel at any given time.
+///
+/// Layer commitments are not read from this channel as they are already known to the verifier
+/// after the commit phase.
+pub(super) struct StreamingVerifierChannel<E, H, I>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    I: Iterator<Item = (Vec<E>, BatchMerkleProof<H>)>,
+{
+    layer_openings: I,
+    next_layer_queries: Vec<E>,
+    remainder: Vec<E>,
+    folding_factor: usize,
+    num_partitions: usize,
+    num_layers_read: usize,
+    exhausted: bool,
+}
+
+impl<E, H, I> StreamingVerifierChannel<E, H, I>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    I: Iterator<Item = (Vec<E>, BatchMerkleProof<H>)>,
+{
+    /// Returns a new streaming channel which reads layer openings from the specified iterator.
+    pub fn new(
+        layer_openings: I,
+        remainder: Vec<E>,
+        folding_factor: usize,
+        num_partitions: usize,
+    ) -> Self {
+        StreamingVerifierChannel {
+            layer_openings,
+            next_layer_queries: Vec::new(),
+            remainder,
+            folding_factor,
+            num_partitions,
+            num_layers_read: 0,
+            exhausted: false,
+        }
+    }
+
+    /// Returns the number of layer openings read from the underlying iterator so far.
+    pub fn num_layers_read(&self) -> usize {
+        self.num_layers_read
+    }
+
+    /// Returns true if an attempt was made to read a layer opening after the underlying iterator
+    /// has been exhausted.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Consumes the remaining layer openings from the underlying iterator and returns their
+    /// number.
+    pub fn count_remaining(self) -> usize {
+        self.layer_openings.count()
+    }
+}
+
+impl<E, H, I> VerifierChannel<E> for StreamingVerifierChannel<E, H, I>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    I: Iterator<Item = (Vec<E>, BatchMerkleProof<H>)>,
+{
+    type Hasher = H;
+
+    fn read_fri_num_partitions(&self) -> usize {
+        self.num_partitions
+    }
+
+    fn read_fri_layer_commitments(&mut self) -> Vec<H::Digest> {
+        Vec::new()
+    }
+
+    /// Reads the next layer opening from the underlying iterator; query values of the opening are
+    /// retained by the channel until they are taken via `take_next_fri_layer_queries()`.
+    ///
+    /// If the iterator has been exhausted, an empty proof is returned. In this case, as well as in
+    /// the case when the number of query values is not a multiple of the folding factor, the query
+    /// values for the layer are empty, and thus, will fail verification against the layer
+    /// commitment.
+    fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<H> {
+        match self.layer_openings.next() {
+            Some((layer_queries, layer_proof)) => {
+                self.num_layers_read += 1;
+                self.next_layer_queries = if layer_queries.len() % self.folding_factor == 0 {
+                    layer_queries
+                } else {
+                    Vec::new()
+                };
+                layer_proof
+            }
+            None => {
+                self.exhausted = true;
+                self.next_layer_queries = Vec::new();
+                BatchMerkleProof {
+                    leaves: Vec::new(),
+                    nodes: Vec::new(),
+                    depth: 0,
+                }
+            }
+        }
+    }
+
+    fn take_next_fri_layer_queries(&mut self) -> Vec<E> {
+        mem::take(&mut self.next_layer_queries)
+    }
+
+    fn take_fri_remainder(&mut self) -> Vec<E> {
+        mem::take(&mut self.remainder)
+    }
+}
@@ -5,13 +5,18 @@
 
 //! Contains an implementation of FRI verifier and associated components.
 
-use crate::{folding::fold_positions, utils::map_positions_to_indexes, FriOptions, VerifierError};
+use crate::{
+    folding::fold_positions,
+    utils::{interpolate_remainder, map_positions_to_indexes},
+    FriOptions, VerifierError,
+};
 use core::{convert::TryInto, marker::PhantomData, mem};
-use crypto::{ElementHasher, RandomCoin};
-use math::{fft, log2, polynom, FieldElement, StarkField};
+use crypto::{BatchMerkleProof, ElementHasher, RandomCoin};
+use math::{log2, polynom, FieldElement, StarkField};
 use utils::collections::Vec;
 
 mod channel;
+use channel::StreamingVerifierChannel;
 pub use channel::{DefaultVerifierChannel, VerifierChannel};
 
 // FRI VERIFIER
@@ -104,23 +109,81 @@ where
         options: FriOptions,
         max_poly_degree: usize,
     ) -> Result<Self, VerifierError> {
-        // infer evaluation domain info
-        let domain_size = max_poly_degree.next_power_of_two() * options.blowup_factor();
-        let domain_generator = B::get_root_of_unity(log2(domain_size));
-
-        let num_partitions = channel.read_fri_num_partitions();
-
         // read layer commitments from the channel and use them to build a list of alphas
         let layer_commitments = channel.read_fri_layer_commitments();
         let mut layer_alphas = Vec::with_capacity(layer_commitments.len());
-        let mut max_degree_plus_1 = max_poly_degree + 1;
-        for (depth, commitment) in layer_commitments.iter().enumerate() {
+        for commitment in layer_commitments.iter() {
             public_coin.reseed(*commitment);
             let alpha = public_coin.draw().map_err(VerifierError::PublicCoinError)?;
             layer_alphas.push(alpha);
+        }
 
-            // make sure the degree can be reduced by the folding factor at all layers
-            // but the remainder layer
+        Self::build(
+            channel,
+            layer_commitments,
+            layer_alphas,
+            options,
+            max_poly_degree,
+        )
+    }
+
+    /// Returns a new instance of FRI verifier which uses the provided `layer_alphas` instead of
+    /// drawing α values from a public coin.
+    ///
+    /// This executes the commit phase of the FRI protocol in the same way as
+    /// [new()](FriVerifier::new()) does, except that α values for all FRI layers (including the
+    /// remainder layer) are supplied by the caller. This is useful when FRI challenges are derived
+    /// from a transcript shared with an outer protocol, or when generating deterministic test
+    /// vectors. The same α values must have been used by the prover to build FRI layers.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of `layer_alphas` is not equal to the number of FRI layer commitments read
+    ///   from the channel.
+    /// * `max_poly_degree` is inconsistent with the number of FRI layers read from the channel
+    ///   and `folding_factor` specified in the `options` parameter.
+    pub fn new_with_alphas(
+        channel: &mut C,
+        layer_alphas: Vec<E>,
+        options: FriOptions,
+        max_poly_degree: usize,
+    ) -> Result<Self, VerifierError> {
+        let layer_commitments = channel.read_fri_layer_commitments();
+        if layer_alphas.len() != layer_commitments.len() {
+            return Err(VerifierError::NumLayerAlphasMismatch(
+                layer_commitments.len(),
+                layer_alphas.len(),
+            ));
+        }
+
+        Self::build(
+            channel,
+            layer_commitments,
+            layer_alphas,
+            options,
+            max_poly_degree,
+        )
+    }
+
+    /// Builds a FRI verifier from the layer commitments read from the `channel` and the α values
+    /// corresponding to these commitments.
+    fn build(
+        channel: &mut C,
+        layer_commitments: Vec<H::Digest>,
+        layer_alphas: Vec<E>,
+        options: FriOptions,
+        max_poly_degree: usize,
+    ) -> Result<Self, VerifierError> {
+        // infer evaluation domain info
+        let domain_size = max_poly_degree.next_power_of_two() * options.blowup_factor();
+        let domain_generator = B::get_root_of_unity(log2(domain_size));
+
+        let num_partitions = channel.read_fri_num_partitions();
+
+        // make sure the degree can be reduced by the folding factor at all layers but the
+        // remainder layer
+        let mut max_degree_plus_1 = max_poly_degree + 1;
+        for depth in 0..layer_commitments.len() {
             if depth != layer_commitments.len() - 1
                 && max_degree_plus_1 % options.folding_factor() != 0
             {
@@ -205,6 +268,85 @@ where
         evaluations: &[E],
         positions: &[usize],
     ) -> Result<(), VerifierError> {
+        self.verify_with_channel(channel, evaluations, positions)
+    }
+
+    /// Executes the query phase of the FRI protocol against layer openings read from the
+    /// specified iterator.
+    ///
+    /// This is equivalent to [verify()](FriVerifier::verify()), but instead of reading layer
+    /// openings from a [VerifierChannel], the verifier pulls them from `layer_openings` one FRI
+    /// layer at a time: each opening is checked against its layer commitment and folded into the
+    /// next layer before the next opening is requested. Thus, if openings are produced lazily
+    /// (e.g., deserialized as they arrive over the network), peak memory is proportional to the
+    /// size of a single layer opening rather than to the size of the entire FRI proof.
+    ///
+    /// Each item of `layer_openings` must contain query values and a batch Merkle proof for a
+    /// single FRI layer, in the same form as returned by
+    /// [FriProof::parse_layers()](crate::FriProof::parse_layers()). The `remainder` parameter
+    /// specifies the values of the last FRI layer.
+    ///
+    /// A proof is accepted by this method if and only if it is accepted by
+    /// [verify()](FriVerifier::verify()).
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of items in `layer_openings` is not equal to the number of FRI layers.
+    /// * Any of the conditions listed for [verify()](FriVerifier::verify()) is not satisfied.
+    pub fn verify_streaming<I>(
+        &self,
+        layer_openings: I,
+        remainder: Vec<E>,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<(), VerifierError>
+    where
+        I: IntoIterator<Item = (Vec<E>, BatchMerkleProof<H>)>,
+    {
+        let num_layers = self.options.num_fri_layers(self.domain_size);
+        let mut channel = StreamingVerifierChannel::new(
+            layer_openings.into_iter(),
+            remainder,
+            self.options.folding_factor(),
+            self.num_partitions,
+        );
+        let result = self.verify_with_channel(&mut channel, evaluations, positions);
+
+        // make sure the number of streamed openings matches the number of FRI layers; if the
+        // stream ran out of openings, verification has already failed, but we report the more
+        // specific error instead
+        let num_layers_read = channel.num_layers_read();
+        if channel.is_exhausted() {
+            return Err(VerifierError::NumLayerOpeningsMismatch(
+                num_layers,
+                num_layers_read,
+            ));
+        }
+        if result.is_ok() {
+            let num_remaining = channel.count_remaining();
+            if num_remaining != 0 {
+                return Err(VerifierError::NumLayerOpeningsMismatch(
+                    num_layers,
+                    num_layers_read + num_remaining,
+                ));
+            }
+        }
+
+        result
+    }
+
+    /// Checks the consistency of `evaluations` and `positions`, and dispatches the verification
+    /// procedure based on the folding factor. Layer openings and the remainder are read from the
+    /// specified `channel`.
+    fn verify_with_channel<V>(
+        &self,
+        channel: &mut V,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<(), VerifierError>
+    where
+        V: VerifierChannel<E, Hasher = H>,
+    {
         if evaluations.len() != positions.len() {
             return Err(VerifierError::NumPositionEvaluationMismatch(
                 positions.len(),
@@ -215,22 +357,25 @@ where
         // static dispatch for folding factor parameter
         let folding_factor = self.options.folding_factor();
         match folding_factor {
-            2 => self.verify_generic::<2>(channel, evaluations, positions),
-            4 => self.verify_generic::<4>(channel, evaluations, positions),
-            8 => self.verify_generic::<8>(channel, evaluations, positions),
-            16 => self.verify_generic::<16>(channel, evaluations, positions),
+            2 => self.verify_generic::<_, 2>(channel, evaluations, positions),
+            4 => self.verify_generic::<_, 4>(channel, evaluations, positions),
+            8 => self.verify_generic::<_, 8>(channel, evaluations, positions),
+            16 => self.verify_generic::<_, 16>(channel, evaluations, positions),
             _ => Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
         }
     }
 
     /// This is the actual implementation of the verification procedure described above, but it
     /// also takes folding factor as a generic parameter N.
-    fn verify_generic<const N: usize>(
+    fn verify_generic<V, const N: usize>(
         &self,
-        channel: &mut C,
+        channel: &mut V,
         evaluations: &[E],
         positions: &[usize],
-    ) -> Result<(), VerifierError> {
+    ) -> Result<(), VerifierError>
+    where
+        V: VerifierChannel<E, Hasher = H>,
+    {
         // pre-compute roots of unity used in computing x coordinates in the folded domain
         let folding_roots = (0..N)
             .map(|i| {
@@ -263,8 +408,11 @@ where
             let layer_values = channel.read_layer_queries(&position_indexes, &layer_commitment)?;
             let query_values =
                 get_query_values::<E, N>(&layer_values, &positions, &folded_positions, domain_size);
-            if evaluations != query_values {
-                return Err(VerifierError::InvalidLayerFolding(depth));
+            if let Some(i) = (0..evaluations.len()).find(|&i| evaluations[i] != query_values[i]) {
+                return Err(VerifierError::QueryMismatch {
+                    position: positions[i],
+                    layer: depth,
+                });
             }
 
             // build a set of x coordinates for each row polynomial
@@ -315,8 +463,17 @@ where
             }
         }
 
+        // if the remainder degree is capped, make sure the degree implied by max_poly_degree
+        // does not exceed the cap
+        let max_remainder_degree = max_degree_plus_1 - 1;
+        if let Some(degree_cap) = self.options.max_remainder_degree() {
+            if max_remainder_degree > degree_cap {
+                return Err(VerifierError::RemainderDegreeMismatch(degree_cap));
+            }
+        }
+
         // make sure the remainder values satisfy the degree
-        verify_remainder(remainder, max_degree_plus_1 - 1)
+        verify_remainder(remainder, max_remainder_degree)
     }
 }
 
@@ -325,7 +482,7 @@ where
 /// Returns Ok(true) if values in the `remainder` slice represent evaluations of a polynomial
 /// with degree <= `max_degree` against a domain of the same size as `remainder`.
 fn verify_remainder<B: StarkField, E: FieldElement<BaseField = B>>(
-    mut remainder: Vec<E>,
+    remainder: Vec<E>,
     max_degree: usize,
 ) -> Result<(), VerifierError> {
     if max_degree >= remainder.len() - 1 {
@@ -343,9 +500,7 @@ fn verify_remainder<B: StarkField, E: FieldElement<BaseField = B>>(
         // interpolate remainder polynomial from its evaluations; we don't shift the domain here
         // because the degree of the polynomial will not change as long as we interpolate over a
         // coset of the original domain.
-        let inv_twiddles = fft::get_inv_twiddles(remainder.len());
-        fft::interpolate_poly(&mut remainder, &inv_twiddles);
-        let poly = remainder;
+        let poly = interpolate_remainder(remainder);
 
         // make sure the degree is valid
         if max_degree < polynom::degree_of(&poly) {
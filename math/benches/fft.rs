@@ -13,6 +13,7 @@ use winter_math::{
 };
 
 const SIZES: [usize; 3] = [262_144, 524_288, 1_048_576];
+const NUM_REPEATED_FFTS: usize = 100;
 
 fn fft_evaluate_poly<B, E>(c: &mut Criterion, field_name: &str)
 where
@@ -100,6 +101,45 @@ fn get_twiddles(c: &mut Criterion) {
     group.finish();
 }
 
+fn reuse_twiddles(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fft_reuse_twiddles");
+    group.sample_size(10);
+
+    // evaluate the same batch of polynomials with twiddles computed for every FFT, and with
+    // twiddles computed once and cached
+    let size = 4096;
+    let polys: Vec<Vec<f128::BaseElement>> =
+        (0..NUM_REPEATED_FFTS).map(|_| rand_vector(size)).collect();
+
+    group.bench_function(BenchmarkId::new("fresh", size), |bench| {
+        bench.iter_batched_ref(
+            || polys.clone(),
+            |polys| {
+                for p in polys.iter_mut() {
+                    let twiddles = fft::get_twiddles::<f128::BaseElement>(size);
+                    fft::evaluate_poly(p, &twiddles);
+                }
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function(BenchmarkId::new("cached", size), |bench| {
+        let twiddles = fft::Twiddles::<f128::BaseElement>::new(size);
+        bench.iter_batched_ref(
+            || polys.clone(),
+            |polys| {
+                for p in polys.iter_mut() {
+                    twiddles.evaluate_poly(p);
+                }
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
 fn bench_fft(c: &mut Criterion) {
     fft_evaluate_poly::<f62::BaseElement, f62::BaseElement>(c, "f62");
     fft_evaluate_poly::<f64::BaseElement, f64::BaseElement>(c, "f64");
@@ -116,5 +156,5 @@ fn bench_fft(c: &mut Criterion) {
     fft_interpolate_poly::<f128::BaseElement, f128::BaseElement>(c, "f128");
 }
 
-criterion_group!(fft_group, bench_fft, get_twiddles);
+criterion_group!(fft_group, bench_fft, get_twiddles, reuse_twiddles);
 criterion_main!(fft_group);
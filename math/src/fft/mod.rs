@@ -521,6 +521,129 @@ where
     inv_twiddles
 }
 
+// TWIDDLES CACHE
+// ================================================================================================
+
+/// Precomputed twiddles and inverse twiddles for FFTs over a domain of a given size.
+///
+/// Computing twiddles requires computing the powers of a root of unity for the domain, and can
+/// account for a noticeable portion of the cost of a single FFT. When many FFTs over domains of
+/// the same size need to be computed, twiddles can be computed once via [Twiddles::new()] and
+/// then reused for all of them. Since twiddles are elements of the base field `B`, the same cache
+/// can be used to evaluate and interpolate polynomials over any extension of `B`.
+///
+/// # Examples
+/// ```
+/// # use winter_math::{fft::*, fields::{f128::BaseElement, QuadExtension}};
+/// # use rand_utils::rand_vector;
+/// let n = 2048;
+/// let twiddles = Twiddles::<BaseElement>::new(n);
+///
+/// // the same twiddles can be used for polynomials in the base field and in its extensions
+/// let p: Vec<BaseElement> = rand_vector(n);
+/// let mut ys = p.clone();
+/// twiddles.evaluate_poly(&mut ys);
+/// twiddles.interpolate_poly(&mut ys);
+/// assert_eq!(p, ys);
+///
+/// let p: Vec<QuadExtension<BaseElement>> = rand_vector(n);
+/// let mut ys = p.clone();
+/// twiddles.evaluate_poly(&mut ys);
+/// twiddles.interpolate_poly(&mut ys);
+/// assert_eq!(p, ys);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Twiddles<B: StarkField> {
+    domain_size: usize,
+    twiddles: Vec<B>,
+    inv_twiddles: Vec<B>,
+}
+
+impl<B: StarkField> Twiddles<B> {
+    /// Returns twiddles and inverse twiddles for a domain of the specified size.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two.
+    /// * Field specified by `B` does not contain a multiplicative subgroup of size `domain_size`.
+    pub fn new(domain_size: usize) -> Self {
+        Self {
+            domain_size,
+            twiddles: get_twiddles(domain_size),
+            inv_twiddles: get_inv_twiddles(domain_size),
+        }
+    }
+
+    /// Returns the size of the domain for which these twiddles were computed.
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    /// Returns twiddles for FFT-based polynomial evaluation; these are the same as the twiddles
+    /// returned by [get_twiddles()] for the domain size of this cache.
+    pub fn twiddles(&self) -> &[B] {
+        &self.twiddles
+    }
+
+    /// Returns inverse twiddles for FFT-based polynomial interpolation; these are the same as the
+    /// twiddles returned by [get_inv_twiddles()] for the domain size of this cache.
+    pub fn inv_twiddles(&self) -> &[B] {
+        &self.inv_twiddles
+    }
+
+    /// Evaluates polynomial `p` in place using the cached twiddles.
+    ///
+    /// This is equivalent to calling [evaluate_poly()] with `self.twiddles()`, and thus, the
+    /// length of `p` must be equal to the domain size of this cache.
+    pub fn evaluate_poly<E>(&self, p: &mut [E])
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        evaluate_poly(p, &self.twiddles)
+    }
+
+    /// Evaluates polynomial `p` over a domain expanded by the `blowup_factor` and shifted by the
+    /// `domain_offset` using the cached twiddles.
+    ///
+    /// This is equivalent to calling [evaluate_poly_with_offset()] with `self.twiddles()`, and
+    /// thus, the length of `p` must be equal to the domain size of this cache.
+    pub fn evaluate_poly_with_offset<E>(
+        &self,
+        p: &[E],
+        domain_offset: B,
+        blowup_factor: usize,
+    ) -> Vec<E>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        evaluate_poly_with_offset(p, &self.twiddles, domain_offset, blowup_factor)
+    }
+
+    /// Interpolates `evaluations` into a polynomial in place using the cached inverse twiddles.
+    ///
+    /// This is equivalent to calling [interpolate_poly()] with `self.inv_twiddles()`, and thus,
+    /// the length of `evaluations` must be equal to the domain size of this cache.
+    pub fn interpolate_poly<E>(&self, evaluations: &mut [E])
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        interpolate_poly(evaluations, &self.inv_twiddles)
+    }
+
+    /// Interpolates `evaluations` over a domain shifted by the `domain_offset` into a polynomial
+    /// in place using the cached inverse twiddles.
+    ///
+    /// This is equivalent to calling [interpolate_poly_with_offset()] with
+    /// `self.inv_twiddles()`, and thus, the length of `evaluations` must be equal to the domain
+    /// size of this cache.
+    pub fn interpolate_poly_with_offset<E>(&self, evaluations: &mut [E], domain_offset: B)
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        interpolate_poly_with_offset(evaluations, &self.inv_twiddles, domain_offset)
+    }
+}
+
 // DEGREE INFERENCE
 // ================================================================================================
 
@@ -583,6 +706,62 @@ where
     super::polynom::degree_of(&poly)
 }
 
+// BIT-REVERSAL PERMUTATIONS
+// ================================================================================================
+
+/// Returns the index to which `index` is moved by the bit-reversal permutation of a slice of
+/// length 2^`log_n`.
+///
+/// The returned index is obtained by reversing the order of the lowest `log_n` bits of `index`.
+///
+/// # Panics
+/// Panics if `index` is greater than or equal to 2^`log_n`.
+///
+/// # Examples
+/// ```
+/// # use winter_math::fft::bit_reverse_index;
+/// assert_eq!(0b110, bit_reverse_index(0b011, 3));
+/// assert_eq!(0b0110, bit_reverse_index(0b0110, 4));
+/// assert_eq!(0, bit_reverse_index(0, 0));
+/// ```
+pub fn bit_reverse_index(index: usize, log_n: u32) -> usize {
+    assert!(
+        index.checked_shr(log_n).unwrap_or(0) == 0,
+        "index must be smaller than 2^{}, but was {}",
+        log_n,
+        index
+    );
+    reverse_index_bits(index, log_n)
+}
+
+/// Reorders `values` in-place according to the bit-reversal permutation.
+///
+/// After the permutation, the value at index `i` is the value which was previously located at
+/// index [bit_reverse_index()]`(i, log2(values.len()))`.
+///
+/// # Panics
+/// Panics if length of `values` is not a power of two.
+pub fn permute_bit_reverse<T>(values: &mut [T]) {
+    assert!(
+        values.len().is_power_of_two(),
+        "number of values must be a power of two, but was {}",
+        values.len()
+    );
+    serial::permute(values);
+}
+
+/// Reverts the bit-reversal permutation of `values` in-place.
+///
+/// Since the bit-reversal permutation is its own inverse, this is equivalent to
+/// [permute_bit_reverse()]; the function is provided for readability at call sites which undo a
+/// previously applied permutation.
+///
+/// # Panics
+/// Panics if length of `values` is not a power of two.
+pub fn inv_permute_bit_reverse<T>(values: &mut [T]) {
+    permute_bit_reverse(values);
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -597,10 +776,14 @@ fn permute<E: FieldElement>(v: &mut [E]) {
 
 fn permute_index(size: usize, index: usize) -> usize {
     debug_assert!(index < size);
-    if size == 1 {
+    debug_assert!(size.is_power_of_two());
+    reverse_index_bits(index, size.trailing_zeros())
+}
+
+#[inline(always)]
+fn reverse_index_bits(index: usize, num_bits: u32) -> usize {
+    if num_bits == 0 {
         return 0;
     }
-    debug_assert!(size.is_power_of_two());
-    let bits = size.trailing_zeros() as usize;
-    index.reverse_bits() >> (USIZE_BITS - bits)
+    index.reverse_bits() >> (USIZE_BITS - num_bits as usize)
 }
@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{
-    field::{f128::BaseElement, StarkField},
+    field::{f128::BaseElement, QuadExtension, StarkField},
     polynom,
     utils::{get_power_series, log2},
 };
@@ -69,6 +69,115 @@ fn fft_get_twiddles() {
     assert_eq!(expected, twiddles);
 }
 
+#[test]
+fn fft_twiddles_cache() {
+    let n = super::MIN_CONCURRENT_SIZE * 2;
+    let offset = BaseElement::GENERATOR;
+    let twiddles = super::get_twiddles::<BaseElement>(n);
+    let inv_twiddles = super::get_inv_twiddles::<BaseElement>(n);
+
+    let cache = super::Twiddles::<BaseElement>::new(n);
+    assert_eq!(n, cache.domain_size());
+    assert_eq!(twiddles, cache.twiddles());
+    assert_eq!(inv_twiddles, cache.inv_twiddles());
+
+    // results of FFTs with cached twiddles must be the same as with freshly computed twiddles;
+    // the same cache is reused for polynomials over the base field and over its extension
+    let p: Vec<BaseElement> = rand_vector(n);
+    let mut expected = p.clone();
+    super::evaluate_poly(&mut expected, &twiddles);
+    let mut actual = p.clone();
+    cache.evaluate_poly(&mut actual);
+    assert_eq!(expected, actual);
+
+    super::interpolate_poly(&mut expected, &inv_twiddles);
+    cache.interpolate_poly(&mut actual);
+    assert_eq!(expected, actual);
+    assert_eq!(p, actual);
+
+    let mut expected = super::evaluate_poly_with_offset(&p, &twiddles, offset, 1);
+    let mut actual = cache.evaluate_poly_with_offset(&p, offset, 1);
+    assert_eq!(expected, actual);
+
+    super::interpolate_poly_with_offset(&mut expected, &inv_twiddles, offset);
+    cache.interpolate_poly_with_offset(&mut actual, offset);
+    assert_eq!(expected, actual);
+    assert_eq!(p, actual);
+
+    let p: Vec<QuadExtension<BaseElement>> = rand_vector(n);
+    let mut expected = p.clone();
+    super::evaluate_poly(&mut expected, &twiddles);
+    let mut actual = p.clone();
+    cache.evaluate_poly(&mut actual);
+    assert_eq!(expected, actual);
+
+    cache.interpolate_poly(&mut actual);
+    assert_eq!(p, actual);
+}
+
+// BIT-REVERSAL PERMUTATIONS
+// ================================================================================================
+
+#[test]
+fn bit_reverse_index() {
+    assert_eq!(0, super::bit_reverse_index(0, 0));
+    assert_eq!(1, super::bit_reverse_index(1, 1));
+    assert_eq!(0b100, super::bit_reverse_index(0b001, 3));
+    assert_eq!(0b1101, super::bit_reverse_index(0b1011, 4));
+
+    // the index function is an involution
+    for log_n in 0..10 {
+        for i in 0..1 << log_n {
+            let j = super::bit_reverse_index(i, log_n);
+            assert!(j < 1 << log_n);
+            assert_eq!(i, super::bit_reverse_index(j, log_n));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "index must be smaller than 2^3, but was 8")]
+fn bit_reverse_index_out_of_bounds() {
+    super::bit_reverse_index(8, 3);
+}
+
+#[test]
+fn permute_bit_reverse() {
+    for n in [1, 2, 8, super::MIN_CONCURRENT_SIZE * 2] {
+        let values = (0..n).collect::<Vec<_>>();
+
+        // the in-place permutation matches the index function
+        let mut permuted = values.clone();
+        super::permute_bit_reverse(&mut permuted);
+        for (i, &value) in permuted.iter().enumerate() {
+            assert_eq!(super::bit_reverse_index(i, log2(n)), value);
+        }
+
+        // the permutation is consistent with the one used by the FFT functions
+        let mut elements = values
+            .iter()
+            .map(|&v| BaseElement::from(v as u64))
+            .collect::<Vec<_>>();
+        let mut expected = elements.clone();
+        super::permute(&mut expected);
+        super::permute_bit_reverse(&mut elements);
+        assert_eq!(expected, elements);
+
+        // applying the permutation twice, or applying the inverse, restores the original order
+        let mut twice = permuted.clone();
+        super::permute_bit_reverse(&mut twice);
+        assert_eq!(values, twice);
+        super::inv_permute_bit_reverse(&mut permuted);
+        assert_eq!(values, permuted);
+    }
+}
+
+#[test]
+#[should_panic(expected = "number of values must be a power of two, but was 6")]
+fn permute_bit_reverse_invalid_length() {
+    super::permute_bit_reverse(&mut [0_u8; 6]);
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -38,6 +38,17 @@ impl<B: ExtensibleField<3>> CubeExtension<B> {
         <B as ExtensibleField<3>>::is_supported()
     }
 
+    /// Returns the base field element embedded in this extension element, or `None` if the
+    /// coefficients of φ and φ^2 are not both zero (i.e., the element does not belong to the base
+    /// field).
+    pub fn to_base_element(&self) -> Option<B> {
+        if self.1 == B::ZERO && self.2 == B::ZERO {
+            Some(self.0)
+        } else {
+            None
+        }
+    }
+
     /// Converts a vector of base elements into a vector of elements in a cubic extension field
     /// by fusing three adjacent base elements together. The output vector is half the length of
     /// the source vector.
@@ -93,6 +104,12 @@ impl<B: ExtensibleField<3>> FieldElement for CubeExtension<B> {
         Self(result[0], result[1], result[2])
     }
 
+    #[inline]
+    fn frobenius(&self) -> Self {
+        let result = <B as ExtensibleField<3>>::frobenius([self.0, self.1, self.2]);
+        Self(result[0], result[1], result[2])
+    }
+
     fn elements_as_bytes(elements: &[Self]) -> &[u8] {
         unsafe {
             slice::from_raw_parts(
@@ -382,6 +399,28 @@ mod tests {
         }
     }
 
+    // TYPE CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn base_element_conversions() {
+        // base element -> extension element -> base element round trip
+        let r: BaseElement = rand_value();
+        let e = CubeExtension::<BaseElement>::from(r);
+        assert_eq!(CubeExtension(r, BaseElement::ZERO, BaseElement::ZERO), e);
+        assert_eq!(Some(r), e.to_base_element());
+        assert_eq!(Ok(r), BaseElement::try_from(e));
+
+        // elements which do not belong to the base field cannot be converted
+        for e in [
+            CubeExtension(r, BaseElement::ONE, BaseElement::ZERO),
+            CubeExtension(r, BaseElement::ZERO, BaseElement::ONE),
+        ] {
+            assert_eq!(None, e.to_base_element());
+            assert!(BaseElement::try_from(e).is_err());
+        }
+    }
+
     // SERIALIZATION / DESERIALIZATION
     // --------------------------------------------------------------------------------------------
 
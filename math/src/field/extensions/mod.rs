@@ -9,4 +9,70 @@ pub use quadratic::QuadExtension;
 mod cubic;
 pub use cubic::CubeExtension;
 
+mod quartic;
+pub use quartic::QuarticExtension;
+
+mod sextic;
+pub use sextic::SexticExtension;
+
 use super::{ExtensibleField, ExtensionOf, FieldElement};
+
+// BASE FIELD CONVERSIONS
+// ================================================================================================
+
+/// Implements conversions from quadratic and cubic extension elements into elements of the
+/// specified base field. A conversion succeeds only if the extension element belongs to the base
+/// field (i.e., all of its higher coordinates are zero).
+///
+/// Conversions in the opposite direction are provided by generic `From<B>` implementations on the
+/// extension types; the conversions below cannot be generic over the base field due to coherence
+/// rules, and thus, are implemented for each base field separately.
+macro_rules! impl_base_element_try_from {
+    ($base:ty) => {
+        impl core::convert::TryFrom<QuadExtension<$base>> for $base {
+            type Error = utils::string::String;
+
+            fn try_from(value: QuadExtension<$base>) -> Result<Self, Self::Error> {
+                value.to_base_element().ok_or_else(|| {
+                    format!(
+                        "quadratic extension element {} does not belong to the base field",
+                        value
+                    )
+                })
+            }
+        }
+
+        impl core::convert::TryFrom<CubeExtension<$base>> for $base {
+            type Error = utils::string::String;
+
+            fn try_from(value: CubeExtension<$base>) -> Result<Self, Self::Error> {
+                value.to_base_element().ok_or_else(|| {
+                    format!(
+                        "cubic extension element {} does not belong to the base field",
+                        value
+                    )
+                })
+            }
+        }
+    };
+}
+
+impl_base_element_try_from!(super::f62::BaseElement);
+impl_base_element_try_from!(super::f64::BaseElement);
+impl_base_element_try_from!(super::f128::BaseElement);
+impl_base_element_try_from!(super::f252::BaseElement);
+
+/// Degree 6 extensions are implemented only for the f62 field, and thus, the conversion is
+/// implemented only for this field as well.
+impl core::convert::TryFrom<SexticExtension<super::f62::BaseElement>> for super::f62::BaseElement {
+    type Error = utils::string::String;
+
+    fn try_from(value: SexticExtension<super::f62::BaseElement>) -> Result<Self, Self::Error> {
+        value.to_base_element().ok_or_else(|| {
+            format!(
+                "sextic extension element {} does not belong to the base field",
+                value
+            )
+        })
+    }
+}
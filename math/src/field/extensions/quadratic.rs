@@ -38,6 +38,16 @@ impl<B: ExtensibleField<2>> QuadExtension<B> {
         <B as ExtensibleField<2>>::is_supported()
     }
 
+    /// Returns the base field element embedded in this extension element, or `None` if the
+    /// coefficient of φ is not zero (i.e., the element does not belong to the base field).
+    pub fn to_base_element(&self) -> Option<B> {
+        if self.1 == B::ZERO {
+            Some(self.0)
+        } else {
+            None
+        }
+    }
+
     /// Converts a vector of base elements into a vector of elements in a quadratic extension
     /// field by fusing two adjacent base elements together. The output vector is half the length
     /// of the source vector.
@@ -86,6 +96,12 @@ impl<B: ExtensibleField<2>> FieldElement for QuadExtension<B> {
         Self(result[0], result[1])
     }
 
+    #[inline]
+    fn frobenius(&self) -> Self {
+        let result = <B as ExtensibleField<2>>::frobenius([self.0, self.1]);
+        Self(result[0], result[1])
+    }
+
     fn elements_as_bytes(elements: &[Self]) -> &[u8] {
         unsafe {
             slice::from_raw_parts(
@@ -324,7 +340,7 @@ impl<B: ExtensibleField<2>> Deserializable for QuadExtension<B> {
 
 #[cfg(test)]
 mod tests {
-    use super::{DeserializationError, FieldElement, QuadExtension, Vec};
+    use super::{DeserializationError, FieldElement, QuadExtension, TryFrom, Vec};
     use crate::field::f128::BaseElement;
     use rand_utils::rand_value;
 
@@ -371,6 +387,24 @@ mod tests {
         }
     }
 
+    // TYPE CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn base_element_conversions() {
+        // base element -> extension element -> base element round trip
+        let r: BaseElement = rand_value();
+        let e = QuadExtension::<BaseElement>::from(r);
+        assert_eq!(QuadExtension(r, BaseElement::ZERO), e);
+        assert_eq!(Some(r), e.to_base_element());
+        assert_eq!(Ok(r), BaseElement::try_from(e));
+
+        // elements which do not belong to the base field cannot be converted
+        let e = QuadExtension(r, BaseElement::ONE);
+        assert_eq!(None, e.to_base_element());
+        assert!(BaseElement::try_from(e).is_err());
+    }
+
     // SERIALIZATION / DESERIALIZATION
     // --------------------------------------------------------------------------------------------
 
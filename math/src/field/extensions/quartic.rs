@@ -0,0 +1,496 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ExtensionOf, FieldElement, QuadExtension};
+use crate::field::QuarticTowerField;
+use core::{
+    convert::TryFrom,
+    fmt,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    slice,
+};
+use utils::{
+    collections::Vec, string::ToString, AsBytes, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Randomizable, Serializable, SliceReader,
+};
+
+// QUARTIC EXTENSION FIELD
+// ================================================================================================
+
+/// Represents an element in a degree 4 extension of a [StarkField](crate::StarkField) built as a
+/// tower of two quadratic extensions.
+///
+/// The extension element is defined as α + β * ψ, where ψ is a root of an irreducible polynomial
+/// y<sup>2</sup> - ξ defined by the implementation of the [QuarticTowerField] trait, and α and β
+/// are elements of the quadratic extension of the base field (see [QuadExtension]).
+///
+/// In memory, the element is laid out as four base field elements which are the coefficients of
+/// 1, φ, ψ, and φ * ψ respectively, where φ is a root of the polynomial defining the quadratic
+/// extension. Thus, a base field element is embedded into the lowest coordinate.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct QuarticExtension<B: QuarticTowerField>(QuadExtension<B>, QuadExtension<B>);
+
+impl<B: QuarticTowerField> QuarticExtension<B> {
+    /// Returns a new extension element instantiated from the provided elements of the quadratic
+    /// extension; the elements are the coefficients of 1 and ψ respectively.
+    pub fn new(a: QuadExtension<B>, b: QuadExtension<B>) -> Self {
+        Self(a, b)
+    }
+
+    /// Returns true if the base field specified by B type parameter supports quadratic extensions,
+    /// and thus, supports degree 4 tower extensions.
+    pub fn is_supported() -> bool {
+        QuadExtension::<B>::is_supported()
+    }
+
+    /// Returns the base field element embedded in this extension element, or `None` if any of the
+    /// coefficients of φ, ψ, and φ * ψ is not zero (i.e., the element does not belong to the base
+    /// field).
+    pub fn to_base_element(&self) -> Option<B> {
+        if self.1 == QuadExtension::ZERO {
+            self.0.to_base_element()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the non-square ξ of the quadratic extension which defines this extension.
+    #[inline(always)]
+    fn non_residue() -> QuadExtension<B> {
+        let [a, b] = B::QUARTIC_NON_RESIDUE;
+        QuadExtension::new(a, b)
+    }
+
+    /// Converts a vector of base elements into a vector of elements in a degree 4 extension field
+    /// by fusing four adjacent base elements together. The output vector is one fourth the length
+    /// of the source vector.
+    fn base_to_quartic_vector(source: Vec<B>) -> Vec<Self> {
+        debug_assert!(
+            source.len() % 4 == 0,
+            "source vector length must be divisible by four, but was {}",
+            source.len()
+        );
+        let mut v = core::mem::ManuallyDrop::new(source);
+        let p = v.as_mut_ptr();
+        let len = v.len() / 4;
+        let cap = v.capacity() / 4;
+        unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
+    }
+}
+
+impl<B: QuarticTowerField> FieldElement for QuarticExtension<B> {
+    type PositiveInteger = B::PositiveInteger;
+    type BaseField = B;
+
+    const ELEMENT_BYTES: usize = B::ELEMENT_BYTES * 4;
+    const IS_CANONICAL: bool = B::IS_CANONICAL;
+    const ZERO: Self = Self(QuadExtension::ZERO, QuadExtension::ZERO);
+    const ONE: Self = Self(QuadExtension::ONE, QuadExtension::ZERO);
+
+    #[inline]
+    fn inv(self) -> Self {
+        if self == Self::ZERO {
+            return self;
+        }
+
+        // (α + β * ψ)^-1 = (α - β * ψ) / (α^2 - ξ * β^2), where the denominator is the norm of
+        // the element in the quadratic extension
+        let norm = self.0.square() - Self::non_residue() * self.1.square();
+        let denom_inv = norm.inv();
+
+        Self(self.0 * denom_inv, -self.1 * denom_inv)
+    }
+
+    #[inline]
+    fn conjugate(&self) -> Self {
+        self.frobenius()
+    }
+
+    #[inline]
+    fn frobenius(&self) -> Self {
+        // (α + β * ψ)^p = α^p + β^p * ψ^p, and ψ^p = ξ^((p - 1) / 2) * ψ
+        let [a, b] = B::QUARTIC_FROBENIUS;
+        Self(
+            self.0.frobenius(),
+            self.1.frobenius() * QuadExtension::new(a, b),
+        )
+    }
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        unsafe {
+            slice::from_raw_parts(
+                elements.as_ptr() as *const u8,
+                elements.len() * Self::ELEMENT_BYTES,
+            )
+        }
+    }
+
+    unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        if bytes.len() % Self::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of bytes ({}) does not divide into whole number of field elements",
+                bytes.len(),
+            )));
+        }
+
+        let p = bytes.as_ptr();
+        let len = bytes.len() / Self::ELEMENT_BYTES;
+
+        // make sure the bytes are aligned on the boundary consistent with base element alignment
+        if (p as usize) % Self::BaseField::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(
+                "slice memory alignment is not valid for this field element type".to_string(),
+            ));
+        }
+
+        Ok(slice::from_raw_parts(p as *const Self, len))
+    }
+
+    fn zeroed_vector(n: usize) -> Vec<Self> {
+        // get four times the number of base elements, and re-interpret them as quartic field
+        // elements
+        let result = B::zeroed_vector(n * 4);
+        Self::base_to_quartic_vector(result)
+    }
+
+    fn as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+        let ptr = elements.as_ptr();
+        let len = elements.len() * 4;
+        unsafe { slice::from_raw_parts(ptr as *const Self::BaseField, len) }
+    }
+}
+
+impl<B: QuarticTowerField> ExtensionOf<B> for QuarticExtension<B> {
+    #[inline(always)]
+    fn mul_base(self, other: B) -> Self {
+        Self(self.0.mul_base(other), self.1.mul_base(other))
+    }
+}
+
+impl<B: QuarticTowerField> Randomizable for QuarticExtension<B> {
+    const VALUE_SIZE: usize = Self::ELEMENT_BYTES;
+
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::try_from(bytes).ok()
+    }
+}
+
+impl<B: QuarticTowerField> fmt::Display for QuarticExtension<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+// OVERLOADED OPERATORS
+// ------------------------------------------------------------------------------------------------
+
+impl<B: QuarticTowerField> Add for QuarticExtension<B> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<B: QuarticTowerField> AddAssign for QuarticExtension<B> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl<B: QuarticTowerField> Sub for QuarticExtension<B> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<B: QuarticTowerField> SubAssign for QuarticExtension<B> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<B: QuarticTowerField> Mul for QuarticExtension<B> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        // performs multiplication using 3 multiplications in the quadratic extension (plus one
+        // multiplication by ξ) via Karatsuba's method
+        let a0b0 = self.0 * rhs.0;
+        let a1b1 = self.1 * rhs.1;
+        Self(
+            a0b0 + Self::non_residue() * a1b1,
+            (self.0 + self.1) * (rhs.0 + rhs.1) - a0b0 - a1b1,
+        )
+    }
+}
+
+impl<B: QuarticTowerField> MulAssign for QuarticExtension<B> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl<B: QuarticTowerField> Div for QuarticExtension<B> {
+    type Output = Self;
+
+    #[inline]
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl<B: QuarticTowerField> DivAssign for QuarticExtension<B> {
+    #[inline]
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl<B: QuarticTowerField> Neg for QuarticExtension<B> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(-self.0, -self.1)
+    }
+}
+
+// TYPE CONVERSIONS
+// ------------------------------------------------------------------------------------------------
+
+impl<B: QuarticTowerField> From<B> for QuarticExtension<B> {
+    fn from(value: B) -> Self {
+        Self(QuadExtension::from(value), QuadExtension::ZERO)
+    }
+}
+
+impl<B: QuarticTowerField> From<QuadExtension<B>> for QuarticExtension<B> {
+    fn from(value: QuadExtension<B>) -> Self {
+        Self(value, QuadExtension::ZERO)
+    }
+}
+
+impl<B: QuarticTowerField> From<u128> for QuarticExtension<B> {
+    fn from(value: u128) -> Self {
+        Self(QuadExtension::from(value), QuadExtension::ZERO)
+    }
+}
+
+impl<B: QuarticTowerField> From<u64> for QuarticExtension<B> {
+    fn from(value: u64) -> Self {
+        Self(QuadExtension::from(value), QuadExtension::ZERO)
+    }
+}
+
+impl<B: QuarticTowerField> From<u32> for QuarticExtension<B> {
+    fn from(value: u32) -> Self {
+        Self(QuadExtension::from(value), QuadExtension::ZERO)
+    }
+}
+
+impl<B: QuarticTowerField> From<u16> for QuarticExtension<B> {
+    fn from(value: u16) -> Self {
+        Self(QuadExtension::from(value), QuadExtension::ZERO)
+    }
+}
+
+impl<B: QuarticTowerField> From<u8> for QuarticExtension<B> {
+    fn from(value: u8) -> Self {
+        Self(QuadExtension::from(value), QuadExtension::ZERO)
+    }
+}
+
+impl<'a, B: QuarticTowerField> TryFrom<&'a [u8]> for QuarticExtension<B> {
+    type Error = DeserializationError;
+
+    /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
+    /// is not a valid field element. The bytes are assumed to be in little-endian byte order.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < Self::ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "not enough bytes for a full field element; expected {} bytes, but was {} bytes",
+                Self::ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        if bytes.len() > Self::ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "too many bytes for a field element; expected {} bytes, but was {} bytes",
+                Self::ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        let mut reader = SliceReader::new(bytes);
+        Self::read_from(&mut reader)
+    }
+}
+
+impl<B: QuarticTowerField> AsBytes for QuarticExtension<B> {
+    fn as_bytes(&self) -> &[u8] {
+        // TODO: take endianness into account
+        let self_ptr: *const Self = self;
+        unsafe { slice::from_raw_parts(self_ptr as *const u8, Self::ELEMENT_BYTES) }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl<B: QuarticTowerField> Serializable for QuarticExtension<B> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.0.write_into(target);
+        self.1.write_into(target);
+    }
+}
+
+impl<B: QuarticTowerField> Deserializable for QuarticExtension<B> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let value0 = QuadExtension::read_from(source)?;
+        let value1 = QuadExtension::read_from(source)?;
+        Ok(Self(value0, value1))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{DeserializationError, FieldElement, QuadExtension, QuarticExtension, Vec};
+    use crate::field::f64::BaseElement;
+    use rand_utils::rand_value;
+
+    // BASIC ALGEBRA
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn add() {
+        // identity
+        let r: QuarticExtension<BaseElement> = rand_value();
+        assert_eq!(r, r + QuarticExtension::<BaseElement>::ZERO);
+
+        // test random values
+        let r1: QuarticExtension<BaseElement> = rand_value();
+        let r2: QuarticExtension<BaseElement> = rand_value();
+
+        let expected = QuarticExtension(r1.0 + r2.0, r1.1 + r2.1);
+        assert_eq!(expected, r1 + r2);
+    }
+
+    #[test]
+    fn sub() {
+        // identity
+        let r: QuarticExtension<BaseElement> = rand_value();
+        assert_eq!(r, r - QuarticExtension::<BaseElement>::ZERO);
+
+        // test random values
+        let r1: QuarticExtension<BaseElement> = rand_value();
+        let r2: QuarticExtension<BaseElement> = rand_value();
+
+        let expected = QuarticExtension(r1.0 - r2.0, r1.1 - r2.1);
+        assert_eq!(expected, r1 - r2);
+    }
+
+    // INITIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn zeroed_vector() {
+        let result = QuarticExtension::<BaseElement>::zeroed_vector(4);
+        assert_eq!(4, result.len());
+        for element in result.into_iter() {
+            assert_eq!(QuarticExtension::<BaseElement>::ZERO, element);
+        }
+    }
+
+    // TYPE CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn base_element_conversions() {
+        // base element -> extension element -> base element round trip
+        let r: BaseElement = rand_value();
+        let e = QuarticExtension::<BaseElement>::from(r);
+        assert_eq!(
+            [r, BaseElement::ZERO, BaseElement::ZERO, BaseElement::ZERO],
+            QuarticExtension::<BaseElement>::as_base_elements(&[e])
+        );
+        assert_eq!(Some(r), e.to_base_element());
+
+        // elements which do not belong to the base field cannot be converted
+        let e = QuarticExtension(QuadExtension::from(r), QuadExtension::ONE);
+        assert_eq!(None, e.to_base_element());
+        let e = QuarticExtension(QuadExtension::new(r, BaseElement::ONE), QuadExtension::ZERO);
+        assert_eq!(None, e.to_base_element());
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn elements_as_bytes() {
+        let source = vec![build_quartic([1, 2, 3, 4]), build_quartic([5, 6, 7, 8])];
+
+        // the elements are laid out in memory as consecutive base field elements
+        let base_elements = (1..9).map(BaseElement::new).collect::<Vec<_>>();
+        let expected = BaseElement::elements_as_bytes(&base_elements);
+
+        assert_eq!(
+            expected,
+            QuarticExtension::<BaseElement>::elements_as_bytes(&source)
+        );
+    }
+
+    #[test]
+    fn bytes_as_elements() {
+        let source = vec![build_quartic([1, 2, 3, 4]), build_quartic([5, 6, 7, 8])];
+
+        let mut bytes = QuarticExtension::<BaseElement>::elements_as_bytes(&source).to_vec();
+        bytes.push(9);
+
+        let result = unsafe { QuarticExtension::<BaseElement>::bytes_as_elements(&bytes[..64]) };
+        assert!(result.is_ok());
+        assert_eq!(source, result.unwrap());
+
+        let result = unsafe { QuarticExtension::<BaseElement>::bytes_as_elements(&bytes) };
+        assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+    }
+
+    // UTILITIES
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn as_base_elements() {
+        let elements = vec![build_quartic([1, 2, 3, 4]), build_quartic([5, 6, 7, 8])];
+
+        let expected = (1..9).map(BaseElement::new).collect::<Vec<_>>();
+
+        assert_eq!(
+            expected,
+            QuarticExtension::<BaseElement>::as_base_elements(&elements)
+        );
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn build_quartic(values: [u64; 4]) -> QuarticExtension<BaseElement> {
+        QuarticExtension(
+            QuadExtension::new(BaseElement::new(values[0]), BaseElement::new(values[1])),
+            QuadExtension::new(BaseElement::new(values[2]), BaseElement::new(values[3])),
+        )
+    }
+}
@@ -0,0 +1,521 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ExtensibleField, ExtensionOf, FieldElement};
+use core::{
+    convert::TryFrom,
+    fmt,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    slice,
+};
+use utils::{
+    collections::Vec, string::ToString, AsBytes, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Randomizable, Serializable, SliceReader,
+};
+
+// SEXTIC EXTENSION FIELD
+// ================================================================================================
+
+/// Represents an element in a degree 6 extension of a [StarkField](crate::StarkField).
+///
+/// The extension element is defined as a_0 + a_1 * φ + ... + a_5 * φ^5, where φ is a root of an
+/// irreducible polynomial defined by the implementation of the [ExtensibleField] trait, and
+/// a_0, ..., a_5 are base field elements.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct SexticExtension<B: ExtensibleField<6>>([B; 6]);
+
+impl<B: ExtensibleField<6>> SexticExtension<B> {
+    /// Returns a new extension element instantiated from the provided base elements; the base
+    /// elements are the coefficients of 1, φ, ..., φ^5 respectively.
+    pub fn new(coefficients: [B; 6]) -> Self {
+        Self(coefficients)
+    }
+
+    /// Returns true if the base field specified by B type parameter supports degree 6
+    /// extensions.
+    pub fn is_supported() -> bool {
+        <B as ExtensibleField<6>>::is_supported()
+    }
+
+    /// Returns the base field element embedded in this extension element, or `None` if the
+    /// coefficients of φ, ..., φ^5 are not all zero (i.e., the element does not belong to the
+    /// base field).
+    pub fn to_base_element(&self) -> Option<B> {
+        if self.0[1..].iter().all(|&c| c == B::ZERO) {
+            Some(self.0[0])
+        } else {
+            None
+        }
+    }
+
+    /// Converts a vector of base elements into a vector of elements in a degree 6 extension field
+    /// by fusing six adjacent base elements together. The output vector is one sixth the length
+    /// of the source vector.
+    fn base_to_sextic_vector(source: Vec<B>) -> Vec<Self> {
+        debug_assert!(
+            source.len() % 6 == 0,
+            "source vector length must be divisible by six, but was {}",
+            source.len()
+        );
+        let mut v = core::mem::ManuallyDrop::new(source);
+        let p = v.as_mut_ptr();
+        let len = v.len() / 6;
+        let cap = v.capacity() / 6;
+        unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
+    }
+}
+
+impl<B: ExtensibleField<6>> FieldElement for SexticExtension<B> {
+    type PositiveInteger = B::PositiveInteger;
+    type BaseField = B;
+
+    const ELEMENT_BYTES: usize = B::ELEMENT_BYTES * 6;
+    const IS_CANONICAL: bool = B::IS_CANONICAL;
+    const ZERO: Self = Self([B::ZERO; 6]);
+    const ONE: Self = Self([B::ONE, B::ZERO, B::ZERO, B::ZERO, B::ZERO, B::ZERO]);
+
+    #[inline]
+    fn inv(self) -> Self {
+        if self == Self::ZERO {
+            return self;
+        }
+
+        // the numerator is the product of all conjugates of x (other than x itself); multiplying
+        // it by x yields the norm of x, which is an element of the base field
+        let mut conjugate = <B as ExtensibleField<6>>::frobenius(self.0);
+        let mut numerator = conjugate;
+        for _ in 0..4 {
+            conjugate = <B as ExtensibleField<6>>::frobenius(conjugate);
+            numerator = <B as ExtensibleField<6>>::mul(numerator, conjugate);
+        }
+
+        let norm = <B as ExtensibleField<6>>::mul(self.0, numerator);
+        debug_assert!(
+            norm[1..].iter().all(|&c| c == B::ZERO),
+            "norm must be in the base field"
+        );
+        let denom_inv = norm[0].inv();
+
+        Self(<B as ExtensibleField<6>>::mul_base(numerator, denom_inv))
+    }
+
+    #[inline]
+    fn conjugate(&self) -> Self {
+        Self(<B as ExtensibleField<6>>::frobenius(self.0))
+    }
+
+    #[inline]
+    fn frobenius(&self) -> Self {
+        Self(<B as ExtensibleField<6>>::frobenius(self.0))
+    }
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        unsafe {
+            slice::from_raw_parts(
+                elements.as_ptr() as *const u8,
+                elements.len() * Self::ELEMENT_BYTES,
+            )
+        }
+    }
+
+    unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        if bytes.len() % Self::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of bytes ({}) does not divide into whole number of field elements",
+                bytes.len(),
+            )));
+        }
+
+        let p = bytes.as_ptr();
+        let len = bytes.len() / Self::ELEMENT_BYTES;
+
+        // make sure the bytes are aligned on the boundary consistent with base element alignment
+        if (p as usize) % Self::BaseField::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(
+                "slice memory alignment is not valid for this field element type".to_string(),
+            ));
+        }
+
+        Ok(slice::from_raw_parts(p as *const Self, len))
+    }
+
+    fn zeroed_vector(n: usize) -> Vec<Self> {
+        // get six times the number of base elements and re-interpret them as sextic field
+        // elements
+        let result = B::zeroed_vector(n * 6);
+        Self::base_to_sextic_vector(result)
+    }
+
+    fn as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+        let ptr = elements.as_ptr();
+        let len = elements.len() * 6;
+        unsafe { slice::from_raw_parts(ptr as *const Self::BaseField, len) }
+    }
+}
+
+impl<B: ExtensibleField<6>> ExtensionOf<B> for SexticExtension<B> {
+    #[inline(always)]
+    fn mul_base(self, other: B) -> Self {
+        Self(<B as ExtensibleField<6>>::mul_base(self.0, other))
+    }
+}
+
+impl<B: ExtensibleField<6>> Randomizable for SexticExtension<B> {
+    const VALUE_SIZE: usize = Self::ELEMENT_BYTES;
+
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::try_from(bytes).ok()
+    }
+}
+
+impl<B: ExtensibleField<6>> fmt::Display for SexticExtension<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = &self.0;
+        write!(
+            f,
+            "({}, {}, {}, {}, {}, {})",
+            c[0], c[1], c[2], c[3], c[4], c[5]
+        )
+    }
+}
+
+// OVERLOADED OPERATORS
+// ------------------------------------------------------------------------------------------------
+
+impl<B: ExtensibleField<6>> Add for SexticExtension<B> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        let mut result = self.0;
+        for (r, &b) in result.iter_mut().zip(rhs.0.iter()) {
+            *r += b;
+        }
+        Self(result)
+    }
+}
+
+impl<B: ExtensibleField<6>> AddAssign for SexticExtension<B> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl<B: ExtensibleField<6>> Sub for SexticExtension<B> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        let mut result = self.0;
+        for (r, &b) in result.iter_mut().zip(rhs.0.iter()) {
+            *r -= b;
+        }
+        Self(result)
+    }
+}
+
+impl<B: ExtensibleField<6>> SubAssign for SexticExtension<B> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<B: ExtensibleField<6>> Mul for SexticExtension<B> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self(<B as ExtensibleField<6>>::mul(self.0, rhs.0))
+    }
+}
+
+impl<B: ExtensibleField<6>> MulAssign for SexticExtension<B> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl<B: ExtensibleField<6>> Div for SexticExtension<B> {
+    type Output = Self;
+
+    #[inline]
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl<B: ExtensibleField<6>> DivAssign for SexticExtension<B> {
+    #[inline]
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl<B: ExtensibleField<6>> Neg for SexticExtension<B> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        let mut result = self.0;
+        for r in result.iter_mut() {
+            *r = -*r;
+        }
+        Self(result)
+    }
+}
+
+// TYPE CONVERSIONS
+// ------------------------------------------------------------------------------------------------
+
+impl<B: ExtensibleField<6>> From<B> for SexticExtension<B> {
+    fn from(value: B) -> Self {
+        Self([value, B::ZERO, B::ZERO, B::ZERO, B::ZERO, B::ZERO])
+    }
+}
+
+impl<B: ExtensibleField<6>> From<u128> for SexticExtension<B> {
+    fn from(value: u128) -> Self {
+        Self::from(B::from(value))
+    }
+}
+
+impl<B: ExtensibleField<6>> From<u64> for SexticExtension<B> {
+    fn from(value: u64) -> Self {
+        Self::from(B::from(value))
+    }
+}
+
+impl<B: ExtensibleField<6>> From<u32> for SexticExtension<B> {
+    fn from(value: u32) -> Self {
+        Self::from(B::from(value))
+    }
+}
+
+impl<B: ExtensibleField<6>> From<u16> for SexticExtension<B> {
+    fn from(value: u16) -> Self {
+        Self::from(B::from(value))
+    }
+}
+
+impl<B: ExtensibleField<6>> From<u8> for SexticExtension<B> {
+    fn from(value: u8) -> Self {
+        Self::from(B::from(value))
+    }
+}
+
+impl<'a, B: ExtensibleField<6>> TryFrom<&'a [u8]> for SexticExtension<B> {
+    type Error = DeserializationError;
+
+    /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
+    /// is not a valid field element. The bytes are assumed to be in little-endian byte order.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < Self::ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "not enough bytes for a full field element; expected {} bytes, but was {} bytes",
+                Self::ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        if bytes.len() > Self::ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "too many bytes for a field element; expected {} bytes, but was {} bytes",
+                Self::ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        let mut reader = SliceReader::new(bytes);
+        Self::read_from(&mut reader)
+    }
+}
+
+impl<B: ExtensibleField<6>> AsBytes for SexticExtension<B> {
+    fn as_bytes(&self) -> &[u8] {
+        // TODO: take endianness into account
+        let self_ptr: *const Self = self;
+        unsafe { slice::from_raw_parts(self_ptr as *const u8, Self::ELEMENT_BYTES) }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl<B: ExtensibleField<6>> Serializable for SexticExtension<B> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        for value in self.0.iter() {
+            value.write_into(target);
+        }
+    }
+}
+
+impl<B: ExtensibleField<6>> Deserializable for SexticExtension<B> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let mut result = [B::ZERO; 6];
+        for value in result.iter_mut() {
+            *value = B::read_from(source)?;
+        }
+        Ok(Self(result))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Deserializable, DeserializationError, FieldElement, Serializable, SexticExtension,
+        SliceReader, Vec,
+    };
+    use crate::field::f62::BaseElement;
+    use rand_utils::rand_value;
+
+    // BASIC ALGEBRA
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn add() {
+        // identity
+        let r: SexticExtension<BaseElement> = rand_value();
+        assert_eq!(r, r + SexticExtension::<BaseElement>::ZERO);
+
+        // test random values
+        let r1: SexticExtension<BaseElement> = rand_value();
+        let r2: SexticExtension<BaseElement> = rand_value();
+
+        let mut expected = r1.0;
+        for (e, &b) in expected.iter_mut().zip(r2.0.iter()) {
+            *e = *e + b;
+        }
+        assert_eq!(SexticExtension(expected), r1 + r2);
+    }
+
+    #[test]
+    fn sub() {
+        // identity
+        let r: SexticExtension<BaseElement> = rand_value();
+        assert_eq!(r, r - SexticExtension::<BaseElement>::ZERO);
+
+        // test random values
+        let r1: SexticExtension<BaseElement> = rand_value();
+        let r2: SexticExtension<BaseElement> = rand_value();
+
+        let mut expected = r1.0;
+        for (e, &b) in expected.iter_mut().zip(r2.0.iter()) {
+            *e = *e - b;
+        }
+        assert_eq!(SexticExtension(expected), r1 - r2);
+        assert_eq!(SexticExtension::<BaseElement>::ZERO, r1 + (-r1));
+    }
+
+    // INITIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn zeroed_vector() {
+        let result = SexticExtension::<BaseElement>::zeroed_vector(4);
+        assert_eq!(4, result.len());
+        for element in result.into_iter() {
+            assert_eq!(SexticExtension::<BaseElement>::ZERO, element);
+        }
+    }
+
+    // TYPE CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn base_element_conversions() {
+        // base element -> extension element -> base element round trip
+        let r: BaseElement = rand_value();
+        let e = SexticExtension::<BaseElement>::from(r);
+        assert_eq!(Some(r), e.to_base_element());
+        assert_eq!(Ok(r), BaseElement::try_from(e));
+
+        // elements which do not belong to the base field cannot be converted
+        for i in 1..6 {
+            let mut coefficients = [BaseElement::ZERO; 6];
+            coefficients[0] = r;
+            coefficients[i] = BaseElement::ONE;
+            let e = SexticExtension(coefficients);
+            assert_eq!(None, e.to_base_element());
+            assert!(BaseElement::try_from(e).is_err());
+        }
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn elements_as_bytes() {
+        let source = build_elements();
+
+        // elements are laid out in memory as consecutive base elements
+        let base_elements = SexticExtension::<BaseElement>::as_base_elements(&source);
+        let expected = BaseElement::elements_as_bytes(base_elements);
+        assert_eq!(96, expected.len());
+
+        assert_eq!(
+            expected,
+            SexticExtension::<BaseElement>::elements_as_bytes(&source)
+        );
+    }
+
+    #[test]
+    fn bytes_as_elements() {
+        let expected = build_elements();
+        let mut bytes = SexticExtension::<BaseElement>::elements_as_bytes(&expected).to_vec();
+        bytes.push(7);
+
+        let result = unsafe { SexticExtension::<BaseElement>::bytes_as_elements(&bytes[..96]) };
+        assert!(result.is_ok());
+        assert_eq!(expected, result.unwrap());
+
+        let result = unsafe { SexticExtension::<BaseElement>::bytes_as_elements(&bytes) };
+        assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+
+        let result = unsafe { SexticExtension::<BaseElement>::bytes_as_elements(&bytes[1..]) };
+        assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+    }
+
+    #[test]
+    fn serialization_round_trip() {
+        let source = build_elements();
+        let mut bytes = Vec::new();
+        source.write_into(&mut bytes);
+        assert_eq!(96, bytes.len());
+
+        let mut reader = SliceReader::new(&bytes);
+        let result = SexticExtension::<BaseElement>::read_batch_from(&mut reader, 2).unwrap();
+        assert_eq!(source, result);
+    }
+
+    // UTILITIES
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn as_base_elements() {
+        let elements = build_elements();
+        let expected = (1..13).map(BaseElement::new).collect::<Vec<_>>();
+
+        assert_eq!(
+            expected,
+            SexticExtension::<BaseElement>::as_base_elements(&elements)
+        );
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn build_elements() -> Vec<SexticExtension<BaseElement>> {
+        let base = (1..13).map(BaseElement::new).collect::<Vec<_>>();
+        base.chunks(6)
+            .map(|chunk| SexticExtension::new(chunk.try_into().unwrap()))
+            .collect()
+    }
+}
@@ -18,6 +18,7 @@ use core::{
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
     slice,
 };
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 use utils::{
     collections::Vec,
     string::{String, ToString},
@@ -156,6 +157,21 @@ impl StarkField for BaseElement {
     fn as_int(&self) -> Self::PositiveInteger {
         self.0
     }
+
+    #[inline]
+    fn from_canonical(value: Self::PositiveInteger) -> Self {
+        Self::new(value)
+    }
+
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+
+    #[inline]
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(u128::conditional_select(&a.0, &b.0, choice))
+    }
 }
 
 impl Randomizable for BaseElement {
@@ -7,7 +7,10 @@ use super::{
     AsBytes, BaseElement, ByteReader, Deserializable, DeserializationError, FieldElement,
     StarkField, M,
 };
-use crate::field::{ExtensionOf, QuadExtension};
+use crate::{
+    field::{ExtensionOf, QuadExtension},
+    Choice,
+};
 use core::convert::TryFrom;
 use num_bigint::BigUint;
 use rand_utils::{rand_value, rand_vector};
@@ -122,6 +125,42 @@ fn conjugate() {
     assert_eq!(a, b);
 }
 
+#[test]
+fn ct_eq() {
+    assert!(bool::from(BaseElement::ONE.ct_eq(&BaseElement::new(M + 1))));
+    assert!(!bool::from(BaseElement::ONE.ct_eq(&BaseElement::ZERO)));
+
+    // constant-time comparison agrees with regular comparison
+    for _ in 0..100 {
+        let a: BaseElement = rand_value();
+        let b: BaseElement = rand_value();
+        assert_eq!(a == b, bool::from(a.ct_eq(&b)));
+        assert!(bool::from(a.ct_eq(&BaseElement::new(a.as_int()))));
+    }
+}
+
+#[test]
+fn conditional_select() {
+    let a: BaseElement = rand_value();
+    let b: BaseElement = rand_value();
+    assert_eq!(a, BaseElement::conditional_select(&a, &b, Choice::from(0)));
+    assert_eq!(b, BaseElement::conditional_select(&a, &b, Choice::from(1)));
+}
+
+#[test]
+fn montgomery_conversions() {
+    let a = BaseElement::from(5u8);
+    assert_eq!(5, a.to_canonical());
+    assert_eq!(a, BaseElement::from_canonical(5));
+    assert_eq!(247390116249595, a.to_montgomery());
+    assert_eq!(a, BaseElement::from_montgomery(a.to_montgomery()));
+
+    let a: BaseElement = rand_value();
+    assert_eq!(a.as_int(), a.to_canonical());
+    assert_eq!(a, BaseElement::from_canonical(a.to_canonical()));
+    assert_eq!(a, BaseElement::from_montgomery(a.to_montgomery()));
+}
+
 // ROOTS OF UNITY
 // ================================================================================================
 
@@ -140,12 +179,68 @@ fn get_root_of_unity() {
     assert_eq!(BaseElement::ONE, root_39.exp(u128::pow(2, 39)));
 }
 
+#[test]
+fn get_root_of_unity_is_primitive() {
+    for n in 1..=BaseElement::TWO_ADICITY {
+        let root = BaseElement::get_root_of_unity(n);
+        assert_eq!(BaseElement::ONE, root.exp(1u128 << n));
+
+        // the order of the root is exactly 2^n
+        assert_eq!(-BaseElement::ONE, root.exp(1u128 << (n - 1)));
+    }
+}
+
+#[test]
+#[should_panic(expected = "exceeds two-adicity")]
+fn get_root_of_unity_exceeds_two_adicity() {
+    BaseElement::get_root_of_unity(BaseElement::TWO_ADICITY + 1);
+}
+
 #[test]
 fn test_g_is_2_exp_40_root() {
     let g = BaseElement::TWO_ADIC_ROOT_OF_UNITY;
     assert_eq!(g.exp(1u128 << 40), BaseElement::ONE);
 }
 
+// SQUARE ROOTS
+// ================================================================================================
+
+#[test]
+fn sqrt() {
+    // zero is a square of itself
+    assert!(BaseElement::ZERO.is_quadratic_residue());
+    assert_eq!(Some(BaseElement::ZERO), BaseElement::ZERO.sqrt());
+    assert_eq!(Some(BaseElement::ONE), BaseElement::ONE.sqrt());
+
+    for _ in 0..100 {
+        let r: BaseElement = rand_value();
+        let x = r.square();
+        assert!(x.is_quadratic_residue());
+
+        let root = x.sqrt().unwrap();
+        assert_eq!(x, root.square());
+        assert!(root == r || root == -r);
+    }
+}
+
+#[test]
+fn sqrt_non_residue() {
+    // the generator of the multiplicative group is not a square, and neither is a product of
+    // a non-zero square and a non-residue
+    assert!(!BaseElement::GENERATOR.is_quadratic_residue());
+    assert_eq!(None, BaseElement::GENERATOR.sqrt());
+
+    for _ in 0..100 {
+        let r: BaseElement = rand_value();
+        if r == BaseElement::ZERO {
+            continue;
+        }
+        let x = r.square() * BaseElement::GENERATOR;
+        assert!(!x.is_quadratic_residue());
+        assert_eq!(None, x.sqrt());
+    }
+}
+
 // FIELD EXTENSIONS
 // ================================================================================================
 
@@ -19,19 +19,22 @@ use utils::{
 
 use ff::{Field, PrimeField};
 
+#[cfg(feature = "std")]
 use serde::{Serialize, Deserialize};
+#[cfg(feature = "std")]
 use serde_hex::{SerHex, Strict};
 
 // FIELD ELEMENT
 // ================================================================================================
 
 // Note that the internal representation of Fr is assumed to be in Montgomery form with R=2^256
-#[derive(PrimeField, Serialize, Deserialize)]
+#[derive(PrimeField)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[PrimeFieldModulus = "3618502788666131213697322783095070105623107215331596699973092056135872020481"]
 #[PrimeFieldGenerator = "3"]
 #[PrimeFieldReprEndianness = "little"]
 struct Fr(
-    #[serde(with = "SerHex::<Strict>")]
+    #[cfg_attr(feature = "std", serde(with = "SerHex::<Strict>"))]
     [u64; 4]
 );
 
@@ -43,7 +46,8 @@ const ELEMENT_BYTES: usize = core::mem::size_of::<Fr>();
 pub struct BigInt(pub [u64; 4]);
 
 // Represents a base field element, using Fr as the backing type.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct BaseElement(Fr);
 
 impl FieldElement for BaseElement {
@@ -142,6 +146,22 @@ impl StarkField for BaseElement {
     fn as_int(&self) -> Self::PositiveInteger {
         self.0.to_raw()
     }
+
+    /// Convert into Montgomery form
+    #[inline]
+    fn from_canonical(value: Self::PositiveInteger) -> Self {
+        Self::from_raw(value.0)
+    }
+
+    #[inline]
+    fn to_montgomery(&self) -> Self::PositiveInteger {
+        BigInt((self.0).0)
+    }
+
+    #[inline]
+    fn from_montgomery(value: Self::PositiveInteger) -> Self {
+        BaseElement(Fr(value.0))
+    }
 }
 
 impl Randomizable for BaseElement {
@@ -229,13 +249,25 @@ impl Neg for BaseElement {
 // ================================================================================================
 
 /// Defines a quadratic extension of the base field over an irreducible polynomial x<sup>2</sup> -
-/// x - 1. Thus, an extension element is defined as α + β * φ, where φ is a root of this polynomial,
+/// 3. Thus, an extension element is defined as α + β * φ, where φ is a root of this polynomial,
 /// and α and β are base field elements.
+///
+/// The polynomial is irreducible because 3 is a generator of the multiplicative group of the
+/// base field, and thus, is not a quadratic residue:
+///
+/// sage: GF(MODULUS)(3).is_square() \
+/// False
 impl ExtensibleField<2> for BaseElement {
     #[inline(always)]
     fn mul(a: [Self; 2], b: [Self; 2]) -> [Self; 2] {
-        let z = a[0] * b[0];
-        [z + a[1] * b[1], (a[0] + a[1]) * (b[0] + b[1]) - z]
+        // performs multiplication in the extension field using 3 multiplications in the base
+        // field; multiplication by 3 is performed via additions.
+        let a0b0 = a[0] * b[0];
+        let a1b1 = a[1] * b[1];
+        [
+            a0b0 + a1b1.double() + a1b1,
+            (a[0] + a[1]) * (b[0] + b[1]) - a0b0 - a1b1,
+        ]
     }
 
     #[inline(always)]
@@ -245,34 +277,114 @@ impl ExtensibleField<2> for BaseElement {
 
     #[inline(always)]
     fn frobenius(x: [Self; 2]) -> [Self; 2] {
-        [x[0] + x[1], Self::ZERO - x[1]]
+        // φ^p = -φ since φ^(p - 1) = 3^((p - 1) / 2) = -1
+        [x[0], -x[1]]
     }
 }
 
 // CUBIC EXTENSION
 // ================================================================================================
 
-/// Cubic extension for this field is not implemented as quadratic extension already provides
-/// sufficient security level.
+/// Defines a cubic extension of the base field over an irreducible polynomial x<sup>3</sup> +
+/// x - 1. Thus, an extension element is defined as α + β * φ + γ * φ^2, where φ is a root of this
+/// polynomial, and α, β and γ are base field elements.
+///
+/// Since MODULUS = 2 mod 3, every base field element is a cube, and thus, no binomial of the
+/// form x<sup>3</sup> - c is irreducible over the base field:
+///
+/// sage: R.<x> = GF(MODULUS)[] \
+/// sage: (x^3 + x - 1).is_irreducible() \
+/// True
 impl ExtensibleField<3> for BaseElement {
-    fn mul(_a: [Self; 3], _b: [Self; 3]) -> [Self; 3] {
-        unimplemented!()
-    }
-
     #[inline(always)]
-    fn mul_base(_a: [Self; 3], _b: Self) -> [Self; 3] {
-        unimplemented!()
+    fn mul(a: [Self; 3], b: [Self; 3]) -> [Self; 3] {
+        // performs multiplication in the extension field using 6 multiplications in the base
+        // field; the product is then reduced using φ^3 = 1 - φ and φ^4 = φ - φ^2.
+        let a0b0 = a[0] * b[0];
+        let a1b1 = a[1] * b[1];
+        let a2b2 = a[2] * b[2];
+
+        let a0b1_a1b0 = (a[0] + a[1]) * (b[0] + b[1]) - a0b0 - a1b1;
+        let a0b2_a2b0 = (a[0] + a[2]) * (b[0] + b[2]) - a0b0 - a2b2;
+        let a1b2_a2b1 = (a[1] + a[2]) * (b[1] + b[2]) - a1b1 - a2b2;
+
+        [
+            a0b0 + a1b2_a2b1,
+            a0b1_a1b0 - a1b2_a2b1 + a2b2,
+            a0b2_a2b0 + a1b1 - a2b2,
+        ]
     }
 
     #[inline(always)]
-    fn frobenius(_x: [Self; 3]) -> [Self; 3] {
-        unimplemented!()
+    fn mul_base(a: [Self; 3], b: Self) -> [Self; 3] {
+        [a[0] * b, a[1] * b, a[2] * b]
     }
 
-    fn is_supported() -> bool {
-        false
-    }
-}
+    #[inline(always)]
+    fn frobenius(x: [Self; 3]) -> [Self; 3] {
+        // φ^p and φ^(2p) reduced modulo x^3 + x - 1; coefficients were computed using SageMath
+        [
+            x[0] + CUBE_FROBENIUS_1[0] * x[1] + CUBE_FROBENIUS_2[0] * x[2],
+            CUBE_FROBENIUS_1[1] * x[1] + CUBE_FROBENIUS_2[1] * x[2],
+            CUBE_FROBENIUS_1[2] * x[1] + CUBE_FROBENIUS_2[2] * x[2],
+        ]
+    }
+}
+
+/// Coefficients of φ^p in the cubic extension (in Montgomery form):
+///
+/// sage: F.<phi> = GF(MODULUS^3, modulus=x^3 + x - 1) \
+/// sage: (phi^MODULUS).polynomial().list() \
+/// [2854926965201963198849669180022354183652393807379012118198801093063539395430,
+///  2805082883038285983714432871955226807594778851271180565974210403257091619236,
+///  663887659136813584577180986938461169855483495736921477325109583459437072664]
+const CUBE_FROBENIUS_1: [BaseElement; 3] = [
+    BaseElement(Fr([
+        0x8376_0858_29e6_c7e2,
+        0x1146_1914_29f5_7c23,
+        0xf7b6_f848_c604_db85,
+        0x00db_f307_dfa1_c44c,
+    ])),
+    BaseElement(Fr([
+        0xe7c9_92c6_5e47_41cd,
+        0x66dd_b86d_5e68_574f,
+        0xad5b_aea3_bd8a_edeb,
+        0x05ee_e2d1_b72b_fac5,
+    ])),
+    BaseElement(Fr([
+        0x4531_0c84_3eda_2bd3,
+        0x99e9_259e_3ef0_3a35,
+        0x7392_746d_2907_4947,
+        0x0149_ec8b_cf72_a673,
+    ])),
+];
+
+/// Coefficients of φ^(2p) in the cubic extension (in Montgomery form):
+///
+/// sage: (phi^(2 * MODULUS)).polynomial().list() \
+/// [2954615129529317629120141796156608935767623719594675222647982472676434947816,
+///  1427463482600981599424834590011177091826196903689506059099400546531769697715,
+///  813419905627845229982889911139843298028328364060416133998881652878780401244]
+const CUBE_FROBENIUS_2: [BaseElement; 3] = [
+    BaseElement(Fr([
+        0xbace_f37b_c125_d44e,
+        0x6616_da61_c10f_c5ca,
+        0x8c6d_8b92_d6f8_b6b8,
+        0x06b6_1374_308d_5bbd,
+    ])),
+    BaseElement(Fr([
+        0xc1bb_042c_14f3_63f1,
+        0x88a3_0c8a_14fa_be11,
+        0x7bdb_7c24_6302_6dc2,
+        0x006d_f983_efd0_e226,
+    ])),
+    BaseElement(Fr([
+        0x1836_6d39_a1b8_be54,
+        0x9922_4792_a197_a8b0,
+        0x52a4_515c_4275_1214,
+        0x0211_1d2e_48d4_076b,
+    ])),
+];
 
 // TYPE CONVERSIONS
 // ================================================================================================
@@ -600,6 +712,9 @@ fn write_le_bytes(value: [u64; 4], out: &mut [u8]) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::field::{CubeExtension, QuadExtension};
+    use rand_utils::rand_value;
+    use utils::SliceReader;
 
     #[test]
     fn as_int() {
@@ -607,4 +722,205 @@ mod tests {
         let b: u64 = a.as_int().try_into().unwrap();
         assert_eq!(b, 3);
     }
+
+    #[test]
+    fn montgomery_conversions() {
+        let a = BaseElement::from(5u8);
+        assert_eq!(BigInt([5, 0, 0, 0]), a.to_canonical());
+        assert_eq!(a, BaseElement::from_canonical(BigInt([5, 0, 0, 0])));
+        // 5 * 2^256 mod M
+        let expected = BigInt([
+            0xffff_ffff_ffff_ff61,
+            0xffff_ffff_ffff_ffff,
+            0xffff_ffff_ffff_ffff,
+            0x7ff_ffff_ffff_f570,
+        ]);
+        assert_eq!(expected, a.to_montgomery());
+        assert_eq!(a, BaseElement::from_montgomery(expected));
+
+        let a = BaseElement::from(u128::MAX);
+        assert_eq!(a, BaseElement::from_canonical(a.to_canonical()));
+        assert_eq!(a, BaseElement::from_montgomery(a.to_montgomery()));
+    }
+
+    #[test]
+    fn get_root_of_unity_is_primitive() {
+        for n in 1..=BaseElement::TWO_ADICITY {
+            let root = BaseElement::get_root_of_unity(n);
+            assert_eq!(BaseElement::ONE, root.exp(BigInt::from(1u32) << n));
+
+            // the order of the root is exactly 2^n
+            assert_eq!(-BaseElement::ONE, root.exp(BigInt::from(1u32) << (n - 1)));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds two-adicity")]
+    fn get_root_of_unity_exceeds_two_adicity() {
+        BaseElement::get_root_of_unity(BaseElement::TWO_ADICITY + 1);
+    }
+
+    // SQUARE ROOTS
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn sqrt() {
+        // zero is a square of itself
+        assert!(BaseElement::ZERO.is_quadratic_residue());
+        assert_eq!(Some(BaseElement::ZERO), BaseElement::ZERO.sqrt());
+        assert_eq!(Some(BaseElement::ONE), BaseElement::ONE.sqrt());
+
+        for _ in 0..20 {
+            let r: BaseElement = rand_value();
+            let x = r.square();
+            assert!(x.is_quadratic_residue());
+
+            let root = x.sqrt().unwrap();
+            assert_eq!(x, root.square());
+            assert!(root == r || root == -r);
+        }
+    }
+
+    #[test]
+    fn sqrt_non_residue() {
+        // the generator of the multiplicative group is not a square, and neither is a product of
+        // a non-zero square and a non-residue
+        assert!(!BaseElement::GENERATOR.is_quadratic_residue());
+        assert_eq!(None, BaseElement::GENERATOR.sqrt());
+
+        for _ in 0..20 {
+            let r: BaseElement = rand_value();
+            if r == BaseElement::ZERO {
+                continue;
+            }
+            let x = r.square() * BaseElement::GENERATOR;
+            assert!(!x.is_quadratic_residue());
+            assert_eq!(None, x.sqrt());
+        }
+    }
+
+    #[test]
+    fn bytes_be_round_trip() {
+        let a = BaseElement::from(u128::MAX) * BaseElement::from(7u8);
+        let bytes_le = a.to_bytes();
+        let bytes_be = a.to_bytes_be();
+        assert_eq!(BaseElement::ELEMENT_BYTES, bytes_be.len());
+        assert_eq!(bytes_le.iter().rev().copied().collect::<Vec<_>>(), bytes_be);
+
+        // little-endian and big-endian encodings are both exact inverses of decoding
+        assert_eq!(a, BaseElement::from_bytes_be(&bytes_be).unwrap());
+        assert_eq!(a, BaseElement::read_from(&mut SliceReader::new(&bytes_le)).unwrap());
+
+        assert!(BaseElement::from_bytes_be(&bytes_be[1..]).is_err());
+    }
+
+    // QUADRATIC EXTENSION
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn quad_mul_inv() {
+        // x^2 - 3
+        let modulus = [-BaseElement::from(3u8), BaseElement::ZERO, BaseElement::ONE];
+
+        for _ in 0..100 {
+            let a: QuadExtension<BaseElement> = rand_value();
+            let b: QuadExtension<BaseElement> = rand_value();
+
+            let expected = reference_mul(
+                QuadExtension::as_base_elements(&[a]),
+                QuadExtension::as_base_elements(&[b]),
+                &modulus,
+            );
+            assert_eq!(expected, QuadExtension::as_base_elements(&[a * b]));
+
+            let a_inv = a.inv();
+            let expected = reference_mul(
+                QuadExtension::as_base_elements(&[a]),
+                QuadExtension::as_base_elements(&[a_inv]),
+                &modulus,
+            );
+            assert_eq!(vec![BaseElement::ONE, BaseElement::ZERO], expected);
+            assert_eq!(<QuadExtension<BaseElement>>::ONE, a * a_inv);
+        }
+    }
+
+    #[test]
+    fn quad_frobenius() {
+        let a: QuadExtension<BaseElement> = rand_value();
+        assert_eq!(a.exp(BaseElement::MODULUS), a.frobenius());
+        assert_eq!(a, a.frobenius().frobenius());
+    }
+
+    // CUBIC EXTENSION
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn cube_mul_inv() {
+        // x^3 + x - 1
+        let modulus = [
+            -BaseElement::ONE,
+            BaseElement::ONE,
+            BaseElement::ZERO,
+            BaseElement::ONE,
+        ];
+
+        for _ in 0..100 {
+            let a: CubeExtension<BaseElement> = rand_value();
+            let b: CubeExtension<BaseElement> = rand_value();
+
+            let expected = reference_mul(
+                CubeExtension::as_base_elements(&[a]),
+                CubeExtension::as_base_elements(&[b]),
+                &modulus,
+            );
+            assert_eq!(expected, CubeExtension::as_base_elements(&[a * b]));
+
+            let a_inv = a.inv();
+            let expected = reference_mul(
+                CubeExtension::as_base_elements(&[a]),
+                CubeExtension::as_base_elements(&[a_inv]),
+                &modulus,
+            );
+            assert_eq!(
+                vec![BaseElement::ONE, BaseElement::ZERO, BaseElement::ZERO],
+                expected
+            );
+            assert_eq!(<CubeExtension<BaseElement>>::ONE, a * a_inv);
+        }
+    }
+
+    #[test]
+    fn cube_frobenius() {
+        let a: CubeExtension<BaseElement> = rand_value();
+        assert_eq!(a.exp(BaseElement::MODULUS), a.frobenius());
+        assert_eq!(a, a.frobenius().frobenius().frobenius());
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Multiplies polynomials a and b using schoolbook multiplication, and then reduces the result
+    /// modulo the specified monic polynomial via long division.
+    fn reference_mul(
+        a: &[BaseElement],
+        b: &[BaseElement],
+        modulus: &[BaseElement],
+    ) -> Vec<BaseElement> {
+        let n = modulus.len() - 1;
+        let mut result = vec![BaseElement::ZERO; a.len() + b.len() - 1];
+        for (i, &a_i) in a.iter().enumerate() {
+            for (j, &b_j) in b.iter().enumerate() {
+                result[i + j] += a_i * b_j;
+            }
+        }
+
+        for k in (n..result.len()).rev() {
+            let c = result[k];
+            for (i, &m) in modulus.iter().enumerate() {
+                result[k - n + i] -= c * m;
+            }
+        }
+        result.truncate(n);
+        result
+    }
 }
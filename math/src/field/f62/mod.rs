@@ -9,7 +9,7 @@
 //! fast modular arithmetic including branchless multiplication and addition. Base elements are
 //! stored in the Montgomery form using `u64` as the backing type.
 
-use super::{ExtensibleField, FieldElement, StarkField};
+use super::{ExtensibleField, FieldElement, QuarticTowerField, StarkField};
 use core::{
     convert::{TryFrom, TryInto},
     fmt::{Debug, Display, Formatter},
@@ -17,6 +17,7 @@ use core::{
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
     slice,
 };
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, ConstantTimeGreater};
 use utils::{
     collections::Vec, string::ToString, AsBytes, ByteReader, ByteWriter, Deserializable,
     DeserializationError, Randomizable, Serializable,
@@ -186,6 +187,34 @@ impl StarkField for BaseElement {
         // since the result of multiplication can be in [0, 2M), we need to normalize it
         normalize(result)
     }
+
+    #[inline]
+    fn from_canonical(value: Self::PositiveInteger) -> Self {
+        Self::new(value)
+    }
+
+    #[inline]
+    fn to_montgomery(&self) -> Self::PositiveInteger {
+        // the internal value is already in Montgomery representation, but can be in [0, 2M)
+        normalize(self.0)
+    }
+
+    #[inline]
+    fn from_montgomery(value: Self::PositiveInteger) -> Self {
+        Self(value)
+    }
+
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        // since either of the elements can be in [0, 2M) range, we normalize them first; the
+        // Montgomery representations of equal elements are equal after normalization
+        ct_normalize(self.0).ct_eq(&ct_normalize(other.0))
+    }
+
+    #[inline]
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(u64::conditional_select(&a.0, &b.0, choice))
+    }
 }
 
 impl Randomizable for BaseElement {
@@ -360,6 +389,123 @@ impl ExtensibleField<3> for BaseElement {
     }
 }
 
+// QUARTIC EXTENSION
+// ================================================================================================
+
+/// Defines a degree 4 extension of the base field as a quadratic extension of the quadratic
+/// extension over an irreducible polynomial y<sup>2</sup> - ξ, where ξ = φ + 2. Thus, an extension
+/// element is defined as α + β * ψ, where ψ is a root of this polynomial, and α and β are elements
+/// of the quadratic extension.
+///
+/// The polynomial is irreducible because the norm of ξ is 2^2 + 2 - 1 = 5, which is not a
+/// quadratic residue in the base field, and thus, ξ is not a square in the quadratic extension.
+impl QuarticTowerField for BaseElement {
+    const QUARTIC_NON_RESIDUE: [Self; 2] = [BaseElement::new(2), BaseElement::new(1)];
+
+    const QUARTIC_FROBENIUS: [Self; 2] = [BaseElement::new(M - 1), BaseElement::new(1)];
+}
+
+// SEXTIC EXTENSION
+// ================================================================================================
+
+/// Coefficients of φ^(i * M) for i in 1..6 expressed in the basis 1, φ, ..., φ^5 of the degree 6
+/// extension; these are used to compute Frobenius automorphisms. The coefficients were computed
+/// using Python.
+const SEXTIC_FROBENIUS: [[BaseElement; 6]; 5] = [
+    [
+        BaseElement::new(1957039043350359852),
+        BaseElement::new(3891951276096073298),
+        BaseElement::new(3958801231391166738),
+        BaseElement::new(3311363441305550973),
+        BaseElement::new(973878562356216065),
+        BaseElement::new(3185503142618023782),
+    ],
+    [
+        BaseElement::new(1587721607858698272),
+        BaseElement::new(3438172435527498103),
+        BaseElement::new(3659168289820941930),
+        BaseElement::new(125540787167925937),
+        BaseElement::new(3788731176902792349),
+        BaseElement::new(3628684065208017678),
+    ],
+    [
+        BaseElement::new(305733045069433943),
+        BaseElement::new(2713584443355955397),
+        BaseElement::new(1832456972587468147),
+        BaseElement::new(3506104534475058124),
+        BaseElement::new(1680327387380607370),
+        BaseElement::new(3322420342342316338),
+    ],
+    [
+        BaseElement::new(4505569289575261000),
+        BaseElement::new(3407135134494031268),
+        BaseElement::new(470752821003221164),
+        BaseElement::new(3448882507451089374),
+        BaseElement::new(2903966843859827337),
+        BaseElement::new(3816566843573779474),
+    ],
+    [
+        BaseElement::new(3179679961734498958),
+        BaseElement::new(4174259560625223222),
+        BaseElement::new(4347366313885310853),
+        BaseElement::new(941490907406027981),
+        BaseElement::new(4584445880593746090),
+        BaseElement::new(4485309037876284658),
+    ],
+];
+
+/// Defines a degree 6 extension of the base field over an irreducible polynomial x<sup>6</sup> -
+/// x - 4. Thus, an extension element is defined as a_0 + a_1 * φ + ... + a_5 * φ^5, where φ is a
+/// root of this polynomial, and a_0, ..., a_5 are base field elements.
+///
+/// Since M = 2 mod 3, binomials of degree 6 are reducible over the base field; thus, a trinomial
+/// is used instead.
+impl ExtensibleField<6> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 6], b: [Self; 6]) -> [Self; 6] {
+        // compute the product of a and b as polynomials of degree 5
+        let mut c = [Self::ZERO; 11];
+        for (i, &ai) in a.iter().enumerate() {
+            for (j, &bj) in b.iter().enumerate() {
+                c[i + j] += ai * bj;
+            }
+        }
+
+        // reduce the product using φ^k = φ^(k - 5) + 4 * φ^(k - 6) for k >= 6; none of the
+        // reduced terms have degree greater than 5, and thus, a single pass is sufficient
+        let mut result = [c[0], c[1], c[2], c[3], c[4], c[5]];
+        for (k, &ck) in c.iter().enumerate().skip(6) {
+            result[k - 5] += ck;
+            result[k - 6] += ck.double().double();
+        }
+        result
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 6], b: Self) -> [Self; 6] {
+        [a[0] * b, a[1] * b, a[2] * b, a[3] * b, a[4] * b, a[5] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 6]) -> [Self; 6] {
+        // (sum x_i * φ^i)^M = sum x_i * φ^(i * M) since x_i are in the base field
+        let mut result = [
+            x[0],
+            Self::ZERO,
+            Self::ZERO,
+            Self::ZERO,
+            Self::ZERO,
+            Self::ZERO,
+        ];
+        for (&xi, row) in x.iter().skip(1).zip(SEXTIC_FROBENIUS.iter()) {
+            for (r, &coeff) in result.iter_mut().zip(row.iter()) {
+                *r += xi * coeff;
+            }
+        }
+        result
+    }
+}
+
 // TYPE CONVERSIONS
 // ================================================================================================
 
@@ -586,3 +732,10 @@ fn normalize(value: u64) -> u64 {
         value
     }
 }
+
+/// Reduces any value in [0, 2M) range to [0, M) range without branching on the value.
+#[inline(always)]
+fn ct_normalize(value: u64) -> u64 {
+    let is_reduced = M.ct_gt(&value);
+    u64::conditional_select(&value.wrapping_sub(M), &value, is_reduced)
+}
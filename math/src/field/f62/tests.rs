@@ -4,7 +4,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{AsBytes, BaseElement, DeserializationError, FieldElement, Serializable, StarkField};
-use crate::field::{CubeExtension, ExtensionOf, QuadExtension};
+use crate::field::{CubeExtension, ExtensionOf, QuadExtension, QuarticExtension, SexticExtension};
+use crate::Choice;
 use core::convert::TryFrom;
 use num_bigint::BigUint;
 use proptest::prelude::*;
@@ -90,6 +91,37 @@ fn exp() {
     assert_eq!(a.exp(3), a * a * a);
 }
 
+#[test]
+fn exp_const_time() {
+    let a = BaseElement::ZERO;
+    assert_eq!(a.exp_const_time(0), BaseElement::ONE);
+    assert_eq!(a.exp_const_time(1), BaseElement::ZERO);
+
+    let a: BaseElement = rand_value();
+    for power in [0, 1, 2, 3, 255, 1 << 32, super::M - 1, u64::MAX] {
+        assert_eq!(a.exp(power), a.exp_const_time(power));
+        assert_eq!(a.exp(power), a.exp_vartime(power));
+    }
+}
+
+#[test]
+fn exp_field() {
+    let a: BaseElement = rand_value();
+    let power: BaseElement = rand_value();
+    assert_eq!(a.exp(power.as_int()), a.exp_field(power));
+
+    // the exponent is reduced to its canonical value
+    let power = BaseElement::new(3 * super::M + 5);
+    assert_eq!(a.exp(5), a.exp_field(power));
+    assert_eq!(a, a.exp_field(BaseElement::new(super::M + 1)));
+
+    // elements of extension fields can be raised to powers in the base field
+    let b: QuadExtension<BaseElement> = rand_value();
+    assert_eq!(b.exp(power.as_int()), b.exp_field(power));
+    let c: CubeExtension<BaseElement> = rand_value();
+    assert_eq!(c.exp(power.as_int()), c.exp_field(power));
+}
+
 #[test]
 fn inv() {
     // identity
@@ -119,6 +151,47 @@ fn equals() {
     assert_ne!(a.as_bytes(), b.as_bytes());
 }
 
+#[test]
+fn ct_eq() {
+    let a = BaseElement::ONE;
+    let b = BaseElement::new(super::M - 1) * BaseElement::new(super::M - 1);
+
+    // elements with different internal representations are equal in constant time as well
+    assert_ne!(a.0, b.0);
+    assert!(bool::from(a.ct_eq(&b)));
+    assert!(!bool::from(a.ct_eq(&BaseElement::ZERO)));
+
+    // constant-time comparison agrees with regular comparison
+    for _ in 0..100 {
+        let a: BaseElement = rand_value();
+        let b: BaseElement = rand_value();
+        assert_eq!(a == b, bool::from(a.ct_eq(&b)));
+        assert!(bool::from(a.ct_eq(&(a + BaseElement::ZERO))));
+    }
+}
+
+#[test]
+fn conditional_select() {
+    let a: BaseElement = rand_value();
+    let b: BaseElement = rand_value();
+    assert_eq!(a, BaseElement::conditional_select(&a, &b, Choice::from(0)));
+    assert_eq!(b, BaseElement::conditional_select(&a, &b, Choice::from(1)));
+}
+
+#[test]
+fn montgomery_conversions() {
+    let a = BaseElement::from(5u8);
+    assert_eq!(5, a.to_canonical());
+    assert_eq!(a, BaseElement::from_canonical(5));
+    assert_eq!(1220457906831340, a.to_montgomery());
+    assert_eq!(a, BaseElement::from_montgomery(a.to_montgomery()));
+
+    let a: BaseElement = rand_value();
+    assert_eq!(a.as_int(), a.to_canonical());
+    assert_eq!(a, BaseElement::from_canonical(a.to_canonical()));
+    assert_eq!(a, BaseElement::from_montgomery(a.to_montgomery()));
+}
+
 // QUADRATIC EXTENSION
 // ------------------------------------------------------------------------------------------------
 
@@ -209,6 +282,211 @@ fn cube_mul_base() {
     assert_eq!(expected, a.mul_base(b0));
 }
 
+// QUARTIC EXTENSION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn quartic_mul() {
+    // identity
+    let r: QuarticExtension<BaseElement> = rand_value();
+    assert_eq!(
+        <QuarticExtension<BaseElement>>::ZERO,
+        r * <QuarticExtension<BaseElement>>::ZERO
+    );
+    assert_eq!(r, r * <QuarticExtension<BaseElement>>::ONE);
+
+    // ψ^4 = 5 * ψ^2 - 5 = 5 * φ + 5
+    let psi = build_quartic([0, 0, 1, 0]);
+    assert_eq!(build_quartic([5, 5, 0, 0]), psi.exp(4));
+
+    // test multiplication with overflow; the expected value was computed using Python via
+    // arithmetic modulo the degree 4 polynomial ψ^4 - 5 * ψ^2 + 5
+    let a = build_quartic([
+        4611624995532046322,
+        1390,
+        4611624995532037737,
+        2305812497766023171,
+    ]);
+    let b = build_quartic([
+        4611624995532046117,
+        2305812497765992657,
+        4611624995532046335,
+        17,
+    ]);
+    let expected = build_quartic([
+        2305812497723503806,
+        2305812497723342811,
+        3458718746650874134,
+        3458718746911377419,
+    ]);
+    assert_eq!(expected, a * b);
+    assert_eq!(expected, b * a);
+}
+
+#[test]
+fn quartic_mul_base() {
+    let a: QuarticExtension<BaseElement> = rand_value();
+    let b0 = rand_value();
+    let b = <QuarticExtension<BaseElement>>::from(b0);
+
+    let expected = a * b;
+    assert_eq!(expected, a.mul_base(b0));
+}
+
+#[test]
+fn quartic_inv() {
+    let r: QuarticExtension<BaseElement> = rand_value();
+    assert_eq!(<QuarticExtension<BaseElement>>::ONE, r * r.inv());
+    assert_eq!(
+        <QuarticExtension<BaseElement>>::ZERO,
+        <QuarticExtension<BaseElement>>::ZERO.inv()
+    );
+
+    // the expected value was computed using Python
+    let a = build_quartic([
+        4611624995532046322,
+        1390,
+        4611624995532037737,
+        2305812497766023171,
+    ]);
+    let expected = build_quartic([
+        4127007476090546562,
+        2930077742034384920,
+        194232713073050364,
+        1079263578164409153,
+    ]);
+    assert_eq!(expected, a.inv());
+}
+
+#[test]
+fn quartic_frobenius() {
+    let a: QuarticExtension<BaseElement> = rand_value();
+    assert_eq!(a.exp(super::M), a.frobenius());
+
+    // applying Frobenius automorphism 4 times yields the original element
+    assert_eq!(a, a.frobenius().frobenius().frobenius().frobenius());
+
+    // base field elements are fixed by Frobenius automorphism
+    let b = <QuarticExtension<BaseElement>>::from(rand_value::<BaseElement>());
+    assert_eq!(b, b.frobenius());
+
+    // the expected value was computed using Python
+    let a = build_quartic([
+        4611624995532046322,
+        1390,
+        4611624995532037737,
+        2305812497766023171,
+    ]);
+    let expected = build_quartic([1375, 4611624995532044947, 8595, 2305812497766014571]);
+    assert_eq!(expected, a.frobenius());
+}
+
+// SEXTIC EXTENSION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn sextic_mul() {
+    // identity
+    let r: SexticExtension<BaseElement> = rand_value();
+    assert_eq!(
+        <SexticExtension<BaseElement>>::ZERO,
+        r * <SexticExtension<BaseElement>>::ZERO
+    );
+    assert_eq!(r, r * <SexticExtension<BaseElement>>::ONE);
+
+    // φ^6 = φ + 4
+    let phi = build_sextic([0, 1, 0, 0, 0, 0]);
+    assert_eq!(build_sextic([4, 1, 0, 0, 0, 0]), phi.exp(6));
+
+    // test multiplication with overflow; the expected value was computed using Python
+    let a = build_sextic([
+        4611624995532046322,
+        1390,
+        4611624995532037737,
+        2305843009213693959,
+        42,
+        4611624995532046336,
+    ]);
+    let b = build_sextic([
+        4611624995532046117,
+        2305812497766022990,
+        4611624995532046335,
+        17,
+        4611624995531591792,
+        3,
+    ]);
+    let expected = build_sextic([
+        2074794077982112,
+        2170527216591799167,
+        2272227640139999046,
+        2299191513605721472,
+        1147459955481079904,
+        4611563972004733681,
+    ]);
+    assert_eq!(expected, a * b);
+    assert_eq!(expected, b * a);
+}
+
+#[test]
+fn sextic_mul_base() {
+    let a: SexticExtension<BaseElement> = rand_value();
+    let b0 = rand_value();
+    let b = <SexticExtension<BaseElement>>::from(b0);
+
+    let expected = a * b;
+    assert_eq!(expected, a.mul_base(b0));
+}
+
+#[test]
+fn sextic_frobenius() {
+    let a: SexticExtension<BaseElement> = rand_value();
+    assert_eq!(a.exp(super::M), a.frobenius());
+
+    // applying Frobenius automorphism 6 times yields the original element
+    let mut b = a;
+    for _ in 0..6 {
+        b = b.frobenius();
+    }
+    assert_eq!(a, b);
+
+    // the expected value was computed using Python
+    let a = build_sextic([
+        4611624995532046322,
+        1390,
+        4611624995532037737,
+        2305843009213693959,
+        42,
+        4611624995532046336,
+    ]);
+    let expected = build_sextic([
+        4098309899063219761,
+        2559083100440936366,
+        4335387657032363921,
+        2319171827918357638,
+        2148581461154372964,
+        2460628113975410407,
+    ]);
+    assert_eq!(expected, a.frobenius());
+}
+
+#[test]
+fn sextic_elements_as_bytes() {
+    let source = vec![
+        build_sextic([1, 2, 3, 4, 5, 6]),
+        build_sextic([7, 8, 9, 10, 11, 12]),
+    ];
+
+    let mut expected = vec![];
+    for i in 1..13 {
+        expected.extend_from_slice(&BaseElement::new(i).0.to_le_bytes());
+    }
+
+    assert_eq!(
+        expected,
+        SexticExtension::<BaseElement>::elements_as_bytes(&source)
+    );
+}
+
 // ROOTS OF UNITY
 // ------------------------------------------------------------------------------------------------
 
@@ -224,6 +502,23 @@ fn get_root_of_unity() {
     assert_eq!(BaseElement::ONE, root_38.exp(1u64 << 38));
 }
 
+#[test]
+fn get_root_of_unity_is_primitive() {
+    for n in 1..=BaseElement::TWO_ADICITY {
+        let root = BaseElement::get_root_of_unity(n);
+        assert_eq!(BaseElement::ONE, root.exp(1u64 << n));
+
+        // the order of the root is exactly 2^n
+        assert_eq!(-BaseElement::ONE, root.exp(1u64 << (n - 1)));
+    }
+}
+
+#[test]
+#[should_panic(expected = "exceeds two-adicity")]
+fn get_root_of_unity_exceeds_two_adicity() {
+    BaseElement::get_root_of_unity(BaseElement::TWO_ADICITY + 1);
+}
+
 // SERIALIZATION AND DESERIALIZATION
 // ------------------------------------------------------------------------------------------------
 
@@ -359,6 +654,12 @@ proptest! {
         prop_assert_eq!(expected, result.as_int());
     }
 
+    #[test]
+    fn exp_const_time_proptest(a in any::<u64>(), b in any::<u64>()) {
+        let a = BaseElement::from(a);
+        prop_assert_eq!(a.exp(b), a.exp_const_time(b));
+    }
+
     #[test]
     fn inv_proptest(a in any::<u64>()) {
         let a = BaseElement::from(a);
@@ -368,6 +669,14 @@ proptest! {
         prop_assert_eq!(expected, a * b);
     }
 
+    #[test]
+    fn ct_eq_proptest(a in any::<u64>(), b in any::<u64>()) {
+        let v1 = BaseElement::from(a);
+        let v2 = BaseElement::from(b);
+        prop_assert_eq!(v1 == v2, bool::from(v1.ct_eq(&v2)));
+        prop_assert!(bool::from(v1.ct_eq(&BaseElement::from(a))));
+    }
+
     #[test]
     fn element_as_int_proptest(a in any::<u64>()) {
         let e = BaseElement::new(a);
@@ -409,4 +718,33 @@ proptest! {
         };
         prop_assert_eq!(expected, a * b);
     }
+
+    // SEXTIC EXTENSION
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn sextic_mul_inv_proptest(a in prop::array::uniform6(any::<u64>())) {
+        let a = SexticExtension::<BaseElement>::new(a.map(BaseElement::from));
+        let b = a.inv();
+
+        let expected = if a == SexticExtension::<BaseElement>::ZERO {
+            SexticExtension::<BaseElement>::ZERO
+        } else {
+            SexticExtension::<BaseElement>::ONE
+        };
+        prop_assert_eq!(expected, a * b);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_quartic(values: [u64; 4]) -> QuarticExtension<BaseElement> {
+    QuarticExtension::new(
+        QuadExtension::new(BaseElement::new(values[0]), BaseElement::new(values[1])),
+        QuadExtension::new(BaseElement::new(values[2]), BaseElement::new(values[3])),
+    )
+}
+
+fn build_sextic(values: [u64; 6]) -> SexticExtension<BaseElement> {
+    SexticExtension::new(values.map(BaseElement::new))
 }
@@ -14,7 +14,7 @@
 //!
 //! Internally, the values are stored in the range $[0, 2^{64})$ using `u64` as the backing type.
 
-use super::{ExtensibleField, FieldElement, StarkField};
+use super::{ExtensibleField, FieldElement, QuarticTowerField, StarkField};
 use core::{
     convert::{TryFrom, TryInto},
     fmt::{Debug, Display, Formatter},
@@ -22,6 +22,7 @@ use core::{
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
     slice,
 };
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, ConstantTimeGreater};
 use utils::{
     collections::Vec, string::ToString, AsBytes, ByteReader, ByteWriter, Deserializable,
     DeserializationError, Randomizable, Serializable,
@@ -213,6 +214,21 @@ impl StarkField for BaseElement {
             self.0
         }
     }
+
+    #[inline]
+    fn from_canonical(value: Self::PositiveInteger) -> Self {
+        Self::new(value)
+    }
+
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        ct_normalize(self.0).ct_eq(&ct_normalize(other.0))
+    }
+
+    #[inline]
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(u64::conditional_select(&a.0, &b.0, choice))
+    }
 }
 
 impl Randomizable for BaseElement {
@@ -417,6 +433,23 @@ impl ExtensibleField<3> for BaseElement {
     }
 }
 
+// QUARTIC EXTENSION
+// ================================================================================================
+
+/// Defines a degree 4 extension of the base field as a quadratic extension of the quadratic
+/// extension over an irreducible polynomial y<sup>2</sup> - ξ, where ξ = φ + 3. Thus, an extension
+/// element is defined as α + β * ψ, where ψ is a root of this polynomial, and α and β are elements
+/// of the quadratic extension.
+///
+/// The polynomial is irreducible because the norm of ξ is 3^2 + 3 + 2 = 14, which is not a
+/// quadratic residue in the base field, and thus, ξ is not a square in the quadratic extension.
+impl QuarticTowerField for BaseElement {
+    const QUARTIC_NON_RESIDUE: [Self; 2] = [BaseElement::new(3), BaseElement::new(1)];
+
+    const QUARTIC_FROBENIUS: [Self; 2] =
+        [BaseElement::new(0), BaseElement::new(18446743519650381953)];
+}
+
 // TYPE CONVERSIONS
 // ================================================================================================
 
@@ -572,3 +605,10 @@ fn exp_acc<const N: usize>(base: BaseElement, tail: BaseElement) -> BaseElement
     }
     result * tail
 }
+
+/// Reduces any value in [0, 2^64) range to [0, M) range without branching on the value.
+#[inline(always)]
+fn ct_normalize(value: u64) -> u64 {
+    let is_reduced = M.ct_gt(&value);
+    u64::conditional_select(&value.wrapping_sub(M), &value, is_reduced)
+}
@@ -6,11 +6,12 @@
 use super::{
     AsBytes, BaseElement, DeserializationError, FieldElement, Serializable, StarkField, E, M,
 };
-use crate::field::{CubeExtension, ExtensionOf, QuadExtension};
+use crate::field::{CubeExtension, ExtensionOf, QuadExtension, QuarticExtension};
+use crate::Choice;
 use core::convert::TryFrom;
 use num_bigint::BigUint;
 use proptest::prelude::*;
-use rand_utils::rand_value;
+use rand_utils::{rand_value, rand_vector};
 
 // MANUAL TESTS
 // ================================================================================================
@@ -106,6 +107,37 @@ fn exp() {
     assert_eq!(a.exp(3), a * a * a);
 }
 
+#[test]
+fn exp_const_time() {
+    let a = BaseElement::ZERO;
+    assert_eq!(a.exp_const_time(0), BaseElement::ONE);
+    assert_eq!(a.exp_const_time(1), BaseElement::ZERO);
+
+    let a: BaseElement = rand_value();
+    for power in [0, 1, 2, 3, 255, 1 << 32, M - 1, u64::MAX] {
+        assert_eq!(a.exp(power), a.exp_const_time(power));
+        assert_eq!(a.exp(power), a.exp_vartime(power));
+    }
+}
+
+#[test]
+fn exp_field() {
+    let a: BaseElement = rand_value();
+    let power: BaseElement = rand_value();
+    assert_eq!(a.exp(power.as_int()), a.exp_field(power));
+
+    // the exponent is reduced to its canonical value
+    let power = BaseElement::new(u64::MAX);
+    assert_eq!(a.exp(u64::MAX - M), a.exp_field(power));
+    assert_eq!(a, a.exp_field(BaseElement::new(M + 1)));
+
+    // elements of extension fields can be raised to powers in the base field
+    let b: QuadExtension<BaseElement> = rand_value();
+    assert_eq!(b.exp(power.as_int()), b.exp_field(power));
+    let c: CubeExtension<BaseElement> = rand_value();
+    assert_eq!(c.exp(power.as_int()), c.exp_field(power));
+}
+
 #[test]
 fn inv() {
     // identity
@@ -135,6 +167,47 @@ fn equals() {
     assert_ne!(a.as_bytes(), b.as_bytes());
 }
 
+#[test]
+fn ct_eq() {
+    let a = BaseElement::ONE;
+    let b = BaseElement::new(super::M - 1) * BaseElement::new(super::M - 1);
+
+    // elements with different internal representations are equal in constant time as well
+    assert_ne!(a.0, b.0);
+    assert!(bool::from(a.ct_eq(&b)));
+    assert!(!bool::from(a.ct_eq(&BaseElement::ZERO)));
+
+    // constant-time comparison agrees with regular comparison
+    for _ in 0..100 {
+        let a: BaseElement = rand_value();
+        let b: BaseElement = rand_value();
+        assert_eq!(a == b, bool::from(a.ct_eq(&b)));
+        assert!(bool::from(a.ct_eq(&(a + BaseElement::ZERO))));
+    }
+}
+
+#[test]
+fn conditional_select() {
+    let a: BaseElement = rand_value();
+    let b: BaseElement = rand_value();
+    assert_eq!(a, BaseElement::conditional_select(&a, &b, Choice::from(0)));
+    assert_eq!(b, BaseElement::conditional_select(&a, &b, Choice::from(1)));
+}
+
+#[test]
+fn montgomery_conversions() {
+    let a = BaseElement::from(5u8);
+    assert_eq!(5, a.to_canonical());
+    assert_eq!(a, BaseElement::from_canonical(5));
+    assert_eq!(21474836475, a.to_montgomery());
+    assert_eq!(a, BaseElement::from_montgomery(a.to_montgomery()));
+
+    let a: BaseElement = rand_value();
+    assert_eq!(a.as_int(), a.to_canonical());
+    assert_eq!(a, BaseElement::from_canonical(a.to_canonical()));
+    assert_eq!(a, BaseElement::from_montgomery(a.to_montgomery()));
+}
+
 // ROOTS OF UNITY
 // ------------------------------------------------------------------------------------------------
 
@@ -150,6 +223,62 @@ fn get_root_of_unity() {
     assert_eq!(BaseElement::ONE, root_31.exp(1u64 << 31));
 }
 
+#[test]
+fn get_root_of_unity_is_primitive() {
+    for n in 1..=BaseElement::TWO_ADICITY {
+        let root = BaseElement::get_root_of_unity(n);
+        assert_eq!(BaseElement::ONE, root.exp(1u64 << n));
+
+        // the order of the root is exactly 2^n
+        assert_eq!(-BaseElement::ONE, root.exp(1u64 << (n - 1)));
+    }
+}
+
+#[test]
+#[should_panic(expected = "exceeds two-adicity")]
+fn get_root_of_unity_exceeds_two_adicity() {
+    BaseElement::get_root_of_unity(BaseElement::TWO_ADICITY + 1);
+}
+
+// SQUARE ROOTS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn sqrt() {
+    // zero is a square of itself
+    assert!(BaseElement::ZERO.is_quadratic_residue());
+    assert_eq!(Some(BaseElement::ZERO), BaseElement::ZERO.sqrt());
+    assert_eq!(Some(BaseElement::ONE), BaseElement::ONE.sqrt());
+
+    for _ in 0..100 {
+        let r: BaseElement = rand_value();
+        let x = r.square();
+        assert!(x.is_quadratic_residue());
+
+        let root = x.sqrt().unwrap();
+        assert_eq!(x, root.square());
+        assert!(root == r || root == -r);
+    }
+}
+
+#[test]
+fn sqrt_non_residue() {
+    // the generator of the multiplicative group is not a square, and neither is a product of
+    // a non-zero square and a non-residue
+    assert!(!BaseElement::GENERATOR.is_quadratic_residue());
+    assert_eq!(None, BaseElement::GENERATOR.sqrt());
+
+    for _ in 0..100 {
+        let r: BaseElement = rand_value();
+        if r == BaseElement::ZERO {
+            continue;
+        }
+        let x = r.square() * BaseElement::GENERATOR;
+        assert!(!x.is_quadratic_residue());
+        assert_eq!(None, x.sqrt());
+    }
+}
+
 // SERIALIZATION AND DESERIALIZATION
 // ------------------------------------------------------------------------------------------------
 
@@ -198,6 +327,32 @@ fn elements_as_bytes() {
     assert_eq!(expected, BaseElement::elements_as_bytes(&source));
 }
 
+#[test]
+fn bytes_be_round_trip() {
+    let a = BaseElement::new(0x0102_0304_0506_0708);
+    assert_eq!(vec![8, 7, 6, 5, 4, 3, 2, 1], a.to_bytes());
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8], a.to_bytes_be());
+    assert_eq!(a, BaseElement::from_bytes_be(&a.to_bytes_be()).unwrap());
+    assert_eq!(a, BaseElement::try_from(a.to_bytes().as_slice()).unwrap());
+
+    let a: BaseElement = rand_value();
+    assert_eq!(a, BaseElement::from_bytes_be(&a.to_bytes_be()).unwrap());
+
+    // the number of bytes must match the size of an element
+    assert!(BaseElement::from_bytes_be(&[1, 2, 3, 4, 5, 6, 7]).is_err());
+    assert!(BaseElement::from_bytes_be(&[0, 1, 2, 3, 4, 5, 6, 7, 8]).is_err());
+
+    // values outside of the field are rejected
+    assert!(BaseElement::from_bytes_be(&[255; 8]).is_err());
+
+    // for extension field elements, the order of components is reversed as well
+    let b = QuadExtension::<BaseElement>::new(BaseElement::new(1), BaseElement::new(2));
+    let mut expected = BaseElement::new(2).to_bytes_be();
+    expected.extend_from_slice(&BaseElement::new(1).to_bytes_be());
+    assert_eq!(expected, b.to_bytes_be());
+    assert_eq!(b, QuadExtension::from_bytes_be(&expected).unwrap());
+}
+
 #[test]
 fn bytes_as_elements() {
     let elements = vec![
@@ -237,6 +392,61 @@ fn zeroed_vector() {
     }
 }
 
+// HASH TO FIELD
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn from_hash_output() {
+    // values are reduced modulo the field modulus
+    let bytes = (M as u128 + 5).to_le_bytes();
+    assert_eq!(BaseElement::new(5), BaseElement::from_hash_output(&bytes));
+
+    let bytes = u64::MAX.to_le_bytes();
+    assert_eq!(
+        BaseElement::new(u64::MAX % M),
+        BaseElement::from_hash_output(&bytes)
+    );
+
+    // for extension fields, bytes are split evenly between base field components
+    let bytes = rand_vector::<u8>(48);
+    let e = <QuadExtension<BaseElement>>::from_hash_output(&bytes);
+    let expected = [
+        BaseElement::from_hash_output(&bytes[..24]),
+        BaseElement::from_hash_output(&bytes[24..]),
+    ];
+    assert_eq!(
+        &expected,
+        <QuadExtension<BaseElement>>::as_base_elements(&[e])
+    );
+}
+
+#[test]
+fn from_hash_output_distribution() {
+    const NUM_BUCKETS: usize = 16;
+    const NUM_SAMPLES: usize = 16_000;
+
+    // split the field into equally-sized buckets, and count how many samples fall into each
+    let mut counts = [0usize; NUM_BUCKETS];
+    for _ in 0..NUM_SAMPLES {
+        let bytes = rand_vector::<u8>(BaseElement::ELEMENT_BYTES + 16);
+        let value = BaseElement::from_hash_output(&bytes).as_int() as u128;
+        counts[(value * NUM_BUCKETS as u128 / M as u128) as usize] += 1;
+    }
+
+    // compute chi-squared statistic; with 15 degrees of freedom, the probability of the
+    // statistic exceeding 50 for a uniform distribution is less than 10^-5
+    let expected = (NUM_SAMPLES / NUM_BUCKETS) as f64;
+    let chi_squared: f64 = counts
+        .iter()
+        .map(|&count| (count as f64 - expected).powi(2) / expected)
+        .sum();
+    assert!(
+        chi_squared < 50.0,
+        "chi-squared statistic was {}",
+        chi_squared
+    );
+}
+
 // QUADRATIC EXTENSION
 // ------------------------------------------------------------------------------------------------
 #[test]
@@ -307,6 +517,19 @@ fn quad_conjugate() {
     assert_eq!(expected, a.conjugate());
 }
 
+#[test]
+fn quad_frobenius() {
+    let a: QuadExtension<BaseElement> = rand_value();
+    assert_eq!(a.exp(BaseElement::MODULUS), a.frobenius());
+
+    // applying frobenius twice is the identity map
+    assert_eq!(a, a.frobenius().frobenius());
+
+    // base field elements are fixed by frobenius
+    let b = <QuadExtension<BaseElement>>::from(rand_value::<BaseElement>());
+    assert_eq!(b, b.frobenius());
+}
+
 // CUBIC EXTENSION
 // ------------------------------------------------------------------------------------------------
 #[test]
@@ -383,6 +606,123 @@ fn cube_mul_base() {
     assert_eq!(expected, a.mul_base(b0));
 }
 
+#[test]
+fn cube_frobenius() {
+    let a: CubeExtension<BaseElement> = rand_value();
+    assert_eq!(a.exp(BaseElement::MODULUS), a.frobenius());
+
+    // applying frobenius three times is the identity map
+    assert_eq!(a, a.frobenius().frobenius().frobenius());
+
+    // base field elements are fixed by frobenius
+    let b = <CubeExtension<BaseElement>>::from(rand_value::<BaseElement>());
+    assert_eq!(b, b.frobenius());
+}
+
+// QUARTIC EXTENSION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn quartic_mul() {
+    // identity
+    let r: QuarticExtension<BaseElement> = rand_value();
+    assert_eq!(
+        <QuarticExtension<BaseElement>>::ZERO,
+        r * <QuarticExtension<BaseElement>>::ZERO
+    );
+    assert_eq!(r, r * <QuarticExtension<BaseElement>>::ONE);
+
+    // ψ^4 = 7 * ψ^2 - 14 = 7 * φ + 7
+    let psi = build_quartic([0, 0, 1, 0]);
+    assert_eq!(build_quartic([7, 7, 0, 0]), psi.exp(4));
+
+    // test multiplication with overflow; the expected value was computed using Python via
+    // arithmetic modulo the degree 4 polynomial ψ^4 - 7 * ψ^2 + 14
+    let a = build_quartic([
+        18446744069414584306,
+        1390,
+        18446744069414575721,
+        9223372034707292163,
+    ]);
+    let b = build_quartic([
+        18446744069414584101,
+        9223372034707261649,
+        18446744069414584319,
+        17,
+    ]);
+    let expected = build_quartic([
+        85168940,
+        9223372034664465513,
+        9223372034709289488,
+        13835058052323280907,
+    ]);
+    assert_eq!(expected, a * b);
+    assert_eq!(expected, b * a);
+}
+
+#[test]
+fn quartic_mul_base() {
+    let a: QuarticExtension<BaseElement> = rand_value();
+    let b0 = rand_value();
+    let b = <QuarticExtension<BaseElement>>::from(b0);
+
+    let expected = a * b;
+    assert_eq!(expected, a.mul_base(b0));
+}
+
+#[test]
+fn quartic_inv() {
+    let r: QuarticExtension<BaseElement> = rand_value();
+    assert_eq!(<QuarticExtension<BaseElement>>::ONE, r * r.inv());
+    assert_eq!(
+        <QuarticExtension<BaseElement>>::ZERO,
+        <QuarticExtension<BaseElement>>::ZERO.inv()
+    );
+
+    // the expected value was computed using Python
+    let a = build_quartic([
+        18446744069414584306,
+        1390,
+        18446744069414575721,
+        9223372034707292163,
+    ]);
+    let expected = build_quartic([
+        4067139395010849947,
+        13559385984880465379,
+        12118547769645997502,
+        14434523589926681697,
+    ]);
+    assert_eq!(expected, a.inv());
+}
+
+#[test]
+fn quartic_frobenius() {
+    let a: QuarticExtension<BaseElement> = rand_value();
+    assert_eq!(a.exp(BaseElement::MODULUS), a.frobenius());
+
+    // applying Frobenius automorphism 4 times yields the original element
+    assert_eq!(a, a.frobenius().frobenius().frobenius().frobenius());
+
+    // base field elements are fixed by Frobenius automorphism
+    let b = <QuarticExtension<BaseElement>>::from(rand_value::<BaseElement>());
+    assert_eq!(b, b.frobenius());
+
+    // the expected value was computed using Python
+    let a = build_quartic([
+        18446744069414584306,
+        1390,
+        18446744069414575721,
+        9223372034707292163,
+    ]);
+    let expected = build_quartic([
+        1375,
+        18446744069414582931,
+        18446741320593572481,
+        4727972140364800,
+    ]);
+    assert_eq!(expected, a.frobenius());
+}
+
 // RANDOMIZED TESTS
 // ================================================================================================
 
@@ -448,6 +788,12 @@ proptest! {
         prop_assert_eq!(expected, result.as_int());
     }
 
+    #[test]
+    fn exp_const_time_proptest(a in any::<u64>(), b in any::<u64>()) {
+        let a = BaseElement::from(a);
+        prop_assert_eq!(a.exp(b), a.exp_const_time(b));
+    }
+
     #[test]
     fn inv_proptest(a in any::<u64>()) {
         let a = BaseElement::from(a);
@@ -457,6 +803,14 @@ proptest! {
         prop_assert_eq!(expected, a * b);
     }
 
+    #[test]
+    fn ct_eq_proptest(a in any::<u64>(), b in any::<u64>()) {
+        let v1 = BaseElement::from(a);
+        let v2 = BaseElement::from(b);
+        prop_assert_eq!(v1 == v2, bool::from(v1.ct_eq(&v2)));
+        prop_assert!(bool::from(v1.ct_eq(&BaseElement::from(a))));
+    }
+
     #[test]
     fn element_as_int_proptest(a in any::<u64>()) {
         let e = BaseElement::new(a);
@@ -499,3 +853,13 @@ proptest! {
         prop_assert_eq!(expected, a * b);
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_quartic(values: [u64; 4]) -> QuarticExtension<BaseElement> {
+    QuarticExtension::new(
+        QuadExtension::new(BaseElement::new(values[0]), BaseElement::new(values[1])),
+        QuadExtension::new(BaseElement::new(values[2]), BaseElement::new(values[3])),
+    )
+}
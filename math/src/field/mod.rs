@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 mod traits;
-pub use traits::{ExtensibleField, ExtensionOf, FieldElement, StarkField};
+pub use traits::{ExtensibleField, ExtensionOf, FieldElement, QuarticTowerField, StarkField};
 
 pub mod f252;
 pub mod f128;
@@ -12,4 +12,4 @@ pub mod f62;
 pub mod f64;
 
 mod extensions;
-pub use extensions::{CubeExtension, QuadExtension};
+pub use extensions::{CubeExtension, QuadExtension, QuarticExtension, SexticExtension};
@@ -11,8 +11,10 @@ use core::{
         SubAssign,
     },
 };
+use subtle::{Choice, ConstantTimeEq};
 use utils::{
     collections::Vec, AsBytes, Deserializable, DeserializationError, Randomizable, Serializable,
+    SliceReader,
 };
 
 // FIELD ELEMENT
@@ -112,30 +114,48 @@ pub trait FieldElement:
     }
 
     /// Exponentiates this field element by `power` parameter.
+    ///
+    /// Multiplications are skipped for zero bits of `power`, and the computation returns early
+    /// for trivial inputs. Thus, the execution time depends on the value of `power`; when the
+    /// exponent is secret, [exp_const_time()](FieldElement::exp_const_time) should be used
+    /// instead.
     #[must_use]
     fn exp(self, power: Self::PositiveInteger) -> Self {
-        let mut r = Self::ONE;
-        let mut b = self;
-        let mut p = power;
-
-        let int_zero = Self::PositiveInteger::from(0u32);
-        let int_one = Self::PositiveInteger::from(1u32);
+        exp_square_and_multiply(self, power)
+    }
 
-        if p == int_zero {
-            return Self::ONE;
-        } else if b == Self::ZERO {
-            return Self::ZERO;
-        }
+    /// Exponentiates this field element by `power` parameter which is not secret.
+    ///
+    /// This is the same as [exp()](FieldElement::exp); it can be used to make it explicit that
+    /// the execution time is allowed to depend on the value of `power`.
+    #[inline]
+    #[must_use]
+    fn exp_vartime(self, power: Self::PositiveInteger) -> Self {
+        self.exp(power)
+    }
 
-        while p > int_zero {
-            if p & int_one == int_one {
-                r *= b;
-            }
-            p >>= int_one;
-            b = b.square();
-        }
+    /// Exponentiates this field element by `power` parameter which may be secret.
+    ///
+    /// A multiplication is performed for every bit of `power` up to its most significant set bit
+    /// regardless of the value of the bit. Thus, the number of field operations depends only on
+    /// the bit length of `power` (and not on its Hamming weight or on the value of this element).
+    /// This does not guarantee constant-time execution, but makes this method preferable to
+    /// [exp()](FieldElement::exp) when the exponent is secret. This is slower than
+    /// [exp()](FieldElement::exp), and thus, should not be used for public exponents.
+    #[must_use]
+    fn exp_const_time(self, power: Self::PositiveInteger) -> Self {
+        exp_uniform(self, power)
+    }
 
-        r
+    /// Exponentiates this field element by `power` parameter interpreted as an integer.
+    ///
+    /// The exponent is the canonical integer representation of `power` (as returned by
+    /// [StarkField::as_int()]). This is useful when the exponent is itself a result of a
+    /// computation in the base field (e.g., in lookup arguments). The exponentiation is performed
+    /// in the same way as in [exp()](FieldElement::exp).
+    #[must_use]
+    fn exp_field(self, power: Self::BaseField) -> Self {
+        exp_square_and_multiply(self, power.as_int())
     }
 
     /// Returns a multiplicative inverse of this field element. If this element is ZERO, ZERO is
@@ -147,6 +167,16 @@ pub trait FieldElement:
     #[must_use]
     fn conjugate(&self) -> Self;
 
+    /// Returns the image of this field element under the Frobenius automorphism, i.e., this
+    /// element raised to the power p, where p is the characteristic of the field.
+    ///
+    /// For elements in a prime field, the Frobenius automorphism is the identity map, and this
+    /// is what the default implementation returns. Extension fields must override this method.
+    #[must_use]
+    fn frobenius(&self) -> Self {
+        *self
+    }
+
     // SERIALIZATION / DESERIALIZATION
     // --------------------------------------------------------------------------------------------
 
@@ -173,6 +203,39 @@ pub trait FieldElement:
     /// field elements according to their internal representation.
     unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError>;
 
+    /// Returns the encoding of this element in big-endian byte order.
+    ///
+    /// The returned bytes are the bytes written by [Serializable::write_into()] (which encodes
+    /// elements in little-endian byte order) in reverse order; thus, the result always contains
+    /// exactly `Self::ELEMENT_BYTES` bytes. For elements in extension fields, the order of base
+    /// field components is reversed as well (i.e., the highest-degree component comes first).
+    fn to_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Returns an element decoded from the provided bytes in big-endian byte order.
+    ///
+    /// This is the inverse of [FieldElement::to_bytes_be()].
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of provided bytes is not equal to `Self::ELEMENT_BYTES`.
+    /// * The bytes do not encode a valid field element.
+    fn from_bytes_be(bytes: &[u8]) -> Result<Self, DeserializationError> {
+        if bytes.len() != Self::ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected {} bytes to decode a field element, but was {}",
+                Self::ELEMENT_BYTES,
+                bytes.len()
+            )));
+        }
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        Self::read_from(&mut SliceReader::new(&bytes))
+    }
+
     // UTILITIES
     // --------------------------------------------------------------------------------------------
 
@@ -183,6 +246,55 @@ pub trait FieldElement:
         vec![Self::ZERO; n]
     }
 
+    /// Maps the output of a hash function (or any other uniformly random bytes) to a field
+    /// element.
+    ///
+    /// The bytes are split into as many equal-sized chunks as there are base field elements in
+    /// this field element (any leftover bytes are added to the last chunk); each chunk is then
+    /// interpreted as an integer in little-endian byte order and reduced modulo the base field
+    /// modulus. Unlike [Randomizable::from_random_bytes()], this never fails, but the resulting
+    /// distribution is not exactly uniform: for a chunk of `n` bytes and a modulus `p`, the
+    /// statistical distance from the uniform distribution is at most `p / 2^(8n)`. Thus, to
+    /// keep the bias negligible, each chunk should contain at least
+    /// `Self::BaseField::ELEMENT_BYTES + 16` bytes (i.e., about 128 bits more than needed to
+    /// encode an element of the base field).
+    ///
+    /// # Panics
+    /// Panics if `bytes` contains fewer bytes than there are base field elements in this field
+    /// element.
+    fn from_hash_output(bytes: &[u8]) -> Self {
+        let num_components = Self::ELEMENT_BYTES / Self::BaseField::ELEMENT_BYTES;
+        assert!(
+            bytes.len() >= num_components,
+            "expected at least {} bytes, but was {}",
+            num_components,
+            bytes.len()
+        );
+
+        // reduce each chunk of bytes into a base field element; the reduction is performed by
+        // evaluating the chunk as a polynomial in 256 using Horner's method
+        let chunk_size = bytes.len() / num_components;
+        let radix = Self::BaseField::from(256u32);
+        let mut element_bytes = Vec::with_capacity(Self::ELEMENT_BYTES);
+        for i in 0..num_components {
+            let chunk_end = if i == num_components - 1 {
+                bytes.len()
+            } else {
+                (i + 1) * chunk_size
+            };
+            let value = bytes[i * chunk_size..chunk_end]
+                .iter()
+                .rev()
+                .fold(Self::BaseField::ZERO, |acc, &byte| {
+                    acc * radix + Self::BaseField::from(byte)
+                });
+            value.write_into(&mut element_bytes);
+        }
+
+        Self::read_from(&mut SliceReader::new(&element_bytes))
+            .expect("failed to build a field element from base field elements")
+    }
+
     /// Converts a list of field elements into a list of elements in the underlying base field.
     ///
     /// For base STARK fields, the input and output lists are the same. For extension field, the
@@ -191,6 +303,68 @@ pub trait FieldElement:
     fn as_base_elements(elements: &[Self]) -> &[Self::BaseField];
 }
 
+/// Computes `base^power` via square-and-multiply, skipping multiplications for zero bits of
+/// `power`.
+///
+/// The exponent can be of any integer type supporting the operations needed to iterate over its
+/// bits; this allows raising elements of extension fields to powers represented by integers of
+/// the base field.
+fn exp_square_and_multiply<E, P>(base: E, power: P) -> E
+where
+    E: FieldElement,
+    P: Copy + PartialOrd + ShrAssign + BitAnd<Output = P> + From<u32>,
+{
+    let mut r = E::ONE;
+    let mut b = base;
+    let mut p = power;
+
+    let int_zero = P::from(0u32);
+    let int_one = P::from(1u32);
+
+    if p == int_zero {
+        return E::ONE;
+    } else if b == E::ZERO {
+        return E::ZERO;
+    }
+
+    while p > int_zero {
+        if p & int_one == int_one {
+            r *= b;
+        }
+        p >>= int_one;
+        b = b.square();
+    }
+
+    r
+}
+
+/// Computes `base^power` performing a squaring and a multiplication for every bit of `power` up
+/// to its most significant set bit; for zero bits, the multiplication is by ONE.
+///
+/// The exponent can be of any integer type supporting the operations needed to iterate over its
+/// bits; this allows raising elements of extension fields to powers represented by integers of
+/// the base field.
+fn exp_uniform<E, P>(base: E, power: P) -> E
+where
+    E: FieldElement,
+    P: Copy + PartialOrd + ShrAssign + BitAnd<Output = P> + From<u32>,
+{
+    let int_zero = P::from(0u32);
+    let int_one = P::from(1u32);
+
+    let mut r = E::ONE;
+    let mut b = base;
+    let mut p = power;
+    while p > int_zero {
+        let factor = if p & int_one == int_one { b } else { E::ONE };
+        r *= factor;
+        p >>= int_one;
+        b = b.square();
+    }
+
+    r
+}
+
 // STARK FIELD
 // ================================================================================================
 
@@ -217,15 +391,22 @@ pub trait StarkField: FieldElement<BaseField = Self> {
     /// computed as Self::GENERATOR^`k`.
     const TWO_ADIC_ROOT_OF_UNITY: Self;
 
-    /// Returns the root of unity of order 2^`n`.
+    /// Returns a primitive root of unity of order 2^`n`.
+    ///
+    /// The returned element generates the multiplicative subgroup of size 2^`n`. For `n` equal
+    /// to [TWO_ADICITY](StarkField::TWO_ADICITY), this is the largest power-of-two subgroup of
+    /// the field, and the returned element is
+    /// [TWO_ADIC_ROOT_OF_UNITY](StarkField::TWO_ADIC_ROOT_OF_UNITY).
     ///
     /// # Panics
-    /// Panics if the root of unity for the specified order does not exist in this field.
+    /// Panics if `n` is zero or is greater than [TWO_ADICITY](StarkField::TWO_ADICITY) (i.e.,
+    /// the root of unity for the specified order does not exist in this field).
     fn get_root_of_unity(n: u32) -> Self {
         assert!(n != 0, "cannot get root of unity for n = 0");
         assert!(
             n <= Self::TWO_ADICITY,
-            "order cannot exceed 2^{}",
+            "cannot get root of unity of order 2^{}: n exceeds two-adicity of the field ({})",
+            n,
             Self::TWO_ADICITY
         );
         let power = Self::PositiveInteger::from(1u32) << (Self::TWO_ADICITY - n);
@@ -237,6 +418,129 @@ pub trait StarkField: FieldElement<BaseField = Self> {
 
     /// Returns a canonical integer representation of this field element.
     fn as_int(&self) -> Self::PositiveInteger;
+
+    /// Returns a field element with the specified canonical integer representation.
+    ///
+    /// The value is expected to be smaller than the field modulus.
+    fn from_canonical(value: Self::PositiveInteger) -> Self;
+
+    // REPRESENTATION CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a canonical integer representation of this field element.
+    ///
+    /// This is equivalent to [StarkField::as_int()].
+    fn to_canonical(&self) -> Self::PositiveInteger {
+        self.as_int()
+    }
+
+    /// Returns the Montgomery representation of this field element.
+    ///
+    /// The Montgomery representation of an element `a` is defined as `a * R mod M`, where `M` is
+    /// the field modulus and `R = 2^(8 * ELEMENT_BYTES)`. This is independent of how the element
+    /// is represented internally. Fields which store elements in Montgomery form internally
+    /// return the internal representation (normalized to be smaller than the modulus).
+    fn to_montgomery(&self) -> Self::PositiveInteger {
+        (*self * montgomery_r::<Self>()).as_int()
+    }
+
+    /// Returns a field element from its Montgomery representation.
+    ///
+    /// This is the inverse of [StarkField::to_montgomery()]; the value is expected to be smaller
+    /// than the field modulus.
+    fn from_montgomery(value: Self::PositiveInteger) -> Self {
+        Self::from_canonical(value) * montgomery_r::<Self>().inv()
+    }
+
+    // SQUARE ROOTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if this element is a quadratic residue, i.e., if there exists an element `r`
+    /// such that `r^2` is equal to this element.
+    ///
+    /// ZERO is considered to be a quadratic residue since it is a square of itself. For all
+    /// other elements, this is determined by Euler's criterion: `a^((p - 1) / 2) = 1`.
+    fn is_quadratic_residue(&self) -> bool {
+        // since the modulus is odd, (p - 1) / 2 = p >> 1
+        *self == Self::ZERO || self.exp(Self::MODULUS >> 1) == Self::ONE
+    }
+
+    /// Returns a square root of this element, or None if this element is not a quadratic residue.
+    ///
+    /// The root is computed using the Tonelli-Shanks algorithm with
+    /// [TWO_ADIC_ROOT_OF_UNITY](StarkField::TWO_ADIC_ROOT_OF_UNITY) used as the quadratic
+    /// non-residue. For ZERO, ZERO is returned; for all other residues, the returned value is one
+    /// of the two roots `r` and `-r`, and no guarantees are made about which one.
+    fn sqrt(&self) -> Option<Self> {
+        if *self == Self::ZERO {
+            return Some(Self::ZERO);
+        }
+        if !self.is_quadratic_residue() {
+            return None;
+        }
+
+        // let p - 1 = k * 2^n where k is odd; then (k - 1) / 2 = p >> (n + 1), and we compute
+        // t = a^k and r = a^((k + 1) / 2)
+        let a_pow = self.exp(Self::MODULUS >> (Self::TWO_ADICITY + 1));
+        let mut r = a_pow * *self;
+        let mut t = a_pow * r;
+        let mut c = Self::TWO_ADIC_ROOT_OF_UNITY;
+        let mut m = Self::TWO_ADICITY;
+
+        // invariant: r^2 = a * t, where t is a 2^(m - 1) root of unity
+        while t != Self::ONE {
+            // find the smallest i such that t^(2^i) = 1; since `self` is a residue, i < m
+            let mut i = 0;
+            let mut t_pow = t;
+            while t_pow != Self::ONE {
+                t_pow = t_pow.square();
+                i += 1;
+            }
+
+            // b = c^(2^(m - i - 1))
+            let mut b = c;
+            for _ in 0..(m - i - 1) {
+                b = b.square();
+            }
+
+            m = i;
+            c = b.square();
+            t *= c;
+            r *= b;
+        }
+
+        Some(r)
+    }
+
+    // CONSTANT-TIME OPERATIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a [Choice] set to 1 if this element is equal to `other`, and set to 0 otherwise.
+    ///
+    /// Unlike `==`, this comparison does not branch on the values of the elements, and thus, can
+    /// be used when the elements carry secret data.
+    ///
+    /// The default implementation compares canonical serializations of the elements in constant
+    /// time. Fields whose conversion to canonical form branches on the value of an element should
+    /// override this method.
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let self_bytes = self.to_bytes();
+        let other_bytes = other.to_bytes();
+        self_bytes.as_slice().ct_eq(other_bytes.as_slice())
+    }
+
+    /// Returns `a` if `choice` is 0, and `b` if `choice` is 1, without branching on `choice`.
+    ///
+    /// The default implementation computes a + (b - a) * choice using field arithmetic; fields
+    /// can override this method to select between internal representations directly.
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        *a + (*b - *a) * Self::from(choice.unwrap_u8())
+    }
+}
+
+/// Returns the Montgomery radix R = 2^(8 * ELEMENT_BYTES) as an element of the specified field.
+fn montgomery_r<B: StarkField>() -> B {
+    B::from(2u32).exp(((B::ELEMENT_BYTES * 8) as u32).into())
 }
 
 // EXTENSIBLE FIELD
@@ -267,6 +571,29 @@ pub trait ExtensibleField<const N: usize>: StarkField {
     }
 }
 
+// QUARTIC TOWER FIELD
+// ================================================================================================
+
+/// Defines a degree 4 extension of a [StarkField] as a quadratic extension of the quadratic
+/// extension of the field.
+///
+/// The quadratic extension of the field is defined by the implementation of the
+/// [ExtensibleField<2>] trait. The degree 4 extension is then defined over an irreducible
+/// polynomial y<sup>2</sup> - ξ, where ξ is an element of the quadratic extension which is not a
+/// square in it. For some fields (e.g., 64-bit fields), multiplication in such a tower could be
+/// cheaper than multiplication in a degree 4 extension defined over a single irreducible
+/// polynomial of degree 4.
+pub trait QuarticTowerField: ExtensibleField<2> {
+    /// Coefficients of 1 and φ of the non-square ξ in the quadratic extension of this field.
+    const QUARTIC_NON_RESIDUE: [Self; 2];
+
+    /// Coefficients of 1 and φ of ξ<sup>(p - 1) / 2</sup> in the quadratic extension of this
+    /// field, where p is the modulus of this field. This is used to compute the Frobenius
+    /// automorphism since ψ<sup>p</sup> = ξ<sup>(p - 1) / 2</sup> * ψ, where ψ is a root of
+    /// y<sup>2</sup> - ξ.
+    const QUARTIC_FROBENIUS: [Self; 2];
+}
+
 // EXTENSION OF
 // ================================================================================================
 
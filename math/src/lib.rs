@@ -35,9 +35,9 @@
 //!
 //! ## Extension fields
 //!
-//! Currently, the library provides a generic way to create quadratic and cubic extensions of
-//! supported STARK fields. This can be done by implementing [ExtensibleField] trait for
-//! degrees 2 and 3.
+//! Currently, the library provides a generic way to create quadratic, cubic, and degree 6
+//! extensions of supported STARK fields. This can be done by implementing [ExtensibleField] trait
+//! for degrees 2, 3, and 6.
 //!
 //! Quadratic extension fields are defined using the following irreducible polynomials:
 //! * For [f62](crate::fields::f62) field, the polynomial is x<sup>2</sup> - x - 1.
@@ -49,6 +49,15 @@
 //! * For [f64](crate::fields::f64) field, the polynomial is x<sup>3</sup> - x - 1.
 //! * For [f128](crate::fields::f128) field, cubic extensions are not supported.
 //!
+//! Degree 6 extension fields are currently supported only for the [f62](crate::fields::f62)
+//! field, for which the extension is defined using polynomial x<sup>6</sup> - x - 4.
+//!
+//! Degree 4 extension fields are built as a tower of two quadratic extensions by implementing
+//! [QuarticTowerField] trait. The second quadratic extension is defined using polynomial
+//! y<sup>2</sup> - ξ, where ξ is an element of the quadratic extension of the base field:
+//! * For [f62](crate::fields::f62) field, ξ = φ + 2.
+//! * For [f64](crate::fields::f64) field, ξ = φ + 3.
+//!
 //! # Polynomials
 //! [Polynomials](polynom) module implements basic polynomial operations such as:
 //!
@@ -64,6 +73,8 @@
 //! field (also called [Number-theoretic transform](https://en.wikipedia.org/wiki/Discrete_Fourier_transform_(general)#Number-theoretic_transform)).
 //! This can be used to interpolate and evaluate polynomials in *O(n log n)* time as long as
 //! the domain of the polynomial is a multiplicative subgroup with size which is a power of 2.
+//! Twiddles for domains of a given size can be computed once and reused across many FFTs via
+//! [Twiddles](fft::Twiddles) struct.
 //!
 //! # Concurrent execution
 //!
@@ -97,7 +108,8 @@ pub mod fft;
 pub mod polynom;
 
 pub mod field;
-pub use field::{ExtensibleField, ExtensionOf, FieldElement, StarkField};
+pub use field::{ExtensibleField, ExtensionOf, FieldElement, QuarticTowerField, StarkField};
+pub use subtle::Choice;
 pub mod fields {
     //! Finite field implementations.
     //!
@@ -110,6 +122,8 @@ pub mod fields {
     pub use super::field::f64;
     pub use super::field::CubeExtension;
     pub use super::field::QuadExtension;
+    pub use super::field::QuarticExtension;
+    pub use super::field::SexticExtension;
 }
 
 mod utils;
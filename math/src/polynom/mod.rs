@@ -6,11 +6,12 @@
 //! Basic polynomial operations.
 //!
 //! This module provides a set of function for basic polynomial operations, including:
-//! - Polynomial evaluation using Horner method.
+//! - Polynomial evaluation using Horner method, and over multiplicative subgroups using FFT.
 //! - Polynomial interpolation using Lagrange method.
 //! - Polynomial addition, subtraction, multiplication, and division.
 //! - Synthetic polynomial division for efficient division by polynomials of the form
 //!   `x`^`a` - `b`.
+//! - Random linear combination of polynomials with degree adjustment.
 //!
 //! In the context of this module any slice of field elements is considered to be a polynomial
 //! in reverse coefficient form. A few examples:
@@ -24,13 +25,27 @@
 //! let p = [BaseElement::new(3), BaseElement::ZERO, BaseElement::new(4)];
 //! ```
 
-use crate::{field::FieldElement, utils::batch_inversion};
+use crate::{
+    fft,
+    field::{FieldElement, StarkField},
+    utils::{batch_inversion, get_power_series_with_offset, log2},
+};
 use core::mem;
-use utils::{collections::Vec, group_vector_elements};
+use utils::{collections::Vec, group_vector_elements, iter};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
 
 #[cfg(test)]
 mod tests;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Smallest domain size for which [eval_domain()] evaluates a polynomial using FFT; over smaller
+/// domains, the polynomial is evaluated at each point of the domain using Horner method.
+const MIN_FFT_EVAL_DOMAIN_SIZE: usize = 64;
+
 // POLYNOMIAL EVALUATION
 // ================================================================================================
 
@@ -66,6 +81,9 @@ where
 /// Evaluates polynomial `p` at all coordinates in `xs` slice by repeatedly invoking
 /// `polynom::eval()` function.
 ///
+/// When `concurrent` feature is enabled, the evaluations are done concurrently in multiple
+/// threads.
+///
 /// # Examples
 /// ```
 /// # use winter_math::polynom::*;
@@ -82,7 +100,59 @@ where
     B: FieldElement,
     E: FieldElement + From<B>,
 {
-    xs.iter().map(|x| eval(p, *x)).collect()
+    iter!(xs).map(|x| eval(p, *x)).collect()
+}
+
+/// Evaluates a polynomial over a multiplicative subgroup of the base field shifted by the
+/// specified offset and returns a vector of results.
+///
+/// More precisely, for a domain of size `n` with generator `g` and offset `s`, evaluates
+/// polynomial `p` at points [s, s * g, s * g^2, ..., s * g^(n-1)].
+///
+/// For large domains (at least 64 points) and polynomials with no more coefficients than the
+/// domain size, the evaluation is done using FFT; otherwise, the polynomial is evaluated at each
+/// point of the domain using [eval_many()].
+///
+/// # Panics
+/// Panics if:
+/// * `domain_size` is not a power of two.
+/// * A multiplicative subgroup of size `domain_size` does not exist in the base field.
+/// * `domain_offset` is ZERO.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement, StarkField, get_power_series_with_offset};
+/// # use rand_utils::rand_vector;
+/// let p: Vec<BaseElement> = rand_vector(32);
+/// let offset = BaseElement::GENERATOR;
+///
+/// let g = BaseElement::get_root_of_unity(7);
+/// let domain = get_power_series_with_offset(g, offset, 128);
+/// assert_eq!(eval_many(&p, &domain), eval_domain(&p, 128, offset));
+/// ```
+pub fn eval_domain<B, E>(p: &[E], domain_size: usize, domain_offset: B) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    assert!(
+        domain_size.is_power_of_two(),
+        "domain size must be a power of 2, but was {}",
+        domain_size
+    );
+    assert_ne!(domain_offset, B::ZERO, "domain offset cannot be zero");
+
+    if domain_size >= MIN_FFT_EVAL_DOMAIN_SIZE && p.len() <= domain_size {
+        let mut coefficients = p.to_vec();
+        coefficients.resize(domain_size, E::ZERO);
+        let twiddles = fft::get_twiddles::<B>(domain_size);
+        fft::evaluate_poly_with_offset(&coefficients, &twiddles, domain_offset, 1)
+    } else {
+        let g = B::get_root_of_unity(log2(domain_size));
+        let domain = get_power_series_with_offset(E::from(g), E::from(domain_offset), domain_size);
+        eval_many(p, &domain)
+    }
 }
 
 // POLYNOMIAL INTERPOLATION
@@ -561,6 +631,70 @@ where
     }
 }
 
+// RANDOM LINEAR COMBINATION
+// ================================================================================================
+
+/// Returns a random linear combination of the provided polynomials with each polynomial
+/// adjusted to the specified target degree.
+///
+/// Specifically, for polynomials p_i(x) of degrees d_i and coefficients c_i, computes
+/// sum(c_i * x^(`target_degree` - d_i) * p_i(x)). Thus, each term of the sum is a polynomial of
+/// degree `target_degree`, and when the coefficients are drawn at random, the degree of the
+/// result is `target_degree` with overwhelming probability. The returned polynomial is in the
+/// coefficient form and contains exactly `target_degree` + 1 coefficients.
+///
+/// # Panics
+/// Panics if:
+/// * The number of polynomials is not the same as the number of coefficients.
+/// * The degree of any of the polynomials is greater than `target_degree`.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// // p1(x) = x^2 + 2
+/// let p1 = vec![BaseElement::new(2), BaseElement::ZERO, BaseElement::ONE];
+/// // p2(x) = 3 * x + 1
+/// let p2 = vec![BaseElement::ONE, BaseElement::new(3)];
+///
+/// // expected result = 2 * (x^2 + 2) + 5 * x^2 * (3 * x + 1) = 15 * x^3 + 7 * x^2 + 4
+/// let coeffs = [BaseElement::new(2), BaseElement::new(5)];
+/// let expected = vec![
+///     BaseElement::new(4),
+///     BaseElement::ZERO,
+///     BaseElement::new(7),
+///     BaseElement::new(15),
+/// ];
+/// assert_eq!(expected, combine_polys(&[p1, p2], &coeffs, 3));
+/// ```
+pub fn combine_polys<E>(polys: &[Vec<E>], coeffs: &[E], target_degree: usize) -> Vec<E>
+where
+    E: FieldElement,
+{
+    assert_eq!(
+        polys.len(),
+        coeffs.len(),
+        "number of coefficients must be the same as the number of polynomials"
+    );
+
+    let mut result = vec![E::ZERO; target_degree + 1];
+    for (i, (poly, &coeff)) in polys.iter().zip(coeffs).enumerate() {
+        let degree = degree_of(poly);
+        assert!(
+            degree <= target_degree,
+            "degree of polynomial {} cannot be greater than {}, but was {}",
+            i,
+            target_degree,
+            degree
+        );
+        let shift = target_degree - degree;
+        for (acc, &value) in result[shift..].iter_mut().zip(poly.iter().take(degree + 1)) {
+            *acc += value * coeff;
+        }
+    }
+    result
+}
+
 // DEGREE INFERENCE
 // ================================================================================================
 
@@ -6,8 +6,9 @@
 use super::remove_leading_zeros;
 use crate::{
     field::{f128::BaseElement, FieldElement, StarkField},
-    utils::{get_power_series, log2},
+    utils::{get_power_series, get_power_series_with_offset, log2},
 };
+use rand_utils::rand_vector;
 use utils::collections::Vec;
 
 #[test]
@@ -43,6 +44,46 @@ fn eval() {
     );
 }
 
+#[test]
+fn eval_many() {
+    let poly: Vec<BaseElement> = rand_vector(64);
+    let xs: Vec<BaseElement> = rand_vector(2048);
+
+    let expected = xs
+        .iter()
+        .map(|&x| super::eval(&poly, x))
+        .collect::<Vec<_>>();
+    assert_eq!(expected, super::eval_many(&poly, &xs));
+}
+
+#[test]
+fn eval_domain() {
+    let offset = BaseElement::GENERATOR;
+
+    for (num_coefficients, domain_size) in [(8, 16), (64, 256), (32, 64), (128, 64)] {
+        let poly: Vec<BaseElement> = rand_vector(num_coefficients);
+
+        let g = BaseElement::get_root_of_unity(log2(domain_size));
+        let domain = get_power_series_with_offset(g, offset, domain_size);
+        let expected = domain
+            .iter()
+            .map(|&x| super::eval(&poly, x))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, super::eval_domain(&poly, domain_size, offset));
+
+        // unshifted domain
+        let domain = get_power_series(g, domain_size);
+        let expected = domain
+            .iter()
+            .map(|&x| super::eval(&poly, x))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            expected,
+            super::eval_domain(&poly, domain_size, BaseElement::ONE)
+        );
+    }
+}
+
 #[test]
 fn add() {
     let poly1: [BaseElement; 3] = [
@@ -256,3 +297,49 @@ fn syn_div() {
     let result = super::syn_div(&poly, 4, root.exp(4));
     assert_eq!(poly, remove_leading_zeros(&super::mul(&result, &z_poly)));
 }
+
+#[test]
+fn combine_polys() {
+    let target_degree = 15;
+    let polys: Vec<Vec<BaseElement>> = vec![
+        rand_vector(16),
+        rand_vector(8),
+        rand_vector(5),
+        // leading zeros should not affect the degree adjustment
+        [rand_vector(3), vec![BaseElement::ZERO; 4]].concat(),
+    ];
+    let coeffs: Vec<BaseElement> = rand_vector(polys.len());
+
+    let result = super::combine_polys(&polys, &coeffs, target_degree);
+    assert_eq!(target_degree + 1, result.len());
+    assert_eq!(target_degree, super::degree_of(&result));
+
+    // evaluations of the result must match the combination of evaluations of individual
+    // polynomials multiplied by the degree adjustment factors
+    let xs: Vec<BaseElement> = rand_vector(4);
+    for &x in xs.iter() {
+        let mut expected = BaseElement::ZERO;
+        for (poly, &coeff) in polys.iter().zip(coeffs.iter()) {
+            let adjustment = x.exp((target_degree - super::degree_of(poly)) as u128);
+            expected += coeff * adjustment * super::eval(poly, x);
+        }
+        assert_eq!(expected, super::eval(&result, x));
+    }
+
+    // the result must match coefficient-wise combination of the degree-adjusted polynomials
+    let mut expected = vec![BaseElement::ZERO; target_degree + 1];
+    for (poly, &coeff) in polys.iter().zip(coeffs.iter()) {
+        let mut adjusted = vec![BaseElement::ZERO; target_degree - super::degree_of(poly)];
+        adjusted.extend_from_slice(&remove_leading_zeros(poly));
+        expected = super::add(&expected, &super::mul_by_scalar(&adjusted, coeff));
+    }
+    assert_eq!(expected, result);
+}
+
+#[test]
+#[should_panic(expected = "degree of polynomial 1 cannot be greater than 7, but was 8")]
+fn combine_polys_degree_too_high() {
+    let polys: Vec<Vec<BaseElement>> = vec![rand_vector(8), rand_vector(9)];
+    let coeffs: Vec<BaseElement> = rand_vector(2);
+    super::combine_polys(&polys, &coeffs, 7);
+}
@@ -45,12 +45,13 @@ where
 {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new prover channel for the specified `air` and public inputs.
-    pub fn new(air: &'a A, pub_inputs_bytes: Vec<u8>) -> Self {
+    /// Creates a new prover channel for the specified `air` with the public coin seeded with a
+    /// hash of the provided `coin_seed` elements.
+    pub fn new(air: &'a A, coin_seed: &[A::BaseField]) -> Self {
         let context = Context::new::<A::BaseField>(air.trace_info(), air.options().clone());
         ProverChannel {
             air,
-            public_coin: RandomCoin::new(&pub_inputs_bytes),
+            public_coin: RandomCoin::from_elements(coin_seed),
             context,
             commitments: Commitments::default(),
             ood_frame: OodFrame::default(),
@@ -159,6 +160,12 @@ where
         self.public_coin.reseed_with_int(nonce);
     }
 
+    /// Returns the events recorded by the public coin of this channel.
+    #[cfg(feature = "transcript")]
+    pub fn transcript(&self) -> &[crypto::TranscriptEvent] {
+        self.public_coin.transcript()
+    }
+
     // PROOF BUILDER
     // --------------------------------------------------------------------------------------------
     /// Builds a proof from the previously committed values as well as values passed into
@@ -203,3 +210,124 @@ where
         self.public_coin.draw().expect("failed to draw FRI alpha")
     }
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::ProverChannel;
+    use crate::{
+        tests::{CounterAir, CounterProver},
+        Prover,
+    };
+    use air::{get_public_coin_seed, Air, TraceInfo};
+    use crypto::{hashers::Blake2s_256, Digest, RandomCoin};
+    use math::{fields::f128::BaseElement, FieldElement};
+
+    type Blake2s = Blake2s_256<BaseElement>;
+
+    #[test]
+    fn public_coin_seed() {
+        let options = CounterProver::default().options().clone();
+        let pub_inputs = BaseElement::new(15);
+        let extra_seed = [BaseElement::new(7), BaseElement::new(11)];
+        let seed = get_public_coin_seed(&pub_inputs, &extra_seed);
+
+        // the verifier seeds its coin with a hash of public input elements followed by the
+        // extra seed elements
+        let mut verifier_coin = RandomCoin::<BaseElement, Blake2s>::from_elements(&[
+            pub_inputs,
+            extra_seed[0],
+            extra_seed[1],
+        ]);
+
+        // the prover must start from the same state as the verifier
+        let air = CounterAir::new(TraceInfo::new(2, 16), pub_inputs, options.clone());
+        let mut channel = ProverChannel::<_, BaseElement, Blake2s>::new(&air, &seed);
+        assert_eq!(
+            verifier_coin.seed.as_bytes(),
+            channel.public_coin.seed.as_bytes()
+        );
+        assert_eq!(
+            verifier_coin.draw::<BaseElement>().unwrap(),
+            channel.public_coin.draw::<BaseElement>().unwrap()
+        );
+
+        // trace info is not a part of the seed
+        let expected = RandomCoin::<BaseElement, Blake2s>::from_elements(&seed);
+        let air = CounterAir::new(TraceInfo::with_meta(2, 32, vec![1, 2]), pub_inputs, options);
+        let channel = ProverChannel::<_, BaseElement, Blake2s>::new(&air, &seed);
+        assert_eq!(
+            expected.seed.as_bytes(),
+            channel.public_coin.seed.as_bytes()
+        );
+
+        // different public inputs result in a different seed
+        let seed = get_public_coin_seed(&(pub_inputs + BaseElement::ONE), &extra_seed);
+        let channel = ProverChannel::<_, BaseElement, Blake2s>::new(&air, &seed);
+        assert_ne!(
+            expected.seed.as_bytes(),
+            channel.public_coin.seed.as_bytes()
+        );
+    }
+
+    #[cfg(feature = "transcript")]
+    #[test]
+    fn prover_and_verifier_transcripts_match() {
+        use crate::{tests::build_counter_columns, ProverObserver, TraceTable};
+        use core::cell::RefCell;
+        use crypto::TranscriptEvent;
+        use utils::collections::Vec;
+
+        /// Counter prover which records the transcript of its public coin.
+        #[derive(Default)]
+        struct TranscriptProver {
+            inner: CounterProver,
+            transcript: RefCell<Vec<TranscriptEvent>>,
+        }
+
+        impl ProverObserver for TranscriptProver {
+            fn on_transcript(&self, transcript: &[TranscriptEvent]) {
+                self.transcript.borrow_mut().extend_from_slice(transcript);
+            }
+        }
+
+        impl Prover for TranscriptProver {
+            type BaseField = BaseElement;
+            type Air = CounterAir;
+            type Trace = TraceTable<BaseElement>;
+
+            fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+                self.inner.get_pub_inputs(trace)
+            }
+
+            fn options(&self) -> &air::ProofOptions {
+                self.inner.options()
+            }
+
+            fn observer(&self) -> &dyn ProverObserver {
+                self
+            }
+        }
+
+        let prover = TranscriptProver::default();
+        let trace = TraceTable::init(build_counter_columns(16));
+        let result = prover.get_pub_inputs(&trace);
+        let proof = prover.prove(trace).unwrap();
+        let prover_transcript = prover.transcript.into_inner();
+        assert!(!prover_transcript.is_empty());
+
+        // for a valid proof, the transcripts of the prover and the verifier are identical
+        let (verdict, transcript) =
+            verifier::verify_with_transcript::<CounterAir>(proof.clone(), result);
+        assert!(verdict.is_ok());
+        assert_eq!(prover_transcript, transcript);
+
+        // for wrong public inputs, the transcripts diverge starting with the initial seed
+        let (verdict, transcript) =
+            verifier::verify_with_transcript::<CounterAir>(proof, result + BaseElement::ONE);
+        assert!(verdict.is_err());
+        assert_ne!(prover_transcript[0], transcript[0]);
+    }
+}
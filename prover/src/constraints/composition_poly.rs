@@ -19,19 +19,20 @@ pub struct CompositionPoly<E: FieldElement> {
 impl<E: FieldElement> CompositionPoly<E> {
     /// Returns a new composition polynomial.
     pub fn new(coefficients: Vec<E>, trace_length: usize) -> Self {
-        assert!(
-            coefficients.len().is_power_of_two(),
-            "size of composition polynomial must be a power of 2, but was {}",
-            coefficients.len(),
-        );
         assert!(
             trace_length.is_power_of_two(),
             "trace length must be a power of 2, but was {}",
             trace_length
         );
         assert!(
-            trace_length < coefficients.len(),
-            "trace length must be smaller than size of composition polynomial"
+            coefficients.len() % trace_length == 0,
+            "size of composition polynomial must be a multiple of trace length {}, but was {}",
+            trace_length,
+            coefficients.len(),
+        );
+        assert!(
+            !coefficients.is_empty(),
+            "composition polynomial must contain at least one column"
         );
         assert!(
             coefficients[coefficients.len() - 1] != E::ZERO,
@@ -99,6 +100,21 @@ impl<E: FieldElement> CompositionPoly<E> {
     pub fn into_columns(self) -> Vec<Vec<E>> {
         self.data.into_columns()
     }
+
+    /// Transforms this composition polynomial into a single vector of coefficients by merging
+    /// the column polynomials back together; this is the inverse of splitting the polynomial
+    /// into columns.
+    pub fn into_coefficients(self) -> Vec<E> {
+        let num_columns = self.num_columns();
+        let column_len = self.column_len();
+        let mut result = unsafe { uninit_vector(num_columns * column_len) };
+        for (col_idx, column) in self.into_columns().into_iter().enumerate() {
+            for (row_idx, coeff) in column.into_iter().enumerate() {
+                result[row_idx * num_columns + col_idx] = coeff;
+            }
+        }
+        result
+    }
 }
 
 // HELPER FUNCTIONS
@@ -133,9 +149,18 @@ fn transpose<E: FieldElement>(coefficients: Vec<E>, num_columns: usize) -> Vec<V
 #[cfg(test)]
 mod tests {
 
-    use math::fields::f128::BaseElement;
+    use crate::{
+        channel::ProverChannel,
+        tests::{build_counter_columns, CounterProver},
+        Prover, StarkDomain, Trace, TraceTable,
+    };
+    use air::{Air, PublicCoin};
+    use crypto::hashers::Blake2s_256;
+    use math::{fields::f128::BaseElement, log2, polynom, FieldElement, StarkField};
     use utils::collections::Vec;
 
+    type Blake2s = Blake2s_256<BaseElement>;
+
     #[test]
     fn transpose() {
         let values = (0u128..16).map(BaseElement::new).collect::<Vec<_>>();
@@ -151,4 +176,106 @@ mod tests {
 
         assert_eq!(expected, actual)
     }
+
+    #[test]
+    fn into_coefficients() {
+        let coefficients = (1u128..17).map(BaseElement::new).collect::<Vec<_>>();
+        let poly = super::CompositionPoly::new(coefficients.clone(), 4);
+        assert_eq!(4, poly.num_columns());
+        assert_eq!(coefficients, poly.into_coefficients());
+    }
+
+    #[test]
+    fn build_composition_poly() {
+        let prover = CounterProver::default();
+        let coefficients = prover
+            .build_composition_poly::<BaseElement, Blake2s>(build_trace())
+            .unwrap();
+
+        // build the composition polynomial which is used internally by the prover
+        let trace = build_trace();
+        let pub_inputs = prover.get_pub_inputs(&trace);
+        let air = <CounterProver as Prover>::Air::new(
+            trace.get_info(),
+            pub_inputs,
+            prover.options().clone(),
+        );
+        let mut channel =
+            ProverChannel::<_, BaseElement, Blake2s>::new(&air, &pub_inputs.to_elements());
+        let domain = StarkDomain::new(&air);
+        let (_, _, composition_poly) = prover
+            .commit_to_trace_and_compose(trace, &air, &mut channel, &domain, &|_| Ok(()))
+            .unwrap();
+        assert_eq!(
+            composition_poly.num_columns() * composition_poly.column_len(),
+            coefficients.len()
+        );
+
+        // evaluations of the returned coefficients over the LDE domain must match evaluations
+        // of the composition polynomial columns combined as the verifier combines them
+        let g = BaseElement::get_root_of_unity(log2(domain.lde_domain_size()));
+        let mut x = domain.offset();
+        for _ in 0..domain.lde_domain_size() {
+            let column_evaluations = composition_poly.evaluate_at(x);
+            let mut expected = BaseElement::ZERO;
+            let mut x_i = BaseElement::ONE;
+            for &value in column_evaluations.iter() {
+                expected += x_i * value;
+                x_i *= x;
+            }
+            assert_eq!(expected, polynom::eval(&coefficients, x));
+            x *= g;
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "diagnostics")]
+    fn build_composition_evaluations() {
+        use math::fft;
+
+        let prover = CounterProver::default();
+        let coefficients = prover
+            .build_composition_poly::<BaseElement, Blake2s>(build_trace())
+            .unwrap();
+        let (evaluations, ood) = prover
+            .build_composition_evaluations::<BaseElement, Blake2s>(build_trace())
+            .unwrap();
+
+        let trace = build_trace();
+        let pub_inputs = prover.get_pub_inputs(&trace);
+        let air = <CounterProver as Prover>::Air::new(
+            trace.get_info(),
+            pub_inputs,
+            prover.options().clone(),
+        );
+        let domain = StarkDomain::new(&air);
+        assert_eq!(domain.lde_domain_size(), evaluations.len());
+
+        // the evaluations interpolate to a polynomial of the expected degree
+        let degree = polynom::degree_of(&coefficients);
+        assert!(degree <= air.context().composition_degree());
+        assert_eq!(degree, fft::infer_degree(&evaluations, domain.offset()));
+
+        // and this polynomial is the composition polynomial
+        let mut poly = evaluations.clone();
+        let inv_twiddles = fft::get_inv_twiddles::<BaseElement>(poly.len());
+        fft::interpolate_poly_with_offset(&mut poly, &inv_twiddles, domain.offset());
+        assert_eq!(coefficients, poly[..coefficients.len()]);
+        assert!(poly[coefficients.len()..]
+            .iter()
+            .all(|&c| c == BaseElement::ZERO));
+
+        // out-of-domain evaluations are consistent with the composition polynomial
+        assert_eq!(
+            polynom::eval(&coefficients, ood.z),
+            ood.composition_evaluation()
+        );
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn build_trace() -> TraceTable<BaseElement> {
+        TraceTable::pad_to_power_of_two(build_counter_columns(100), None)
+    }
 }
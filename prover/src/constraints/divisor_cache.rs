@@ -0,0 +1,222 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{evaluation_table::get_inv_evaluation, ConstraintDivisor};
+use air::Air;
+use math::{FieldElement, StarkField};
+use utils::{batch_iter_mut, collections::Vec, uninit_vector};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// DIVISOR CACHE
+// ================================================================================================
+/// Precomputed evaluations of transition constraint divisors over a constraint evaluation domain.
+///
+/// Transition constraint divisors have the form $(x^n - 1) / e(x)$, where $n$ is the length of
+/// the execution trace and $e(x)$ describes the exemption points. Evaluating inverses of these
+/// divisors over the constraint evaluation domain requires a batch inversion and an evaluation
+/// of $e(x)$ at every point of the domain. These values depend only on the trace length, the
+/// constraint evaluation domain (its blowup factor and offset), and the number of transition
+/// exemptions - and thus, when the same AIR is proven repeatedly, they can be computed once and
+/// reused across proofs.
+///
+/// A cache is built for a specific instance of an AIR via [DivisorCache::new()] and can be
+/// supplied to the prover via [Prover::divisor_cache()](crate::Prover::divisor_cache). The
+/// prover uses the cache only if [DivisorCache::is_valid_for()] returns true for the AIR of the
+/// proof being generated; otherwise, divisors are evaluated from scratch. A stale cache (e.g.,
+/// after the number of transition exemptions has changed) can be rebuilt via
+/// [DivisorCache::update()].
+pub struct DivisorCache<B: StarkField> {
+    trace_length: usize,
+    ce_blowup_factor: usize,
+    domain_offset: B,
+    transition_exemptions: Vec<usize>,
+    entries: Vec<(ConstraintDivisor<B>, Vec<B>)>,
+}
+
+impl<B: StarkField> DivisorCache<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new cache containing inverse evaluations of all distinct transition constraint
+    /// divisors of the specified AIR over its constraint evaluation domain.
+    pub fn new<A: Air<BaseField = B>>(air: &A) -> Self {
+        let trace_length = air.trace_length();
+        let domain_size = air.ce_domain_size();
+        let domain_offset = air.domain_offset();
+        let transition_exemptions = air.context().num_transition_exemptions();
+
+        // transition constraints with the same number of exemptions share the same divisor; so,
+        // we evaluate each distinct divisor only once
+        let mut entries: Vec<(ConstraintDivisor<B>, Vec<B>)> = Vec::new();
+        for &num_exemptions in transition_exemptions.iter() {
+            let divisor = ConstraintDivisor::from_transition(trace_length, num_exemptions);
+            if entries.iter().all(|(cached, _)| cached != &divisor) {
+                let evaluations = evaluate_inv_divisor(&divisor, domain_size, domain_offset);
+                entries.push((divisor, evaluations));
+            }
+        }
+
+        DivisorCache {
+            trace_length,
+            ce_blowup_factor: air.ce_blowup_factor(),
+            domain_offset,
+            transition_exemptions,
+            entries,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the length of the execution trace for which this cache was built.
+    pub fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    /// Returns the blowup factor of the constraint evaluation domain for which this cache was
+    /// built.
+    pub fn ce_blowup_factor(&self) -> usize {
+        self.ce_blowup_factor
+    }
+
+    /// Returns the number of distinct divisors stored in this cache.
+    pub fn num_divisors(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if this cache can be used to generate proofs for the specified AIR.
+    ///
+    /// This is the case when the trace length, the blowup factor and the offset of the constraint
+    /// evaluation domain, and the numbers of transition exemptions of the AIR are all the same as
+    /// the ones used to build this cache.
+    pub fn is_valid_for<A: Air<BaseField = B>>(&self, air: &A) -> bool {
+        self.trace_length == air.trace_length()
+            && self.ce_blowup_factor == air.ce_blowup_factor()
+            && self.domain_offset == air.domain_offset()
+            && self.transition_exemptions == air.context().num_transition_exemptions()
+    }
+
+    /// Returns inverse evaluations of the specified divisor over the constraint evaluation
+    /// domain, or None if the divisor is not in this cache.
+    pub fn get(&self, divisor: &ConstraintDivisor<B>) -> Option<&[B]> {
+        self.entries
+            .iter()
+            .find(|(cached, _)| cached == divisor)
+            .map(|(_, evaluations)| evaluations.as_slice())
+    }
+
+    // PUBLIC MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Rebuilds this cache for the specified AIR if the cache is not valid for it.
+    ///
+    /// Returns true if the cache was rebuilt, and false if the cache was already valid for the
+    /// specified AIR.
+    pub fn update<A: Air<BaseField = B>>(&mut self, air: &A) -> bool {
+        if self.is_valid_for(air) {
+            return false;
+        }
+        *self = Self::new(air);
+        true
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes evaluations of 1 / z(x) over the domain of the specified size and offset, where
+/// z(x) = (x^n - 1) / e(x) is a transition constraint divisor.
+fn evaluate_inv_divisor<B: StarkField>(
+    divisor: &ConstraintDivisor<B>,
+    domain_size: usize,
+    domain_offset: B,
+) -> Vec<B> {
+    // compute inverse evaluations of the divisor's numerator; these repeat with a period of
+    // domain_size / n, and thus, only the first period is computed
+    let z = get_inv_evaluation(divisor, domain_size, domain_offset);
+
+    // multiply the inverse numerator by e(x) at every point of the domain
+    let g = B::get_root_of_unity(domain_size.trailing_zeros());
+    let mut result = unsafe { uninit_vector(domain_size) };
+    batch_iter_mut!(
+        &mut result,
+        128, // min batch size
+        |batch: &mut [B], batch_offset: usize| {
+            let mut x = domain_offset * g.exp((batch_offset as u64).into());
+            for (i, evaluation) in batch.iter_mut().enumerate() {
+                let e = divisor.evaluate_exemptions_at(x);
+                *evaluation = z[(batch_offset + i) % z.len()] * e;
+                x *= g;
+            }
+        }
+    );
+    result
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::DivisorCache;
+    use crate::tests::MockAir;
+    use air::{Air, ConstraintDivisor};
+    use math::{
+        fields::f128::BaseElement, get_power_series_with_offset, log2, FieldElement, StarkField,
+    };
+
+    #[test]
+    fn divisor_cache() {
+        let trace_length = 16;
+        let air = MockAir::with_trace_length(trace_length);
+        let cache = DivisorCache::new(&air);
+
+        assert_eq!(trace_length, cache.trace_length());
+        assert_eq!(air.ce_blowup_factor(), cache.ce_blowup_factor());
+        assert_eq!(1, cache.num_divisors());
+        assert!(cache.is_valid_for(&air));
+
+        // cached values should be equal to 1 / z(x) for all x in the constraint evaluation domain
+        let divisor = ConstraintDivisor::<BaseElement>::from_transition(trace_length, 1);
+        let g = BaseElement::get_root_of_unity(log2(air.ce_domain_size()));
+        let domain = get_power_series_with_offset(g, air.domain_offset(), air.ce_domain_size());
+        let evaluations = cache.get(&divisor).unwrap();
+        assert_eq!(domain.len(), evaluations.len());
+        for (&x, &evaluation) in domain.iter().zip(evaluations) {
+            assert_eq!(divisor.evaluate_at(x).inv(), evaluation);
+        }
+
+        // divisors which were not cached should not be found
+        let divisor = ConstraintDivisor::<BaseElement>::from_transition(trace_length, 2);
+        assert!(cache.get(&divisor).is_none());
+
+        // cache is not valid for an AIR with a different trace length
+        let mut cache = cache;
+        let air = MockAir::with_trace_length(trace_length * 2);
+        assert!(!cache.is_valid_for(&air));
+        assert!(cache.update(&air));
+        assert!(cache.is_valid_for(&air));
+        assert!(!cache.update(&air));
+    }
+
+    #[test]
+    fn divisor_cache_invalidated_by_exemptions() {
+        let trace_length = 16;
+        let air = MockAir::with_trace_length(trace_length);
+        let mut cache = DivisorCache::new(&air);
+
+        // changing the number of exemptions makes the cache stale
+        let air = MockAir::with_transition_exemptions(2, trace_length);
+        assert!(!cache.is_valid_for(&air));
+        assert!(cache.update(&air));
+        assert!(cache.is_valid_for(&air));
+
+        let divisor = ConstraintDivisor::<BaseElement>::from_transition(trace_length, 2);
+        assert!(cache.get(&divisor).is_some());
+        let divisor = ConstraintDivisor::<BaseElement>::from_transition(trace_length, 1);
+        assert!(cache.get(&divisor).is_none());
+    }
+}
@@ -3,8 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{CompositionPoly, ConstraintDivisor, ProverError, StarkDomain};
-use math::{batch_inversion, fft, FieldElement, StarkField};
+use super::{CompositionPoly, ConstraintDivisor, DivisorCache, ProverError, StarkDomain};
+use math::{batch_inversion, fft, polynom, FieldElement, StarkField};
 use utils::{batch_iter_mut, collections::Vec, iter_mut, uninit_vector};
 
 #[cfg(debug_assertions)]
@@ -26,6 +26,7 @@ pub struct ConstraintEvaluationTable<E: FieldElement> {
     divisors: Vec<ConstraintDivisor<E::BaseField>>,
     domain_offset: E::BaseField,
     trace_length: usize,
+    composition_degree: usize,
 
     #[cfg(debug_assertions)]
     main_transition_evaluations: Vec<Vec<E::BaseField>>,
@@ -45,9 +46,14 @@ impl<E: FieldElement> ConstraintEvaluationTable<E> {
     /// Returns a new constraint evaluation table with number of columns equal to the number of
     /// specified divisors, and number of rows equal to the size of constraint evaluation domain
     /// divided by the evaluation frame shift.
+    ///
+    /// `composition_degree` is the degree to which all constraint polynomials are normalized
+    /// before they are composed together; the composition polynomial built from this table is
+    /// expected to have exactly this degree.
     #[cfg(not(debug_assertions))]
     pub fn new(
         domain: &StarkDomain<E::BaseField>,
+        composition_degree: usize,
         divisors: Vec<ConstraintDivisor<E::BaseField>>,
     ) -> Self {
         let num_columns = divisors.len();
@@ -57,6 +63,7 @@ impl<E: FieldElement> ConstraintEvaluationTable<E> {
             divisors,
             domain_offset: domain.offset(),
             trace_length: domain.trace_length(),
+            composition_degree,
         }
     }
 
@@ -66,6 +73,7 @@ impl<E: FieldElement> ConstraintEvaluationTable<E> {
     #[cfg(debug_assertions)]
     pub fn new(
         domain: &StarkDomain<E::BaseField>,
+        composition_degree: usize,
         divisors: Vec<ConstraintDivisor<E::BaseField>>,
         transition_constraints: &TransitionConstraints<E>,
         main_divisors: Vec<ConstraintDivisor<E::BaseField>>,
@@ -90,6 +98,7 @@ impl<E: FieldElement> ConstraintEvaluationTable<E> {
             divisors,
             domain_offset: domain.offset(),
             trace_length: domain.trace_length(),
+            composition_degree,
             main_transition_evaluations: uninit_matrix(num_tm_columns, num_rows),
             aux_transition_evaluations: uninit_matrix(num_ta_columns, num_rows),
             expected_transition_degrees,
@@ -174,7 +183,14 @@ impl<E: FieldElement> ConstraintEvaluationTable<E> {
     /// Divides constraint evaluation columns by their respective divisor (in evaluation form),
     /// combines the results into a single column, and interpolates this column into a composition
     /// polynomial in coefficient form.
-    pub fn into_poly(self) -> Result<CompositionPoly<E>, ProverError> {
+    ///
+    /// If `divisor_cache` is provided, inverse divisor evaluations found in the cache are used
+    /// instead of being computed from scratch. The cache is assumed to have been built for the
+    /// same constraint evaluation domain as the one used by this table.
+    pub fn into_poly(
+        self,
+        divisor_cache: Option<&DivisorCache<E::BaseField>>,
+    ) -> Result<CompositionPoly<E>, ProverError> {
         let domain_offset = self.domain_offset;
 
         // allocate memory for the combined polynomial
@@ -187,10 +203,14 @@ impl<E: FieldElement> ConstraintEvaluationTable<E> {
             // in debug mode, make sure post-division degree of each column matches the expected
             // degree
             #[cfg(debug_assertions)]
-            validate_column_degree(&column, divisor, domain_offset, column.len() - 1)?;
+            validate_column_degree(&column, divisor, domain_offset, self.composition_degree)?;
 
-            // divide the column by the divisor and accumulate the result into combined_poly
-            acc_column(column, divisor, domain_offset, &mut combined_poly);
+            // divide the column by the divisor and accumulate the result into combined_poly; if
+            // inverse evaluations of the divisor are cached, use them directly
+            match divisor_cache.and_then(|cache| cache.get(divisor)) {
+                Some(inv_divisor) => acc_column_cached(column, inv_divisor, &mut combined_poly),
+                None => acc_column(column, divisor, domain_offset, &mut combined_poly),
+            }
         }
 
         // at this point, combined_poly contains evaluations of the combined constraint polynomial;
@@ -198,6 +218,19 @@ impl<E: FieldElement> ConstraintEvaluationTable<E> {
         let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(combined_poly.len());
         fft::interpolate_poly_with_offset(&mut combined_poly, &inv_twiddles, domain_offset);
 
+        // the constraint evaluation domain may be larger than needed to describe the composition
+        // polynomial (e.g., when a composition degree hint was provided); in such cases, the
+        // polynomial must not have any coefficients beyond the composition degree - otherwise,
+        // the proof would be invalid
+        let degree = polynom::degree_of(&combined_poly);
+        if degree > self.composition_degree {
+            return Err(ProverError::CompositionDegreeExceeded(
+                self.composition_degree,
+                degree,
+            ));
+        }
+        combined_poly.truncate(self.composition_degree + 1);
+
         Ok(CompositionPoly::new(combined_poly, self.trace_length))
     }
 
@@ -241,6 +274,30 @@ impl<E: FieldElement> ConstraintEvaluationTable<E> {
             max_degree = core::cmp::max(max_degree, degree);
         }
 
+        // make sure none of the constraints exceeds its declared degree; an understated degree
+        // would make the proof unsound, and thus, we report the offending constraint explicitly
+        let num_main_constraints = self.main_transition_evaluations.len();
+        for (i, (&expected, &actual)) in self
+            .expected_transition_degrees
+            .iter()
+            .zip(actual_degrees.iter())
+            .enumerate()
+        {
+            let (segment, index) = if i < num_main_constraints {
+                ("main", i)
+            } else {
+                ("auxiliary", i - num_main_constraints)
+            };
+            assert!(
+                actual <= expected,
+                "degree of {} transition constraint {} exceeds its declared degree; expected at most {}, but was {}",
+                segment,
+                index,
+                expected,
+                actual
+            );
+        }
+
         // make sure expected and actual degrees are equal
         assert_eq!(
             self.expected_transition_degrees, actual_degrees,
@@ -363,9 +420,10 @@ fn acc_column<E: FieldElement>(
     // constraints, it is computed similarly, but the result is also multiplied by the divisor's
     // denominator (exclusion point).
     if divisor.exemptions().is_empty() {
-        // the column represents merged evaluations of boundary constraints, and divisor has the
-        // form of (x^a - b); thus to divide the column by the divisor, we compute: value * z,
-        // where z = 1 / (x^a - 1) and has already been computed above.
+        // the column represents merged evaluations of boundary constraints (or of cyclic
+        // transition constraints), and divisor has the form of (x^a - b); thus to divide the
+        // column by the divisor, we compute: value * z, where z = 1 / (x^a - 1) and has already
+        // been computed above.
         iter_mut!(result, 1024)
             .zip(column)
             .enumerate()
@@ -403,9 +461,25 @@ fn acc_column<E: FieldElement>(
     }
 }
 
+/// Divides column values by the divisor using precomputed inverse evaluations of the divisor and
+/// accumulates the results into `result`.
+fn acc_column_cached<E: FieldElement>(
+    column: Vec<E>,
+    inv_divisor: &[E::BaseField],
+    result: &mut [E],
+) {
+    debug_assert_eq!(column.len(), inv_divisor.len());
+    iter_mut!(result, 1024)
+        .zip(column)
+        .zip(inv_divisor)
+        .for_each(|((acc_value, value), &z)| {
+            *acc_value += value.mul_base(z);
+        });
+}
+
 /// Computes evaluations of the divisor's numerator over the domain of the specified size and offset.
 #[allow(clippy::many_single_char_names)]
-fn get_inv_evaluation<B: StarkField>(
+pub(super) fn get_inv_evaluation<B: StarkField>(
     divisor: &ConstraintDivisor<B>,
     domain_size: usize,
     domain_offset: B,
@@ -454,6 +528,9 @@ fn build_transition_constraint_degrees<E: FieldElement>(
     main_divisors: Vec<ConstraintDivisor<E::BaseField>>,
     aux_divisors: Vec<ConstraintDivisor<E::BaseField>>,
 ) -> Vec<usize> {
+    // for cyclic transition constraints the divisor has degree equal to the trace length; if
+    // the evaluation degree is smaller than that, the constraint polynomial must be identically
+    // zero, and thus, the degree of the quotient is 0
     let mut result = Vec::new();
 
     for (constraint_degree, divisor) in constraints
@@ -461,7 +538,11 @@ fn build_transition_constraint_degrees<E: FieldElement>(
         .iter()
         .zip(main_divisors)
     {
-        result.push(constraint_degree.get_evaluation_degree(trace_length) - divisor.degree())
+        result.push(
+            constraint_degree
+                .get_evaluation_degree(trace_length)
+                .saturating_sub(divisor.degree()),
+        )
     }
 
     for (constraint_degree, divisor) in constraints
@@ -469,7 +550,11 @@ fn build_transition_constraint_degrees<E: FieldElement>(
         .iter()
         .zip(aux_divisors)
     {
-        result.push(constraint_degree.get_evaluation_degree(trace_length) - divisor.degree())
+        result.push(
+            constraint_degree
+                .get_evaluation_degree(trace_length)
+                .saturating_sub(divisor.degree()),
+        )
     }
 
     //for constraint in constraints.main_constraints() {
@@ -71,11 +71,53 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
     /// Evaluates constraints against the provided extended execution trace. Constraints are
     /// evaluated over a constraint evaluation domain. This is an optimization because constraint
     /// evaluation domain can be many times smaller than the full LDE domain.
+    ///
+    /// When `concurrent` feature is enabled, the constraint evaluation domain is broken into
+    /// fragments which are evaluated in parallel (unless the domain is small).
     pub fn evaluate(
         self,
         trace: &TraceLde<E>,
         domain: &StarkDomain<E::BaseField>,
     ) -> ConstraintEvaluationTable<E> {
+        // when `concurrent` feature is enabled, break the evaluation table into multiple fragments
+        // to evaluate them into multiple threads; unless the constraint evaluation domain is small,
+        // then don't bother with concurrent evaluation
+
+        #[cfg(not(feature = "concurrent"))]
+        let num_fragments = 1;
+
+        #[cfg(feature = "concurrent")]
+        let num_fragments = if domain.ce_domain_size() >= MIN_CONCURRENT_DOMAIN_SIZE {
+            rayon::current_num_threads().next_power_of_two()
+        } else {
+            1
+        };
+
+        self.evaluate_in_fragments(trace, domain, num_fragments)
+    }
+
+    /// Evaluates constraints against the provided extended execution trace by breaking the
+    /// constraint evaluation domain into the specified number of fragments.
+    ///
+    /// Each fragment covers a disjoint range of the constraint evaluation table and reads
+    /// evaluation frames into its own buffers; thus, fragments can be evaluated independently.
+    /// When `concurrent` feature is enabled, fragments are evaluated in parallel. The result
+    /// does not depend on the number of fragments.
+    ///
+    /// # Panics
+    /// Panics if `num_fragments` is not a power of two, or if fragments would be smaller than 16
+    /// rows.
+    pub fn evaluate_in_fragments(
+        self,
+        trace: &TraceLde<E>,
+        domain: &StarkDomain<E::BaseField>,
+        num_fragments: usize,
+    ) -> ConstraintEvaluationTable<E> {
+        assert!(
+            num_fragments.is_power_of_two(),
+            "number of fragments must be a power of two, but was {}",
+            num_fragments
+        );
         assert_eq!(
             trace.trace_len(),
             domain.lde_domain_size(),
@@ -95,30 +137,18 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         // memory to hold all transition constraint evaluations (before they are merged into a
         // single value) so that we can check their degrees later
         #[cfg(not(debug_assertions))]
-        let mut evaluation_table = ConstraintEvaluationTable::<E>::new(domain, divisors);
+        let mut evaluation_table =
+            ConstraintEvaluationTable::<E>::new(domain, self.air.composition_degree(), divisors);
         #[cfg(debug_assertions)]
         let mut evaluation_table = ConstraintEvaluationTable::<E>::new(
             domain,
+            self.air.composition_degree(),
             divisors,
             &self.transition_constraints,
             main_divisors,
             aux_divisors,
         );
 
-        // when `concurrent` feature is enabled, break the evaluation table into multiple fragments
-        // to evaluate them into multiple threads; unless the constraint evaluation domain is small,
-        // then don't bother with concurrent evaluation
-
-        #[cfg(not(feature = "concurrent"))]
-        let num_fragments = 1;
-
-        #[cfg(feature = "concurrent")]
-        let num_fragments = if domain.ce_domain_size() >= MIN_CONCURRENT_DOMAIN_SIZE {
-            rayon::current_num_threads().next_power_of_two()
-        } else {
-            1
-        };
-
         // evaluate constraints for each fragment; if the trace consist of multiple segments
         // we evaluate constraints for all segments. otherwise, we evaluate constraints only
         // for the main segment.
@@ -303,12 +333,18 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         // TODO: use a more efficient way to zero out memory
         evaluations.fill(E::BaseField::ZERO);
 
-        // get periodic values at the evaluation step
+        // get periodic values at the evaluation step and at the next step of the trace
         let periodic_values = self.periodic_values.get_row(step);
+        let next_periodic_values = self.periodic_values.get_next_row(step);
 
         // evaluate transition constraints over the main segment of the execution trace and save
         // the results into evaluations buffer
-        self.air.evaluate_transition(main_frame, periodic_values, evaluations);
+        self.air.evaluate_transition(
+            main_frame,
+            periodic_values,
+            next_periodic_values,
+            evaluations,
+        );
 
         // merge transition constraint evaluations into a single value and return it;
         self.transition_constraints.main_constraints().iter().map(|group| {
@@ -333,13 +369,21 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         // TODO: use a more efficient way to zero out memory
         evaluations.fill(E::ZERO);
 
-        // get periodic values at the evaluation step
+        // get periodic values at the evaluation step and at the next step of the trace
         let periodic_values = self.periodic_values.get_row(step);
+        let next_periodic_values = self.periodic_values.get_next_row(step);
 
         // evaluate transition constraints over auxiliary trace segments and save the results into
         // evaluations buffer
         self.air.evaluate_aux_transition(
             main_frame,
+            aux_frame,
+            periodic_values,
+            next_periodic_values,
+            &self.aux_rand_elements,
+            evaluations,
+        );
+        self.air.evaluate_lagrange_kernel_aux_transition(
             aux_frame,
             periodic_values,
             &self.aux_rand_elements,
@@ -366,3 +410,234 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         self.transition_constraints.num_aux_constraints()
     }
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{ConstraintEvaluator, StarkDomain, TraceLde};
+    use crate::{
+        tests::{build_counter_columns, CounterAir, CounterProver},
+        Prover, Trace, TraceTable,
+    };
+    use air::{
+        Air, AuxTraceRandElements, DefaultEvaluationFrame, EvaluationFrame, Table,
+        TransitionConstraintDegree,
+    };
+    use crypto::{hashers::Blake2s_256, RandomCoin};
+    use math::{fields::f128::BaseElement, FieldElement};
+
+    type Blake2s = Blake2s_256<BaseElement>;
+
+    #[test]
+    fn evaluate_in_fragments() {
+        let trace = TraceTable::pad_to_power_of_two(build_counter_columns(100), None);
+        let prover = CounterProver::default();
+        let air = CounterAir::new(
+            trace.get_info(),
+            prover.get_pub_inputs(&trace),
+            prover.options().clone(),
+        );
+
+        // extend the trace over the LDE domain
+        let domain = StarkDomain::new(&air);
+        let trace_polys = trace.main_segment().interpolate_columns();
+        let trace_lde = TraceLde::<BaseElement>::new(
+            trace_polys.evaluate_columns_over(&domain),
+            domain.trace_to_lde_blowup(),
+        );
+
+        let mut public_coin = RandomCoin::<BaseElement, Blake2s>::new(&[]);
+        let coefficients = air
+            .get_constraint_composition_coefficients::<BaseElement, Blake2s>(&mut public_coin)
+            .unwrap();
+
+        // evaluating constraints in a single fragment is the same as evaluating them serially
+        let expected =
+            ConstraintEvaluator::new(&air, AuxTraceRandElements::new(), coefficients.clone())
+                .evaluate_in_fragments(&trace_lde, &domain, 1)
+                .into_poly(None)
+                .unwrap()
+                .into_columns();
+
+        for num_fragments in [2, 4, 16] {
+            let actual =
+                ConstraintEvaluator::new(&air, AuxTraceRandElements::new(), coefficients.clone())
+                    .evaluate_in_fragments(&trace_lde, &domain, num_fragments)
+                    .into_poly(None)
+                    .unwrap()
+                    .into_columns();
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn evaluate_transition_at_random_point() {
+        let trace = TraceTable::pad_to_power_of_two(build_counter_columns(100), None);
+        let prover = CounterProver::default();
+        let air = CounterAir::new(
+            trace.get_info(),
+            prover.get_pub_inputs(&trace),
+            prover.options().clone(),
+        );
+        assert_eq!(2, check_composition_at_random_point(&air, &trace));
+    }
+
+    #[test]
+    fn evaluate_with_composition_degree_hint() {
+        let trace = TraceTable::pad_to_power_of_two(build_counter_columns(100), None);
+        let prover = CounterProver::default();
+        let air = CounterAir::new(
+            trace.get_info(),
+            prover.get_pub_inputs(&trace),
+            prover.options().clone(),
+        );
+        assert_eq!(2, air.num_composition_columns());
+
+        // all constraints of the counter AIR have degree at most 2; thus, once divided by their
+        // divisors, they have degree at most trace_length - 1 and fit into a single column
+        let air = air.with_composition_degree_hint(trace.length() - 1);
+        assert_eq!(1, air.num_composition_columns());
+        assert_eq!(1, check_composition_at_random_point(&air, &trace));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "degree of main transition constraint 1 exceeds its declared degree")]
+    fn evaluate_with_understated_degree() {
+        let trace = TraceTable::pad_to_power_of_two(build_counter_columns(100), None);
+        let prover = CounterProver::default();
+        let air = CounterAir::new(
+            trace.get_info(),
+            prover.get_pub_inputs(&trace),
+            prover.options().clone(),
+        );
+
+        // the constraint which checks that the flag is binary has degree 2, but is declared as
+        // a degree 1 constraint
+        let air = air.with_transition_degrees(vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(2),
+        ]);
+        check_composition_at_random_point(&air, &trace);
+    }
+
+    // in debug mode, degrees of individual constraint evaluation columns are validated before
+    // they are combined, and thus, an invalid composition polynomial is reported earlier
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn evaluate_with_composition_degree_exceeded() {
+        use crate::ProverError;
+
+        // the flag is not binary at step 3, and thus, the trace does not satisfy the constraints
+        let mut columns = build_counter_columns(16);
+        columns[1][3] = BaseElement::new(2);
+        let trace = TraceTable::init(columns);
+
+        let prover = CounterProver::default();
+        let air = CounterAir::new(
+            trace.get_info(),
+            prover.get_pub_inputs(&trace),
+            prover.options().clone(),
+        )
+        .with_composition_degree_hint(trace.length() - 1);
+
+        // extend the trace over the LDE domain
+        let domain = StarkDomain::new(&air);
+        let trace_polys = trace.main_segment().interpolate_columns();
+        let trace_lde = TraceLde::<BaseElement>::new(
+            trace_polys.evaluate_columns_over(&domain),
+            domain.trace_to_lde_blowup(),
+        );
+
+        let mut public_coin = RandomCoin::<BaseElement, Blake2s>::new(&[]);
+        let coefficients = air
+            .get_constraint_composition_coefficients::<BaseElement, Blake2s>(&mut public_coin)
+            .unwrap();
+
+        // quotients of unsatisfied constraints are not polynomials, and thus, their interpolants
+        // have degrees beyond the bound implied by the composition degree hint
+        let result = ConstraintEvaluator::new(&air, AuxTraceRandElements::new(), coefficients)
+            .evaluate(&trace_lde, &domain)
+            .into_poly(None);
+        assert!(matches!(
+            result,
+            Err(ProverError::CompositionDegreeExceeded(15, degree)) if degree > 15
+        ));
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that the composition polynomial built by the prover for the specified trace matches
+    /// an independent computation of its value at a random point, and returns the number of
+    /// composition polynomial columns.
+    fn check_composition_at_random_point(
+        air: &CounterAir,
+        trace: &TraceTable<BaseElement>,
+    ) -> usize {
+        // extend the trace over the LDE domain
+        let domain = StarkDomain::new(air);
+        let trace_polys = trace.main_segment().interpolate_columns();
+        let trace_lde = TraceLde::<BaseElement>::new(
+            trace_polys.evaluate_columns_over(&domain),
+            domain.trace_to_lde_blowup(),
+        );
+
+        let mut public_coin = RandomCoin::<BaseElement, Blake2s>::new(&[]);
+        let coefficients = air
+            .get_constraint_composition_coefficients::<BaseElement, Blake2s>(&mut public_coin)
+            .unwrap();
+        let z = public_coin.draw::<BaseElement>().unwrap();
+
+        // evaluate the composition polynomial built by the prover at z
+        let composition_poly =
+            ConstraintEvaluator::new(air, AuxTraceRandElements::new(), coefficients.clone())
+                .evaluate(&trace_lde, &domain)
+                .into_poly(None)
+                .unwrap();
+        let expected = composition_poly
+            .evaluate_at(z)
+            .into_iter()
+            .rev()
+            .fold(BaseElement::ZERO, |acc, value| acc * z + value);
+
+        // independently compute the same value from trace polynomials evaluated at z and z * g
+        let g = air.trace_domain_generator();
+        let frame = DefaultEvaluationFrame::from_table(Table::from_rows(vec![
+            trace_polys.evaluate_columns_at(z),
+            trace_polys.evaluate_columns_at(z * g),
+        ]));
+
+        let quotients = air.evaluate_transition_at(&frame, z);
+        let t_constraints = air.get_transition_constraints(&coefficients.transition);
+        let t_value = t_constraints
+            .main_constraints()
+            .iter()
+            .fold(BaseElement::ZERO, |acc, group| {
+                acc + group.merge_evaluations::<BaseElement, BaseElement>(&quotients, z)
+            });
+
+        // the quotients must be consistent with the way the verifier combines raw evaluations
+        let mut evaluations = BaseElement::zeroed_vector(quotients.len());
+        air.evaluate_transition(&frame, &[], &[], &mut evaluations);
+        assert_eq!(
+            t_constraints.combine_evaluations::<BaseElement>(&evaluations, &[], z),
+            t_value
+        );
+
+        let b_constraints =
+            air.get_boundary_constraints(&AuxTraceRandElements::new(), &coefficients.boundary);
+        let mut b_value = BaseElement::ZERO;
+        for group in b_constraints.main_constraints() {
+            let xp = z.exp(group.degree_adjustment().into());
+            b_value += group.evaluate_at(frame.current(), z, xp);
+        }
+
+        assert_eq!(expected, t_value + b_value);
+
+        composition_poly.num_columns()
+    }
+}
@@ -20,5 +20,8 @@ pub use composition_poly::CompositionPoly;
 mod evaluation_table;
 pub use evaluation_table::ConstraintEvaluationTable;
 
+mod divisor_cache;
+pub use divisor_cache::DivisorCache;
+
 mod commitment;
 pub use commitment::ConstraintCommitment;
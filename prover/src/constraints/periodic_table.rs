@@ -4,16 +4,14 @@
 // LICENSE file in the root directory of this source tree.
 
 use air::Air;
-use math::{fft, StarkField};
-use utils::{
-    collections::{BTreeMap, Vec},
-    uninit_vector,
-};
+use math::StarkField;
+use utils::{collections::Vec, uninit_vector};
 
 pub struct PeriodicValueTable<B: StarkField> {
     values: Vec<B>,
     length: usize,
     width: usize,
+    ce_blowup: usize,
 }
 
 impl<B: StarkField> PeriodicValueTable<B> {
@@ -23,43 +21,26 @@ impl<B: StarkField> PeriodicValueTable<B> {
     /// values of all periodic columns normalized to the same length. This enables simple lookup
     /// into the able using step index of the constraint evaluation domain.
     pub fn new<A: Air<BaseField = B>>(air: &A) -> PeriodicValueTable<B> {
-        // get a list of polynomials describing periodic columns from AIR. if there are no
+        // evaluate all periodic columns over the constraint evaluation domain. if there are no
         // periodic columns return an empty table
-        let polys = air.get_periodic_column_polys();
-        if polys.is_empty() {
+        let evaluations =
+            air.get_periodic_column_evaluations(air.ce_domain_size(), air.domain_offset());
+        if evaluations.is_empty() {
             return PeriodicValueTable {
                 values: Vec::new(),
                 length: 0,
                 width: 0,
+                ce_blowup: air.ce_blowup_factor(),
             };
         }
 
-        // determine the size of the biggest polynomial in the set. unwrap is OK here
-        // because if we get here, there must be at least one polynomial in the set.
-        let max_poly_size = polys.iter().max_by_key(|p| p.len()).unwrap().len();
-
-        // cache twiddles used for polynomial evaluation here so that we don't have to re-build
-        // them for polynomials of the same size
-        let mut twiddle_map = BTreeMap::new();
-
-        let evaluations = polys
-            .iter()
-            .map(|poly| {
-                let poly_size = poly.len();
-                let num_cycles = (air.trace_length() / poly_size) as u64;
-                let offset = air.domain_offset().exp(num_cycles.into());
-                let twiddles = twiddle_map
-                    .entry(poly_size)
-                    .or_insert_with(|| fft::get_twiddles(poly_size));
-
-                fft::evaluate_poly_with_offset(poly, twiddles, offset, air.ce_blowup_factor())
-            })
-            .collect::<Vec<_>>();
+        // determine the length of the longest evaluation vector in the set. unwrap is OK here
+        // because if we get here, there must be at least one column in the set.
+        let column_length = evaluations.iter().map(|e| e.len()).max().unwrap();
 
         // allocate memory to hold all expanded values and copy polynomial evaluations into the
         // table in such a way that values for the same row are adjacent to each other.
-        let row_width = polys.len();
-        let column_length = max_poly_size * air.ce_blowup_factor();
+        let row_width = evaluations.len();
         let mut values = unsafe { uninit_vector(row_width * column_length) };
         for i in 0..column_length {
             for (j, column) in evaluations.iter().enumerate() {
@@ -71,6 +52,7 @@ impl<B: StarkField> PeriodicValueTable<B> {
             values,
             length: column_length,
             width: row_width,
+            ce_blowup: air.ce_blowup_factor(),
         }
     }
 
@@ -89,6 +71,15 @@ impl<B: StarkField> PeriodicValueTable<B> {
             &self.values[start..start + self.width]
         }
     }
+
+    /// Returns periodic values at the step of the execution trace which follows the specified
+    /// step of the constraint evaluation domain.
+    ///
+    /// Moving one step forward in the execution trace corresponds to moving `ce_blowup` steps
+    /// forward in the constraint evaluation domain.
+    pub fn get_next_row(&self, ce_step: usize) -> &[B] {
+        self.get_row(ce_step + self.ce_blowup)
+    }
 }
 
 // TESTS
@@ -154,6 +145,43 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn periodic_value_table_next_row() {
+        let trace_length = 32;
+
+        // instantiate AIR with 2 periodic columns
+        let col1 = vec![1u128, 2]
+            .into_iter()
+            .map(BaseElement::new)
+            .collect::<Vec<_>>();
+        let col2 = vec![3u128, 4, 5, 6]
+            .into_iter()
+            .map(BaseElement::new)
+            .collect::<Vec<_>>();
+        let air = MockAir::with_periodic_columns(vec![col1, col2], trace_length);
+
+        // build a table of periodic values
+        let table = super::PeriodicValueTable::new(&air);
+
+        let polys = air.get_periodic_column_polys();
+        let domain = build_ce_domain(air.ce_domain_size(), air.domain_offset());
+        let g = air.trace_domain_generator();
+
+        // next row at every step of ce_domain should be equal to evaluations of polynomials at
+        // x * g, where g is the generator of the trace domain
+        for (i, &x) in domain.iter().enumerate() {
+            let expected = polys
+                .iter()
+                .map(|poly| {
+                    let num_cycles = trace_length / poly.len();
+                    let x = (x * g).exp((num_cycles as u32).into());
+                    polynom::eval(poly, x)
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(expected, table.get_next_row(i));
+        }
+    }
+
     fn build_ce_domain(domain_size: usize, domain_offset: BaseElement) -> Vec<BaseElement> {
         let g = BaseElement::get_root_of_unity(log2(domain_size));
         get_power_series_with_offset(g, domain_offset, domain_size)
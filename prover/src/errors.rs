@@ -18,9 +18,30 @@ pub enum ProverError {
     /// This error occurs when polynomials built from the columns of a constraint evaluation
     /// table do not all have the same degree.
     MismatchedConstraintPolynomialDegree(usize, usize),
+    /// This error occurs when the degree of the constraint composition polynomial exceeds the
+    /// composition degree defined by the AIR (e.g., because a composition degree hint was too
+    /// low). The values are: expected maximum degree, and actual degree.
+    CompositionDegreeExceeded(usize, usize),
     /// This error occurs when the base field specified by the AIR does not support field extension
     /// of degree specified by proof options.
     UnsupportedFieldExtension(usize),
+    /// This error occurs when a polynomial describing a trace column has a degree which is
+    /// greater than the degree hint provided for this column. The values are: column index,
+    /// degree hint, and actual degree.
+    ColumnDegreeHintExceeded(usize, usize, usize),
+    /// This error occurs when a trace segment index is greater than or equal to the number of
+    /// segments in an execution trace. The values are: number of segments, and requested index.
+    TraceSegmentIndexOutOfBounds(usize, usize),
+    /// This error occurs when a column index is greater than or equal to the number of columns
+    /// in an execution trace (or trace segment). The values are: number of columns, and requested
+    /// index.
+    TraceColumnIndexOutOfBounds(usize, usize),
+    /// This error occurs when a row index is greater than or equal to the number of rows in an
+    /// execution trace (or its extension). The values are: number of rows, and requested index.
+    TraceRowIndexOutOfBounds(usize, usize),
+    /// This error occurs when the deadline for proof generation has passed before the specified
+    /// phase of proof generation could start.
+    DeadlineExceeded(ProverPhase),
 }
 
 impl fmt::Display for ProverError {
@@ -33,9 +54,126 @@ impl fmt::Display for ProverError {
             Self::MismatchedConstraintPolynomialDegree(expected, actual) => {
                 write!(f, "the constraint polynomial's components do not all have the same degree; expected {}, but was {}", expected, actual)
             }
+            Self::CompositionDegreeExceeded(expected, actual) => {
+                write!(f, "the constraint composition polynomial degree exceeds the composition degree; expected at most {}, but was {}", expected, actual)
+            }
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {} is not supported for the specified base field", degree)
             }
+            Self::ColumnDegreeHintExceeded(column, hint, degree) => {
+                write!(f, "degree of trace column {} polynomial exceeds the degree hint; expected at most {}, but was {}", column, hint, degree)
+            }
+            Self::TraceSegmentIndexOutOfBounds(num_segments, idx) => {
+                write!(f, "trace segment index {} is out of bounds; the trace has {} segments", idx, num_segments)
+            }
+            Self::TraceColumnIndexOutOfBounds(num_cols, idx) => {
+                write!(f, "trace column index {} is out of bounds; the trace has {} columns", idx, num_cols)
+            }
+            Self::TraceRowIndexOutOfBounds(num_rows, idx) => {
+                write!(f, "trace row index {} is out of bounds; the trace has {} rows", idx, num_rows)
+            }
+            Self::DeadlineExceeded(phase) => {
+                write!(f, "proof generation deadline was exceeded before {} could start", phase)
+            }
+        }
+    }
+}
+
+// TRACE ERROR
+// ================================================================================================
+/// Represents an error returned when an execution trace cannot be built from the provided data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceError {
+    /// This error occurs when an execution trace is built from an empty set of columns.
+    NoColumns,
+    /// This error occurs when an execution trace contains more columns than allowed. The values
+    /// are: maximum allowed number of columns, and actual number of columns.
+    TooManyColumns(usize, usize),
+    /// This error occurs when columns of an execution trace are not all of the same length. The
+    /// values are: column index, expected length, and actual length of the column.
+    MismatchedColumnLength(usize, usize, usize),
+    /// This error occurs when an execution trace is shorter than the minimum trace length. The
+    /// values are: minimum trace length, and actual trace length.
+    TraceTooShort(usize, usize),
+    /// This error occurs when an execution trace is shorter than the blowup factor with which it
+    /// is to be extended. The values are: blowup factor, and actual trace length.
+    TraceShorterThanBlowup(usize, usize),
+    /// This error occurs when the length of an execution trace is not a power of two.
+    TraceLengthNotPowerOfTwo(usize),
+    /// This error occurs when the base field does not contain a multiplicative subgroup large
+    /// enough for the execution trace. The values are: log2 of the maximum trace length, and
+    /// log2 of the actual trace length.
+    TraceTooLong(u32, u32),
+    /// This error occurs when the number of columns in the main segment of an execution trace
+    /// differs from the width specified by the trace layout. The values are: expected number of
+    /// columns, and actual number of columns.
+    MismatchedTraceWidth(usize, usize),
+    /// This error occurs when the total width of auxiliary trace segments reported by an
+    /// execution trace differs from the sum of auxiliary segment widths in its layout. The values
+    /// are: reported width, and sum of segment widths.
+    MismatchedAuxTraceWidth(usize, usize),
+    /// This error occurs when execution trace metadata is longer than allowed. The values are:
+    /// maximum allowed number of bytes, and actual number of bytes.
+    MetadataTooLong(usize, usize),
+}
+
+impl fmt::Display for TraceError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoColumns => {
+                write!(f, "execution trace must consist of at least one column")
+            }
+            Self::TooManyColumns(max, actual) => {
+                write!(f, "execution trace width cannot be greater than {}, but was {}", max, actual)
+            }
+            Self::MismatchedColumnLength(column, expected, actual) => {
+                write!(f, "all columns traces must have the same length; expected column {} to have {} rows, but was {}", column, expected, actual)
+            }
+            Self::TraceTooShort(min, actual) => {
+                write!(f, "execution trace must be at least {} steps long, but was {}", min, actual)
+            }
+            Self::TraceShorterThanBlowup(blowup, actual) => {
+                write!(f, "execution trace must be at least {} steps long for blowup factor {}, but was {}", blowup, blowup, actual)
+            }
+            Self::TraceLengthNotPowerOfTwo(length) => {
+                write!(f, "execution trace length must be a power of 2, but was {}", length)
+            }
+            Self::TraceTooLong(max_log, actual_log) => {
+                write!(f, "execution trace length cannot exceed 2^{} steps, but was 2^{}", max_log, actual_log)
+            }
+            Self::MismatchedTraceWidth(expected, actual) => {
+                write!(f, "main trace segment must have {} columns as specified by the trace layout, but was {}", expected, actual)
+            }
+            Self::MismatchedAuxTraceWidth(reported, expected) => {
+                write!(f, "auxiliary trace width must be equal to the sum of auxiliary segment widths {}, but was {}", expected, reported)
+            }
+            Self::MetadataTooLong(max, actual) => {
+                write!(f, "execution trace metadata cannot be longer than {} bytes, but was {}", max, actual)
+            }
+        }
+    }
+}
+
+// PROVER PHASE
+// ================================================================================================
+/// Represents a major phase of proof generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProverPhase {
+    /// Low-degree extension of the execution trace and commitment to the extended trace.
+    TraceLde,
+    /// Evaluation of constraints and construction of the constraint composition polynomial.
+    ConstraintComposition,
+    /// Construction of FRI layers for the DEEP composition polynomial.
+    Fri,
+}
+
+impl fmt::Display for ProverPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TraceLde => write!(f, "trace low-degree extension"),
+            Self::ConstraintComposition => write!(f, "constraint composition"),
+            Self::Fri => write!(f, "FRI layer construction"),
         }
     }
 }
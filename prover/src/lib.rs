@@ -44,11 +44,15 @@
 #[macro_use]
 extern crate alloc;
 
+use air::get_public_coin_seed;
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
+    build_lagrange_kernel_column,
+    proof::{MembershipProof, OodFrame, ProofSizeBreakdown, ProofStats, StarkProof},
+    select_constraint, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
     BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, DefaultEvaluationFrame, EvaluationFrame, FieldExtension,
-    HashFunction, ProofOptions, Table, TraceInfo, TraceLayout, TransitionConstraintDegree,
+    DeepCompositionCoefficients, DefaultEvaluationFrame, DomainInfo, EvaluationFrame,
+    FieldExtension, HashFunction, ProofOptions, PublicCoin, Table, TraceInfo, TraceLayout,
+    TraceMeta, TraceMetaError, TraceMetaSchema, TransitionConstraintDegree,
     TransitionConstraintGroup,
 };
 pub use utils::{
@@ -56,10 +60,14 @@ pub use utils::{
     SliceReader,
 };
 
+pub use rand_core::RngCore;
+
 use fri::FriProver;
 use utils::collections::Vec;
 
 pub use math;
+#[cfg(feature = "diagnostics")]
+use math::fft;
 use math::{
     fft::infer_degree,
     fields::{CubeExtension, QuadExtension},
@@ -83,23 +91,33 @@ mod domain;
 pub use domain::StarkDomain;
 
 mod matrix;
-pub use matrix::Matrix;
+pub use matrix::{Matrix, RowView};
 
 mod constraints;
+pub use constraints::DivisorCache;
 use constraints::{CompositionPoly, ConstraintCommitment, ConstraintEvaluator};
 
 mod composer;
 use composer::DeepCompositionPoly;
 
 mod trace;
-pub use trace::{Trace, TraceTable, TraceTableFragment};
-use trace::{TraceCommitment, TraceLde, TracePolyTable};
+use trace::TraceCommitment;
+pub use trace::{
+    prove_column_membership, MultiTrace, SparseColumn, SparseTrace, Trace, TraceColumn, TraceLde,
+    TracePolyCache, TracePolyTable, TraceTable, TraceTableFragment,
+};
 
 mod channel;
 use channel::ProverChannel;
 
 mod errors;
-pub use errors::ProverError;
+pub use errors::{ProverError, ProverPhase, TraceError};
+
+mod observer;
+pub use observer::ProverObserver;
+
+mod ood;
+pub use ood::OodEvaluations;
 
 #[cfg(test)]
 pub mod tests;
@@ -151,6 +169,44 @@ pub trait Prover {
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns a cache of precomputed transition constraint divisor evaluations to be used when
+    /// generating proofs.
+    ///
+    /// Provers which generate many proofs for the same AIR (i.e., same trace length, blowup
+    /// factor, and transition exemptions) can override this method to avoid re-computing divisor
+    /// evaluations for every proof. The cache is used only if it is valid for the AIR of the
+    /// proof being generated; otherwise, it is ignored.
+    ///
+    /// The default implementation returns None.
+    fn divisor_cache(&self) -> Option<&DivisorCache<Self::BaseField>> {
+        None
+    }
+
+    /// Returns a cache of precomputed polynomials for main trace segment columns which are fixed
+    /// across proofs.
+    ///
+    /// Provers which generate many proofs for traces containing columns independent of the
+    /// inputs (e.g., preprocessed columns) can override this method to avoid re-interpolating
+    /// these columns for every proof. The cache is used only if it is valid for the trace of the
+    /// proof being generated, and a cached polynomial is used only if the values of its column
+    /// match the values used to build the cache; otherwise, the column is interpolated from
+    /// scratch.
+    ///
+    /// The default implementation returns None.
+    fn trace_poly_cache(&self) -> Option<&TracePolyCache<Self::BaseField>> {
+        None
+    }
+
+    /// Returns an observer which is notified about the progress of proof generation.
+    ///
+    /// Provers which generate long-running proofs can override this method to receive progress
+    /// notifications (see [ProverObserver] for the list of notifications and their order).
+    ///
+    /// The default implementation returns a no-op observer.
+    fn observer(&self) -> &dyn ProverObserver {
+        &()
+    }
+
     /// Returns a STARK proof attesting to a correct execution of a computation defined by the
     /// provided trace.
     ///
@@ -158,28 +214,219 @@ pub trait Prover {
     /// the computation described by [Self::Air](Prover::Air) and generated using some set of
     /// secret and public inputs. Public inputs must match the value returned from
     /// [Self::get_pub_inputs()](Prover::get_pub_inputs) for the provided trace.
-    #[rustfmt::skip]
     fn prove(&self, trace: Self::Trace) -> Result<StarkProof, ProverError> {
+        self.prove_with_seed(trace, &[])
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, and bound to the provided `extra_seed`.
+    ///
+    /// This is similar to [Prover::prove()], but the public coin is seeded with `extra_seed`
+    /// elements (appended to the public inputs) in addition to the public inputs. This can be
+    /// used to bind the proof to an external context (e.g., a session nonce). To verify the
+    /// resulting proof, the verifier must be supplied with the same seed (e.g., via
+    /// `verify_with_seed()` function of the verifier crate).
+    ///
+    /// When `extra_seed` is empty, the resulting proof is identical to the one generated by
+    /// [Prover::prove()].
+    fn prove_with_seed(
+        &self,
+        trace: Self::Trace,
+        extra_seed: &[Self::BaseField],
+    ) -> Result<StarkProof, ProverError> {
+        self.prove_with_phase_check(trace, extra_seed, &|_| Ok(()))
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, or an error if the proof could not be generated before the `deadline`.
+    ///
+    /// This is similar to [Prover::prove()], but before starting each of the expensive phases of
+    /// proof generation (trace low-degree extension, constraint composition, and FRI), the
+    /// prover checks whether the deadline has passed. If it has, proof generation is aborted
+    /// and [ProverError::DeadlineExceeded] is returned with the phase which was about to start.
+    ///
+    /// A phase which has already started is always run to completion, and thus, proof generation
+    /// may run past the deadline by up to the duration of a single phase.
+    #[cfg(feature = "std")]
+    fn prove_with_deadline(
+        &self,
+        trace: Self::Trace,
+        deadline: Instant,
+    ) -> Result<StarkProof, ProverError> {
+        self.prove_with_phase_check(trace, &[], &|phase| {
+            if Instant::now() >= deadline {
+                Err(ProverError::DeadlineExceeded(phase))
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    /// Returns coefficients of the constraint composition polynomial for the provided trace,
+    /// before the polynomial is committed to and passed on to FRI.
+    ///
+    /// This is intended for external commitment schemes which need to commit to the composition
+    /// polynomial themselves. The coefficients are in order of increasing degree; the polynomial
+    /// is of degree `trace_length * m - 1`, where `m` is the number of composition polynomial
+    /// columns. Evaluating the polynomial at a point x yields the same value as combining the
+    /// column evaluations as Σ x^i * H_i(x^m) which the verifier uses in the out-of-domain
+    /// consistency check.
+    ///
+    /// The random coefficients used to compose the constraints (and the random elements used to
+    /// build auxiliary trace segments) are drawn from a public coin seeded with the public inputs
+    /// and Merkle roots of the extended trace segments built using hash function `H`. Thus, to
+    /// obtain the composition polynomial of a given proof, `E` and `H` must match the field
+    /// extension and the hash function specified by the proof options.
+    ///
+    /// # Errors
+    /// Returns an error if the composition polynomial could not be built (e.g., if degrees of
+    /// trace polynomials do not match their degree hints).
+    fn build_composition_poly<E, H>(&self, trace: Self::Trace) -> Result<Vec<E>, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        H: ElementHasher<BaseField = Self::BaseField>,
+    {
+        let (_, _, _, composition_poly, _) = self.commit_and_draw_ood_point::<E, H>(trace)?;
+        Ok(composition_poly.into_coefficients())
+    }
+
+    /// Returns the out-of-domain point z and evaluations of trace and constraint composition
+    /// polynomials at this point for the provided trace.
+    ///
+    /// This is intended for debugging and for cross-checking constraint satisfaction
+    /// analytically: the returned values are the same as the ones which are included in the
+    /// out-of-domain frame of a proof generated for the same trace via [Prover::prove()].
+    ///
+    /// Same as with [Prover::build_composition_poly()], `E` and `H` must match the field extension
+    /// and the hash function specified by the proof options for z to be the same as the point
+    /// used in the proof.
+    ///
+    /// # Errors
+    /// Returns an error if the composition polynomial could not be built (e.g., if degrees of
+    /// trace polynomials do not match their degree hints).
+    fn build_ood_evaluations<E, H>(
+        &self,
+        trace: Self::Trace,
+    ) -> Result<OodEvaluations<E>, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        H: ElementHasher<BaseField = Self::BaseField>,
+    {
+        let (_, _, _, _, ood_evaluations) = self.commit_and_draw_ood_point::<E, H>(trace)?;
+        Ok(ood_evaluations)
+    }
+
+    /// Returns the low-degree extension of the provided trace (including all auxiliary segments)
+    /// together with the table of trace polynomials.
+    ///
+    /// This is intended for debugging constraint failures: single evaluations can be inspected
+    /// via [TraceLde::get_evaluation()] and [TracePolyTable::evaluate_at()] without accessing the
+    /// underlying matrices.
+    ///
+    /// Auxiliary trace segments are built using random elements drawn in the same way as during
+    /// proof generation. Thus, same as with [Prover::build_composition_poly()], `E` and `H` must
+    /// match the field extension and the hash function specified by the proof options for the
+    /// auxiliary segments to be the same as the ones committed to in the proof.
+    ///
+    /// # Errors
+    /// Returns an error if the trace could not be extended (e.g., if degrees of trace polynomials
+    /// do not match their degree hints).
+    fn build_trace_lde<E, H>(
+        &self,
+        trace: Self::Trace,
+    ) -> Result<(TraceLde<E>, TracePolyTable<E>), ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        H: ElementHasher<BaseField = Self::BaseField>,
+    {
+        let (_, trace_commitment, trace_polys, _, _) =
+            self.commit_and_draw_ood_point::<E, H>(trace)?;
+        Ok((trace_commitment.into_trace_table(), trace_polys))
+    }
+
+    /// Returns evaluations of the constraint composition polynomial over the LDE domain together
+    /// with the out-of-domain evaluations for the provided trace.
+    ///
+    /// This is intended for research and debugging (e.g., for visualizing the degree or the
+    /// sparsity of the composition polynomial) and does not affect proof generation. The i-th
+    /// returned evaluation is the value of the composition polynomial (i.e., the polynomial
+    /// returned by [Prover::build_composition_poly()]) at x = offset * g^i, where g is the
+    /// generator of the LDE domain and offset is the domain offset specified by the proof
+    /// options. The out-of-domain evaluations are the same as the ones returned by
+    /// [Prover::build_ood_evaluations()] for the same trace.
+    ///
+    /// Same as with [Prover::build_composition_poly()], `E` and `H` must match the field extension
+    /// and the hash function specified by the proof options for the results to correspond to a
+    /// proof generated for the same trace.
+    ///
+    /// # Errors
+    /// Returns an error if the composition polynomial could not be built (e.g., if degrees of
+    /// trace polynomials do not match their degree hints).
+    #[cfg(feature = "diagnostics")]
+    fn build_composition_evaluations<E, H>(
+        &self,
+        trace: Self::Trace,
+    ) -> Result<(Vec<E>, OodEvaluations<E>), ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        H: ElementHasher<BaseField = Self::BaseField>,
+    {
+        let (domain, _, _, composition_poly, ood_evaluations) =
+            self.commit_and_draw_ood_point::<E, H>(trace)?;
+
+        // evaluate the merged composition polynomial over the LDE domain; the number of
+        // coefficients is padded to the next power of two so that the polynomial can be
+        // evaluated via FFT
+        let mut coefficients = composition_poly.into_coefficients();
+        coefficients.resize(coefficients.len().next_power_of_two(), E::ZERO);
+        let twiddles = fft::get_twiddles::<Self::BaseField>(coefficients.len());
+        let blowup_factor = domain.lde_domain_size() / coefficients.len();
+        let evaluations = fft::evaluate_poly_with_offset(
+            &coefficients,
+            &twiddles,
+            domain.offset(),
+            blowup_factor,
+        );
+
+        Ok((evaluations, ood_evaluations))
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Selects the extension field and hash function specified by proof options, and generates
+    /// the proof using them.
+    ///
+    /// `check_phase` is invoked before each major phase of proof generation; if it returns an
+    /// error, proof generation is aborted and the error is returned.
+    #[doc(hidden)]
+    #[rustfmt::skip]
+    fn prove_with_phase_check(
+        &self,
+        trace: Self::Trace,
+        extra_seed: &[Self::BaseField],
+        check_phase: &dyn Fn(ProverPhase) -> Result<(), ProverError>,
+    ) -> Result<StarkProof, ProverError> {
         // figure out which version of the generic proof generation procedure to run. this is a sort
         // of static dispatch for selecting two generic parameter: extension field and hash function.
         match self.options().field_extension() {
             FieldExtension::None => match self.options().hash_fn() {
-                HashFunction::Blake3_256 => self.generate_proof::<Self::BaseField, Blake3_256<Self::BaseField>>(trace),
-                HashFunction::Blake2s_256 => self.generate_proof::<Self::BaseField, Blake2s_256<Self::BaseField>>(trace),
-                HashFunction::Blake3_192 => self.generate_proof::<Self::BaseField, Blake3_192<Self::BaseField>>(trace),
-                HashFunction::Sha3_256 => self.generate_proof::<Self::BaseField, Sha3_256<Self::BaseField>>(trace),
-                HashFunction::Pedersen_256 => self.generate_proof::<Self::BaseField, Pedersen_256<Self::BaseField>>(trace),
+                HashFunction::Blake3_256 => self.generate_proof::<Self::BaseField, Blake3_256<Self::BaseField>>(trace, extra_seed, check_phase),
+                HashFunction::Blake2s_256 => self.generate_proof::<Self::BaseField, Blake2s_256<Self::BaseField>>(trace, extra_seed, check_phase),
+                HashFunction::Blake3_192 => self.generate_proof::<Self::BaseField, Blake3_192<Self::BaseField>>(trace, extra_seed, check_phase),
+                HashFunction::Sha3_256 => self.generate_proof::<Self::BaseField, Sha3_256<Self::BaseField>>(trace, extra_seed, check_phase),
+                HashFunction::Pedersen_256 => self.generate_proof::<Self::BaseField, Pedersen_256<Self::BaseField>>(trace, extra_seed, check_phase),
             },
             FieldExtension::Quadratic => {
                 if !<QuadExtension<Self::BaseField>>::is_supported() {
                     return Err(ProverError::UnsupportedFieldExtension(2));
                 }
                 match self.options().hash_fn() {
-                    HashFunction::Blake3_256 => self.generate_proof::<QuadExtension<Self::BaseField>, Blake3_256<Self::BaseField>>(trace),
-                    HashFunction::Blake2s_256 => self.generate_proof::<QuadExtension<Self::BaseField>, Blake2s_256<Self::BaseField>>(trace),
-                    HashFunction::Blake3_192 => self.generate_proof::<QuadExtension<Self::BaseField>, Blake3_192<Self::BaseField>>(trace),
-                    HashFunction::Sha3_256 => self.generate_proof::<QuadExtension<Self::BaseField>, Sha3_256<Self::BaseField>>(trace),
-                    HashFunction::Pedersen_256 => self.generate_proof::<QuadExtension<Self::BaseField>, Pedersen_256<Self::BaseField>>(trace),
+                    HashFunction::Blake3_256 => self.generate_proof::<QuadExtension<Self::BaseField>, Blake3_256<Self::BaseField>>(trace, extra_seed, check_phase),
+                    HashFunction::Blake2s_256 => self.generate_proof::<QuadExtension<Self::BaseField>, Blake2s_256<Self::BaseField>>(trace, extra_seed, check_phase),
+                    HashFunction::Blake3_192 => self.generate_proof::<QuadExtension<Self::BaseField>, Blake3_192<Self::BaseField>>(trace, extra_seed, check_phase),
+                    HashFunction::Sha3_256 => self.generate_proof::<QuadExtension<Self::BaseField>, Sha3_256<Self::BaseField>>(trace, extra_seed, check_phase),
+                    HashFunction::Pedersen_256 => self.generate_proof::<QuadExtension<Self::BaseField>, Pedersen_256<Self::BaseField>>(trace, extra_seed, check_phase),
                 }
             }
             FieldExtension::Cubic => {
@@ -187,34 +434,36 @@ pub trait Prover {
                     return Err(ProverError::UnsupportedFieldExtension(3));
                 }
                 match self.options().hash_fn() {
-                    HashFunction::Blake3_256 => self.generate_proof::<CubeExtension<Self::BaseField>, Blake3_256<Self::BaseField>>(trace),
-                    HashFunction::Blake2s_256 => self.generate_proof::<CubeExtension<Self::BaseField>, Blake2s_256<Self::BaseField>>(trace),
-                    HashFunction::Blake3_192 => self.generate_proof::<CubeExtension<Self::BaseField>, Blake3_192<Self::BaseField>>(trace),
-                    HashFunction::Sha3_256 => self.generate_proof::<CubeExtension<Self::BaseField>, Sha3_256<Self::BaseField>>(trace),
-                    HashFunction::Pedersen_256 => self.generate_proof::<CubeExtension<Self::BaseField>, Pedersen_256<Self::BaseField>>(trace),
+                    HashFunction::Blake3_256 => self.generate_proof::<CubeExtension<Self::BaseField>, Blake3_256<Self::BaseField>>(trace, extra_seed, check_phase),
+                    HashFunction::Blake2s_256 => self.generate_proof::<CubeExtension<Self::BaseField>, Blake2s_256<Self::BaseField>>(trace, extra_seed, check_phase),
+                    HashFunction::Blake3_192 => self.generate_proof::<CubeExtension<Self::BaseField>, Blake3_192<Self::BaseField>>(trace, extra_seed, check_phase),
+                    HashFunction::Sha3_256 => self.generate_proof::<CubeExtension<Self::BaseField>, Sha3_256<Self::BaseField>>(trace, extra_seed, check_phase),
+                    HashFunction::Pedersen_256 => self.generate_proof::<CubeExtension<Self::BaseField>, Pedersen_256<Self::BaseField>>(trace, extra_seed, check_phase),
                 }
             }
         }
     }
 
-    // HELPER METHODS
-    // --------------------------------------------------------------------------------------------
-
     /// Performs the actual proof generation procedure, generating the proof that the provided
     /// execution `trace` is valid against this prover's AIR.
     /// TODO: make this function un-callable externally?
     #[doc(hidden)]
-    fn generate_proof<E, H>(&self, mut trace: Self::Trace) -> Result<StarkProof, ProverError>
+    fn generate_proof<E, H>(
+        &self,
+        trace: Self::Trace,
+        extra_seed: &[Self::BaseField],
+        check_phase: &dyn Fn(ProverPhase) -> Result<(), ProverError>,
+    ) -> Result<StarkProof, ProverError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
         H: ElementHasher<BaseField = Self::BaseField>,
     {
         // 0 ----- instantiate AIR and prover channel ---------------------------------------------
 
-        // serialize public inputs; these will be included in the seed for the public coin
+        // reduce public inputs to field elements and append the extra seed elements (if any);
+        // the hash of these elements will be the seed for the public coin
         let pub_inputs = self.get_pub_inputs(&trace);
-        let mut pub_inputs_bytes = Vec::new();
-        pub_inputs.write_into(&mut pub_inputs_bytes);
+        let coin_seed = get_public_coin_seed(&pub_inputs, extra_seed);
 
         // create an instance of AIR for the provided parameters. this takes a generic description
         // of the computation (provided via AIR type), and creates a description of a specific
@@ -224,9 +473,10 @@ pub trait Prover {
         // create a channel which is used to simulate interaction between the prover and the
         // verifier; the channel will be used to commit to values and to draw randomness that
         // should come from the verifier.
-        let mut channel = ProverChannel::<Self::Air, E, H>::new(&air, pub_inputs_bytes);
+        let mut channel = ProverChannel::<Self::Air, E, H>::new(&air, &coin_seed);
 
         // 1 ----- Commit to the execution trace --------------------------------------------------
+        check_phase(ProverPhase::TraceLde)?;
 
         // build computation domain; this is used later for polynomial evaluations
         #[cfg(feature = "std")]
@@ -239,96 +489,13 @@ pub trait Prover {
             now.elapsed().as_millis()
         );
 
-        // extend the main execution trace and build a Merkle tree from the extended trace
-        let (main_trace_lde, main_trace_tree, main_trace_polys) =
-            self.build_trace_commitment::<Self::BaseField, H>(trace.main_segment(), &domain);
-
-        // commit to the LDE of the main trace by writing the root of its Merkle tree into
-        // the channel
-        channel.commit_trace(*main_trace_tree.root());
-
-        // initialize trace commitment and trace polynomial table structs with the main trace
-        // data; for multi-segment traces these structs will be used as accumulators of all
-        // trace segments
-        let mut trace_commitment = TraceCommitment::new(
-            main_trace_lde,
-            main_trace_tree,
-            domain.trace_to_lde_blowup(),
-        );
-        let mut trace_polys = TracePolyTable::new(main_trace_polys);
-
-        // build auxiliary trace segments (if any), and append the resulting segments to trace
-        // commitment and trace polynomial table structs
-        let mut aux_trace_segments = Vec::new();
-        let mut aux_trace_rand_elements = AuxTraceRandElements::new();
-        for i in 0..trace.layout().num_aux_segments() {
-            // draw a set of random elements required to build an auxiliary trace segment
-            let rand_elements = channel.get_aux_trace_segment_rand_elements(i);
-
-            // build the trace segment
-            let aux_segment = trace
-                .build_aux_segment(&aux_trace_segments, &rand_elements)
-                .expect("failed build auxiliary trace segment");
-
-            // extend the auxiliary trace segment and build a Merkle tree from the extended trace
-            let (aux_segment_lde, aux_segment_tree, aux_segment_polys) =
-                self.build_trace_commitment::<E, H>(&aux_segment, &domain);
-
-            // commit to the LDE of the extended auxiliary trace segment  by writing the root of
-            // its Merkle tree into the channel
-            channel.commit_trace(*aux_segment_tree.root());
-
-            // append the segment to the trace commitment and trace polynomial table structs
-            trace_commitment.add_segment(aux_segment_lde, aux_segment_tree);
-            trace_polys.add_aux_segment(aux_segment_polys);
-            aux_trace_rand_elements.add_segment_elements(rand_elements);
-            aux_trace_segments.push(aux_segment);
-        }
-
-        // make sure the specified trace (including auxiliary segments) is valid against the AIR.
-        // This checks validity of both, assertions and state transitions. We do this in debug
-        // mode only because this is a very expensive operation.
-        //#[cfg(debug_assertions)]
-        trace.validate(&air, &aux_trace_segments, &aux_trace_rand_elements);
-
-        // 2 ----- evaluate constraints -----------------------------------------------------------
-        // evaluate constraints specified by the AIR over the constraint evaluation domain, and
-        // compute random linear combinations of these evaluations using coefficients drawn from
-        // the channel; this step evaluates only constraint numerators, thus, only constraints with
-        // identical denominators are merged together. the results are saved into a constraint
-        // evaluation table where each column contains merged evaluations of constraints with
-        // identical denominators.
-        #[cfg(feature = "std")]
-        let now = Instant::now();
-        let constraint_coeffs = channel.get_constraint_composition_coeffs();
-        let evaluator = ConstraintEvaluator::new(&air, aux_trace_rand_elements, constraint_coeffs);
-        let constraint_evaluations = evaluator.evaluate(trace_commitment.trace_table(), &domain);
-        #[cfg(feature = "std")]
-        println!(
-            "Evaluated constraints over domain of 2^{} elements in {} ms",
-            log2(constraint_evaluations.num_rows()),
-            now.elapsed().as_millis()
-        );
+        // commit to the execution trace, evaluate constraints over the LDE of the trace, and
+        // build the constraint composition polynomial from the evaluations
+        let (trace_commitment, trace_polys, composition_poly) =
+            self.commit_to_trace_and_compose(trace, &air, &mut channel, &domain, check_phase)?;
 
         // 3 ----- commit to constraint evaluations -----------------------------------------------
 
-        // first, build constraint composition polynomial from the constraint evaluation table:
-        // - divide all constraint evaluation columns by their respective divisors
-        // - combine them into a single column of evaluations,
-        // - interpolate the column into a polynomial in coefficient form
-        // - "break" the polynomial into a set of column polynomials each of degree equal to
-        //   trace_length - 1
-        #[cfg(feature = "std")]
-        let now = Instant::now();
-        let composition_poly = constraint_evaluations.into_poly()?;
-        #[cfg(feature = "std")]
-        println!(
-            "Converted constraint evaluations into {} composition polynomial columns of degree {} in {} ms",
-            composition_poly.num_columns(),
-            composition_poly.column_degree(),
-            now.elapsed().as_millis()
-        );
-
         // then, build a commitment to the evaluations of the composition polynomial columns
         let constraint_commitment =
             self.build_constraint_commitment::<E, H>(&composition_poly, &domain);
@@ -408,10 +575,14 @@ pub trait Prover {
         );
 
         // 6 ----- compute FRI layers for the composition polynomial ------------------------------
+        check_phase(ProverPhase::Fri)?;
+
         #[cfg(feature = "std")]
         let now = Instant::now();
         let mut fri_prover = FriProver::new(air.options().to_fri_options());
-        fri_prover.build_layers(&mut channel, deep_evaluations);
+        fri_prover.build_layers_with_observer(&mut channel, deep_evaluations, |idx, size| {
+            self.observer().on_fri_layer(idx, size)
+        });
         #[cfg(feature = "std")]
         println!(
             "Computed {} FRI layers from composition polynomial evaluations in {} ms",
@@ -451,14 +622,212 @@ pub trait Prover {
         // merged into a single value and Merkle authentication paths contain these values already
         let constraint_queries = constraint_commitment.query(&query_positions);
 
+        // pass the transcript of the public coin to the observer; the coin is not used after the
+        // query positions have been drawn
+        #[cfg(feature = "transcript")]
+        self.observer().on_transcript(channel.transcript());
+
         // build the proof object
         let proof = channel.build_proof(trace_queries, constraint_queries, fri_proof);
         #[cfg(feature = "std")]
         println!("Built proof object in {} ms", now.elapsed().as_millis());
 
+        self.observer().on_proof_complete();
         Ok(proof)
     }
 
+    /// Replays steps 0 - 4 of proof generation for the provided `trace` up to (and including)
+    /// drawing the out-of-domain point z, and evaluates trace and constraint composition
+    /// polynomials at this point.
+    ///
+    /// The public coin is seeded with the public inputs only (i.e., without any extra seed
+    /// elements). Returns the computation domain, the trace commitment, the table of trace
+    /// polynomials, the constraint composition polynomial, and the out-of-domain evaluations.
+    /// This is used by the diagnostic methods of the prover so that all of them follow the same
+    /// sequence of protocol steps as [Prover::prove()].
+    #[doc(hidden)]
+    #[allow(clippy::type_complexity)]
+    fn commit_and_draw_ood_point<E, H>(
+        &self,
+        trace: Self::Trace,
+    ) -> Result<
+        (
+            StarkDomain<Self::BaseField>,
+            TraceCommitment<E, H>,
+            TracePolyTable<E>,
+            CompositionPoly<E>,
+            OodEvaluations<E>,
+        ),
+        ProverError,
+    >
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        H: ElementHasher<BaseField = Self::BaseField>,
+    {
+        let pub_inputs = self.get_pub_inputs(&trace);
+        let coin_seed = get_public_coin_seed(&pub_inputs, &[]);
+
+        let air = Self::Air::new(trace.get_info(), pub_inputs, self.options().clone());
+        let mut channel = ProverChannel::<Self::Air, E, H>::new(&air, &coin_seed);
+        let domain = StarkDomain::new(&air);
+
+        let (trace_commitment, trace_polys, composition_poly) =
+            self.commit_to_trace_and_compose(trace, &air, &mut channel, &domain, &|_| Ok(()))?;
+
+        // commit to the constraint evaluations in the same way as during proof generation so
+        // that z is drawn from the same state of the public coin
+        let constraint_commitment =
+            self.build_constraint_commitment::<E, H>(&composition_poly, &domain);
+        channel.commit_constraints(constraint_commitment.root());
+
+        let z = channel.get_ood_point();
+        let ood_evaluations = OodEvaluations {
+            z,
+            trace_states: trace_polys.get_ood_frame(z, air.eval_frame_offsets::<E>()),
+            constraint_evaluations: composition_poly.evaluate_at(z),
+        };
+
+        Ok((
+            domain,
+            trace_commitment,
+            trace_polys,
+            composition_poly,
+            ood_evaluations,
+        ))
+    }
+
+    /// Commits to the provided execution `trace` (including all auxiliary segments) via the
+    /// `channel`, evaluates constraints of the `air` over the LDE of the trace, and builds the
+    /// constraint composition polynomial from these evaluations.
+    ///
+    /// Returns the trace commitment, the table of trace polynomials, and the composition
+    /// polynomial. This covers steps 1 - 3 (excluding the constraint commitment) of proof
+    /// generation.
+    #[doc(hidden)]
+    #[allow(clippy::type_complexity)]
+    fn commit_to_trace_and_compose<E, H>(
+        &self,
+        mut trace: Self::Trace,
+        air: &Self::Air,
+        channel: &mut ProverChannel<Self::Air, E, H>,
+        domain: &StarkDomain<Self::BaseField>,
+        check_phase: &dyn Fn(ProverPhase) -> Result<(), ProverError>,
+    ) -> Result<(TraceCommitment<E, H>, TracePolyTable<E>, CompositionPoly<E>), ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        H: ElementHasher<BaseField = Self::BaseField>,
+    {
+        // extend the main execution trace and build a Merkle tree from the extended trace
+        let main_degree_hints = trace.main_segment_degree_hints();
+        let (main_trace_lde, main_trace_tree, main_trace_polys) = self
+            .build_trace_commitment::<Self::BaseField, H>(
+                trace.main_segment(),
+                main_degree_hints.as_deref(),
+                self.trace_poly_cache(),
+                domain,
+            )?;
+
+        // commit to the LDE of the main trace by writing the root of its Merkle tree into
+        // the channel
+        channel.commit_trace(*main_trace_tree.root());
+        self.observer().on_trace_committed(0);
+
+        // initialize trace commitment and trace polynomial table structs with the main trace
+        // data; for multi-segment traces these structs will be used as accumulators of all
+        // trace segments
+        let mut trace_commitment = TraceCommitment::new(
+            main_trace_lde,
+            main_trace_tree,
+            domain.trace_to_lde_blowup(),
+        );
+        let mut trace_polys = TracePolyTable::new(main_trace_polys);
+
+        // build auxiliary trace segments (if any), and append the resulting segments to trace
+        // commitment and trace polynomial table structs
+        let mut aux_trace_segments = Vec::new();
+        let mut aux_trace_rand_elements = AuxTraceRandElements::new();
+        for i in 0..trace.layout().num_aux_segments() {
+            // draw a set of random elements required to build an auxiliary trace segment
+            let rand_elements = channel.get_aux_trace_segment_rand_elements(i);
+
+            // build the trace segment
+            let aux_segment = trace
+                .build_aux_segment(&aux_trace_segments, &rand_elements)
+                .expect("failed build auxiliary trace segment");
+
+            // extend the auxiliary trace segment and build a Merkle tree from the extended trace
+            let aux_degree_hints = trace.aux_segment_degree_hints(i);
+            let (aux_segment_lde, aux_segment_tree, aux_segment_polys) = self
+                .build_trace_commitment::<E, H>(
+                    &aux_segment,
+                    aux_degree_hints.as_deref(),
+                    None,
+                    domain,
+                )?;
+
+            // commit to the LDE of the extended auxiliary trace segment  by writing the root of
+            // its Merkle tree into the channel
+            channel.commit_trace(*aux_segment_tree.root());
+            self.observer().on_trace_committed(i + 1);
+
+            // append the segment to the trace commitment and trace polynomial table structs
+            trace_commitment.add_segment(aux_segment_lde, aux_segment_tree);
+            trace_polys.add_aux_segment(aux_segment_polys);
+            aux_trace_rand_elements.add_segment_elements(rand_elements);
+            aux_trace_segments.push(aux_segment);
+        }
+
+        // make sure the specified trace (including auxiliary segments) is valid against the AIR.
+        // This checks validity of both, assertions and state transitions. We do this in debug
+        // mode only because this is a very expensive operation.
+        //#[cfg(debug_assertions)]
+        trace.validate(air, &aux_trace_segments, &aux_trace_rand_elements);
+
+        // 2 ----- evaluate constraints -----------------------------------------------------------
+        check_phase(ProverPhase::ConstraintComposition)?;
+
+        // evaluate constraints specified by the AIR over the constraint evaluation domain, and
+        // compute random linear combinations of these evaluations using coefficients drawn from
+        // the channel; this step evaluates only constraint numerators, thus, only constraints with
+        // identical denominators are merged together. the results are saved into a constraint
+        // evaluation table where each column contains merged evaluations of constraints with
+        // identical denominators.
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let constraint_coeffs = channel.get_constraint_composition_coeffs();
+        let evaluator = ConstraintEvaluator::new(air, aux_trace_rand_elements, constraint_coeffs);
+        let constraint_evaluations = evaluator.evaluate(trace_commitment.trace_table(), domain);
+        #[cfg(feature = "std")]
+        println!(
+            "Evaluated constraints over domain of 2^{} elements in {} ms",
+            log2(constraint_evaluations.num_rows()),
+            now.elapsed().as_millis()
+        );
+
+        // 3 ----- build constraint composition polynomial -----------------------------------------
+
+        // build constraint composition polynomial from the constraint evaluation table:
+        // - divide all constraint evaluation columns by their respective divisors
+        // - combine them into a single column of evaluations,
+        // - interpolate the column into a polynomial in coefficient form
+        // - "break" the polynomial into a set of column polynomials each of degree equal to
+        //   trace_length - 1
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let divisor_cache = self.divisor_cache().filter(|cache| cache.is_valid_for(air));
+        let composition_poly = constraint_evaluations.into_poly(divisor_cache)?;
+        #[cfg(feature = "std")]
+        println!(
+            "Converted constraint evaluations into {} composition polynomial columns of degree {} in {} ms",
+            composition_poly.num_columns(),
+            composition_poly.column_degree(),
+            now.elapsed().as_millis()
+        );
+        self.observer().on_constraints_evaluated();
+
+        Ok((trace_commitment, trace_polys, composition_poly))
+    }
+
     /// Computes a low-degree extension (LDE) of the provided execution trace over the specified
     /// domain and build a commitment to the extended trace.
     ///
@@ -466,13 +835,25 @@ pub trait Prover {
     /// polynomial of degree = trace_length - 1, and then evaluating the polynomial over the LDE
     /// domain.
     ///
+    /// When `degree_hints` are provided, degrees of the interpolated polynomials are first checked
+    /// against the hints, and then the hints are used to reduce the cost of evaluating low-degree
+    /// polynomials over the LDE domain.
+    ///
+    /// When `poly_cache` is provided, polynomials for the columns found in the cache are cloned
+    /// from the cache rather than interpolated.
+    ///
     /// Trace commitment is computed by hashing each row of the extended execution trace, and then
     /// building a Merkle tree from the resulting hashes.
+    ///
+    /// # Errors
+    /// Returns an error if degree of any of the trace polynomials exceeds its degree hint.
     fn build_trace_commitment<E, H>(
         &self,
         trace: &Matrix<E>,
+        degree_hints: Option<&[usize]>,
+        poly_cache: Option<&TracePolyCache<E>>,
         domain: &StarkDomain<Self::BaseField>,
-    ) -> (Matrix<E>, MerkleTree<H>, Matrix<E>)
+    ) -> Result<(Matrix<E>, MerkleTree<H>, Matrix<E>), ProverError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
         H: ElementHasher<BaseField = Self::BaseField>,
@@ -480,8 +861,17 @@ pub trait Prover {
         // extend the execution trace
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let trace_polys = trace.interpolate_columns();
-        let trace_lde = trace_polys.evaluate_columns_over(domain);
+        let trace_polys = match poly_cache {
+            Some(cache) => cache.interpolate_columns(trace),
+            None => trace.interpolate_columns(),
+        };
+        let trace_lde = match degree_hints {
+            Some(degree_hints) => {
+                trace_polys.validate_column_degrees(degree_hints)?;
+                trace_polys.evaluate_columns_over_with_degrees(domain, degree_hints)
+            }
+            None => trace_polys.evaluate_columns_over(domain),
+        };
         #[cfg(feature = "std")]
         debug!(
             "Extended execution trace of {} columns from 2^{} to 2^{} steps ({}x blowup) in {} ms",
@@ -503,7 +893,7 @@ pub trait Prover {
             now.elapsed().as_millis()
         );
 
-        (trace_lde, trace_tree, trace_polys)
+        Ok((trace_lde, trace_tree, trace_polys))
     }
 
     /// Evaluates constraint composition polynomial over the LDE domain and builds a commitment
@@ -1,8 +1,12 @@
-use super::StarkDomain;
+use super::{ProverError, StarkDomain};
 use core::{iter::FusedIterator, slice};
 use crypto::{ElementHasher, MerkleTree};
 use math::{fft, polynom, FieldElement};
-use utils::{batch_iter_mut, collections::Vec, iter, iter_mut, uninit_vector, TableReader};
+use utils::{
+    batch_iter_mut,
+    collections::{BTreeMap, Vec},
+    iter, iter_mut, uninit_vector, TableReader,
+};
 
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
@@ -63,6 +67,42 @@ impl<E: FieldElement> Matrix<E> {
         Self { columns }
     }
 
+    /// Returns a new [Matrix] instantiated with the data from the specified columns; column
+    /// lengths are validated in parallel when `concurrent` feature is enabled.
+    ///
+    /// This is intended for matrices with many columns, each of which is produced by an
+    /// independent (and potentially expensive) computation. Since matrix columns are stored as
+    /// separate vectors, the provided columns are moved into the matrix without copying; thus,
+    /// the resulting matrix is identical to the one built via [Matrix::new()].
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The provided vector of columns is empty.
+    /// * Not all of the columns have the same number of elements.
+    /// * Number of rows is smaller than or equal to 1.
+    /// * Number of rows is not a power of two.
+    pub fn from_columns_par(columns: Vec<Vec<E>>) -> Self {
+        assert!(
+            !columns.is_empty(),
+            "a matrix must contain at least one column"
+        );
+        let num_rows = columns[0].len();
+        assert!(
+            num_rows > 1,
+            "number of rows in a matrix must be greater than one"
+        );
+        assert!(
+            num_rows.is_power_of_two(),
+            "number of rows in a matrix must be a power of 2"
+        );
+        assert!(
+            iter!(columns).all(|column| column.len() == num_rows),
+            "all matrix columns must have the same length"
+        );
+
+        Self { columns }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -93,6 +133,12 @@ impl<E: FieldElement> Matrix<E> {
     }
 
     /// Returns a reference to the column at the specified index.
+    ///
+    /// Since the matrix is stored in column-major order, the returned slice is contiguous, and
+    /// `get_column(c)[r]` is the same as `get(c, r)`.
+    ///
+    /// # Panics
+    /// Panics if `col_idx` is out of bounds for this matrix.
     pub fn get_column(&self, col_idx: usize) -> &[E] {
         &self.columns[col_idx]
     }
@@ -135,6 +181,81 @@ impl<E: FieldElement> Matrix<E> {
         ColumnIterMut::new(self)
     }
 
+    /// Returns a view of the row at the specified index.
+    ///
+    /// The view does not copy any data; since the matrix is stored in column-major order,
+    /// iterating over the view reads the element at `row_idx` from each column in turn.
+    ///
+    /// # Panics
+    /// Panics if `row_idx` is out of bounds for this matrix.
+    pub fn row(&self, row_idx: usize) -> RowView<E> {
+        assert!(
+            row_idx < self.num_rows(),
+            "row index {} is out of bounds for a matrix with {} rows",
+            row_idx,
+            self.num_rows()
+        );
+        RowView {
+            matrix: self,
+            row_idx,
+        }
+    }
+
+    // COLUMN AGGREGATES
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a column of accumulated values computed by scanning the column at the specified
+    /// index with the provided function.
+    ///
+    /// The first element of the result is `init`, and every subsequent element is computed as
+    /// `result[i + 1] = f(result[i], column[i])`. Thus, the result has the same length as the
+    /// column, and the value at row `i` aggregates column values at rows `0..i`.
+    ///
+    /// This matches how transition constraints are enforced: a constraint of the form
+    /// `next = f(current, column)` holds for all steps but the last one, and the value in the
+    /// last row of the column does not contribute to the result (i.e., there is no wrap-around
+    /// from the last row to the first one).
+    ///
+    /// # Panics
+    /// Panics if `col_idx` is out of bounds for this matrix.
+    pub fn scan<F>(&self, col_idx: usize, init: E, f: F) -> Vec<E>
+    where
+        F: Fn(E, E) -> E,
+    {
+        let column = &self.columns[col_idx];
+        let mut result = Vec::with_capacity(column.len());
+        let mut acc = init;
+        for &value in column[..column.len() - 1].iter() {
+            result.push(acc);
+            acc = f(acc, value);
+        }
+        result.push(acc);
+        result
+    }
+
+    /// Returns a running sum of the column at the specified index.
+    ///
+    /// The first element of the result is ZERO, and `result[i + 1] = result[i] + column[i]`. See
+    /// [Matrix::scan()] for how the last row of the column is handled.
+    ///
+    /// # Panics
+    /// Panics if `col_idx` is out of bounds for this matrix.
+    pub fn running_sum(&self, col_idx: usize) -> Vec<E> {
+        self.scan(col_idx, E::ZERO, |acc, value| acc + value)
+    }
+
+    /// Returns a running product of the column at the specified index shifted by `rand`.
+    ///
+    /// The first element of the result is ONE, and every subsequent element is computed as
+    /// `result[i + 1] = result[i] * (column[i] + rand)`. This is the building block of multiset
+    /// (permutation) checks. See [Matrix::scan()] for how the last row of the column is handled.
+    ///
+    /// # Panics
+    /// Panics if `col_idx` is out of bounds for this matrix.
+    pub fn running_product(&self, col_idx: usize, rand: E) -> Vec<E> {
+        self.scan(col_idx, E::ONE, |acc, value| acc * (value + rand))
+    }
+
     // POLYNOMIAL METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -196,6 +317,101 @@ impl<E: FieldElement> Matrix<E> {
         Self { columns }
     }
 
+    /// Evaluates polynomials contained in the columns of this matrix over the specified domain
+    /// taking into account the provided column degree hints, and returns the result.
+    ///
+    /// This is similar to [Matrix::evaluate_columns_over()], but for a column with degree hint
+    /// `d`, only the first `m` coefficients of the column are used, where `m` is the smallest
+    /// power of two greater than `d`. The polynomial is then evaluated over the LDE domain using
+    /// FFTs of size `m` rather than FFTs of size `num_rows`, which reduces the cost of the
+    /// extension for low-degree columns. Columns with hints greater than or equal to
+    /// `num_rows - 1` are evaluated in the same way as in [Matrix::evaluate_columns_over()].
+    ///
+    /// This method does not check that the hints are correct; this can be done via
+    /// [Matrix::validate_column_degrees()] method. If a column polynomial has a degree greater
+    /// than its hint, the returned column will not contain evaluations of that polynomial.
+    ///
+    /// # Panics
+    /// Panics if the number of degree hints is not equal to the number of columns in this matrix.
+    pub fn evaluate_columns_over_with_degrees(
+        &self,
+        domain: &StarkDomain<E::BaseField>,
+        degree_hints: &[usize],
+    ) -> Self {
+        assert_eq!(
+            self.num_cols(),
+            degree_hints.len(),
+            "number of degree hints must be equal to the number of columns; expected {}, but was {}",
+            self.num_cols(),
+            degree_hints.len()
+        );
+
+        // build twiddles for all reduced polynomial sizes upfront so that they can be shared
+        // across columns with the same size
+        let num_rows = self.num_rows();
+        let mut twiddle_map = BTreeMap::new();
+        for &degree in degree_hints {
+            let poly_size = get_reduced_poly_size(degree, num_rows);
+            if poly_size < num_rows {
+                twiddle_map
+                    .entry(poly_size)
+                    .or_insert_with(|| fft::get_twiddles::<E::BaseField>(poly_size));
+            }
+        }
+
+        let lde_domain_size = domain.lde_domain_size();
+        let columns = iter!(self.columns)
+            .enumerate()
+            .map(|(i, poly)| {
+                let poly_size = get_reduced_poly_size(degree_hints[i], num_rows);
+                match twiddle_map.get(&poly_size) {
+                    Some(twiddles) => fft::evaluate_poly_with_offset(
+                        &poly[..poly_size],
+                        twiddles,
+                        domain.offset(),
+                        lde_domain_size / poly_size,
+                    ),
+                    None => fft::evaluate_poly_with_offset(
+                        poly,
+                        domain.trace_twiddles(),
+                        domain.offset(),
+                        domain.trace_to_lde_blowup(),
+                    ),
+                }
+            })
+            .collect();
+        Self { columns }
+    }
+
+    /// Makes sure that polynomials contained in the columns of this matrix have degrees which do
+    /// not exceed the provided degree hints.
+    ///
+    /// Each column of the matrix is interpreted as coefficients of a polynomial.
+    ///
+    /// # Errors
+    /// Returns an error if a degree of any of the column polynomials is greater than the degree
+    /// hint for that column.
+    ///
+    /// # Panics
+    /// Panics if the number of degree hints is not equal to the number of columns in this matrix.
+    pub fn validate_column_degrees(&self, degree_hints: &[usize]) -> Result<(), ProverError> {
+        assert_eq!(
+            self.num_cols(),
+            degree_hints.len(),
+            "number of degree hints must be equal to the number of columns; expected {}, but was {}",
+            self.num_cols(),
+            degree_hints.len()
+        );
+
+        for (i, (poly, &hint)) in self.columns.iter().zip(degree_hints).enumerate() {
+            let degree = polynom::degree_of(poly);
+            if degree > hint {
+                return Err(ProverError::ColumnDegreeHintExceeded(i, hint, degree));
+            }
+        }
+        Ok(())
+    }
+
     /// Evaluates polynomials contained in the columns of this matrix at a single point `x`.
     pub fn evaluate_columns_at<F>(&self, x: F) -> Vec<F>
     where
@@ -244,6 +460,21 @@ impl<E: FieldElement> Matrix<E> {
     // CONVERSIONS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns a new matrix which is the transpose of this matrix.
+    ///
+    /// That is, the value located at column `c` and row `r` of this matrix is located at column
+    /// `r` and row `c` of the returned matrix.
+    ///
+    /// # Panics
+    /// Panics if the number of columns in this matrix is not a power of two or is smaller than 2,
+    /// as such a matrix cannot be transposed into a valid [Matrix].
+    pub fn transpose(&self) -> Self {
+        let columns = (0..self.num_rows())
+            .map(|row_idx| self.columns.iter().map(|column| column[row_idx]).collect())
+            .collect();
+        Self::new(columns)
+    }
+
     /// Returns the columns of this matrix as a list of vectors.
     ///
     /// TODO: replace this with an iterator.
@@ -265,6 +496,22 @@ impl<E: FieldElement> TableReader<E> for &Matrix<E> {
     }
 }
 
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of coefficients needed to describe a polynomial of the specified degree
+/// in the context of a matrix with the specified number of rows.
+///
+/// The result is the smallest power of two greater than `degree`, but it is never smaller than
+/// 2 (the smallest size supported by FFT), and never greater than `num_rows`.
+fn get_reduced_poly_size(degree: usize, num_rows: usize) -> usize {
+    if degree >= num_rows - 1 {
+        num_rows
+    } else {
+        core::cmp::max(degree + 1, 2).next_power_of_two()
+    }
+}
+
 // COLUMN ITERATOR
 // ================================================================================================
 
@@ -302,6 +549,100 @@ impl<'a, E: FieldElement> ExactSizeIterator for ColumnIter<'a, E> {
 
 impl<'a, E: FieldElement> FusedIterator for ColumnIter<'a, E> {}
 
+// ROW VIEW
+// ================================================================================================
+
+/// A view of a single row of a [Matrix].
+///
+/// The view borrows the matrix and reads elements of the row from the underlying columns on
+/// demand.
+#[derive(Debug, Clone, Copy)]
+pub struct RowView<'a, E: FieldElement> {
+    matrix: &'a Matrix<E>,
+    row_idx: usize,
+}
+
+impl<'a, E: FieldElement> RowView<'a, E> {
+    /// Returns the index of this row in the underlying matrix.
+    pub fn index(&self) -> usize {
+        self.row_idx
+    }
+
+    /// Returns the number of elements in this row.
+    pub fn len(&self) -> usize {
+        self.matrix.num_cols()
+    }
+
+    /// Returns true if this row contains no elements; this is never the case for a valid matrix.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element of this row located in the specified column.
+    ///
+    /// # Panics
+    /// Panics if `col_idx` is out of bounds for the underlying matrix.
+    pub fn get(&self, col_idx: usize) -> E {
+        self.matrix.get(col_idx, self.row_idx)
+    }
+
+    /// Returns an iterator over the elements of this row.
+    pub fn iter(&self) -> RowIter<'a, E> {
+        RowIter {
+            columns: self.matrix.columns.iter(),
+            row_idx: self.row_idx,
+        }
+    }
+
+    /// Copies elements of this row into a new vector and returns the result.
+    pub fn to_vec(&self) -> Vec<E> {
+        self.iter().collect()
+    }
+}
+
+impl<'a, E: FieldElement> IntoIterator for RowView<'a, E> {
+    type Item = E;
+    type IntoIter = RowIter<'a, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// ROW ITERATOR
+// ================================================================================================
+
+/// An iterator over the elements of a single row of a [Matrix].
+///
+/// Each step of the iteration moves to the next column of the matrix, and thus, the iterator
+/// strides over the column-major storage of the matrix.
+pub struct RowIter<'a, E: FieldElement> {
+    columns: slice::Iter<'a, Vec<E>>,
+    row_idx: usize,
+}
+
+impl<'a, E: FieldElement> Iterator for RowIter<'a, E> {
+    type Item = E;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.columns.next().map(|column| column[self.row_idx])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.columns.size_hint()
+    }
+}
+
+impl<'a, E: FieldElement> DoubleEndedIterator for RowIter<'a, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.columns.next_back().map(|column| column[self.row_idx])
+    }
+}
+
+impl<'a, E: FieldElement> ExactSizeIterator for RowIter<'a, E> {}
+
+impl<'a, E: FieldElement> FusedIterator for RowIter<'a, E> {}
+
 // MUTABLE COLUMN ITERATOR
 // ================================================================================================
 
@@ -405,3 +746,178 @@ impl<'a, E: FieldElement> ExactSizeIterator for MultiColumnIter<'a, E> {
 }
 
 impl<'a, E: FieldElement> FusedIterator for MultiColumnIter<'a, E> {}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::Matrix;
+    use air::{DefaultEvaluationFrame, EvaluationFrame, Table};
+    use math::{fields::f128::BaseElement, FieldElement};
+    use utils::collections::Vec;
+
+    #[test]
+    fn matrix_transpose() {
+        let columns = (0..4u128)
+            .map(|c| (0..8u128).map(|r| BaseElement::new(c * 8 + r)).collect())
+            .collect::<Vec<_>>();
+        let matrix = Matrix::new(columns);
+
+        let transposed = matrix.transpose();
+        assert_eq!(8, transposed.num_cols());
+        assert_eq!(4, transposed.num_rows());
+        for col_idx in 0..matrix.num_cols() {
+            for row_idx in 0..matrix.num_rows() {
+                assert_eq!(
+                    matrix.get(col_idx, row_idx),
+                    transposed.get(row_idx, col_idx)
+                );
+                assert_eq!(
+                    matrix.get(col_idx, row_idx),
+                    matrix.get_column(col_idx)[row_idx]
+                );
+            }
+        }
+
+        assert_eq!(matrix.columns, transposed.transpose().columns);
+    }
+
+    #[test]
+    #[should_panic(expected = "number of rows in a matrix must be a power of 2")]
+    fn matrix_transpose_invalid_width() {
+        let matrix = Matrix::new(vec![vec![BaseElement::ONE; 4]; 3]);
+        matrix.transpose();
+    }
+
+    #[test]
+    fn matrix_row_view() {
+        let matrix = build_matrix(4, 8);
+
+        let mut expected = vec![BaseElement::ZERO; matrix.num_cols()];
+        for row_idx in 0..matrix.num_rows() {
+            matrix.read_row_into(row_idx, &mut expected);
+            let row = matrix.row(row_idx);
+            assert_eq!(matrix.num_cols(), row.len());
+            assert_eq!(matrix.num_cols(), row.iter().len());
+            assert_eq!(expected, row.to_vec());
+            assert_eq!(expected[2], row.get(2));
+
+            let reversed = row.iter().rev().collect::<Vec<_>>();
+            assert_eq!(expected.iter().rev().copied().collect::<Vec<_>>(), reversed);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "row index 8 is out of bounds for a matrix with 8 rows")]
+    fn matrix_row_view_out_of_bounds() {
+        build_matrix(4, 8).row(8);
+    }
+
+    #[test]
+    fn matrix_row_view_frames() {
+        let matrix = build_matrix(4, 16);
+        let num_rows = matrix.num_rows();
+
+        for blowup in [1, 2, 4] {
+            for step in 0..num_rows {
+                let mut expected = DefaultEvaluationFrame::<BaseElement>::from_table(Table::new(
+                    2,
+                    matrix.num_cols(),
+                ));
+                expected.read_from(&matrix, step, 0, blowup);
+
+                let frame = DefaultEvaluationFrame::<BaseElement>::from_table(Table::from_rows(
+                    DefaultEvaluationFrame::<BaseElement>::offsets()
+                        .iter()
+                        .map(|offset| matrix.row((step + offset * blowup) % num_rows).to_vec())
+                        .collect(),
+                ));
+                assert_eq!(expected.current(), frame.current());
+                assert_eq!(expected.next(), frame.next());
+            }
+        }
+    }
+
+    #[test]
+    fn matrix_from_columns_par() {
+        let matrix = build_matrix(16, 64);
+        let parallel = Matrix::from_columns_par(matrix.columns.clone());
+        assert_eq!(matrix.num_cols(), parallel.num_cols());
+        assert_eq!(matrix.num_rows(), parallel.num_rows());
+        assert_eq!(matrix.columns, parallel.columns);
+    }
+
+    #[test]
+    #[should_panic(expected = "all matrix columns must have the same length")]
+    fn matrix_from_columns_par_invalid_length() {
+        let mut columns = build_matrix(16, 64).columns;
+        columns[9].pop();
+        Matrix::from_columns_par(columns);
+    }
+
+    #[test]
+    fn matrix_running_product() {
+        let matrix = build_matrix(4, 16);
+        let rand = BaseElement::new(11);
+
+        let result = matrix.running_product(2, rand);
+        assert_eq!(matrix.num_rows(), result.len());
+
+        let mut expected = BaseElement::ONE;
+        for (i, &value) in matrix.get_column(2).iter().enumerate() {
+            assert_eq!(expected, result[i]);
+            expected *= value + rand;
+        }
+
+        // with zero shift, the running product is the cumulative product of the column
+        let result = matrix.running_product(1, BaseElement::ZERO);
+        let column = matrix.get_column(1);
+        for i in 0..matrix.num_rows() {
+            let expected = column[..i]
+                .iter()
+                .fold(BaseElement::ONE, |acc, &value| acc * value);
+            assert_eq!(expected, result[i]);
+        }
+    }
+
+    #[test]
+    fn matrix_running_sum() {
+        let matrix = build_matrix(4, 16);
+        let result = matrix.running_sum(3);
+        let column = matrix.get_column(3);
+        for i in 0..matrix.num_rows() {
+            let expected = column[..i]
+                .iter()
+                .fold(BaseElement::ZERO, |acc, &value| acc + value);
+            assert_eq!(expected, result[i]);
+        }
+    }
+
+    #[test]
+    fn matrix_scan() {
+        let matrix = build_matrix(4, 8);
+        let init = BaseElement::new(5);
+        let result = matrix.scan(0, init, |acc, value| acc.double() - value);
+
+        assert_eq!(init, result[0]);
+        let column = matrix.get_column(0);
+        for i in 1..matrix.num_rows() {
+            assert_eq!(result[i - 1].double() - column[i - 1], result[i]);
+        }
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn build_matrix(num_cols: usize, num_rows: usize) -> Matrix<BaseElement> {
+        let columns = (0..num_cols as u128)
+            .map(|c| {
+                (0..num_rows as u128)
+                    .map(|r| BaseElement::new(c * num_rows as u128 + r))
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        Matrix::new(columns)
+    }
+}
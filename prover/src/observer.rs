@@ -0,0 +1,55 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+#[cfg(feature = "transcript")]
+use crypto::TranscriptEvent;
+
+// PROVER OBSERVER
+// ================================================================================================
+
+/// Receives notifications about the progress of proof generation.
+///
+/// An observer is supplied to the prover via [Prover::observer()](crate::Prover::observer), and
+/// its methods are invoked by the prover in the following order:
+/// 1. [on_trace_committed()](ProverObserver::on_trace_committed) once for the main trace segment
+///    and then once for each auxiliary trace segment.
+/// 2. [on_constraints_evaluated()](ProverObserver::on_constraints_evaluated) once the constraint
+///    composition polynomial has been built.
+/// 3. [on_fri_layer()](ProverObserver::on_fri_layer) once for each FRI layer (including the
+///    remainder layer).
+/// 4. `on_transcript()` once the query positions have been drawn (only when `transcript` feature
+///    is enabled).
+/// 5. [on_proof_complete()](ProverObserver::on_proof_complete) once the proof has been built.
+///
+/// All methods have empty default implementations, and thus, an observer needs to implement only
+/// the methods it is interested in. The methods are invoked from the thread which called
+/// [Prover::prove()](crate::Prover::prove); an observer which needs to record notifications can
+/// use interior mutability to do so.
+pub trait ProverObserver {
+    /// Invoked after the trace segment with the specified index has been extended and committed
+    /// to; the main segment has index 0, and auxiliary segments have indexes starting with 1.
+    fn on_trace_committed(&self, _segment_idx: usize) {}
+
+    /// Invoked after constraints have been evaluated over the constraint evaluation domain and
+    /// the constraint composition polynomial has been built from these evaluations.
+    fn on_constraints_evaluated(&self) {}
+
+    /// Invoked after the FRI layer with the specified index has been built; `domain_size` is the
+    /// size of the domain over which the layer was evaluated.
+    fn on_fri_layer(&self, _layer_idx: usize, _domain_size: usize) {}
+
+    /// Invoked after the query positions have been drawn with the events recorded by the public
+    /// coin of the prover. Comparing these events to the ones recorded by the verifier helps to
+    /// identify the first operation at which the transcripts of the prover and the verifier
+    /// diverge.
+    #[cfg(feature = "transcript")]
+    fn on_transcript(&self, _transcript: &[TranscriptEvent]) {}
+
+    /// Invoked after proof generation has been completed.
+    fn on_proof_complete(&self) {}
+}
+
+/// A no-op observer used by default.
+impl ProverObserver for () {}
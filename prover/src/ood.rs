@@ -0,0 +1,45 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use math::FieldElement;
+use utils::collections::Vec;
+
+// OUT-OF-DOMAIN EVALUATIONS
+// ================================================================================================
+
+/// Evaluations of trace and constraint composition polynomials at an out-of-domain point.
+///
+/// These evaluations are built by [Prover::build_ood_evaluations()](crate::Prover::build_ood_evaluations)
+/// and are the same as the evaluations which the prover sends to the verifier (as a part of
+/// [OodFrame](crate::OodFrame)) when generating a proof for the same trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OodEvaluations<E: FieldElement> {
+    /// The out-of-domain point z drawn from the public coin.
+    pub z: E,
+    /// Evaluations of all trace polynomials (main segment followed by auxiliary segments) at
+    /// points z * g^o_i, where g is the generator of the trace domain and o_i are the row offsets
+    /// of the evaluation frame. For the default evaluation frame, the first row contains
+    /// evaluations at z, and the second row contains evaluations at z * g.
+    pub trace_states: Vec<Vec<E>>,
+    /// Evaluations of all constraint composition polynomial columns at z^m, where m is the number
+    /// of columns.
+    pub constraint_evaluations: Vec<E>,
+}
+
+impl<E: FieldElement> OodEvaluations<E> {
+    /// Returns the evaluation of the constraint composition polynomial at z.
+    ///
+    /// This is computed as sum(z^i * value_i), where value_i is the evaluation of the ith column
+    /// polynomial at z^m; this is the same value the verifier compares against the evaluation of
+    /// constraints over the out-of-domain trace frame.
+    pub fn composition_evaluation(&self) -> E {
+        self.constraint_evaluations
+            .iter()
+            .enumerate()
+            .fold(E::ZERO, |result, (i, &value)| {
+                result + self.z.exp((i as u32).into()) * value
+            })
+    }
+}
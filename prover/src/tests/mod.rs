@@ -3,14 +3,17 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::TraceTable;
+use crate::{Prover, Trace, TracePolyCache, TraceTable};
 use air::{
-    Air, AirContext, Assertion, DefaultEvaluationFrame, FieldExtension, HashFunction, ProofOptions,
-    TraceInfo, TransitionConstraintDegree,
+    Air, AirContext, Assertion, DefaultEvaluationFrame, EvaluationFrame, FieldExtension,
+    HashFunction, ProofOptions, TraceInfo, TransitionConstraintDegree,
 };
 use math::{fields::f128::BaseElement, FieldElement, StarkField};
+use rand_utils::SeededRng;
 use utils::collections::Vec;
 
+mod proof;
+
 // FIBONACCI TRACE BUILDER
 // ================================================================================================
 
@@ -41,7 +44,7 @@ impl MockAir {
     pub fn with_trace_length(trace_length: usize) -> Self {
         Self::new(
             TraceInfo::new(4, trace_length),
-            (),
+            BaseElement::ZERO,
             ProofOptions::new(
                 32,
                 8,
@@ -60,7 +63,7 @@ impl MockAir {
     ) -> Self {
         let mut result = Self::new(
             TraceInfo::new(4, trace_length),
-            (),
+            BaseElement::ZERO,
             ProofOptions::new(
                 32,
                 8,
@@ -78,7 +81,7 @@ impl MockAir {
     pub fn with_assertions(assertions: Vec<Assertion<BaseElement>>, trace_length: usize) -> Self {
         let mut result = Self::new(
             TraceInfo::new(4, trace_length),
-            (),
+            BaseElement::ZERO,
             ProofOptions::new(
                 32,
                 8,
@@ -92,15 +95,21 @@ impl MockAir {
         result.assertions = assertions;
         result
     }
+
+    pub fn with_transition_exemptions(num_exemptions: usize, trace_length: usize) -> Self {
+        let mut result = Self::with_trace_length(trace_length);
+        result.context = result.context.set_num_transition_exemptions(num_exemptions);
+        result
+    }
 }
 
 impl Air for MockAir {
     type BaseField = BaseElement;
-    type PublicInputs = ();
+    type PublicInputs = BaseElement;
     type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
     type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
 
-    fn new(trace_info: TraceInfo, _pub_inputs: (), _options: ProofOptions) -> Self {
+    fn new(trace_info: TraceInfo, _pub_inputs: BaseElement, _options: ProofOptions) -> Self {
         let context = build_context(trace_info, 8, 1);
         MockAir {
             context,
@@ -117,6 +126,7 @@ impl Air for MockAir {
         &self,
         _frame: &Self::Frame<E>,
         _periodic_values: &[E],
+        _next_periodic_values: &[E],
         _result: &mut [E],
     ) {
     }
@@ -130,6 +140,260 @@ impl Air for MockAir {
     }
 }
 
+// COUNTER AIR
+// ================================================================================================
+
+/// AIR for a counter which is incremented while a flag is set, and stays unchanged once the flag
+/// is cleared; thus, repeating the last row of a terminated counter trace keeps all transition
+/// constraints satisfied.
+pub struct CounterAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for CounterAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+    type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
+    type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+        ];
+        CounterAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &Self::Frame<E>,
+        _periodic_values: &[E],
+        _next_periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        // the counter is incremented by the flag
+        result[0] = next[0] - current[0] - current[1];
+        // the flag is binary
+        result[1] = current[1] * (current[1] - E::ONE);
+        // once the flag is cleared, it stays cleared
+        result[2] = next[1] * (E::ONE - current[1]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ZERO),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(0, last_step, self.result),
+        ]
+    }
+}
+
+impl CounterAir {
+    /// Returns this AIR with the composition degree hint set to the specified degree.
+    pub fn with_composition_degree_hint(mut self, degree: usize) -> Self {
+        self.context = self.context.set_composition_degree_hint(degree);
+        self
+    }
+
+    /// Returns this AIR with transition constraint degrees replaced by the specified degrees.
+    pub fn with_transition_degrees(mut self, degrees: Vec<TransitionConstraintDegree>) -> Self {
+        self.context = AirContext::new(
+            self.trace_info().clone(),
+            degrees,
+            3,
+            self.options().clone(),
+        );
+        self
+    }
+}
+
+pub struct CounterProver {
+    options: ProofOptions,
+    poly_cache: Option<TracePolyCache<BaseElement>>,
+}
+
+impl CounterProver {
+    /// Returns a prover which generates proofs using the specified options.
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            poly_cache: None,
+        }
+    }
+
+    /// Returns a prover which uses the specified trace polynomial cache.
+    pub fn with_poly_cache(cache: TracePolyCache<BaseElement>) -> Self {
+        Self {
+            poly_cache: Some(cache),
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for CounterProver {
+    fn default() -> Self {
+        Self {
+            options: ProofOptions::new(
+                32,
+                8,
+                0,
+                HashFunction::Blake2s_256,
+                FieldExtension::None,
+                4,
+                256,
+            ),
+            poly_cache: None,
+        }
+    }
+}
+
+impl Prover for CounterProver {
+    type BaseField = BaseElement;
+    type Air = CounterAir;
+    type Trace = TraceTable<BaseElement>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(0, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn trace_poly_cache(&self) -> Option<&TracePolyCache<BaseElement>> {
+        self.poly_cache.as_ref()
+    }
+}
+
+/// Builds unpadded columns of a counter trace which counts up to `num_steps - 1`.
+pub fn build_counter_columns(num_steps: usize) -> Vec<Vec<BaseElement>> {
+    let counter = (0..num_steps as u128).map(BaseElement::new).collect();
+    let mut flag = vec![BaseElement::ONE; num_steps - 1];
+    flag.push(BaseElement::ZERO);
+    vec![counter, flag]
+}
+
+// BLINDED COUNTER AIR
+// ================================================================================================
+
+/// Returns the number of blinding rows for the specified options: one row for each query and for
+/// each row of the out-of-domain evaluation frame.
+pub fn num_blinding_rows(options: &ProofOptions) -> usize {
+    options.num_queries() + DefaultEvaluationFrame::<BaseElement>::num_rows()
+}
+
+/// AIR for a counter which reserves the last rows of the execution trace for blinding values. The
+/// transition constraints are delegated to [CounterAir].
+pub struct BlindedCounterAir {
+    context: AirContext<BaseElement>,
+    inner: CounterAir,
+    result: BaseElement,
+}
+
+impl Air for BlindedCounterAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+    type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
+    type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+        ];
+        let context = AirContext::new(trace_info.clone(), degrees, 3, options.clone())
+            .set_num_blinding_rows(
+                num_blinding_rows(&options),
+                DefaultEvaluationFrame::<BaseElement>::num_rows(),
+            );
+        BlindedCounterAir {
+            context,
+            inner: CounterAir::new(trace_info, pub_inputs, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &Self::Frame<E>,
+        periodic_values: &[E],
+        next_periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        self.inner
+            .evaluate_transition(frame, periodic_values, next_periodic_values, result)
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the result is placed in the last row before the blinding rows
+        let last_step = self.trace_length() - self.context.num_blinding_rows() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ZERO),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(0, last_step, self.result),
+        ]
+    }
+}
+
+pub struct BlindedCounterProver {
+    options: ProofOptions,
+}
+
+impl BlindedCounterProver {
+    pub fn new(options: ProofOptions) -> Self {
+        Self { options }
+    }
+
+    /// Builds a counter trace of the specified length and overwrites its last rows with random
+    /// blinding values drawn from a generator instantiated with the specified `seed`; the same
+    /// seed always yields the same trace.
+    pub fn build_trace(&self, trace_length: usize, seed: [u8; 32]) -> TraceTable<BaseElement> {
+        let num_blinding_rows = num_blinding_rows(&self.options);
+        let columns = build_counter_columns(trace_length - num_blinding_rows);
+        let mut trace = TraceTable::pad_to_power_of_two(columns, None);
+        trace.randomize_zk_with_rng(num_blinding_rows, &mut SeededRng::new(seed));
+        trace
+    }
+}
+
+impl Default for BlindedCounterProver {
+    fn default() -> Self {
+        Self::new(CounterProver::default().options)
+    }
+}
+
+impl Prover for BlindedCounterProver {
+    type BaseField = BaseElement;
+    type Air = BlindedCounterAir;
+    type Trace = TraceTable<BaseElement>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        let last_step = trace.length() - num_blinding_rows(&self.options) - 1;
+        trace.get(0, last_step)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
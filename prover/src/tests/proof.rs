@@ -0,0 +1,311 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_counter_columns, BlindedCounterAir, BlindedCounterProver, CounterAir, CounterProver,
+};
+use crate::{Prover, Trace, TraceTable};
+use air::{
+    proof::StarkProof, Air, AirContext, Assertion, DefaultEvaluationFrame, EvaluationFrame,
+    FieldExtension, HashFunction, ProofOptions, TraceInfo, TransitionConstraintDegree,
+};
+use crypto::{
+    hashers::{Blake2s_256, Pedersen_256, Tip5},
+    MerkleTree,
+};
+use math::{
+    fields::{f128::BaseElement, f252, f64},
+    FieldElement,
+};
+use utils::{collections::Vec, DeserializationError, Serializable};
+use verifier::{BatchVerifier, VerifierError};
+
+// CAIRO CALLDATA
+// ================================================================================================
+
+#[test]
+fn proof_to_cairo_calldata() {
+    type H = Pedersen_256<BaseElement>;
+
+    let options = ProofOptions::new(
+        28,
+        8,
+        0,
+        HashFunction::Pedersen_256,
+        FieldExtension::Quadratic,
+        4,
+        256,
+    );
+    let prover = CounterProver::new(options);
+    let trace = TraceTable::init(build_counter_columns(16));
+    let result = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+
+    // round-trip the proof through the calldata encoding
+    let calldata = proof.to_cairo_calldata::<H>().unwrap();
+    let decoded = StarkProof::from_cairo_calldata::<H>(&calldata).unwrap();
+    assert_eq!(proof, decoded);
+    assert!(verifier::verify::<CounterAir>(decoded, result).is_ok());
+
+    // the calldata starts with the size of the serialized proof context followed by the packed
+    // context bytes
+    let context_size = proof.context.to_bytes().len();
+    assert_eq!(f252::BaseElement::from(context_size as u64), calldata[0]);
+
+    // the context is followed by the list of commitments; each commitment is encoded as the felt
+    // computed by the Pedersen hash function
+    let num_fri_layers = proof
+        .options()
+        .to_fri_options()
+        .num_fri_layers(proof.lde_domain_size());
+    let (trace_roots, constraint_root, fri_roots) = proof
+        .commitments
+        .clone()
+        .parse::<H>(1, num_fri_layers)
+        .unwrap();
+    let commitments = trace_roots
+        .into_iter()
+        .chain([constraint_root])
+        .chain(fri_roots)
+        .collect::<Vec<_>>();
+    let offset = 1 + (context_size + 30) / 31;
+    assert_eq!(
+        f252::BaseElement::from(commitments.len() as u64),
+        calldata[offset]
+    );
+    for (i, commitment) in commitments.iter().enumerate() {
+        let mut bytes = commitment.to_bytes();
+        bytes.reverse();
+        let expected = f252::BaseElement::from_bytes_be(&bytes).unwrap();
+        assert_eq!(expected, calldata[offset + 1 + i]);
+    }
+
+    // truncated or extended calldata must be rejected
+    assert_eq!(
+        Err(DeserializationError::UnexpectedEOF),
+        StarkProof::from_cairo_calldata::<H>(&calldata[..calldata.len() - 1])
+    );
+    let mut extended = calldata;
+    extended.push(f252::BaseElement::from(1u64));
+    assert_eq!(
+        Err(DeserializationError::UnconsumedBytes),
+        StarkProof::from_cairo_calldata::<H>(&extended)
+    );
+}
+
+#[test]
+fn reject_cairo_calldata_for_large_digests() {
+    // digests of Tip5 are 40 bytes long, and thus, do not fit into a Starknet field element
+    type H = Tip5<f64::BaseElement>;
+
+    let prover = CounterProver::default();
+    let proof = prover
+        .prove(TraceTable::init(build_counter_columns(16)))
+        .unwrap();
+
+    assert!(matches!(
+        proof.to_cairo_calldata::<H>(),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+    assert!(matches!(
+        StarkProof::from_cairo_calldata::<H>(&[f252::BaseElement::ZERO]),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+}
+
+// BATCH VERIFICATION
+// ================================================================================================
+
+#[test]
+fn verify_proof_batch() {
+    let prover = CounterProver::default();
+    let trace = TraceTable::init(build_counter_columns(16));
+    let result = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+
+    let blinded_prover = BlindedCounterProver::default();
+    let trace = blinded_prover.build_trace(128, [1; 32]);
+    let blinded_result = blinded_prover.get_pub_inputs(&trace);
+    let blinded_proof = blinded_prover.prove(trace).unwrap();
+
+    // proofs of a single computation
+    let verdicts = verifier::verify_batch::<CounterAir>(vec![
+        (proof.clone(), result),
+        (proof.clone(), result + BaseElement::ONE),
+        (proof.clone(), result),
+    ]);
+    assert_eq!(3, verdicts.len());
+    assert!(verdicts[0].is_ok());
+    assert!(verdicts[1].is_err());
+    assert!(verdicts[2].is_ok());
+
+    // proofs of different computations
+    let mut batch = BatchVerifier::new();
+    batch.add::<BlindedCounterAir>(blinded_proof.clone(), blinded_result + BaseElement::ONE);
+    batch.add::<CounterAir>(proof.clone(), result);
+    batch.add::<BlindedCounterAir>(blinded_proof, blinded_result);
+    batch.add::<CounterAir>(proof, result + BaseElement::ONE);
+    assert_eq!(4, batch.len());
+
+    let verdicts = batch.verify();
+    assert_eq!(4, verdicts.len());
+    assert!(verdicts[0].is_err());
+    assert!(verdicts[1].is_ok());
+    assert!(verdicts[2].is_ok());
+    assert!(verdicts[3].is_err());
+}
+
+// FRI DEGREE BOUND
+// ================================================================================================
+
+#[test]
+fn reject_fri_remainder_of_different_degree() {
+    let prover = CounterProver::default();
+    let trace = TraceTable::init(build_counter_columns(16));
+    let result = prover.get_pub_inputs(&trace);
+    let mut proof = prover.prove(trace).unwrap();
+    assert_eq!(128, proof.lde_domain_size());
+
+    // a proof for a trace of twice the length has the same number of FRI layers, but its FRI
+    // remainder implies a polynomial of twice the degree
+    let trace = TraceTable::init(build_counter_columns(32));
+    let longer_proof = prover.prove(trace).unwrap();
+    assert_eq!(
+        proof.fri_proof.num_layers(),
+        longer_proof.fri_proof.num_layers()
+    );
+
+    proof.fri_proof = longer_proof.fri_proof;
+    assert_eq!(
+        Err(VerifierError::DegreeMismatch(15, 31)),
+        verifier::verify::<CounterAir>(proof, result)
+    );
+}
+
+// QUERY POSITIONS
+// ================================================================================================
+
+#[test]
+fn replay_query_positions() {
+    type H = Blake2s_256<BaseElement>;
+
+    let prover = CounterProver::default();
+    let trace = TraceTable::init(build_counter_columns(16));
+    let result = prover.get_pub_inputs(&trace);
+    let mut proof = prover.prove(trace).unwrap();
+    let lde_domain_size = proof.lde_domain_size();
+
+    // query positions are replayed deterministically from the proof
+    let positions = verifier::get_query_positions::<CounterAir>(proof.clone(), result).unwrap();
+    assert!(!positions.is_empty());
+    assert!(positions.len() <= proof.options().num_queries());
+    assert!(positions.iter().all(|&position| position < lde_domain_size));
+    assert_eq!(
+        Ok(positions.clone()),
+        verifier::get_query_positions::<CounterAir>(proof.clone(), result)
+    );
+
+    // the replayed positions are the ones at which the prover opened the trace commitment
+    let num_fri_layers = proof
+        .options()
+        .to_fri_options()
+        .num_fri_layers(lde_domain_size);
+    let (trace_roots, _, _) = proof
+        .commitments
+        .clone()
+        .parse::<H>(1, num_fri_layers)
+        .unwrap();
+    let (trace_proof, _) = proof.trace_queries[0]
+        .clone()
+        .parse::<H, BaseElement>(lde_domain_size, positions.len(), 2)
+        .unwrap();
+    assert!(MerkleTree::<H>::verify_batch(&trace_roots[0], &positions, &trace_proof).is_ok());
+
+    // the proof-of-work nonce is a part of the transcript, and thus, changing it changes the
+    // query positions
+    proof.pow_nonce += 1;
+    assert!(verifier::get_query_positions::<CounterAir>(proof, result).is_err());
+}
+
+// EXTENSION FIELDS
+// ================================================================================================
+
+#[test]
+fn prove_and_verify_over_f252_extensions() {
+    type Felt = f252::BaseElement;
+
+    /// AIR for a counter over the 252-bit field which is incremented at every step.
+    struct FeltCounterAir {
+        context: AirContext<Felt>,
+        result: Felt,
+    }
+
+    impl Air for FeltCounterAir {
+        type BaseField = Felt;
+        type PublicInputs = Felt;
+        type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
+        type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
+
+        fn new(trace_info: TraceInfo, pub_inputs: Felt, options: ProofOptions) -> Self {
+            let degrees = vec![TransitionConstraintDegree::new(1)];
+            FeltCounterAir {
+                context: AirContext::new(trace_info, degrees, 2, options),
+                result: pub_inputs,
+            }
+        }
+
+        fn context(&self) -> &AirContext<Self::BaseField> {
+            &self.context
+        }
+
+        fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+            &self,
+            frame: &Self::Frame<E>,
+            _periodic_values: &[E],
+            _next_periodic_values: &[E],
+            result: &mut [E],
+        ) {
+            result[0] = frame.next()[0] - frame.current()[0] - E::ONE;
+        }
+
+        fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+            let last_step = self.trace_length() - 1;
+            vec![
+                Assertion::single(0, 0, Felt::ZERO),
+                Assertion::single(0, last_step, self.result),
+            ]
+        }
+    }
+
+    struct FeltCounterProver(ProofOptions);
+
+    impl Prover for FeltCounterProver {
+        type BaseField = Felt;
+        type Air = FeltCounterAir;
+        type Trace = TraceTable<Felt>;
+
+        fn get_pub_inputs(&self, trace: &Self::Trace) -> Felt {
+            trace.get(0, trace.length() - 1)
+        }
+
+        fn options(&self) -> &ProofOptions {
+            &self.0
+        }
+    }
+
+    // elements of f252 extensions do not fit into a single digest, and thus, the random coins of
+    // the prover and the verifier must draw them from several digests in the same way
+    for extension in [FieldExtension::Quadratic, FieldExtension::Cubic] {
+        let options = ProofOptions::new(28, 8, 0, HashFunction::Blake3_256, extension, 4, 256);
+        let prover = FeltCounterProver(options);
+        let trace = TraceTable::init(vec![(0..16u64).map(Felt::from).collect()]);
+        let result = prover.get_pub_inputs(&trace);
+        let proof = prover.prove(trace).unwrap();
+        assert_eq!(extension, proof.options().field_extension());
+
+        assert!(verifier::verify::<FeltCounterAir>(proof.clone(), result).is_ok());
+        assert!(verifier::verify::<FeltCounterAir>(proof, result + Felt::ONE).is_err());
+    }
+}
@@ -19,6 +19,14 @@ use super::TraceLde;
 /// The describes one or more trace segments, each consisting of the following components:
 /// * Evaluations of a trace segment's polynomials over the LDE domain.
 /// * Merkle tree where each leaf in the tree corresponds to a row in the trace LDE matrix.
+///
+/// The commitment cannot be updated in place. Changing a single row of a trace segment changes
+/// every row of its low-degree extension, and thus, a commitment to an updated trace must be
+/// rebuilt from scratch. Callers which maintain their own Merkle trees over data which does
+/// change one leaf at a time can use [MerkleTree::update_leaf()].
+///
+/// TODO: abstract over [VectorCommitment](crypto::VectorCommitment) once trace queries can carry
+/// openings other than batch Merkle proofs.
 pub struct TraceCommitment<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
     trace_lde: TraceLde<E>,
     main_segment_tree: MerkleTree<H>,
@@ -72,6 +80,11 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
         &self.trace_lde
     }
 
+    /// Consumes this commitment and returns the execution trace LDE it was built from.
+    pub fn into_trace_table(self) -> TraceLde<E> {
+        self.trace_lde
+    }
+
     // QUERY TRACE
     // --------------------------------------------------------------------------------------------
     /// Returns trace table rows at the specified positions along with Merkle authentication paths
@@ -0,0 +1,72 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::Matrix;
+use air::proof::MembershipProof;
+use crypto::{ElementHasher, MerkleTree};
+use math::FieldElement;
+
+// COLUMN MEMBERSHIP
+// ================================================================================================
+
+/// Returns a proof that the specified `value` appears in the specified `column` of the committed
+/// `trace`, or None if the value does not appear in the column.
+///
+/// `trace` is expected to be a low-degree extension of a trace segment with the specified
+/// `blowup_factor` (or the trace segment itself, in which case `blowup_factor` should be 1), and
+/// `trace_tree` is expected to be a commitment to this segment built via
+/// [Matrix::commit_to_rows()]. Only rows in the trace domain (i.e., rows at positions which are
+/// multiples of `blowup_factor`) are searched. The proof opens the first such row in which the
+/// column contains the value; it can be verified against the root of `trace_tree` via
+/// [verify_column_membership()](air::proof::verify_column_membership) function.
+///
+/// # Panics
+/// Panics if:
+/// * `column` is greater than or equal to the number of columns in the `trace`.
+/// * The number of rows in the `trace` is not the same as the number of leaves in `trace_tree`.
+/// * `blowup_factor` is zero.
+pub fn prove_column_membership<E, H>(
+    trace: &Matrix<E>,
+    trace_tree: &MerkleTree<H>,
+    column: usize,
+    value: E,
+    blowup_factor: usize,
+) -> Option<MembershipProof<E, H>>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    assert!(
+        column < trace.num_cols(),
+        "column index must be smaller than {}, but was {}",
+        trace.num_cols(),
+        column
+    );
+    assert_eq!(
+        trace.num_rows(),
+        trace_tree.leaves().len(),
+        "number of rows in trace must be the same as number of leaves in trace commitment"
+    );
+    assert!(blowup_factor > 0, "blowup factor must be greater than zero");
+
+    let position = trace
+        .get_column(column)
+        .iter()
+        .step_by(blowup_factor)
+        .position(|&v| v == value)?
+        * blowup_factor;
+
+    let mut row = vec![E::ZERO; trace.num_cols()];
+    trace.read_row_into(position, &mut row);
+    let path = trace_tree
+        .prove(position)
+        .expect("failed to generate a Merkle path for the opened row");
+
+    Some(MembershipProof {
+        position,
+        row,
+        path,
+    })
+}
@@ -3,10 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::Matrix;
+use super::{Matrix, TraceError};
 use air::{Air, AuxTraceRandElements, EvaluationFrame, TraceInfo, TraceLayout};
-use math::{polynom, FieldElement, StarkField};
-use utils::collections::Vec;
+use math::{log2, FieldElement, StarkField};
+use utils::{collections::Vec, string::String};
 
 mod trace_lde;
 pub use trace_lde::TraceLde;
@@ -14,12 +14,24 @@ pub use trace_lde::TraceLde;
 mod poly_table;
 pub use poly_table::TracePolyTable;
 
+mod poly_cache;
+pub use poly_cache::TracePolyCache;
+
 mod trace_table;
 pub use trace_table::{TraceTable, TraceTableFragment};
 
+mod multi_trace;
+pub use multi_trace::MultiTrace;
+
+mod sparse_trace;
+pub use sparse_trace::{SparseColumn, SparseTrace, TraceColumn};
+
 mod commitment;
 pub use commitment::TraceCommitment;
 
+mod membership;
+pub use membership::prove_column_membership;
+
 #[cfg(test)]
 mod tests;
 
@@ -37,8 +49,10 @@ mod tests;
 ///
 /// This crate exposes one concrete implementation of the [Trace] trait: [TraceTable]. This
 /// implementation supports concurrent trace generation and should be sufficient in most
-/// situations. However, if functionality provided by [TraceTable] is not sufficient, uses can
-/// provide custom implementations of the [Trace] trait which better suit their needs.
+/// situations. Several independent traces can also be combined into a single trace using
+/// [MultiTrace], and traces with columns which are zero almost everywhere can be stored compactly
+/// using [SparseTrace]. However, if functionality provided by [TraceTable] is not sufficient, uses
+/// can provide custom implementations of the [Trace] trait which better suit their needs.
 pub trait Trace: Sized {
     /// Base field for this execution trace.
     ///
@@ -76,6 +90,38 @@ pub trait Trace: Sized {
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns upper bounds on the degrees of polynomials describing the columns of the main
+    /// trace segment.
+    ///
+    /// When the hints are provided, the prover uses them to reduce the cost of the low-degree
+    /// extension of the main trace segment. Before extending the segment, the prover makes sure
+    /// that every column polynomial has a degree which does not exceed its hint, and returns an
+    /// error otherwise; thus, incorrect hints cannot result in an unsound proof.
+    ///
+    /// The default implementation returns `None`, in which case all columns are assumed to be
+    /// described by polynomials of degree `trace_length - 1`.
+    fn main_segment_degree_hints(&self) -> Option<Vec<usize>> {
+        None
+    }
+
+    /// Returns upper bounds on the degrees of polynomials describing the columns of the
+    /// auxiliary trace segment at the specified index.
+    ///
+    /// This is similar to [Trace::main_segment_degree_hints()] but applies to auxiliary trace
+    /// segments. The default implementation returns `None`.
+    #[allow(unused_variables)]
+    fn aux_segment_degree_hints(&self, aux_segment_idx: usize) -> Option<Vec<usize>> {
+        None
+    }
+
+    /// Returns the number of rows in this trace before it was padded to a power of two.
+    ///
+    /// The default implementation returns [Trace::length()], i.e., the trace is assumed to
+    /// contain no padding.
+    fn unpadded_length(&self) -> usize {
+        self.length()
+    }
+
     /// Returns trace info for this trace.
     fn get_info(&self) -> TraceInfo {
         TraceInfo::new_multi_segment(self.layout().clone(), self.length(), self.meta().to_vec())
@@ -93,6 +139,80 @@ pub trait Trace: Sized {
 
     // VALIDATION
     // --------------------------------------------------------------------------------------------
+    /// Checks internal consistency of this trace without evaluating any constraints.
+    ///
+    /// Specifically, this makes sure that:
+    /// * Trace length is a power of two, is at least [TraceInfo::MIN_TRACE_LENGTH], and does not
+    ///   exceed the size of the largest multiplicative subgroup of the base field.
+    /// * The main trace segment has as many columns as specified by the trace layout, and all of
+    ///   these columns have [Trace::length()] rows.
+    /// * [Trace::aux_trace_width()] is equal to the sum of auxiliary segment widths in the trace
+    ///   layout.
+    /// * Trace metadata is not longer than [TraceInfo::MAX_META_LENGTH] bytes.
+    ///
+    /// Unlike [Trace::validate()], this does not require an AIR and is cheap enough to be run
+    /// before every proof generation, including in release mode.
+    ///
+    /// # Errors
+    /// Returns an error if any of the above conditions is not satisfied.
+    fn check_structure(&self) -> Result<(), TraceError> {
+        // check trace length
+        let trace_length = self.length();
+        if trace_length < TraceInfo::MIN_TRACE_LENGTH {
+            return Err(TraceError::TraceTooShort(
+                TraceInfo::MIN_TRACE_LENGTH,
+                trace_length,
+            ));
+        }
+        if !trace_length.is_power_of_two() {
+            return Err(TraceError::TraceLengthNotPowerOfTwo(trace_length));
+        }
+        let max_log_length = <Self::BaseField as StarkField>::TWO_ADICITY;
+        if log2(trace_length) > max_log_length {
+            return Err(TraceError::TraceTooLong(max_log_length, log2(trace_length)));
+        }
+
+        // check dimensions of the main trace segment
+        let layout = self.layout();
+        let main_segment = self.main_segment();
+        if main_segment.num_cols() != layout.main_trace_width() {
+            return Err(TraceError::MismatchedTraceWidth(
+                layout.main_trace_width(),
+                main_segment.num_cols(),
+            ));
+        }
+        for (i, column) in main_segment.columns().enumerate() {
+            if column.len() != trace_length {
+                return Err(TraceError::MismatchedColumnLength(
+                    i,
+                    trace_length,
+                    column.len(),
+                ));
+            }
+        }
+
+        // check widths of auxiliary trace segments
+        let aux_segments_width = (0..layout.num_aux_segments())
+            .map(|i| layout.get_aux_segment_width(i))
+            .sum::<usize>();
+        if self.aux_trace_width() != aux_segments_width {
+            return Err(TraceError::MismatchedAuxTraceWidth(
+                self.aux_trace_width(),
+                aux_segments_width,
+            ));
+        }
+
+        // check trace metadata
+        if self.meta().len() > TraceInfo::MAX_META_LENGTH {
+            return Err(TraceError::MetadataTooLong(
+                TraceInfo::MAX_META_LENGTH,
+                self.meta().len(),
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Checks if this trace is valid against the specified AIR, and panics if not.
     ///
     /// NOTE: this is a very expensive operation and is intended for use only in debug mode.
@@ -121,50 +241,50 @@ pub trait Trace: Sized {
             assertion.apply(self.length(), |step, value| {
                 assert!(
                     value == self.main_segment().get(assertion.column(), step),
-                    "trace does not satisfy assertion main_trace({}, {}) == {}",
+                    "trace does not satisfy assertion main_trace({}, {}) == {}{}",
                     assertion.column(),
                     step,
-                    value
+                    value,
+                    padding_note(step, self.unpadded_length())
                 );
             });
         }
 
-        // then, check assertions against auxiliary trace segments
-        for assertion in air.get_aux_assertions(aux_rand_elements) {
+        // then, check assertions against auxiliary trace segments (including the assertion
+        // against the Lagrange kernel column, if any)
+        let mut aux_assertions = air.get_aux_assertions(aux_rand_elements);
+        if let Some(assertion) = air.get_lagrange_kernel_aux_assertion(aux_rand_elements) {
+            aux_assertions.push(assertion);
+        }
+        for assertion in aux_assertions {
             // find which segment the assertion is for and remap assertion column index to the
             // column index in the context of this segment
-            let mut column_idx = assertion.column();
-            let mut segment_idx = 0;
-            for i in 0..self.layout().num_aux_segments() {
-                let segment_width = self.layout().get_aux_segment_width(i);
-                if column_idx < segment_width {
-                    segment_idx = i;
-                    break;
-                }
-                column_idx -= segment_width;
-            }
+            let (segment_idx, column_idx) =
+                self.layout().get_aux_segment_column(assertion.column());
 
             // get the matrix and verify the assertion against it
             assertion.apply(self.length(), |step, value| {
                 assert!(
                     value == aux_segments[segment_idx].get(column_idx, step),
-                    "trace does not satisfy assertion aux_trace({}, {}) == {}",
+                    "trace does not satisfy assertion aux_trace({}, {}) == {}{}",
                     assertion.column(),
                     step,
-                    value
+                    value,
+                    padding_note(step, self.unpadded_length())
                 );
             });
         }
 
         // --- 2. make sure this trace satisfies all transition constraints -----------------------
 
-        // collect the info needed to build periodic values for a specific step
-        let g = air.trace_domain_generator();
-        let periodic_values_polys = air.get_periodic_column_polys();
-        let mut periodic_values = vec![Self::BaseField::ZERO; periodic_values_polys.len()];
+        // evaluate periodic columns over the trace domain; this is done once via FFT so that we
+        // don't need to evaluate periodic polynomials at every step
+        let periodic_columns =
+            air.get_periodic_column_evaluations(self.length(), Self::BaseField::ONE);
+        let mut periodic_values = vec![Self::BaseField::ZERO; periodic_columns.len()];
+        let mut next_periodic_values = periodic_values.clone();
 
         // initialize buffers to hold evaluation frames and results of constraint evaluations
-        let mut x = Self::BaseField::ONE;
         let mut main_frame = A::Frame::new(air);
         let mut aux_frame = if air.trace_info().is_multi_segment() {
             Some(A::AuxFrame::<E>::new(air))
@@ -176,7 +296,8 @@ pub trait Trace: Sized {
         let mut aux_evaluations = vec![E::ZERO; air.context().num_aux_transition_constraints()];
 
         // we check transition constraints on all steps except the last k steps, where k is the
-        // number of steps exempt from transition constraints (guaranteed to be at least 1)
+        // number of steps exempt from transition constraints; for cyclic constraints k is 0, and
+        // the frame for the last step wraps around to the first row of the trace
         // TODO: Handle constraint-specific exemption nums
         //
         let exemption_step = air
@@ -188,17 +309,25 @@ pub trait Trace: Sized {
         for i in 0..self.length() {
             let step = i;
 
-            // build periodic values
-            for (p, v) in periodic_values_polys.iter().zip(periodic_values.iter_mut()) {
-                let num_cycles = air.trace_length() / p.len();
-                let x = x.exp((num_cycles as u32).into());
-                *v = polynom::eval(p, x);
+            // build periodic values for the current and the next steps
+            for ((column, v), next_v) in periodic_columns
+                .iter()
+                .zip(periodic_values.iter_mut())
+                .zip(next_periodic_values.iter_mut())
+            {
+                *v = column[step % column.len()];
+                *next_v = column[(step + 1) % column.len()];
             }
 
             // evaluate transition constraints for the main trace segment and make sure they all
             // evaluate to zeros
             main_frame.read_from(self.main_segment(), step, 0, 1);
-            air.evaluate_transition(&main_frame, &periodic_values, &mut main_evaluations);
+            air.evaluate_transition(
+                &main_frame,
+                &periodic_values,
+                &next_periodic_values,
+                &mut main_evaluations,
+            );
             for (i, &evaluation) in main_evaluations.iter().enumerate() {
                 if step < exemption_step[i] {
                     assert!(
@@ -220,6 +349,13 @@ pub trait Trace: Sized {
                 }
                 air.evaluate_aux_transition(
                     &main_frame,
+                    aux_frame,
+                    &periodic_values,
+                    &next_periodic_values,
+                    aux_rand_elements,
+                    &mut aux_evaluations,
+                );
+                air.evaluate_lagrange_kernel_aux_transition(
                     aux_frame,
                     &periodic_values,
                     aux_rand_elements,
@@ -236,9 +372,22 @@ pub trait Trace: Sized {
                     }
                 }
             }
-
-            // update x coordinate of the domain
-            x *= g;
         }
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a note to be appended to assertion failure messages if the specified step is in the
+/// padded part of an execution trace with the specified unpadded length.
+fn padding_note(step: usize, unpadded_length: usize) -> String {
+    if step < unpadded_length {
+        String::new()
+    } else {
+        format!(
+            "; step {} is in the padded part of the trace (unpadded length is {})",
+            step, unpadded_length
+        )
+    }
+}
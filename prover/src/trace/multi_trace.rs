@@ -0,0 +1,261 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Matrix, Trace};
+use air::TraceLayout;
+use core::ops::Range;
+use math::FieldElement;
+use utils::collections::Vec;
+
+// MULTI TRACE
+// ================================================================================================
+/// An execution trace which combines several independent execution traces into a single trace.
+///
+/// Columns of the sub-traces are placed side by side: the main segment of a multi-trace consists
+/// of the main segment columns of the first sub-trace, followed by the main segment columns of
+/// the second sub-trace etc. Auxiliary segments are combined in the same way, and each auxiliary
+/// segment is built by routing [Trace::build_aux_segment()] to all sub-traces which still have
+/// auxiliary segments to build. Random elements are shared between sub-traces: each sub-trace
+/// receives as many random elements as its own layout requires.
+///
+/// This allows proving several independent computations with a single proof. The AIR for the
+/// combined computation can delegate constraint evaluation to the AIRs of the sub-computations:
+/// [EvaluationFrame::select_columns()](air::EvaluationFrame::select_columns) restricts a frame to
+/// the columns described by [MultiTrace::main_columns()] and [MultiTrace::aux_columns()], and
+/// thus, each sub-AIR can refer to its columns by their local indices. Assertions of a sub-AIR
+/// can be moved to the columns of the combined trace via
+/// [Assertion::remap_column()](air::Assertion::remap_column).
+pub struct MultiTrace<T: Trace> {
+    traces: Vec<T>,
+    layout: TraceLayout,
+    main_segment: Matrix<T::BaseField>,
+    meta: Vec<u8>,
+}
+
+impl<T: Trace> MultiTrace<T> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new trace which combines all provided traces side by side.
+    ///
+    /// Metadata of the sub-traces is not carried over into the combined trace.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `traces` is empty.
+    /// * Not all traces have the same length.
+    /// * The combined width of all traces is greater than 255.
+    pub fn new(traces: Vec<T>) -> Self {
+        assert!(
+            !traces.is_empty(),
+            "a multi-trace must consist of at least one trace"
+        );
+        let length = traces[0].length();
+        for (i, trace) in traces.iter().enumerate() {
+            assert_eq!(
+                length,
+                trace.length(),
+                "all traces must have the same length, but trace {} has length {} instead of {}",
+                i,
+                trace.length(),
+                length
+            );
+        }
+
+        // sum up widths of all segments across the sub-traces; random elements are shared
+        // between sub-traces and so each segment requires as many random elements as the most
+        // demanding sub-trace
+        let main_width = traces.iter().map(|t| t.main_trace_width()).sum();
+        let num_aux_segments = traces
+            .iter()
+            .map(|t| t.layout().num_aux_segments())
+            .max()
+            .unwrap_or(0);
+        let mut aux_widths = vec![0; num_aux_segments];
+        let mut aux_rands = vec![0; num_aux_segments];
+        for layout in traces.iter().map(|t| t.layout()) {
+            for i in 0..layout.num_aux_segments() {
+                aux_widths[i] += layout.get_aux_segment_width(i);
+                aux_rands[i] = aux_rands[i].max(layout.get_aux_segment_rand_elements(i));
+            }
+        }
+        let layout = if num_aux_segments == 0 {
+            TraceLayout::new(main_width, &[0], &[0])
+        } else {
+            TraceLayout::new(main_width, &aux_widths, &aux_rands)
+        };
+
+        let columns = traces
+            .iter()
+            .flat_map(|t| t.main_segment().columns().map(|c| c.to_vec()))
+            .collect();
+
+        Self {
+            traces,
+            layout,
+            main_segment: Matrix::new(columns),
+            meta: Vec::new(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of sub-traces combined in this trace.
+    pub fn num_traces(&self) -> usize {
+        self.traces.len()
+    }
+
+    /// Returns a reference to the sub-trace at the specified index.
+    pub fn get_trace(&self, trace_idx: usize) -> &T {
+        &self.traces[trace_idx]
+    }
+
+    /// Returns the range of main segment columns occupied by the sub-trace at the specified index.
+    pub fn main_columns(&self, trace_idx: usize) -> Range<usize> {
+        let widths = self.traces.iter().map(|t| t.main_trace_width());
+        get_column_range(widths, trace_idx)
+    }
+
+    /// Returns the range of columns occupied by the sub-trace at the specified index in the
+    /// auxiliary segment at the specified index.
+    ///
+    /// The range is empty if the sub-trace does not have an auxiliary segment at this index.
+    pub fn aux_columns(&self, trace_idx: usize, aux_segment_idx: usize) -> Range<usize> {
+        let widths = self
+            .traces
+            .iter()
+            .map(|t| get_aux_segment_width(t.layout(), aux_segment_idx));
+        get_column_range(widths, trace_idx)
+    }
+}
+
+// TRACE TRAIT IMPLEMENTATION
+// ================================================================================================
+
+impl<T: Trace> Trace for MultiTrace<T> {
+    type BaseField = T::BaseField;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.main_segment.num_rows()
+    }
+
+    fn meta(&self) -> &[u8] {
+        &self.meta
+    }
+
+    fn unpadded_length(&self) -> usize {
+        self.traces
+            .iter()
+            .map(|t| t.unpadded_length())
+            .max()
+            .unwrap_or_else(|| self.length())
+    }
+
+    fn main_segment(&self) -> &Matrix<Self::BaseField> {
+        &self.main_segment
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[Matrix<E>],
+        rand_elements: &[E],
+    ) -> Option<Matrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let segment_idx = aux_segments.len();
+        if segment_idx >= self.layout.num_aux_segments() {
+            return None;
+        }
+
+        let mut columns = Vec::with_capacity(self.layout.get_aux_segment_width(segment_idx));
+        let mut offsets = vec![0; segment_idx];
+        for trace in self.traces.iter_mut() {
+            let layout = trace.layout().clone();
+            if segment_idx >= layout.num_aux_segments() {
+                // this sub-trace is already complete, but its columns in the previously built
+                // segments still need to be skipped over
+                for (i, offset) in offsets.iter_mut().enumerate() {
+                    *offset += get_aux_segment_width(&layout, i);
+                }
+                continue;
+            }
+
+            // extract columns of the previously built segments which belong to this sub-trace
+            let local_segments = aux_segments
+                .iter()
+                .zip(offsets.iter_mut())
+                .enumerate()
+                .map(|(i, (segment, offset))| {
+                    let width = layout.get_aux_segment_width(i);
+                    let local_columns = (*offset..*offset + width)
+                        .map(|j| segment.get_column(j).to_vec())
+                        .collect();
+                    *offset += width;
+                    Matrix::new(local_columns)
+                })
+                .collect::<Vec<_>>();
+
+            let num_rand_elements = layout.get_aux_segment_rand_elements(segment_idx);
+            let segment = trace
+                .build_aux_segment(&local_segments, &rand_elements[..num_rand_elements])
+                .expect("failed to build auxiliary segment of a sub-trace");
+            assert_eq!(
+                layout.get_aux_segment_width(segment_idx),
+                segment.num_cols(),
+                "auxiliary segment of a sub-trace does not match its layout"
+            );
+            columns.extend(segment.into_columns());
+        }
+
+        Some(Matrix::new(columns))
+    }
+
+    fn main_segment_degree_hints(&self) -> Option<Vec<usize>> {
+        let hints = self
+            .traces
+            .iter()
+            .map(|t| t.main_segment_degree_hints())
+            .collect::<Vec<_>>();
+        if hints.iter().all(|h| h.is_none()) {
+            return None;
+        }
+
+        let max_degree = self.length() - 1;
+        let result = self
+            .traces
+            .iter()
+            .zip(hints)
+            .flat_map(|(t, h)| h.unwrap_or_else(|| vec![max_degree; t.main_trace_width()]))
+            .collect();
+        Some(result)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the width of the specified auxiliary segment in the provided layout, or zero if the
+/// layout does not have such segment.
+fn get_aux_segment_width(layout: &TraceLayout, segment_idx: usize) -> usize {
+    if segment_idx < layout.num_aux_segments() {
+        layout.get_aux_segment_width(segment_idx)
+    } else {
+        0
+    }
+}
+
+/// Returns the range of columns occupied by the item at the specified index, assuming items of
+/// the provided widths are placed side by side.
+fn get_column_range<I: Iterator<Item = usize>>(widths: I, idx: usize) -> Range<usize> {
+    let widths = widths.collect::<Vec<_>>();
+    let start = widths[..idx].iter().sum::<usize>();
+    start..start + widths[idx]
+}
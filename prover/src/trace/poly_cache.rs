@@ -0,0 +1,151 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::Matrix;
+use math::{fft, FieldElement};
+use utils::collections::Vec;
+
+// TRACE POLYNOMIAL CACHE
+// ================================================================================================
+/// Precomputed polynomials for a set of execution trace columns which are fixed across proofs.
+///
+/// Some computations contain columns whose values do not depend on the inputs of the computation
+/// (e.g., preprocessed selector columns). When the same computation is proven repeatedly, the
+/// polynomials describing such columns can be interpolated once and reused across proofs.
+///
+/// A cache is built from the main segment of an execution trace via
+/// [TracePolyCache::with_fixed_columns()] and can be supplied to the prover via
+/// [Prover::trace_poly_cache()](crate::Prover::trace_poly_cache). The prover uses the cache only
+/// if [TracePolyCache::is_valid_for()] returns true for the main segment of the trace being
+/// proven (i.e., the trace has the same length as the trace for which the cache was built);
+/// otherwise, all columns are interpolated from scratch. Additionally, a cached polynomial is
+/// used for a column only if the values of this column are the same as the values from which
+/// the polynomial was interpolated; thus, a stale cache cannot result in an invalid proof. A
+/// cache for a different trace length can be rebuilt via [TracePolyCache::update()].
+pub struct TracePolyCache<E: FieldElement> {
+    trace_length: usize,
+    entries: Vec<CachedColumn<E>>,
+}
+
+struct CachedColumn<E: FieldElement> {
+    index: usize,
+    values: Vec<E>,
+    poly: Vec<E>,
+}
+
+impl<E: FieldElement> TracePolyCache<E> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new cache containing polynomials for the columns of the provided trace segment
+    /// at the specified indices.
+    ///
+    /// # Panics
+    /// Panics if any of the indices is out of bounds for the provided trace segment.
+    pub fn with_fixed_columns(trace: &Matrix<E>, indices: &[usize]) -> Self {
+        let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(trace.num_rows());
+        let mut entries: Vec<CachedColumn<E>> = Vec::with_capacity(indices.len());
+        for &index in indices {
+            assert!(
+                index < trace.num_cols(),
+                "column index {} is out of bounds for a trace with {} columns",
+                index,
+                trace.num_cols()
+            );
+            if entries.iter().any(|entry| entry.index == index) {
+                continue;
+            }
+
+            let values = trace.get_column(index).to_vec();
+            let mut poly = values.clone();
+            fft::interpolate_poly(&mut poly, &inv_twiddles);
+            entries.push(CachedColumn {
+                index,
+                values,
+                poly,
+            });
+        }
+
+        TracePolyCache {
+            trace_length: trace.num_rows(),
+            entries,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the length of the execution trace for which this cache was built.
+    pub fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    /// Returns the number of columns stored in this cache.
+    pub fn num_columns(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if this cache can be used to interpolate the provided trace segment.
+    ///
+    /// This is the case when the length of the segment is the same as the length of the trace
+    /// used to build this cache.
+    pub fn is_valid_for(&self, trace: &Matrix<E>) -> bool {
+        self.trace_length == trace.num_rows()
+    }
+
+    /// Returns the cached polynomial for the column at the specified index, or None if the
+    /// column is not in this cache or the values of the column in the provided trace segment
+    /// differ from the values used to build the cache.
+    pub fn get(&self, trace: &Matrix<E>, index: usize) -> Option<&[E]> {
+        if !self.is_valid_for(trace) {
+            return None;
+        }
+        self.entries
+            .iter()
+            .find(|entry| entry.index == index && entry.values == trace.get_column(index))
+            .map(|entry| entry.poly.as_slice())
+    }
+
+    // PUBLIC MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Rebuilds this cache for the provided trace segment if the cache is not valid for it.
+    ///
+    /// Returns true if the cache was rebuilt, and false if the cache was already valid for the
+    /// provided segment.
+    pub fn update(&mut self, trace: &Matrix<E>) -> bool {
+        if self.is_valid_for(trace) {
+            return false;
+        }
+        let indices = self.entries.iter().map(|e| e.index).collect::<Vec<_>>();
+        *self = Self::with_fixed_columns(trace, &indices);
+        true
+    }
+
+    // INTERPOLATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Interpolates columns of the provided trace segment into polynomials in coefficient form.
+    ///
+    /// This is equivalent to [Matrix::interpolate_columns()], but polynomials found in this
+    /// cache are cloned instead of being interpolated.
+    pub fn interpolate_columns(&self, trace: &Matrix<E>) -> Matrix<E> {
+        if !self.is_valid_for(trace) {
+            return trace.interpolate_columns();
+        }
+
+        let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(trace.num_rows());
+        let columns = (0..trace.num_cols())
+            .map(|i| match self.get(trace, i) {
+                Some(poly) => poly.to_vec(),
+                None => {
+                    let mut poly = trace.get_column(i).to_vec();
+                    fft::interpolate_poly(&mut poly, &inv_twiddles);
+                    poly
+                }
+            })
+            .collect();
+        Matrix::new(columns)
+    }
+}
@@ -5,9 +5,9 @@
 
 use crate::{
     matrix::{ColumnIter, MultiColumnIter},
-    Matrix,
+    Matrix, ProverError,
 };
-use math::{log2, FieldElement, StarkField};
+use math::{log2, polynom, FieldElement, StarkField};
 use utils::collections::Vec;
 
 // TRACE POLYNOMIAL TABLE
@@ -56,7 +56,7 @@ impl<E: FieldElement> TracePolyTable<E> {
     }
 
     /// Evaluates all trace polynomials (across all trace segments) at the specified point `x`.
-    pub fn evaluate_at(&self, x: E) -> Vec<E> {
+    pub fn evaluate_columns_at(&self, x: E) -> Vec<E> {
         let mut result = self.main_segment_polys.evaluate_columns_at(x);
         for aux_polys in self.aux_segment_polys.iter() {
             result.append(&mut aux_polys.evaluate_columns_at(x));
@@ -64,6 +64,37 @@ impl<E: FieldElement> TracePolyTable<E> {
         result
     }
 
+    /// Evaluates the trace polynomial for the column at the specified index at the specified
+    /// point `x`.
+    ///
+    /// Columns are indexed across all trace segments. That is, columns of the main trace segment
+    /// come first, followed by the columns of the auxiliary segments.
+    ///
+    /// # Errors
+    /// Returns an error if `col` is greater than or equal to the total number of columns in all
+    /// trace segments.
+    pub fn evaluate_at(&self, col: usize, x: E) -> Result<E, ProverError> {
+        let main_width = self.main_segment_polys.num_cols();
+        if col < main_width {
+            return Ok(polynom::eval(self.main_segment_polys.get_column(col), x));
+        }
+
+        let mut col_idx = col - main_width;
+        for segment_polys in self.aux_segment_polys.iter() {
+            if col_idx < segment_polys.num_cols() {
+                return Ok(polynom::eval(segment_polys.get_column(col_idx), x));
+            }
+            col_idx -= segment_polys.num_cols();
+        }
+
+        let num_cols = main_width
+            + self
+                .aux_segment_polys
+                .iter()
+                .fold(0, |s, m| s + m.num_cols());
+        Err(ProverError::TraceColumnIndexOutOfBounds(num_cols, col))
+    }
+
     /// Returns an out-of-domain evaluation frame constructed by evaluating trace polynomials
     /// for all columns at the set of points {z * g^o_i}, where g is the generator of the trace domain,
     /// and {o_i} are the frame row offsets
@@ -71,7 +102,7 @@ impl<E: FieldElement> TracePolyTable<E> {
         let g = E::from(E::BaseField::get_root_of_unity(log2(self.poly_size())));
         frame_offsets
             .into_iter()
-            .map(|i| self.evaluate_at(z * g.exp((*i as u64).into())))
+            .map(|i| self.evaluate_columns_at(z * g.exp((*i as u64).into())))
             .collect()
     }
 
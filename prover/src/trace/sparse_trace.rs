@@ -0,0 +1,348 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Matrix, Trace};
+use crate::TraceError;
+use air::{TraceInfo, TraceLayout};
+use math::{log2, FieldElement, StarkField};
+use utils::collections::Vec;
+
+// SPARSE COLUMN
+// ================================================================================================
+/// A trace column in which only non-zero values are stored.
+///
+/// Non-zero values are stored as (step, value) pairs sorted by step; all other cells of the column
+/// are implicitly set to zero. This is useful for columns which are zero almost everywhere (e.g.,
+/// one-hot selector columns), as memory required to store such columns is proportional to the
+/// number of non-zero cells rather than to the length of the column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseColumn<B: StarkField> {
+    length: usize,
+    entries: Vec<(usize, B)>,
+}
+
+impl<B: StarkField> SparseColumn<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new column of the specified length with all cells set to zero.
+    pub fn new(length: usize) -> Self {
+        Self {
+            length,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Returns a new column of the specified length with cells at the specified steps set to the
+    /// specified values; all other cells are set to zero.
+    ///
+    /// # Panics
+    /// Panics if any of the steps is out of bounds for a column of the specified length, or if
+    /// the same step appears more than once.
+    pub fn with_entries(length: usize, mut entries: Vec<(usize, B)>) -> Self {
+        entries.sort_unstable_by_key(|&(step, _)| step);
+        for (i, &(step, _)) in entries.iter().enumerate() {
+            assert!(
+                step < length,
+                "step {} is out of bounds for column of length {}",
+                step,
+                length
+            );
+            assert!(
+                i == 0 || entries[i - 1].0 != step,
+                "step {} appears more than once",
+                step
+            );
+        }
+        entries.retain(|&(_, value)| value != B::ZERO);
+        Self { length, entries }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of cells in this column.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the number of non-zero cells in this column.
+    pub fn num_nonzero(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns (step, value) pairs for all non-zero cells of this column sorted by step.
+    pub fn entries(&self) -> &[(usize, B)] {
+        &self.entries
+    }
+
+    /// Returns the value of the cell at the specified step.
+    ///
+    /// # Panics
+    /// Panics if `step` is out of bounds for this column.
+    pub fn get(&self, step: usize) -> B {
+        assert!(
+            step < self.length,
+            "step {} is out of bounds for column of length {}",
+            step,
+            self.length
+        );
+        match self.entries.binary_search_by_key(&step, |&(s, _)| s) {
+            Ok(idx) => self.entries[idx].1,
+            Err(_) => B::ZERO,
+        }
+    }
+
+    /// Returns all values of this column, including zeros, as a vector.
+    pub fn to_dense(&self) -> Vec<B> {
+        let mut result = vec![B::ZERO; self.length];
+        for &(step, value) in self.entries.iter() {
+            result[step] = value;
+        }
+        result
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Sets the value of the cell at the specified step.
+    ///
+    /// # Panics
+    /// Panics if `step` is out of bounds for this column.
+    pub fn set(&mut self, step: usize, value: B) {
+        assert!(
+            step < self.length,
+            "step {} is out of bounds for column of length {}",
+            step,
+            self.length
+        );
+        match self.entries.binary_search_by_key(&step, |&(s, _)| s) {
+            Ok(idx) if value == B::ZERO => {
+                self.entries.remove(idx);
+            }
+            Ok(idx) => self.entries[idx].1 = value,
+            Err(_) if value == B::ZERO => (),
+            Err(idx) => self.entries.insert(idx, (step, value)),
+        }
+    }
+}
+
+// TRACE COLUMN
+// ================================================================================================
+/// A column of a [SparseTrace] which can be stored either densely or sparsely.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceColumn<B: StarkField> {
+    /// A column in which values of all cells are stored.
+    Dense(Vec<B>),
+    /// A column in which only values of non-zero cells are stored.
+    Sparse(SparseColumn<B>),
+}
+
+impl<B: StarkField> TraceColumn<B> {
+    /// Returns the number of cells in this column.
+    pub fn length(&self) -> usize {
+        match self {
+            Self::Dense(values) => values.len(),
+            Self::Sparse(column) => column.length(),
+        }
+    }
+
+    /// Returns the value of the cell at the specified step.
+    ///
+    /// # Panics
+    /// Panics if `step` is out of bounds for this column.
+    pub fn get(&self, step: usize) -> B {
+        match self {
+            Self::Dense(values) => values[step],
+            Self::Sparse(column) => column.get(step),
+        }
+    }
+
+    /// Returns all values of this column as a vector.
+    pub fn to_dense(&self) -> Vec<B> {
+        match self {
+            Self::Dense(values) => values.clone(),
+            Self::Sparse(column) => column.to_dense(),
+        }
+    }
+
+    /// Converts this column into a vector of all its values.
+    ///
+    /// Values of a dense column are moved into the returned vector without being copied.
+    pub fn into_dense(self) -> Vec<B> {
+        match self {
+            Self::Dense(values) => values,
+            Self::Sparse(column) => column.to_dense(),
+        }
+    }
+}
+
+impl<B: StarkField> From<Vec<B>> for TraceColumn<B> {
+    fn from(values: Vec<B>) -> Self {
+        Self::Dense(values)
+    }
+}
+
+impl<B: StarkField> From<SparseColumn<B>> for TraceColumn<B> {
+    fn from(column: SparseColumn<B>) -> Self {
+        Self::Sparse(column)
+    }
+}
+
+// SPARSE TRACE
+// ================================================================================================
+/// An execution trace in which some of the columns are stored sparsely.
+///
+/// Each column of the trace is described by a [TraceColumn] and can be either dense (all values
+/// are stored) or sparse (only non-zero values are stored as described by [SparseColumn]). This
+/// reduces memory footprint of traces which contain columns that are zero almost everywhere.
+///
+/// Since the prover interpolates and extends the main segment of a trace as a dense [Matrix],
+/// the columns are materialized into such a matrix when the trace is built: dense columns are
+/// moved into the matrix without being copied, and sparse columns are expanded one at a time.
+/// Thus, sparse columns reduce memory footprint while the trace is being built, but not while
+/// a proof for it is being generated.
+pub struct SparseTrace<B: StarkField> {
+    layout: TraceLayout,
+    main_segment: Matrix<B>,
+    meta: Vec<u8>,
+}
+
+impl<B: StarkField> SparseTrace<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Creates a new execution trace from a list of provided trace columns.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The `columns` vector is empty or has over 255 columns.
+    /// * Number of cells in any of the columns is smaller than 8, greater than the biggest
+    ///   multiplicative subgroup in the field `B`, or is not a power of two.
+    /// * Number of cells is not identical for all columns.
+    pub fn init(columns: Vec<TraceColumn<B>>) -> Self {
+        Self::try_new(columns).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates a new execution trace from a list of provided trace columns, or returns an error
+    /// if the columns do not describe a valid execution trace.
+    ///
+    /// This is the fallible version of [SparseTrace::init()].
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The `columns` vector is empty or has over 255 columns.
+    /// * Number of cells is not identical for all columns.
+    /// * Number of cells in the columns is smaller than 8, greater than the biggest
+    ///   multiplicative subgroup in the field `B`, or is not a power of two.
+    pub fn try_new(columns: Vec<TraceColumn<B>>) -> Result<Self, TraceError> {
+        if columns.is_empty() {
+            return Err(TraceError::NoColumns);
+        }
+        if columns.len() > TraceInfo::MAX_TRACE_WIDTH {
+            return Err(TraceError::TooManyColumns(
+                TraceInfo::MAX_TRACE_WIDTH,
+                columns.len(),
+            ));
+        }
+        let trace_length = columns[0].length();
+        for (i, column) in columns.iter().enumerate().skip(1) {
+            if column.length() != trace_length {
+                return Err(TraceError::MismatchedColumnLength(
+                    i,
+                    trace_length,
+                    column.length(),
+                ));
+            }
+        }
+        if trace_length < TraceInfo::MIN_TRACE_LENGTH {
+            return Err(TraceError::TraceTooShort(
+                TraceInfo::MIN_TRACE_LENGTH,
+                trace_length,
+            ));
+        }
+        if !trace_length.is_power_of_two() {
+            return Err(TraceError::TraceLengthNotPowerOfTwo(trace_length));
+        }
+        if log2(trace_length) > B::TWO_ADICITY {
+            return Err(TraceError::TraceTooLong(B::TWO_ADICITY, log2(trace_length)));
+        }
+
+        Ok(Self {
+            layout: TraceLayout::new(columns.len(), &[0], &[0]),
+            main_segment: Matrix::new(columns.into_iter().map(TraceColumn::into_dense).collect()),
+            meta: Vec::new(),
+        })
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Updates metadata for this execution trace to the specified vector of bytes.
+    ///
+    /// # Panics
+    /// Panics if the length of `meta` is greater than 65535;
+    pub fn set_meta(&mut self, meta: Vec<u8>) {
+        assert!(
+            meta.len() <= TraceInfo::MAX_META_LENGTH,
+            "number of metadata bytes cannot be greater than {}, but was {}",
+            TraceInfo::MAX_META_LENGTH,
+            meta.len()
+        );
+        self.meta = meta
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of columns in this execution trace.
+    pub fn width(&self) -> usize {
+        self.main_segment.num_cols()
+    }
+
+    /// Returns the entire column of values at the specified index.
+    pub fn get_column(&self, col_idx: usize) -> &[B] {
+        self.main_segment.get_column(col_idx)
+    }
+
+    /// Returns value of the cell in the specified column at the specified row of this trace.
+    pub fn get(&self, column: usize, step: usize) -> B {
+        self.main_segment.get(column, step)
+    }
+}
+
+// TRACE TRAIT IMPLEMENTATION
+// ================================================================================================
+
+impl<B: StarkField> Trace for SparseTrace<B> {
+    type BaseField = B;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.main_segment.num_rows()
+    }
+
+    fn meta(&self) -> &[u8] {
+        &self.meta
+    }
+
+    fn main_segment(&self) -> &Matrix<B> {
+        &self.main_segment
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        _aux_segments: &[Matrix<E>],
+        _rand_elements: &[E],
+    ) -> Option<Matrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        None
+    }
+}
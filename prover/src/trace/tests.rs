@@ -4,16 +4,29 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{
-    tests::{build_fib_trace, MockAir},
-    trace::TracePolyTable,
-    StarkDomain, Trace, TraceCommitment,
+    prove_column_membership,
+    tests::{
+        build_counter_columns, build_fib_trace, BlindedCounterAir, BlindedCounterProver,
+        CounterAir, CounterProver, MockAir,
+    },
+    Matrix, Prover, ProverError, SparseColumn, SparseTrace, StarkDomain, Trace, TraceColumn,
+    TraceCommitment, TraceError, TracePolyCache, TracePolyTable, TraceTable,
+};
+use air::{
+    proof::{verify_column_membership, Context, StarkProof},
+    Air, AirContext, Assertion, AuxTraceRandElements, DefaultEvaluationFrame, EvaluationFrame,
+    FieldExtension, HashFunction, MembershipError, ProofOptions, TraceInfo, TraceLayout,
+    TransitionConstraintDegree,
 };
 use crypto::{hashers::Blake2s_256, ElementHasher, MerkleTree};
 use math::{
-    fields::f128::BaseElement, get_power_series, get_power_series_with_offset, log2, polynom,
-    FieldElement, StarkField,
+    fft,
+    fields::{f128::BaseElement, f64::BaseElement as Felt64},
+    get_power_series, get_power_series_with_offset, log2, polynom, FieldElement, StarkField,
 };
+use rand_utils::SeededRng;
 use utils::collections::Vec;
+use verifier::VerifierError;
 
 type Blake2s = Blake2s_256<BaseElement>;
 
@@ -38,6 +51,274 @@ fn new_trace_table() {
     assert_eq!(expected, trace.get_column(1));
 }
 
+#[test]
+fn fill_trace_table_in_parallel() {
+    let trace_length = 64;
+    let mut expected = TraceTable::new(2, trace_length);
+    expected.fill(fib_init, fib_update);
+    assert_eq!(
+        build_fib_trace(trace_length * 2).get_column(1),
+        expected.get_column(1)
+    );
+
+    for num_fragments in [1, 2, 8, 32] {
+        let mut trace = TraceTable::new(2, trace_length);
+        trace.fill_par(num_fragments, fib_init_at, fib_update);
+        assert_eq!(expected.get_column(0), trace.get_column(0));
+        assert_eq!(expected.get_column(1), trace.get_column(1));
+    }
+}
+
+#[test]
+#[should_panic(expected = "state at step 16 is inconsistent with the state at step 15")]
+fn fill_trace_table_in_parallel_inconsistent_fragments() {
+    let mut trace = TraceTable::new(2, 64);
+    // every fragment starts with the initial state of the computation
+    trace.fill_par(4, |_, state| fib_init(state), fib_update);
+}
+
+#[test]
+fn try_new_trace_table() {
+    let trace = TraceTable::try_new(build_counter_columns(16)).unwrap();
+    assert_eq!(2, trace.main_trace_width());
+    assert_eq!(16, trace.length());
+
+    assert_eq!(
+        Err(TraceError::NoColumns),
+        TraceTable::<BaseElement>::try_new(Vec::new()).map(|_| ())
+    );
+
+    let columns = vec![vec![BaseElement::ONE; 16]; TraceInfo::MAX_TRACE_WIDTH + 1];
+    assert_eq!(
+        Err(TraceError::TooManyColumns(
+            TraceInfo::MAX_TRACE_WIDTH,
+            TraceInfo::MAX_TRACE_WIDTH + 1
+        )),
+        TraceTable::try_new(columns).map(|_| ())
+    );
+
+    let mut columns = build_counter_columns(16);
+    columns[1].pop();
+    assert_eq!(
+        Err(TraceError::MismatchedColumnLength(1, 16, 15)),
+        TraceTable::try_new(columns).map(|_| ())
+    );
+
+    assert_eq!(
+        Err(TraceError::TraceTooShort(TraceInfo::MIN_TRACE_LENGTH, 4)),
+        TraceTable::try_new(build_counter_columns(4)).map(|_| ())
+    );
+
+    assert_eq!(
+        Err(TraceError::TraceLengthNotPowerOfTwo(17)),
+        TraceTable::try_new(build_counter_columns(17)).map(|_| ())
+    );
+}
+
+#[test]
+fn try_new_trace_table_with_blowup() {
+    let trace = TraceTable::try_new_with_blowup(build_counter_columns(16), 8).unwrap();
+    assert_eq!(16, trace.length());
+
+    // the trace must be at least as long as the blowup factor
+    assert!(TraceTable::try_new_with_blowup(build_counter_columns(16), 16).is_ok());
+    assert_eq!(
+        Err(TraceError::TraceShorterThanBlowup(32, 16)),
+        TraceTable::try_new_with_blowup(build_counter_columns(16), 32).map(|_| ())
+    );
+
+    // errors of the underlying constructor are propagated
+    assert_eq!(
+        Err(TraceError::TraceTooShort(TraceInfo::MIN_TRACE_LENGTH, 4)),
+        TraceTable::try_new_with_blowup(build_counter_columns(4), 2).map(|_| ())
+    );
+
+    // the low-degree extension of the trace must fit into the largest multiplicative subgroup
+    // of the field; for f64 this subgroup has 2^32 elements
+    let columns = vec![vec![Felt64::ONE; 1 << 17]];
+    assert!(TraceTable::try_new_with_blowup(columns.clone(), 1 << 15).is_ok());
+    assert_eq!(
+        Err(TraceError::TraceTooLong(16, 17)),
+        TraceTable::try_new_with_blowup(columns, 1 << 16).map(|_| ())
+    );
+}
+
+#[test]
+fn pad_trace_table_and_prove() {
+    let columns = build_counter_columns(17);
+
+    // repeat the last row
+    let trace = TraceTable::pad_to_power_of_two(columns.clone(), None);
+    assert_eq!(32, trace.length());
+    assert_eq!(17, trace.unpadded_length());
+    for step in 16..32 {
+        assert_eq!(BaseElement::new(16), trace.get(0, step));
+        assert_eq!(BaseElement::ZERO, trace.get(1, step));
+    }
+
+    // the last step is in the padded part of the trace, but the assertion against it is still
+    // satisfied because the last row is repeated
+    let prover = CounterProver::default();
+    assert_eq!(BaseElement::new(16), prover.get_pub_inputs(&trace));
+    assert!(prover.prove(trace).is_ok());
+
+    // pad with a fixed value
+    let trace = TraceTable::pad_to_power_of_two(columns, Some(BaseElement::ZERO));
+    assert_eq!(32, trace.length());
+    assert_eq!(17, trace.unpadded_length());
+    assert_eq!(BaseElement::new(16), trace.get(0, 16));
+    for step in 17..32 {
+        assert_eq!(BaseElement::ZERO, trace.get(0, step));
+        assert_eq!(BaseElement::ZERO, trace.get(1, step));
+    }
+
+    // traces shorter than the minimum length are padded to the minimum length
+    let trace = TraceTable::pad_to_power_of_two(build_counter_columns(3), None);
+    assert_eq!(TraceInfo::MIN_TRACE_LENGTH, trace.length());
+    assert_eq!(3, trace.unpadded_length());
+
+    // traces which are already of power of two length are left unchanged
+    let trace = TraceTable::pad_to_power_of_two(build_counter_columns(16), None);
+    assert_eq!(16, trace.length());
+    assert_eq!(16, trace.unpadded_length());
+}
+
+#[test]
+fn randomize_trace_table() {
+    let mut trace = build_fib_trace(32);
+    let original = trace.main_segment().clone();
+
+    let mut next_value = BaseElement::new(1000);
+    trace.randomize_zk(4, || {
+        next_value += BaseElement::ONE;
+        next_value
+    });
+    assert_eq!(16, trace.length());
+
+    // rows of the computation are left unchanged
+    for step in 0..12 {
+        assert_eq!(original.get(0, step), trace.get(0, step));
+        assert_eq!(original.get(1, step), trace.get(1, step));
+    }
+
+    // blinding rows are filled row by row with values drawn from the generator
+    let mut expected = BaseElement::new(1000);
+    for step in 12..16 {
+        for column in 0..2 {
+            expected += BaseElement::ONE;
+            assert_eq!(expected, trace.get(column, step));
+        }
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "number of blinding rows must be smaller than trace length 16, but was 16"
+)]
+fn randomize_trace_table_too_many_rows() {
+    build_fib_trace(32).randomize_zk(16, || BaseElement::ONE);
+}
+
+#[test]
+fn randomize_trace_table_with_seed() {
+    let prover = BlindedCounterProver::default();
+    let seed = SeededRng::from_entropy().seed();
+
+    // traces randomized with the same seed are identical
+    let trace1 = prover.build_trace(128, seed);
+    let trace2 = prover.build_trace(128, seed);
+    assert_eq!(
+        trace1.main_segment().columns,
+        trace2.main_segment().columns,
+        "traces randomized with seed {} differ",
+        SeededRng::new(seed)
+    );
+
+    // but traces randomized with different seeds are not
+    let mut other_seed = seed;
+    other_seed[0] ^= 1;
+    let trace3 = prover.build_trace(128, other_seed);
+    assert_ne!(trace1.main_segment().columns, trace3.main_segment().columns);
+}
+
+#[test]
+fn prove_and_verify_blinded_trace() {
+    let prover = BlindedCounterProver::default();
+    let trace1 = prover.build_trace(128, [1; 32]);
+    let trace2 = prover.build_trace(128, [2; 32]);
+    let result = prover.get_pub_inputs(&trace1);
+    assert_eq!(result, prover.get_pub_inputs(&trace2));
+
+    // the same statement is proven with different blinding values, and thus the openings of the
+    // trace revealed in the proofs must differ
+    let proof1 = prover.prove(trace1).unwrap();
+    let proof2 = prover.prove(trace2).unwrap();
+    assert_ne!(proof1.trace_queries, proof2.trace_queries);
+
+    assert!(verifier::verify::<BlindedCounterAir>(proof1, result).is_ok());
+    assert!(verifier::verify::<BlindedCounterAir>(proof2, result).is_ok());
+}
+
+#[test]
+fn prove_blinded_trace_from_seed() {
+    let prover = BlindedCounterProver::default();
+    let seed = [7; 32];
+
+    // proofs for traces blinded with the same seed are byte-identical
+    let proof1 = prover.prove(prover.build_trace(128, seed)).unwrap();
+    let proof2 = prover.prove(prover.build_trace(128, seed)).unwrap();
+    assert_eq!(proof1.to_bytes(), proof2.to_bytes());
+
+    // but proofs for traces blinded with different seeds are not
+    let proof3 = prover.prove(prover.build_trace(128, [8; 32])).unwrap();
+    assert_ne!(proof1.to_bytes(), proof3.to_bytes());
+
+    let result = prover.get_pub_inputs(&prover.build_trace(128, seed));
+    assert!(verifier::verify::<BlindedCounterAir>(proof1, result).is_ok());
+}
+
+#[test]
+fn prove_with_trace_poly_cache() {
+    // the flag column of a counter trace is the same for all counters of the same length, and
+    // thus, can be cached across proofs
+    let trace = TraceTable::pad_to_power_of_two(build_counter_columns(17), None);
+    let cache = TracePolyCache::with_fixed_columns(trace.main_segment(), &[1]);
+    assert_eq!(32, cache.trace_length());
+    assert_eq!(1, cache.num_columns());
+    assert_eq!(
+        trace.main_segment().interpolate_columns().get_column(1),
+        cache
+            .interpolate_columns(trace.main_segment())
+            .get_column(1)
+    );
+
+    // proofs generated with and without the cache must be the same; for the counter of a
+    // different length the flag column differs from the cached one, and thus, is interpolated
+    // from scratch
+    let prover = CounterProver::default();
+    let cached_prover = CounterProver::with_poly_cache(cache);
+    for num_steps in [17, 20] {
+        let columns = build_counter_columns(num_steps);
+        let expected = prover
+            .prove(TraceTable::pad_to_power_of_two(columns.clone(), None))
+            .unwrap();
+        let actual = cached_prover
+            .prove(TraceTable::pad_to_power_of_two(columns, None))
+            .unwrap();
+        assert_eq!(expected.to_bytes(), actual.to_bytes());
+    }
+
+    // the cache is not used for a trace of a different length
+    let trace = TraceTable::pad_to_power_of_two(build_counter_columns(40), None);
+    let mut cache = TracePolyCache::with_fixed_columns(trace.main_segment(), &[1]);
+    let trace = TraceTable::pad_to_power_of_two(build_counter_columns(17), None);
+    assert!(!cache.is_valid_for(trace.main_segment()));
+    assert_eq!(None, cache.get(trace.main_segment(), 1));
+    assert!(cache.update(trace.main_segment()));
+    assert_eq!(32, cache.trace_length());
+    assert!(cache.get(trace.main_segment(), 1).is_some());
+}
+
 #[test]
 fn extend_trace_table() {
     // build the trace and the domain
@@ -127,10 +408,754 @@ fn commit_trace_table() {
     assert_eq!(*expected_tree.root(), trace_comm.main_trace_root())
 }
 
+#[test]
+fn prove_and_verify_column_membership() {
+    let trace_length = 8;
+    let trace = build_fib_trace(trace_length * 2);
+    let trace_tree = trace.main_segment().commit_to_rows::<Blake2s>();
+    let root = *trace_tree.root();
+
+    // a value present in the column can be proven to be a member of the column
+    let value = BaseElement::from(34u8);
+    let proof =
+        prove_column_membership(trace.main_segment(), &trace_tree, 0, value, 1).expect("no proof");
+    assert_eq!(4, proof.position);
+    assert_eq!(Ok(()), verify_column_membership(&root, 0, value, 1, &proof));
+
+    // the same proof does not attest to a different value or to a different column
+    let absent = BaseElement::from(35u8);
+    assert_eq!(
+        Err(MembershipError::ValueMismatch(0)),
+        verify_column_membership(&root, 0, absent, 1, &proof)
+    );
+    assert_eq!(
+        Err(MembershipError::ValueMismatch(1)),
+        verify_column_membership(&root, 1, value, 1, &proof)
+    );
+
+    // a proof with a tampered row is rejected
+    let mut tampered = proof.clone();
+    tampered.row[1] += BaseElement::ONE;
+    assert_eq!(
+        Err(MembershipError::RowHashMismatch),
+        verify_column_membership(&root, 0, value, 1, &tampered)
+    );
+
+    // a proof against a different root is rejected
+    let other_root = *build_fib_trace(trace_length * 4)
+        .main_segment()
+        .commit_to_rows::<Blake2s>()
+        .root();
+    assert_eq!(
+        Err(MembershipError::InvalidMerklePath),
+        verify_column_membership(&other_root, 0, value, 1, &proof)
+    );
+
+    // a value absent from the column cannot be proven
+    assert!(prove_column_membership(trace.main_segment(), &trace_tree, 0, absent, 1).is_none());
+}
+
+#[test]
+fn reject_column_membership_outside_of_trace_domain() {
+    let trace_length = 8;
+    let blowup_factor = 2;
+    let trace = build_fib_trace(trace_length * 2);
+
+    // treat the committed segment as a low-degree extension with blowup factor 2; only rows at
+    // even positions of such extension are in the trace domain
+    let lde = trace.main_segment();
+    let lde_tree = lde.commit_to_rows::<Blake2s>();
+    let root = *lde_tree.root();
+
+    // a value located in the trace domain can be proven
+    let value = BaseElement::from(34u8);
+    let proof = prove_column_membership(lde, &lde_tree, 0, value, blowup_factor).expect("no proof");
+    assert_eq!(4, proof.position);
+    assert_eq!(
+        Ok(()),
+        verify_column_membership(&root, 0, value, blowup_factor, &proof)
+    );
+
+    // a value located only outside of the trace domain cannot be proven
+    let value = BaseElement::from(13u8);
+    assert!(prove_column_membership(lde, &lde_tree, 0, value, blowup_factor).is_none());
+
+    // an opening of a row outside of the trace domain is rejected even though it is valid
+    // against the commitment
+    let proof = prove_column_membership(lde, &lde_tree, 0, value, 1).expect("no proof");
+    assert_eq!(3, proof.position);
+    assert_eq!(Ok(()), verify_column_membership(&root, 0, value, 1, &proof));
+    assert_eq!(
+        Err(MembershipError::PositionNotInTraceDomain(3, blowup_factor)),
+        verify_column_membership(&root, 0, value, blowup_factor, &proof)
+    );
+}
+
+#[test]
+fn extend_trace_with_degree_hints() {
+    // build a trace consisting of a full-degree column, a constant column, and a column of
+    // degree 3, and the corresponding domain
+    let trace_length = 1024;
+    let air = MockAir::with_trace_length(trace_length);
+    let domain = StarkDomain::new(&air);
+    let trace = build_mixed_degree_trace(trace_length);
+    let degree_hints = vec![trace_length - 1, 0, 3];
+
+    // make sure the hints are consistent with the actual column degrees
+    let trace_polys = trace.interpolate_columns();
+    assert!(trace_polys.validate_column_degrees(&degree_hints).is_ok());
+
+    // extend the trace with and without degree hints
+    let expected = trace_polys.evaluate_columns_over(&domain);
+    let actual = trace_polys.evaluate_columns_over_with_degrees(&domain, &degree_hints);
+
+    // the results must be identical
+    assert_eq!(expected.num_rows(), actual.num_rows());
+    for (expected, actual) in expected.columns().zip(actual.columns()) {
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+fn extend_trace_with_invalid_degree_hints() {
+    let trace_length = 64;
+    let trace = build_mixed_degree_trace(trace_length);
+    let trace_polys = trace.interpolate_columns();
+
+    // column 2 is of degree 3, and thus a hint of 2 is too low
+    let degree_hints = vec![trace_length - 1, 0, 2];
+    assert_eq!(
+        Err(ProverError::ColumnDegreeHintExceeded(2, 2, 3)),
+        trace_polys.validate_column_degrees(&degree_hints)
+    );
+}
+
+#[test]
+fn get_trace_lde_evaluation() {
+    // build the trace and the domain
+    let trace_length = 8;
+    let air = MockAir::with_trace_length(trace_length);
+    let trace = build_fib_trace(trace_length * 2);
+    let domain = StarkDomain::new(&air);
+
+    // build extended trace commitment
+    let trace_polys = trace.main_segment().interpolate_columns();
+    let trace_lde = trace_polys.evaluate_columns_over(&domain);
+    let trace_tree = trace_lde.commit_to_rows::<Blake2s>();
+    let trace_comm = TraceCommitment::<BaseElement, Blake2s>::new(
+        trace_lde,
+        trace_tree,
+        domain.trace_to_lde_blowup(),
+    );
+    let trace_lde = trace_comm.trace_table();
+    let poly_table = TracePolyTable::<BaseElement>::new(trace_polys.clone());
+
+    // compare individual evaluations against a manual FFT-based evaluation of trace polynomials
+    let twiddles = fft::get_twiddles::<BaseElement>(trace_length);
+    let lde_domain = build_lde_domain::<BaseElement>(domain.lde_domain_size());
+    for (col, poly) in trace_polys.columns().enumerate() {
+        let expected = fft::evaluate_poly_with_offset(
+            poly,
+            &twiddles,
+            domain.offset(),
+            domain.trace_to_lde_blowup(),
+        );
+        for (i, &value) in expected.iter().enumerate() {
+            assert_eq!(Ok(value), trace_lde.get_evaluation(0, col, i));
+            assert_eq!(Ok(value), poly_table.evaluate_at(col, lde_domain[i]));
+        }
+    }
+
+    // out-of-range indexes should result in errors
+    let lde_size = domain.lde_domain_size();
+    assert_eq!(
+        Err(ProverError::TraceSegmentIndexOutOfBounds(1, 1)),
+        trace_lde.get_evaluation(1, 0, 0)
+    );
+    assert_eq!(
+        Err(ProverError::TraceColumnIndexOutOfBounds(2, 2)),
+        trace_lde.get_evaluation(0, 2, 0)
+    );
+    assert_eq!(
+        Err(ProverError::TraceRowIndexOutOfBounds(lde_size, lde_size)),
+        trace_lde.get_evaluation(0, 0, lde_size)
+    );
+    assert_eq!(
+        Err(ProverError::TraceColumnIndexOutOfBounds(2, 3)),
+        poly_table.evaluate_at(3, BaseElement::ONE)
+    );
+}
+
+#[test]
+fn build_trace_lde_via_prover() {
+    let trace = TraceTable::pad_to_power_of_two(build_counter_columns(16), None);
+    let prover = CounterProver::default();
+    let (trace_lde, trace_polys) = prover
+        .build_trace_lde::<BaseElement, Blake2s>(trace)
+        .unwrap();
+
+    // evaluations of the trace LDE must match evaluations of trace polynomials over LDE domain
+    let lde_domain = build_lde_domain::<BaseElement>(trace_lde.trace_len());
+    assert_eq!(1, trace_lde.num_segments());
+    for col in 0..trace_lde.main_trace_width() {
+        for (i, &x) in lde_domain.iter().enumerate() {
+            assert_eq!(
+                trace_polys.evaluate_at(col, x),
+                trace_lde.get_evaluation(0, col, i)
+            );
+        }
+    }
+}
+
+#[test]
+fn prove_and_verify_with_domain_offset() {
+    let prover = CounterProver::new(build_options().with_domain_offset(5));
+    let trace = TraceTable::init(build_counter_columns(16));
+    let result = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    assert_eq!(
+        BaseElement::new(5),
+        proof.options().domain_offset::<BaseElement>()
+    );
+
+    // the offset changes the LDE domain, and thus the proof
+    let default_proof = CounterProver::default()
+        .prove(TraceTable::init(build_counter_columns(16)))
+        .unwrap();
+    assert_ne!(default_proof.to_bytes(), proof.to_bytes());
+
+    // the offset is carried in the proof, and so the verifier evaluates over the same coset
+    let mut proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert!(verifier::verify::<CounterAir>(proof.clone(), result).is_ok());
+
+    // an offset which does not define a valid coset is rejected by the verifier
+    let options = build_options().with_domain_offset(1);
+    proof.context = Context::new::<BaseElement>(&proof.get_trace_info(), options);
+    assert_eq!(
+        Err(VerifierError::InvalidDomainOffset),
+        verifier::verify::<CounterAir>(proof, result)
+    );
+}
+
+#[test]
+fn permute_trace_columns() {
+    let trace_length = 8;
+    let columns = (0..4u64)
+        .map(|col| {
+            (0..trace_length as u64)
+                .map(|row| BaseElement::from(col * 100 + row))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut trace = TraceTable::init(columns.clone());
+
+    let assertions = vec![
+        Assertion::single(0, 3, BaseElement::from(3u8)),
+        Assertion::periodic(2, 1, 4, BaseElement::ZERO),
+        Assertion::sequence(3, 0, 4, vec![BaseElement::ZERO, BaseElement::ONE]),
+    ];
+
+    // column i is moved to column perm[i]
+    let perm = [2, 0, 3, 1];
+    trace.permute_columns(&perm);
+
+    assert_eq!(4, trace.main_trace_width());
+    for (old_idx, &new_idx) in perm.iter().enumerate() {
+        assert_eq!(columns[old_idx], trace.get_column(new_idx));
+    }
+
+    // remapped assertions should point to the same values as the original assertions
+    for assertion in assertions.iter() {
+        let remapped = assertion.remap_column(&perm);
+        assert_eq!(perm[assertion.column()], remapped.column());
+        assert_eq!(assertion.first_step(), remapped.first_step());
+        assert_eq!(assertion.stride(), remapped.stride());
+        assert_eq!(assertion.values(), remapped.values());
+
+        let mut original_cells = Vec::new();
+        assertion.apply(trace_length, |step, _| {
+            original_cells.push(columns[assertion.column()][step])
+        });
+        let mut remapped_cells = Vec::new();
+        remapped.apply(trace_length, |step, _| {
+            remapped_cells.push(trace.get(remapped.column(), step))
+        });
+        assert_eq!(original_cells, remapped_cells);
+    }
+}
+
+#[test]
+#[should_panic(expected = "column index 1 appears more than once in the permutation")]
+fn permute_trace_columns_invalid_permutation() {
+    let mut trace = build_fib_trace(16);
+    trace.permute_columns(&[1, 1]);
+}
+
+#[test]
+fn validate_trace_with_next_periodic_values() {
+    let trace_length = 16;
+    let air = PeriodicAir::new(
+        TraceInfo::new(1, trace_length),
+        BaseElement::ZERO,
+        build_options(),
+    );
+
+    // a trace which contains the repeated values of the periodic column satisfies the constraint
+    let column = (0..trace_length)
+        .map(|i| PERIODIC_CYCLE[i % PERIODIC_CYCLE.len()])
+        .collect::<Vec<_>>();
+    let trace = TraceTable::init(vec![column]);
+    trace.validate::<PeriodicAir, BaseElement>(&air, &[], &AuxTraceRandElements::new());
+}
+
+#[test]
+#[should_panic(expected = "main transition constraint 0 did not evaluate to ZERO at step 1")]
+fn validate_trace_with_next_periodic_values_invalid() {
+    let trace_length = 16;
+    let air = PeriodicAir::new(
+        TraceInfo::new(1, trace_length),
+        BaseElement::ZERO,
+        build_options(),
+    );
+
+    // changing a single value in the trace breaks the constraint for the transition into
+    // (and out of) the modified step
+    let mut column = (0..trace_length)
+        .map(|i| PERIODIC_CYCLE[i % PERIODIC_CYCLE.len()])
+        .collect::<Vec<_>>();
+    column[2] += BaseElement::ONE;
+    let trace = TraceTable::init(vec![column]);
+    trace.validate::<PeriodicAir, BaseElement>(&air, &[], &AuxTraceRandElements::new());
+}
+
+#[test]
+fn validate_and_prove_trace_with_cyclic_transitions() {
+    let trace_length = 16;
+    let air = CyclicAccumulatorAir::new(
+        TraceInfo::new(2, trace_length),
+        BaseElement::ZERO,
+        build_options(),
+    );
+    assert_eq!(vec![0], air.context().num_transition_exemptions());
+
+    // the accumulator returns to its initial value at the end of the trace, and thus, the
+    // transition from the last row into the first row is valid
+    let trace = TraceTable::init(build_cyclic_accumulator_columns(trace_length));
+    trace.validate::<CyclicAccumulatorAir, BaseElement>(&air, &[], &AuxTraceRandElements::new());
+
+    let prover = CyclicAccumulatorProver {
+        options: build_options(),
+    };
+    assert!(prover.prove(trace).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "main transition constraint 0 did not evaluate to ZERO at step 15")]
+fn validate_trace_with_cyclic_transitions_invalid() {
+    let trace_length = 16;
+    let air = CyclicAccumulatorAir::new(
+        TraceInfo::new(2, trace_length),
+        BaseElement::ZERO,
+        build_options(),
+    );
+
+    // changing the last delta does not affect any of the rows of the trace, but the accumulator
+    // no longer wraps around to its initial value
+    let mut columns = build_cyclic_accumulator_columns(trace_length);
+    columns[1][trace_length - 1] = BaseElement::ZERO;
+    let trace = TraceTable::init(columns);
+    trace.validate::<CyclicAccumulatorAir, BaseElement>(&air, &[], &AuxTraceRandElements::new());
+}
+
+#[test]
+fn check_trace_structure() {
+    // a well-formed trace
+    let trace = build_fib_trace(16);
+    assert_eq!(Ok(()), trace.check_structure());
+    let trace = MockTrace::new(TraceLayout::new(2, &[1], &[1]), 8);
+    assert_eq!(Ok(()), trace.check_structure());
+
+    // trace is too short
+    let mut trace = MockTrace::new(TraceLayout::new(2, &[], &[]), 8);
+    trace.length = 4;
+    assert_eq!(
+        Err(TraceError::TraceTooShort(8, 4)),
+        trace.check_structure()
+    );
+
+    // trace length is not a power of two
+    trace.length = 12;
+    assert_eq!(
+        Err(TraceError::TraceLengthNotPowerOfTwo(12)),
+        trace.check_structure()
+    );
+
+    // trace is too long for the field
+    trace.length = 1 << 41;
+    assert_eq!(
+        Err(TraceError::TraceTooLong(40, 41)),
+        trace.check_structure()
+    );
+
+    // columns of the main segment are shorter than the trace
+    trace.length = 16;
+    assert_eq!(
+        Err(TraceError::MismatchedColumnLength(0, 16, 8)),
+        trace.check_structure()
+    );
+
+    // main segment width differs from the layout
+    let mut trace = MockTrace::new(TraceLayout::new(2, &[], &[]), 8);
+    trace.layout = TraceLayout::new(3, &[], &[]);
+    assert_eq!(
+        Err(TraceError::MismatchedTraceWidth(3, 2)),
+        trace.check_structure()
+    );
+
+    // auxiliary trace width differs from the sum of auxiliary segment widths
+    let mut trace = MockTrace::new(TraceLayout::new(2, &[2, 1], &[1, 1]), 8);
+    trace.aux_trace_width = Some(2);
+    assert_eq!(
+        Err(TraceError::MismatchedAuxTraceWidth(2, 3)),
+        trace.check_structure()
+    );
+
+    // metadata is too long
+    let mut trace = MockTrace::new(TraceLayout::new(2, &[], &[]), 8);
+    trace.meta = vec![0; TraceInfo::MAX_META_LENGTH + 1];
+    assert_eq!(
+        Err(TraceError::MetadataTooLong(
+            TraceInfo::MAX_META_LENGTH,
+            TraceInfo::MAX_META_LENGTH + 1
+        )),
+        trace.check_structure()
+    );
+}
+
+#[test]
+fn sparse_column() {
+    let mut column = SparseColumn::with_entries(
+        16,
+        vec![
+            (9, BaseElement::new(3)),
+            (2, BaseElement::ONE),
+            (5, BaseElement::ZERO),
+        ],
+    );
+    assert_eq!(16, column.length());
+    assert_eq!(2, column.num_nonzero());
+    assert_eq!(
+        &[(2, BaseElement::ONE), (9, BaseElement::new(3))],
+        column.entries()
+    );
+    assert_eq!(BaseElement::ONE, column.get(2));
+    assert_eq!(BaseElement::ZERO, column.get(5));
+
+    // setting a cell to zero removes it, and setting a cell to a non-zero value keeps the
+    // entries sorted
+    column.set(9, BaseElement::ZERO);
+    column.set(0, BaseElement::new(7));
+    column.set(2, BaseElement::new(4));
+    assert_eq!(
+        &[(0, BaseElement::new(7)), (2, BaseElement::new(4))],
+        column.entries()
+    );
+
+    let mut expected = vec![BaseElement::ZERO; 16];
+    expected[0] = BaseElement::new(7);
+    expected[2] = BaseElement::new(4);
+    assert_eq!(expected, column.to_dense());
+    assert_eq!(expected, TraceColumn::from(column).into_dense());
+}
+
+#[test]
+fn sparse_trace() {
+    let trace_length = 32;
+    let dense = build_fib_trace(trace_length * 2);
+    let mut selector = SparseColumn::new(trace_length);
+    selector.set(trace_length - 1, BaseElement::ONE);
+
+    let trace = SparseTrace::init(vec![
+        dense.get_column(0).to_vec().into(),
+        dense.get_column(1).to_vec().into(),
+        selector.clone().into(),
+    ]);
+    assert_eq!(3, trace.width());
+    assert_eq!(trace_length, trace.length());
+    assert_eq!(BaseElement::ONE, trace.get(2, trace_length - 1));
+    assert_eq!(BaseElement::ZERO, trace.get(2, 0));
+    assert_eq!(Ok(()), trace.check_structure());
+
+    // dense columns are moved into the main segment and the sparse column is expanded
+    let main_segment = trace.main_segment();
+    assert_eq!(dense.get_column(0), main_segment.get_column(0));
+    assert_eq!(dense.get_column(1), main_segment.get_column(1));
+    assert_eq!(selector.to_dense(), main_segment.get_column(2));
+    assert_eq!(selector.to_dense(), trace.get_column(2));
+
+    // all columns must have the same length
+    let result = SparseTrace::try_new(vec![
+        dense.get_column(0).to_vec().into(),
+        SparseColumn::new(trace_length / 2).into(),
+    ]);
+    assert_eq!(
+        Some(TraceError::MismatchedColumnLength(
+            1,
+            trace_length,
+            trace_length / 2
+        )),
+        result.err()
+    );
+}
+
+// MOCK TRACE
+// ================================================================================================
+
+/// Trace which reports its dimensions independently of the underlying data, and thus, can be
+/// used to describe malformed traces.
+struct MockTrace {
+    layout: TraceLayout,
+    length: usize,
+    meta: Vec<u8>,
+    main_segment: Matrix<BaseElement>,
+    aux_trace_width: Option<usize>,
+}
+
+impl MockTrace {
+    /// Returns a well-formed trace with the specified layout and number of rows.
+    fn new(layout: TraceLayout, length: usize) -> Self {
+        let columns = vec![vec![BaseElement::ONE; length]; layout.main_trace_width()];
+        Self {
+            layout,
+            length,
+            meta: Vec::new(),
+            main_segment: Matrix::new(columns),
+            aux_trace_width: None,
+        }
+    }
+}
+
+impl Trace for MockTrace {
+    type BaseField = BaseElement;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.length
+    }
+
+    fn meta(&self) -> &[u8] {
+        &self.meta
+    }
+
+    fn main_segment(&self) -> &Matrix<BaseElement> {
+        &self.main_segment
+    }
+
+    fn build_aux_segment<E: FieldElement<BaseField = BaseElement>>(
+        &mut self,
+        _aux_segments: &[Matrix<E>],
+        _rand_elements: &[E],
+    ) -> Option<Matrix<E>> {
+        None
+    }
+
+    fn aux_trace_width(&self) -> usize {
+        self.aux_trace_width
+            .unwrap_or_else(|| self.layout.aux_trace_width())
+    }
+}
+
+// MOCK AIR
+// ================================================================================================
+
+const PERIODIC_CYCLE: [BaseElement; 4] = [
+    BaseElement::new(1),
+    BaseElement::new(2),
+    BaseElement::new(4),
+    BaseElement::new(8),
+];
+
+/// AIR with a single transition constraint which requires the trace column to change by the
+/// same amount as the periodic column between two consecutive steps, i.e.:
+/// next - current = next_periodic - periodic.
+struct PeriodicAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for PeriodicAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+    type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
+    type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
+
+    fn new(trace_info: TraceInfo, _pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::with_cycles(
+            1,
+            vec![PERIODIC_CYCLE.len()],
+        )];
+        PeriodicAir {
+            context: AirContext::new(trace_info, degrees, 1, options),
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &Self::Frame<E>,
+        periodic_values: &[E],
+        next_periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current()[0];
+        let next = frame.next()[0];
+        result[0] = (next - current) - (next_periodic_values[0] - periodic_values[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, PERIODIC_CYCLE[0])]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        vec![PERIODIC_CYCLE.to_vec()]
+    }
+}
+
+/// AIR with a single cyclic transition constraint which requires the first column to accumulate
+/// values of the second column, i.e.: next_acc = acc + delta. Since the constraint is cyclic,
+/// it also applies to the transition from the last row into the first row of the trace.
+struct CyclicAccumulatorAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for CyclicAccumulatorAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+    type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
+    type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
+
+    fn new(trace_info: TraceInfo, _pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1)];
+        CyclicAccumulatorAir {
+            context: AirContext::new(trace_info, degrees, 1, options).set_cyclic_transitions(),
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &Self::Frame<E>,
+        _periodic_values: &[E],
+        _next_periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - current[0] - current[1];
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, BaseElement::ZERO)]
+    }
+}
+
+struct CyclicAccumulatorProver {
+    options: ProofOptions,
+}
+
+impl Prover for CyclicAccumulatorProver {
+    type BaseField = BaseElement;
+    type Air = CyclicAccumulatorAir;
+    type Trace = TraceTable<BaseElement>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> BaseElement {
+        BaseElement::ZERO
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
+fn build_options() -> ProofOptions {
+    ProofOptions::new(
+        32,
+        8,
+        0,
+        HashFunction::Blake2s_256,
+        FieldExtension::None,
+        4,
+        256,
+    )
+}
+
 fn build_lde_domain<B: StarkField>(domain_size: usize) -> Vec<B> {
     let g = B::get_root_of_unity(log2(domain_size));
     get_power_series_with_offset(g, B::GENERATOR, domain_size)
 }
+
+/// Builds a trace with three columns such that the first column has degree `length - 1`, the
+/// second column is constant, and the third column is of degree 3.
+fn build_mixed_degree_trace(length: usize) -> Matrix<BaseElement> {
+    let g = BaseElement::get_root_of_unity(log2(length));
+    let trace_domain = get_power_series(g, length);
+
+    let mut col0 = vec![BaseElement::ONE; length];
+    for i in 1..length {
+        col0[i] = col0[i - 1] * BaseElement::from(3u8) + BaseElement::from(i as u64);
+    }
+    let col1 = vec![BaseElement::from(7u8); length];
+    let poly = [1u8, 2, 3, 4]
+        .iter()
+        .map(|&c| BaseElement::from(c))
+        .collect::<Vec<_>>();
+    let col2 = polynom::eval_many(&poly, &trace_domain);
+
+    Matrix::new(vec![col0, col1, col2])
+}
+
+/// Builds columns of a cyclic accumulator trace in which the deltas sum up to zero, and thus, the
+/// accumulator returns to zero after the last step.
+fn build_cyclic_accumulator_columns(length: usize) -> Vec<Vec<BaseElement>> {
+    let mut delta = (1..length as u128)
+        .map(BaseElement::new)
+        .collect::<Vec<_>>();
+    delta.push(-delta.iter().fold(BaseElement::ZERO, |acc, &d| acc + d));
+
+    let mut acc = vec![BaseElement::ZERO; length];
+    for i in 1..length {
+        acc[i] = acc[i - 1] + delta[i - 1];
+    }
+    vec![acc, delta]
+}
+
+/// Sets the state to the initial state of a Fibonacci computation (2 terms per step).
+fn fib_init(state: &mut [BaseElement]) {
+    state[0] = BaseElement::ONE;
+    state[1] = BaseElement::ONE;
+}
+
+/// Advances the state of a Fibonacci computation by one step.
+fn fib_update(_step: usize, state: &mut [BaseElement]) {
+    state[0] += state[1];
+    state[1] += state[0];
+}
+
+/// Sets the state to the state of a Fibonacci computation at the specified step.
+fn fib_init_at(step: usize, state: &mut [BaseElement]) {
+    fib_init(state);
+    for i in 0..step {
+        fib_update(i, state);
+    }
+}
@@ -3,14 +3,18 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::Matrix;
+use crate::{Matrix, ProverError};
 use air::EvaluationFrame;
 use math::FieldElement;
 use utils::collections::Vec;
 
 // TRACE LOW DEGREE EXTENSION
 // ================================================================================================
-/// TODO: add docs
+/// Evaluations of trace polynomials over the LDE domain for all segments of the execution trace.
+///
+/// Evaluations of the main trace segment are always in the base field. However, evaluations of
+/// the auxiliary trace segments may be either in the base field, or in the extension field,
+/// depending on whether extension field is being used.
 pub struct TraceLde<E: FieldElement> {
     main_segment_lde: Matrix<E::BaseField>,
     aux_segment_ldes: Vec<Matrix<E>>,
@@ -69,6 +73,57 @@ impl<E: FieldElement> TraceLde<E> {
         self.blowup
     }
 
+    /// Returns the number of trace segments in this trace LDE.
+    pub fn num_segments(&self) -> usize {
+        self.aux_segment_ldes.len() + 1
+    }
+
+    /// Returns the evaluation of the trace polynomial for the specified column of the specified
+    /// segment at the point of the LDE domain with the specified index.
+    ///
+    /// Segment 0 is the main trace segment; segments 1, 2 etc. are auxiliary trace segments. The
+    /// column index is relative to the specified segment. Values of the main segment are mapped
+    /// into the field `E`.
+    ///
+    /// # Errors
+    /// Returns an error if `segment`, `col`, or `domain_idx` is out of bounds for this trace LDE.
+    pub fn get_evaluation(
+        &self,
+        segment: usize,
+        col: usize,
+        domain_idx: usize,
+    ) -> Result<E, ProverError> {
+        if segment >= self.num_segments() {
+            return Err(ProverError::TraceSegmentIndexOutOfBounds(
+                self.num_segments(),
+                segment,
+            ));
+        }
+        if domain_idx >= self.trace_len() {
+            return Err(ProverError::TraceRowIndexOutOfBounds(
+                self.trace_len(),
+                domain_idx,
+            ));
+        }
+
+        if segment == 0 {
+            let num_cols = self.main_segment_lde.num_cols();
+            if col >= num_cols {
+                return Err(ProverError::TraceColumnIndexOutOfBounds(num_cols, col));
+            }
+            Ok(E::from(self.main_segment_lde.get(col, domain_idx)))
+        } else {
+            let segment_lde = &self.aux_segment_ldes[segment - 1];
+            if col >= segment_lde.num_cols() {
+                return Err(ProverError::TraceColumnIndexOutOfBounds(
+                    segment_lde.num_cols(),
+                    col,
+                ));
+            }
+            Ok(segment_lde.get(col, domain_idx))
+        }
+    }
+
     /// Reads current and next rows from the main trace segment into the specified frame.
     pub fn read_main_trace_frame_into<F: EvaluationFrame<E::BaseField>>(
         &self,
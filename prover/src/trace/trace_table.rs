@@ -4,8 +4,10 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{Matrix, Trace};
+use crate::TraceError;
 use air::{TraceInfo, TraceLayout};
 use math::{log2, FieldElement, StarkField};
+use rand_core::RngCore;
 use utils::{collections::Vec, uninit_vector};
 
 #[cfg(not(feature = "concurrent"))]
@@ -19,6 +21,9 @@ use utils::{iterators::*, rayon};
 
 const MIN_FRAGMENT_LENGTH: usize = 2;
 
+/// Maximum number of attempts to draw a valid field element from a source of entropy.
+const MAX_DRAW_ATTEMPTS: usize = 1000;
+
 // TRACE TABLE
 // ================================================================================================
 /// A concrete implementation of the [Trace] trait.
@@ -48,6 +53,10 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// This function work just like [TraceTable::new()] function, but also takes a metadata
 /// parameter which can be an arbitrary sequence of bytes up to 64KB in size.
 ///
+/// Lastly, if the natural length of the execution trace is not a power of two, you can use
+/// [TraceTable::pad_to_power_of_two()] function, which works just like [TraceTable::init()]
+/// function, but first extends all columns to the next power of two.
+///
 /// # Concurrent trace generation
 /// For computations which consist of many small independent computations, we can generate the
 /// execution trace of the entire computation by building fragments of the trace in parallel,
@@ -63,6 +72,7 @@ pub struct TraceTable<B: StarkField> {
     layout: TraceLayout,
     trace: Matrix<B>,
     meta: Vec<u8>,
+    unpadded_length: usize,
 }
 
 impl<B: StarkField> TraceTable<B> {
@@ -134,6 +144,7 @@ impl<B: StarkField> TraceTable<B> {
             layout: TraceLayout::new(width, &[0], &[0]),
             trace: Matrix::new(columns),
             meta,
+            unpadded_length: length,
         }
     }
 
@@ -146,46 +157,152 @@ impl<B: StarkField> TraceTable<B> {
     ///   multiplicative subgroup in the field `B`, or is not a power of two.
     /// * Number of elements is not identical for all columns.
     pub fn init(columns: Vec<Vec<B>>) -> Self {
-        assert!(
-            !columns.is_empty(),
-            "execution trace must consist of at least one column"
-        );
-        assert!(
-            columns.len() <= TraceInfo::MAX_TRACE_WIDTH,
-            "execution trace width cannot be greater than {}, but was {}",
-            TraceInfo::MAX_TRACE_WIDTH,
-            columns.len()
-        );
+        Self::try_new(columns).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates a new execution trace from a list of provided trace columns, or returns an error
+    /// if the columns do not describe a valid execution trace.
+    ///
+    /// This is the fallible version of [TraceTable::init()].
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The `columns` vector is empty or has over 255 columns.
+    /// * Number of elements is not identical for all columns.
+    /// * Number of elements in the columns is smaller than 8, greater than the biggest
+    ///   multiplicative subgroup in the field `B`, or is not a power of two.
+    pub fn try_new(columns: Vec<Vec<B>>) -> Result<Self, TraceError> {
+        if columns.is_empty() {
+            return Err(TraceError::NoColumns);
+        }
+        if columns.len() > TraceInfo::MAX_TRACE_WIDTH {
+            return Err(TraceError::TooManyColumns(
+                TraceInfo::MAX_TRACE_WIDTH,
+                columns.len(),
+            ));
+        }
         let trace_length = columns[0].len();
+        for (i, column) in columns.iter().enumerate().skip(1) {
+            if column.len() != trace_length {
+                return Err(TraceError::MismatchedColumnLength(
+                    i,
+                    trace_length,
+                    column.len(),
+                ));
+            }
+        }
+        if trace_length < TraceInfo::MIN_TRACE_LENGTH {
+            return Err(TraceError::TraceTooShort(
+                TraceInfo::MIN_TRACE_LENGTH,
+                trace_length,
+            ));
+        }
+        if !trace_length.is_power_of_two() {
+            return Err(TraceError::TraceLengthNotPowerOfTwo(trace_length));
+        }
+        if log2(trace_length) > B::TWO_ADICITY {
+            return Err(TraceError::TraceTooLong(B::TWO_ADICITY, log2(trace_length)));
+        }
+
+        Ok(Self {
+            layout: TraceLayout::new(columns.len(), &[0], &[0]),
+            trace: Matrix::new(columns),
+            meta: vec![],
+            unpadded_length: trace_length,
+        })
+    }
+
+    /// Creates a new execution trace from a list of provided trace columns, or returns an error
+    /// if the columns do not describe a valid execution trace which can be extended by the
+    /// specified `blowup_factor`.
+    ///
+    /// In addition to the checks performed by [TraceTable::try_new()], this makes sure that the
+    /// trace is at least as long as the blowup factor, and that the low-degree extension of the
+    /// trace fits into the largest multiplicative subgroup of the field `B`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Any of the conditions checked by [TraceTable::try_new()] is not satisfied.
+    /// * Number of elements in the columns is smaller than `blowup_factor`.
+    /// * Number of elements in the columns multiplied by `blowup_factor` is greater than the
+    ///   biggest multiplicative subgroup in the field `B`.
+    ///
+    /// # Panics
+    /// Panics if `blowup_factor` is not a power of two.
+    pub fn try_new_with_blowup(
+        columns: Vec<Vec<B>>,
+        blowup_factor: usize,
+    ) -> Result<Self, TraceError> {
         assert!(
-            trace_length >= TraceInfo::MIN_TRACE_LENGTH,
-            "execution trace must be at least {} steps long, but was {}",
-            TraceInfo::MIN_TRACE_LENGTH,
-            trace_length
+            blowup_factor.is_power_of_two(),
+            "blowup factor must be a power of 2, but was {}",
+            blowup_factor
         );
+        let trace = Self::try_new(columns)?;
+
+        let trace_length = trace.length();
+        if trace_length < blowup_factor {
+            return Err(TraceError::TraceShorterThanBlowup(
+                blowup_factor,
+                trace_length,
+            ));
+        }
+        let max_log_length = B::TWO_ADICITY - log2(blowup_factor);
+        if log2(trace_length) > max_log_length {
+            return Err(TraceError::TraceTooLong(max_log_length, log2(trace_length)));
+        }
+
+        Ok(trace)
+    }
+
+    /// Creates a new execution trace from a list of provided trace columns, padding the columns
+    /// to the next power of two.
+    ///
+    /// All columns are extended to the next power of two (but to no fewer than 8 rows). If
+    /// `pad_value` is `Some`, all padded cells are set to this value; otherwise, the last row of
+    /// the trace is repeated. Repeating the last row is usually the right choice as it keeps
+    /// transition constraints satisfied for computations which stop changing their state once
+    /// they terminate.
+    ///
+    /// The original length of the trace is retained and can be retrieved via
+    /// [unpadded_length()](Trace::unpadded_length) method. Assertions against steps beyond the
+    /// original length are evaluated against the padded rows; when debug assertions are enabled,
+    /// [Trace::validate()] identifies failing assertions placed against padded rows.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The `columns` vector is empty or has over 255 columns.
+    /// * The columns are empty or not all of the same length.
+    /// * The padded length is greater than the biggest multiplicative subgroup in the field `B`.
+    pub fn pad_to_power_of_two(mut columns: Vec<Vec<B>>, pad_value: Option<B>) -> Self {
         assert!(
-            trace_length.is_power_of_two(),
-            "execution trace length must be a power of 2"
+            !columns.is_empty(),
+            "execution trace must consist of at least one column"
         );
+        let unpadded_length = columns[0].len();
         assert!(
-            log2(trace_length) as u32 <= B::TWO_ADICITY,
-            "execution trace length cannot exceed 2^{} steps, but was 2^{}",
-            B::TWO_ADICITY,
-            log2(trace_length)
+            unpadded_length > 0,
+            "execution trace must contain at least one step"
         );
         for column in columns.iter().skip(1) {
             assert_eq!(
                 column.len(),
-                trace_length,
+                unpadded_length,
                 "all columns traces must have the same length"
             );
         }
 
-        Self {
-            layout: TraceLayout::new(columns.len(), &[0], &[0]),
-            trace: Matrix::new(columns),
-            meta: vec![],
+        let trace_length = unpadded_length
+            .next_power_of_two()
+            .max(TraceInfo::MIN_TRACE_LENGTH);
+        for column in columns.iter_mut() {
+            let value = pad_value.unwrap_or(column[unpadded_length - 1]);
+            column.resize(trace_length, value);
         }
+
+        let mut result = Self::init(columns);
+        result.unpadded_length = unpadded_length;
+        result
     }
 
     // DATA MUTATORS
@@ -242,11 +359,179 @@ impl<B: StarkField> TraceTable<B> {
         }
     }
 
+    /// Fill all rows in the execution trace by filling `num_fragments` fragments of the trace
+    /// in parallel (when `concurrent` feature is enabled).
+    ///
+    /// The trace is broken into `num_fragments` fragments of equal length, and each fragment is
+    /// filled by executing the provided closures as follows:
+    /// - `init` closure is used to initialize the first row of the fragment; it receives the
+    ///   step at which the fragment starts and a mutable reference to the state initialized to
+    ///   all zeros. The closure must set the state to the state of the computation at this step
+    ///   (for the first fragment, this is the initial state of the computation).
+    /// - `update` closure is used to populate all subsequent rows of the fragment; it has the
+    ///   same semantics as in [TraceTable::fill()]: it receives the step of the last updated row
+    ///   (in the context of the entire trace) and a mutable reference to the last updated state.
+    ///
+    /// Thus, if `init` computes the same states as a sequential execution of `update` would
+    /// reach, the resulting trace is identical to the trace built by [TraceTable::fill()].
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `num_fragments` is zero, is not a power of two, or results in fragments which are
+    ///   shorter than 2 rows.
+    /// * The first row of any fragment (other than the first one) is not equal to the state
+    ///   obtained by applying `update` to the last row of the previous fragment; that is, if the
+    ///   transition from one fragment to the next one is inconsistent with `update`.
+    pub fn fill_par<I, U>(&mut self, num_fragments: usize, init: I, update: U)
+    where
+        I: Fn(usize, &mut [B]) + Send + Sync,
+        U: Fn(usize, &mut [B]) + Send + Sync,
+    {
+        assert!(
+            num_fragments > 0,
+            "number of fragments must be greater than zero"
+        );
+        assert!(
+            num_fragments.is_power_of_two(),
+            "number of fragments must be a power of 2, but was {}",
+            num_fragments
+        );
+        let fragment_length = self.length() / num_fragments;
+        self.fragments(fragment_length).for_each(|mut fragment| {
+            let offset = fragment.offset();
+            fragment.fill(
+                |state| init(offset, state),
+                |step, state| update(offset + step, state),
+            );
+        });
+
+        // make sure the states at the fragment boundaries are consistent with the update
+        // function
+        let mut state = vec![B::ZERO; self.main_trace_width()];
+        let mut next_state = vec![B::ZERO; self.main_trace_width()];
+        for step in (fragment_length..self.length()).step_by(fragment_length) {
+            self.read_row_into(step - 1, &mut state);
+            update(step - 1, &mut state);
+            self.read_row_into(step, &mut next_state);
+            assert!(
+                state == next_state,
+                "state at step {} is inconsistent with the state at step {}",
+                step,
+                step - 1
+            );
+        }
+    }
+
     /// Updates a single row in the execution trace with provided data.
     pub fn update_row(&mut self, step: usize, state: &[B]) {
         self.trace.update_row(step, state);
     }
 
+    /// Overwrites the last `num_blinding_rows` rows of this execution trace with random values
+    /// drawn from `rng`.
+    ///
+    /// Blinding rows randomize the trace polynomials, and thus, the trace values opened in a
+    /// proof. Every trace polynomial is opened at the queried positions of the LDE domain and at
+    /// each row of the out-of-domain evaluation frame; hence, the number of blinding rows should
+    /// be at least the number of queries plus the number of rows in the evaluation frame. This
+    /// does not randomize openings of the constraint composition polynomial, the DEEP composition
+    /// polynomial, or FRI layers, and thus, does not by itself make a proof zero-knowledge.
+    ///
+    /// For the trace to remain consistent with the AIR, the AIR must exclude these rows from
+    /// transition constraints and must not place assertions against them; this can be done via
+    /// [AirContext::set_num_blinding_rows()](air::AirContext::set_num_blinding_rows).
+    ///
+    /// The length of the trace does not change, and thus the computation must be recorded in the
+    /// first `length - num_blinding_rows` rows of the trace.
+    ///
+    /// # Panics
+    /// Panics if `num_blinding_rows` is zero or is not smaller than the length of this trace.
+    pub fn randomize_zk<R>(&mut self, num_blinding_rows: usize, mut rng: R)
+    where
+        R: FnMut() -> B,
+    {
+        let trace_length = self.length();
+        assert!(
+            num_blinding_rows > 0,
+            "number of blinding rows must be greater than zero"
+        );
+        assert!(
+            num_blinding_rows < trace_length,
+            "number of blinding rows must be smaller than trace length {}, but was {}",
+            trace_length,
+            num_blinding_rows
+        );
+        for step in trace_length - num_blinding_rows..trace_length {
+            for column in 0..self.width() {
+                self.trace.set(column, step, rng());
+            }
+        }
+    }
+
+    /// Overwrites the last `num_blinding_rows` rows of this execution trace with random values
+    /// drawn from the provided source of entropy.
+    ///
+    /// This is equivalent to [TraceTable::randomize_zk()], but field elements are sampled from
+    /// `rng` directly. Thus, the blinding values are fully determined by the state of `rng`:
+    /// when `rng` is instantiated from a fixed seed, the resulting trace (and the proof generated
+    /// for it) is reproducible.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `num_blinding_rows` is zero or is not smaller than the length of this trace.
+    /// * A valid field element could not be drawn from `rng` after 1000 tries.
+    pub fn randomize_zk_with_rng<R: RngCore>(&mut self, num_blinding_rows: usize, rng: &mut R) {
+        self.randomize_zk(num_blinding_rows, || draw_element(rng))
+    }
+
+    /// Reorders columns of this execution trace according to the specified permutation.
+    ///
+    /// The permutation is interpreted as follows: a column at index `i` is moved to index
+    /// `perm[i]`. Column values are moved without copying, and the layout of the trace is left
+    /// unchanged since permuting the columns does not affect the trace width.
+    ///
+    /// Assertions placed against this trace must be remapped using the same permutation via
+    /// [Assertion::remap_column()](air::Assertion::remap_column) for them to keep tracking the
+    /// same values.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The length of `perm` is not equal to the width of this execution trace.
+    /// * `perm` is not a valid permutation (i.e., some index is out of bounds or appears more
+    ///   than once).
+    pub fn permute_columns(&mut self, perm: &[usize]) {
+        let width = self.main_trace_width();
+        assert_eq!(
+            perm.len(),
+            width,
+            "permutation must contain {} indexes, but contained {}",
+            width,
+            perm.len()
+        );
+        let mut seen = vec![false; width];
+        for &new_idx in perm.iter() {
+            assert!(
+                new_idx < width,
+                "column index {} is out of bounds for trace of width {}",
+                new_idx,
+                width
+            );
+            assert!(
+                !seen[new_idx],
+                "column index {} appears more than once in the permutation",
+                new_idx
+            );
+            seen[new_idx] = true;
+        }
+
+        let columns = core::mem::take(&mut self.trace.columns);
+        let mut permuted = vec![Vec::new(); width];
+        for (column, &new_idx) in columns.into_iter().zip(perm.iter()) {
+            permuted[new_idx] = column;
+        }
+        self.trace.columns = permuted;
+    }
+
     // FRAGMENTS
     // --------------------------------------------------------------------------------------------
 
@@ -360,6 +645,10 @@ impl<B: StarkField> Trace for TraceTable<B> {
         &self.meta
     }
 
+    fn unpadded_length(&self) -> usize {
+        self.unpadded_length
+    }
+
     fn main_segment(&self) -> &Matrix<B> {
         &self.trace
     }
@@ -455,3 +744,18 @@ impl<'a, B: StarkField> TraceTableFragment<'a, B> {
         }
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Draws a random field element from the provided source of entropy.
+fn draw_element<B: StarkField, R: RngCore>(rng: &mut R) -> B {
+    let mut bytes = [0u8; 32];
+    for _ in 0..MAX_DRAW_ATTEMPTS {
+        rng.fill_bytes(&mut bytes[..B::VALUE_SIZE]);
+        if let Some(value) = B::from_random_bytes(&bytes[..B::VALUE_SIZE]) {
+            return value;
+        }
+    }
+    panic!("failed to draw a random field element");
+}
@@ -12,8 +12,12 @@ pub use internal::*;
 
 #[cfg(not(target_family = "wasm"))]
 mod internal {
-    use core::{convert::TryInto, fmt::Debug};
+    use core::{
+        convert::TryInto,
+        fmt::{self, Debug},
+    };
     use rand::prelude::*;
+    use rand_chacha::ChaCha20Rng;
     use utils::Randomizable;
 
     // RANDOM VALUE GENERATION
@@ -109,6 +113,100 @@ mod internal {
             .expect("failed to convert vector to array")
     }
 
+    // SEEDED RANDOM VALUE GENERATION
+    // ============================================================================================
+
+    /// A deterministic generator of random values instantiated from a 32-byte seed.
+    ///
+    /// The generator is based on ChaCha20 stream cipher, and thus, generators instantiated with
+    /// the same seed always produce the same sequence of values. This makes it possible to
+    /// reproduce a failing test from the seed of the generator (e.g., printed via its [Display]
+    /// implementation).
+    ///
+    /// [Display]: core::fmt::Display
+    #[derive(Debug, Clone)]
+    pub struct SeededRng {
+        seed: [u8; 32],
+        rng: ChaCha20Rng,
+    }
+
+    impl SeededRng {
+        /// Returns a new generator instantiated from the specified `seed`.
+        pub fn new(seed: [u8; 32]) -> Self {
+            Self {
+                seed,
+                rng: ChaCha20Rng::from_seed(seed),
+            }
+        }
+
+        /// Returns a new generator instantiated from a randomly chosen seed.
+        pub fn from_entropy() -> Self {
+            Self::new(thread_rng().gen::<[u8; 32]>())
+        }
+
+        /// Returns the seed from which this generator was instantiated.
+        pub fn seed(&self) -> [u8; 32] {
+            self.seed
+        }
+
+        /// Returns the next random value of the specified type drawn from this generator.
+        ///
+        /// # Panics
+        /// Panics if:
+        /// * A valid value requires over 32 bytes.
+        /// * A valid value could not be generated after 1000 tries.
+        pub fn draw<R: Randomizable>(&mut self) -> R {
+            for _ in 0..1000 {
+                let bytes = self.rng.gen::<[u8; 32]>();
+                if let Some(value) = R::from_random_bytes(&bytes[..R::VALUE_SIZE]) {
+                    return value;
+                }
+            }
+
+            panic!("failed generate a random field element");
+        }
+
+        /// Returns a vector of the specified length of random values drawn from this generator.
+        ///
+        /// # Panics
+        /// Panics if:
+        /// * A valid value requires over 32 bytes.
+        /// * A valid value could not be generated after 1000 tries.
+        pub fn draw_vector<R: Randomizable>(&mut self, n: usize) -> Vec<R> {
+            (0..n).map(|_| self.draw()).collect()
+        }
+    }
+
+    /// Makes it possible to use the generator wherever a source of entropy is expected (e.g., for
+    /// drawing blinding values of an execution trace).
+    impl RngCore for SeededRng {
+        fn next_u32(&mut self) -> u32 {
+            self.rng.next_u32()
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.rng.next_u64()
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            self.rng.fill_bytes(dest)
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.rng.try_fill_bytes(dest)
+        }
+    }
+
+    impl fmt::Display for SeededRng {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "0x")?;
+            for byte in self.seed.iter() {
+                write!(f, "{:02x}", byte)?;
+            }
+            Ok(())
+        }
+    }
+
     // SHUFFLING
     // ============================================================================================
 
@@ -126,7 +224,7 @@ mod internal {}
 
 #[cfg(test)]
 mod tests {
-    use super::{rand_array, rand_value};
+    use super::{rand_array, rand_value, SeededRng};
 
     #[test]
     fn rand_primitives() {
@@ -161,4 +259,28 @@ mod tests {
         let b = rand_array::<u8, 34>();
         assert_ne!(a, b);
     }
+
+    #[test]
+    fn seeded_rng() {
+        // generators with the same seed produce the same values
+        let mut a = SeededRng::new([7; 32]);
+        let mut b = SeededRng::new([7; 32]);
+        assert_eq!(a.draw_vector::<u64>(16), b.draw_vector::<u64>(16));
+        assert_eq!(a.draw::<u128>(), b.draw::<u128>());
+
+        // a generator can be re-created from its seed
+        let mut a = SeededRng::from_entropy();
+        let mut b = SeededRng::new(a.seed());
+        assert_eq!(a.draw_vector::<u32>(16), b.draw_vector::<u32>(16));
+
+        // generators with different seeds produce different values
+        let mut a = SeededRng::new([1; 32]);
+        let mut b = SeededRng::new([2; 32]);
+        assert_ne!(a.draw_vector::<u64>(16), b.draw_vector::<u64>(16));
+
+        assert_eq!(
+            "0x0101010101010101010101010101010101010101010101010101010101010101",
+            SeededRng::new([1; 32]).to_string()
+        );
+    }
 }
@@ -0,0 +1,104 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{verify, Air, StarkProof, VerifierError};
+use utils::collections::Vec;
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// BATCH VERIFIER
+// ================================================================================================
+
+/// A deferred verification of a single proof in a batch.
+type VerificationJob = Box<dyn FnOnce() -> Result<(), VerifierError> + Send>;
+
+/// A collection of independent STARK proofs which are verified together.
+///
+/// Proofs in a batch may attest to different computations: each proof is added to the batch via
+/// [BatchVerifier::add()] together with the AIR type which describes its computation. The proofs
+/// are verified independently from each other when [BatchVerifier::verify()] is called; when
+/// `concurrent` feature is enabled, the proofs are verified in multiple threads.
+///
+/// Verifying a proof in a batch is equivalent to verifying it via [verify()] function; a batch
+/// merely amortizes the overhead of dispatching verification across many proofs.
+#[derive(Default)]
+pub struct BatchVerifier {
+    jobs: Vec<VerificationJob>,
+}
+
+impl BatchVerifier {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of proofs in this batch.
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Returns true if this batch does not contain any proofs.
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    // BATCH BUILDER
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds a proof of a computation specified by `AIR` type parameter executed against the
+    /// specified public inputs to this batch.
+    pub fn add<AIR: Air>(&mut self, proof: StarkProof, pub_inputs: AIR::PublicInputs)
+    where
+        AIR::PublicInputs: Send + 'static,
+    {
+        self.jobs
+            .push(Box::new(move || verify::<AIR>(proof, pub_inputs)));
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Verifies all proofs in this batch and returns verification results in the order in which
+    /// the proofs were added to the batch.
+    ///
+    /// Failure to verify one of the proofs does not affect verification of other proofs.
+    pub fn verify(self) -> Vec<Result<(), VerifierError>> {
+        #[cfg(not(feature = "concurrent"))]
+        let result = self.jobs.into_iter().map(|job| job()).collect();
+
+        #[cfg(feature = "concurrent")]
+        let result = self.jobs.into_par_iter().map(|job| job()).collect();
+
+        result
+    }
+}
+
+/// Verifies a batch of proofs of computations specified by `AIR` type parameter.
+///
+/// This is equivalent to calling [verify()] function for each pair of a proof and public inputs,
+/// except that when `concurrent` feature is enabled, the proofs are verified in multiple threads.
+/// Verification results are returned in the same order as the provided proofs. To verify proofs
+/// of different computations in a single batch, [BatchVerifier] can be used.
+pub fn verify_batch<AIR: Air>(
+    proofs: Vec<(StarkProof, AIR::PublicInputs)>,
+) -> Vec<Result<(), VerifierError>>
+where
+    AIR::PublicInputs: Send + 'static,
+{
+    let mut batch = BatchVerifier::new();
+    for (proof, pub_inputs) in proofs {
+        batch.add::<AIR>(proof, pub_inputs);
+    }
+    batch.verify()
+}
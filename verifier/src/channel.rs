@@ -99,6 +99,20 @@ impl<
         let fri_remainder = fri_proof
             .parse_remainder()
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+
+        // make sure the degree bound implied by the FRI remainder is the degree of trace
+        // polynomials; otherwise, the DEEP composition polynomial would be checked against a
+        // domain other than the LDE domain
+        let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+        let fri_domain_size =
+            fri_remainder.len() * fri_options.folding_factor().pow(num_fri_layers as u32);
+        if fri_domain_size != lde_domain_size {
+            return Err(VerifierError::DegreeMismatch(
+                air.trace_poly_degree(),
+                (fri_domain_size / air.options().blowup_factor()).saturating_sub(1),
+            ));
+        }
+
         let (fri_layer_queries, fri_layer_proofs) = fri_proof
             .parse_layers::<H, E>(lde_domain_size, fri_options.folding_factor())
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
@@ -109,7 +123,7 @@ impl<
                 main_trace_width,
                 aux_trace_width,
                 air.eval_frame_size::<E>(),
-                air.ce_blowup_factor(),
+                air.num_composition_columns(),
             )
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
         let ood_trace_frame = TraceOodFrame::new(ood_main_trace_frame, ood_aux_trace_frame);
@@ -347,7 +361,11 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintQuer
     ) -> Result<Self, VerifierError> {
         let num_queries = air.options().num_queries();
         let (query_proofs, evaluations) = queries
-            .parse::<H, E>(air.lde_domain_size(), num_queries, air.ce_blowup_factor())
+            .parse::<H, E>(
+                air.lde_domain_size(),
+                num_queries,
+                air.num_composition_columns(),
+            )
             .map_err(|err| {
                 VerifierError::ProofDeserializationError(format!(
                     "constraint evaluation query deserialization failed: {}",
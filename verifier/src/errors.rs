@@ -5,6 +5,7 @@
 
 //! Contains common error types for prover and verifier.
 
+use air::TraceMetaError;
 use core::fmt;
 use utils::string::String;
 
@@ -19,6 +20,10 @@ pub enum VerifierError {
     /// This error occurs when the base field in which the proof was generated does not support
     /// field extension of degree specified by the proof.
     UnsupportedFieldExtension(usize),
+    /// This error occurs when the field extension used by the AIR with which the verifier was
+    /// instantiated is different from the field extension with which the proof was generated.
+    /// The values are: extension degree of the proof, and extension degree of the AIR.
+    ExtensionDegreeMismatch(usize, usize),
     /// This error occurs when a verifier cannot deserialize the specified proof.
     ProofDeserializationError(String),
     /// This error occurs when a verifier fails to draw a random value from a random coin
@@ -41,6 +46,24 @@ pub enum VerifierError {
     /// constraint evaluation queries do not represent a polynomial of the degree expected by the
     /// verifier.
     FriVerificationFailed(fri::VerifierError),
+    /// This error occurs when the degree bound implied by the FRI remainder included in the proof
+    /// is not the degree of trace polynomials, against which the DEEP composition polynomial is
+    /// checked. The values are: expected degree, and actual degree.
+    DegreeMismatch(usize, usize),
+    /// This error occurs when the domain offset specified in the proof is zero in the base field
+    /// of the computation, or is an element of the LDE domain.
+    InvalidDomainOffset,
+    /// This error occurs when trace metadata of the proof does not conform to the metadata schema
+    /// declared by the AIR with which the verifier was instantiated.
+    InvalidTraceMeta(TraceMetaError),
+    /// This error occurs when a proof does not contain enough trace queries to reconstruct the
+    /// execution trace for checking a subset of transition constraints. The values are: the
+    /// required number of queries, and the number of queries in the proof.
+    NotEnoughTraceQueries(usize, usize),
+    /// This error occurs when a transition constraint checked against the execution trace
+    /// reconstructed from trace queries does not evaluate to zero. The values are: index of the
+    /// constraint, and the step of the trace at which it is not satisfied.
+    TransitionConstraintNotSatisfied(usize, usize),
 }
 
 impl fmt::Display for VerifierError {
@@ -53,6 +76,9 @@ impl fmt::Display for VerifierError {
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {} is not supported for the proof base field", degree)
             }
+            Self::ExtensionDegreeMismatch(proof_degree, air_degree) => {
+                write!(f, "proof was generated using field extension of degree {}, but the AIR uses field extension of degree {}", proof_degree, air_degree)
+            }
             Self::ProofDeserializationError(msg) => {
                 write!(f, "proof deserialization failed: {}", msg)
             }
@@ -74,6 +100,60 @@ impl fmt::Display for VerifierError {
             Self::FriVerificationFailed(err) => {
                 write!(f, "verification of low-degree proof failed: {}", err)
             }
+            Self::DegreeMismatch(expected, actual) => {
+                write!(f, "degree bound implied by the FRI remainder is inconsistent with trace polynomials; expected {}, but was {}", expected, actual)
+            }
+            Self::InvalidDomainOffset => {
+                write!(f, "domain offset does not shift the LDE domain into a valid coset")
+            }
+            Self::InvalidTraceMeta(err) => {
+                write!(f, "trace metadata does not conform to the schema of the AIR: {}", err)
+            }
+            Self::NotEnoughTraceQueries(expected, actual) => {
+                write!(f, "at least {} trace queries are required to reconstruct the execution trace, but only {} were provided", expected, actual)
+            }
+            Self::TransitionConstraintNotSatisfied(index, step) => {
+                write!(f, "transition constraint {} is not satisfied at step {}", index, step)
+            }
+        }
+    }
+}
+
+// LDE CONSISTENCY ERROR
+// ================================================================================================
+/// Represents an error returned when reconstructing the LDE of a trace column from queried
+/// column values.
+#[derive(Debug, PartialEq)]
+pub enum LdeConsistencyError {
+    /// This error occurs when the number of query positions does not match the number of
+    /// queried values.
+    NumPositionValueMismatch(usize, usize),
+    /// This error occurs when there are not enough queried values to check consistency with a
+    /// polynomial of the expected degree.
+    NotEnoughQueries(usize, usize),
+    /// This error occurs when a query position is outside of the LDE domain or is repeated.
+    InvalidPosition(usize),
+    /// This error occurs when a queried value is not consistent with a polynomial of the
+    /// expected degree.
+    InconsistentValue(usize),
+}
+
+impl fmt::Display for LdeConsistencyError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NumPositionValueMismatch(num_positions, num_values) => {
+                write!(f, "the number of query positions must be the same as the number of queried values, but {} and {} were provided", num_positions, num_values)
+            }
+            Self::NotEnoughQueries(expected, actual) => {
+                write!(f, "at least {} queried values are required, but only {} were provided", expected, actual)
+            }
+            Self::InvalidPosition(position) => {
+                write!(f, "query position {} is out of bounds or repeated", position)
+            }
+            Self::InconsistentValue(position) => {
+                write!(f, "queried value at position {} is not consistent with a polynomial of the expected degree", position)
+            }
         }
     }
 }
@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use air::{Air, AuxTraceRandElements, ConstraintCompositionCoefficients, EvaluationFrame};
-use math::{polynom, FieldElement};
+use math::FieldElement;
 use utils::collections::Vec;
 
 // CONSTRAINT EVALUATION
@@ -24,26 +24,32 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     // initialize a buffer to hold transition constraint evaluations
     let t_constraints = air.get_transition_constraints(&composition_coefficients.transition);
 
-    // compute values of periodic columns at x
-    let periodic_values = air
-        .get_periodic_column_polys()
-        .iter()
-        .map(|poly| {
-            let num_cycles = air.trace_length() / poly.len();
-            let x = x.exp((num_cycles as u32).into());
-            polynom::eval(poly, x)
-        })
-        .collect::<Vec<_>>();
+    // compute values of periodic columns at x and at the next step of the trace (x * g)
+    let periodic_values = air.get_periodic_column_values_at(x);
+    let x_next = x * E::from(air.trace_domain_generator());
+    let next_periodic_values = air.get_periodic_column_values_at(x_next);
 
     // evaluate transition constraints for the main trace segment
     let mut t_evaluations1 = E::zeroed_vector(t_constraints.num_main_constraints());
-    air.evaluate_transition(main_trace_frame, &periodic_values, &mut t_evaluations1);
+    air.evaluate_transition(
+        main_trace_frame,
+        &periodic_values,
+        &next_periodic_values,
+        &mut t_evaluations1,
+    );
 
     // evaluate transition constraints for auxiliary trace segments (if any)
     let mut t_evaluations2 = E::zeroed_vector(t_constraints.num_aux_constraints());
     if let Some(aux_trace_frame) = aux_trace_frame {
         air.evaluate_aux_transition(
             main_trace_frame,
+            aux_trace_frame,
+            &periodic_values,
+            &next_periodic_values,
+            &aux_rand_elements,
+            &mut t_evaluations2,
+        );
+        air.evaluate_lagrange_kernel_aux_transition(
             aux_trace_frame,
             &periodic_values,
             &aux_rand_elements,
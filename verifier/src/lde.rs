@@ -0,0 +1,192 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::LdeConsistencyError;
+use math::{log2, polynom, FieldElement, StarkField};
+use utils::collections::Vec;
+
+// LDE RECONSTRUCTION
+// ================================================================================================
+
+/// Reconstructs the low-degree extension of a single trace column from queried column values.
+///
+/// `positions` are the queried positions in the LDE domain, and `values` are the values of the
+/// column at these positions (e.g., as read from trace queries of a proof). The LDE domain is
+/// defined by `lde_domain_size` and `domain_offset`, and `max_degree` is the expected degree of
+/// the column polynomial (for a trace column this is `trace_length - 1`).
+///
+/// The first `max_degree + 1` queried values are interpolated into a polynomial; all remaining
+/// values are then checked against this polynomial. If all values are consistent, evaluations
+/// of the polynomial over the entire LDE domain are returned.
+///
+/// This is meant as a debugging and research tool for checking soundness of the LDE empirically;
+/// it runs in time quadratic in `max_degree` and is not used during proof verification.
+///
+/// # Errors
+/// Returns an error if:
+/// * The number of positions is not equal to the number of values.
+/// * Fewer than `max_degree + 2` values were provided, and thus there is nothing to check the
+///   interpolated polynomial against.
+/// * Any of the positions is outside of the LDE domain, or positions are not unique.
+/// * Any of the values beyond the first `max_degree + 1` does not lie on the interpolated
+///   polynomial.
+///
+/// # Panics
+/// Panics if `lde_domain_size` is not a power of two.
+pub fn reconstruct_column_lde<B, E>(
+    positions: &[usize],
+    values: &[E],
+    lde_domain_size: usize,
+    domain_offset: B,
+    max_degree: usize,
+) -> Result<Vec<E>, LdeConsistencyError>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    if positions.len() != values.len() {
+        return Err(LdeConsistencyError::NumPositionValueMismatch(
+            positions.len(),
+            values.len(),
+        ));
+    }
+    if positions.len() < max_degree + 2 {
+        return Err(LdeConsistencyError::NotEnoughQueries(
+            max_degree + 2,
+            positions.len(),
+        ));
+    }
+    for (i, &position) in positions.iter().enumerate() {
+        if position >= lde_domain_size || positions[..i].contains(&position) {
+            return Err(LdeConsistencyError::InvalidPosition(position));
+        }
+    }
+
+    // compute LDE domain coordinates for all query positions
+    let g_lde = B::get_root_of_unity(log2(lde_domain_size));
+    let xs = positions
+        .iter()
+        .map(|&p| E::from(g_lde.exp((p as u64).into()) * domain_offset))
+        .collect::<Vec<_>>();
+
+    // interpolate the polynomial from the first max_degree + 1 values, and make sure the rest of
+    // the values are consistent with it
+    let (interp_xs, check_xs) = xs.split_at(max_degree + 1);
+    let poly = polynom::interpolate(interp_xs, &values[..max_degree + 1], false);
+    for (i, &x) in check_xs.iter().enumerate() {
+        let position = max_degree + 1 + i;
+        if polynom::eval(&poly, x) != values[position] {
+            return Err(LdeConsistencyError::InconsistentValue(positions[position]));
+        }
+    }
+
+    // evaluate the polynomial over the entire LDE domain
+    let domain = (0..lde_domain_size)
+        .map(|i| E::from(g_lde.exp((i as u64).into()) * domain_offset))
+        .collect::<Vec<_>>();
+    Ok(polynom::eval_many(&poly, &domain))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::reconstruct_column_lde;
+    use crate::LdeConsistencyError;
+    use math::{fields::f128::BaseElement, log2, polynom, FieldElement, StarkField};
+    use utils::collections::Vec;
+
+    const LDE_DOMAIN_SIZE: usize = 64;
+    const MAX_DEGREE: usize = 7;
+
+    #[test]
+    fn reconstruct_consistent_column() {
+        let (positions, lde) = build_column_lde();
+        let values = positions.iter().map(|&p| lde[p]).collect::<Vec<_>>();
+
+        let result = reconstruct_column_lde(
+            &positions,
+            &values,
+            LDE_DOMAIN_SIZE,
+            BaseElement::GENERATOR,
+            MAX_DEGREE,
+        );
+        assert_eq!(Ok(lde), result);
+    }
+
+    #[test]
+    fn reconstruct_inconsistent_column() {
+        let (positions, lde) = build_column_lde();
+        let mut values = positions.iter().map(|&p| lde[p]).collect::<Vec<_>>();
+        values[10] += BaseElement::ONE;
+
+        let result = reconstruct_column_lde(
+            &positions,
+            &values,
+            LDE_DOMAIN_SIZE,
+            BaseElement::GENERATOR,
+            MAX_DEGREE,
+        );
+        assert_eq!(
+            Err(LdeConsistencyError::InconsistentValue(positions[10])),
+            result
+        );
+
+        // the column is also inconsistent if the expected degree is too low
+        let values = positions.iter().map(|&p| lde[p]).collect::<Vec<_>>();
+        let result = reconstruct_column_lde(
+            &positions,
+            &values,
+            LDE_DOMAIN_SIZE,
+            BaseElement::GENERATOR,
+            MAX_DEGREE - 1,
+        );
+        assert!(matches!(
+            result,
+            Err(LdeConsistencyError::InconsistentValue(_))
+        ));
+    }
+
+    #[test]
+    fn reconstruct_column_not_enough_queries() {
+        let (positions, lde) = build_column_lde();
+        let values = positions.iter().map(|&p| lde[p]).collect::<Vec<_>>();
+
+        let result = reconstruct_column_lde(
+            &positions[..MAX_DEGREE + 1],
+            &values[..MAX_DEGREE + 1],
+            LDE_DOMAIN_SIZE,
+            BaseElement::GENERATOR,
+            MAX_DEGREE,
+        );
+        assert_eq!(
+            Err(LdeConsistencyError::NotEnoughQueries(
+                MAX_DEGREE + 2,
+                MAX_DEGREE + 1
+            )),
+            result
+        );
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates a polynomial of degree MAX_DEGREE over the LDE domain, and returns these
+    /// evaluations together with a set of queried positions.
+    fn build_column_lde() -> (Vec<usize>, Vec<BaseElement>) {
+        let poly = (0..MAX_DEGREE as u128 + 1)
+            .map(|i| BaseElement::new(i * i + 1))
+            .collect::<Vec<_>>();
+        let g_lde = BaseElement::get_root_of_unity(log2(LDE_DOMAIN_SIZE));
+        let domain = (0..LDE_DOMAIN_SIZE as u64)
+            .map(|i| g_lde.exp(i.into()) * BaseElement::GENERATOR)
+            .collect::<Vec<_>>();
+        let lde = polynom::eval_many(&poly, &domain);
+
+        let positions = (0..16).map(|i| (i * 7 + 3) % LDE_DOMAIN_SIZE).collect();
+        (positions, lde)
+    }
+}
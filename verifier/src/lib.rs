@@ -25,6 +25,16 @@
 //! asserted values. But for the impact to be noticeable, the number of asserted values would
 //! need to be in tens of thousands. And even for hundreds of thousands of asserted values, the
 //! verification time should not exceed 50 ms.
+//!
+//! # no_std support
+//! The verifier can be compiled without the Rust standard library by disabling default
+//! features; in this case, the verifier and all of its dependencies (including the Pedersen hash
+//! function backed by `starknet-crypto`) rely only on the `alloc` crate, and can be built for
+//! targets which do not provide `std` at all (e.g., `thumbv7em-none-eabi`).
+//! [verify()] function, as well as [FriVerifier] and [RandomCoin](crypto::RandomCoin) used
+//! internally by it, do not rely on threads, and thus, never pull in `rayon`. The only part of
+//! this crate which uses multiple threads is [BatchVerifier], and only when `concurrent` feature
+//! is enabled.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -32,28 +42,31 @@
 #[macro_use]
 extern crate alloc;
 
+use air::get_public_coin_seed;
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, HashFunction, ProofOptions,
-    TraceInfo, TransitionConstraintDegree, TransitionConstraintGroup,
+    proof::{verify_column_membership, MembershipProof, StarkProof},
+    Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
+    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
+    EvaluationFrame, FieldExtension, HashFunction, MembershipError, ProofOptions, PublicCoin,
+    TraceInfo, TraceMeta, TraceMetaError, TraceMetaSchema, TransitionConstraintDegree,
+    TransitionConstraintGroup,
 };
 
 pub use math;
 use math::{
     fields::{CubeExtension, QuadExtension},
-    FieldElement,
+    FieldElement, StarkField,
 };
 
-use utils::collections::Vec;
 pub use utils::{
     ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
 };
+use utils::collections::Vec;
 
 pub use crypto;
 use crypto::{
     hashers::{Blake2s_256, Blake3_192, Blake3_256, Sha3_256, Pedersen_256},
-    ElementHasher, RandomCoin,
+    ElementHasher, RandomCoin, TranscriptEvent,
 };
 
 pub use fri::FriVerifier;
@@ -67,8 +80,19 @@ pub use evaluator::evaluate_constraints;
 mod composer;
 pub use composer::DeepComposer;
 
+mod lde;
+pub use lde::reconstruct_column_lde;
+
+mod batch;
+pub use batch::{verify_batch, BatchVerifier};
+
 mod errors;
-pub use errors::VerifierError;
+pub use errors::{LdeConsistencyError, VerifierError};
+
+mod subset;
+
+#[cfg(test)]
+mod tests;
 
 // VERIFIER
 // ================================================================================================
@@ -83,48 +107,176 @@ pub use errors::VerifierError;
 /// a correct execution of the computation. This could happen for many various reasons, including:
 /// - The specified proof was generated for a different computation.
 /// - The specified proof was generated for this computation but for different public inputs.
-#[rustfmt::skip]
 pub fn verify<AIR: Air>(
     proof: StarkProof,
     pub_inputs: AIR::PublicInputs,
 ) -> Result<(), VerifierError> {
-    // build a seed for the public coin; the initial seed is the hash of public inputs and proof
-    // context, but as the protocol progresses, the coin will be reseeded with the info received
-    // from the prover
-    let mut public_coin_seed = Vec::new();
-    pub_inputs.write_into(&mut public_coin_seed);
+    verify_with_seed::<AIR>(proof, pub_inputs, &[])
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// and that the proof is bound to the specified `extra_seed`.
+///
+/// This is similar to [verify()] function, but the public coin is seeded with `extra_seed`
+/// elements in addition to the public inputs. The seed must be the same as the one used by the
+/// prover to generate the proof (e.g., via `Prover::prove_with_seed()` method). When
+/// `extra_seed` is empty, this function is equivalent to [verify()].
+///
+/// # Errors
+/// Returns an error if combination of the provided proof, public inputs, and extra seed does not
+/// attest to a correct execution of the computation. In addition to the reasons listed for
+/// [verify()] function, this could happen if the proof was generated with a different seed.
+pub fn verify_with_seed<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    extra_seed: &[AIR::BaseField],
+) -> Result<(), VerifierError> {
+    verify_and_record::<AIR>(proof, pub_inputs, extra_seed, None, &mut Vec::new()).map(|_| ())
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// and returns the verification result together with the transcript of the public coin.
+///
+/// This is similar to [verify()] function, but in addition to the verification result, the
+/// events recorded by the public coin of the verifier are returned. The events are recorded
+/// until verification completes or fails, and can be compared to the events recorded by the
+/// prover (e.g., via `ProverObserver::on_transcript()` method) to find the first operation at
+/// which the transcripts of the prover and the verifier diverge.
+#[cfg(feature = "transcript")]
+pub fn verify_with_transcript<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+) -> (Result<(), VerifierError>, Vec<TranscriptEvent>) {
+    let mut transcript = Vec::new();
+    let result = verify_and_record::<AIR>(proof, pub_inputs, &[], None, &mut transcript);
+    (result.map(|_| ()), transcript)
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// and returns the positions in the LDE domain at which the proof was queried.
+///
+/// This is similar to [verify()] function, but on success, the query positions drawn by the
+/// verifier (see [query_positions()]) are returned. These are the positions at which the proof
+/// opens trace and constraint commitments, and thus, can be used to replay the queries of a proof
+/// (e.g., to build test vectors or to check other implementations of the protocol).
+///
+/// # Errors
+/// Returns an error for the same reasons as [verify()] function.
+pub fn get_query_positions<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<Vec<usize>, VerifierError> {
+    verify_and_record::<AIR>(proof, pub_inputs, &[], None, &mut Vec::new())
+}
+
+/// Verifies a proof while checking only the transition constraints with the specified indices.
+///
+/// **This is a diagnostic tool, and it is not sound: it must never be used to accept proofs.**
+/// It is meant for localizing a specific failing constraint (e.g., when [verify()] fails with
+/// [VerifierError::InconsistentOodConstraintEvaluations]).
+///
+/// **This works only for short traces:** the execution trace is reconstructed from trace
+/// queries, and thus, the proof must contain at least as many unique queries as there are steps
+/// in the trace. Since the number of queries cannot exceed 128, traces longer than 128 steps (or
+/// than the number of queries specified in the proof options) cannot be checked, and
+/// [VerifierError::NotEnoughTraceQueries] is returned for them.
+///
+/// All commitments, queries, and the low-degree proof are verified as in [verify()], but the
+/// out-of-domain consistency check, which covers all constraints at once, is skipped. Instead,
+/// the execution trace is reconstructed from the trace states queried by the verifier, and each
+/// selected transition constraint is evaluated at every step of the trace to which it applies.
+/// Boundary constraints are not checked.
+///
+/// Constraint indices refer to main transition constraints first, followed by auxiliary
+/// transition constraints. Constraints of the Lagrange kernel column are not checked.
+///
+/// # Errors
+/// Returns an error if the proof fails any of the checks performed by [verify()] other than the
+/// out-of-domain consistency check, and also if:
+/// * The proof contains fewer trace queries than there are steps in the execution trace.
+/// * Any of the selected constraints is not satisfied by the reconstructed execution trace.
+///
+/// # Panics
+/// Panics if any of the constraint indices is greater than or equal to the number of transition
+/// constraints defined by the AIR.
+#[cfg(feature = "diagnostics")]
+pub fn verify_constraint_subset<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    constraint_indices: &[usize],
+) -> Result<(), VerifierError> {
+    verify_and_record::<AIR>(
+        proof,
+        pub_inputs,
+        &[],
+        Some(constraint_indices),
+        &mut Vec::new(),
+    )
+    .map(|_| ())
+}
+
+/// Verifies the proof and appends the events recorded by the public coin of the verifier to
+/// `transcript`; events are recorded only when `transcript` feature is enabled. On success, the
+/// query positions at which the proof was verified are returned.
+///
+/// If `constraint_subset` is provided, only the transition constraints with the specified indices
+/// are checked (see [verify_constraint_subset()]).
+#[rustfmt::skip]
+fn verify_and_record<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    extra_seed: &[AIR::BaseField],
+    constraint_subset: Option<&[usize]>,
+    transcript: &mut Vec<TranscriptEvent>,
+) -> Result<Vec<usize>, VerifierError> {
+    // build a seed for the public coin; the initial seed is the hash of public input elements
+    // followed by extra seed elements, but as the protocol progresses, the coin will be reseeded
+    // with the info received from the prover
+    let public_coin_seed = get_public_coin_seed(&pub_inputs, extra_seed);
+
+    // the domain offset is read from the proof, and thus, must be validated before the AIR is
+    // instantiated; otherwise, an invalid offset would cause a panic
+    if !proof.options().is_valid_domain_offset::<AIR::BaseField>(proof.lde_domain_size()) {
+        return Err(VerifierError::InvalidDomainOffset);
+    }
 
     // create AIR instance for the computation specified in the proof
+    let proof_extension = proof.options().field_extension();
     let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
 
+    // make sure the AIR uses the same field extension as the one used to generate the proof;
+    // otherwise, parts of the proof would be deserialized into elements of a different field
+    if air.options().field_extension() != proof_extension {
+        return Err(VerifierError::ExtensionDegreeMismatch(
+            proof_extension.degree() as usize,
+            air.options().field_extension().degree() as usize,
+        ));
+    }
+
+    // if the AIR declares a schema for trace metadata, make sure the metadata of the proof
+    // conforms to it
+    if let Some(schema) = air.trace_meta_schema() {
+        schema.parse(air.trace_info()).map_err(VerifierError::InvalidTraceMeta)?;
+    }
+
     // figure out which version of the generic proof verification procedure to run. this is a sort
     // of static dispatch for selecting two generic parameter: extension field and hash function.
     match air.options().field_extension() {
         FieldExtension::None => match air.options().hash_fn() {
             HashFunction::Blake3_256 => {
-                let public_coin = RandomCoin::new(&public_coin_seed);
-                let channel = VerifierChannel::new(&air, proof)?;
-                perform_verification::<AIR, AIR::BaseField, Blake3_256<AIR::BaseField>>(air, channel, public_coin)
+                verify_generic::<AIR, AIR::BaseField, Blake3_256<AIR::BaseField>>(air, proof, &public_coin_seed, constraint_subset, transcript)
             }
             HashFunction::Blake3_192 => {
-                let public_coin = RandomCoin::new(&public_coin_seed);
-                let channel = VerifierChannel::new(&air, proof)?;
-                perform_verification::<AIR, AIR::BaseField, Blake3_192<AIR::BaseField>>(air, channel, public_coin)
+                verify_generic::<AIR, AIR::BaseField, Blake3_192<AIR::BaseField>>(air, proof, &public_coin_seed, constraint_subset, transcript)
             }
             HashFunction::Sha3_256 => {
-                let public_coin = RandomCoin::new(&public_coin_seed);
-                let channel = VerifierChannel::new(&air, proof)?;
-                perform_verification::<AIR, AIR::BaseField, Sha3_256<AIR::BaseField>>(air, channel, public_coin)
+                verify_generic::<AIR, AIR::BaseField, Sha3_256<AIR::BaseField>>(air, proof, &public_coin_seed, constraint_subset, transcript)
             }
             HashFunction::Blake2s_256 => {
-                let public_coin = RandomCoin::new(&public_coin_seed);
-                let channel = VerifierChannel::new(&air, proof)?;
-                perform_verification::<AIR, AIR::BaseField, Blake2s_256<AIR::BaseField>>(air, channel, public_coin)
+                verify_generic::<AIR, AIR::BaseField, Blake2s_256<AIR::BaseField>>(air, proof, &public_coin_seed, constraint_subset, transcript)
             }
             HashFunction::Pedersen_256 => {
-                let public_coin = RandomCoin::new(&public_coin_seed);
-                let channel = VerifierChannel::new(&air, proof)?;
-                perform_verification::<AIR, AIR::BaseField, Pedersen_256<AIR::BaseField>>(air, channel, public_coin)
+                verify_generic::<AIR, AIR::BaseField, Pedersen_256<AIR::BaseField>>(air, proof, &public_coin_seed, constraint_subset, transcript)
             }
         },
         FieldExtension::Quadratic => {
@@ -133,29 +285,19 @@ pub fn verify<AIR: Air>(
             }
             match air.options().hash_fn() {
                 HashFunction::Blake3_256 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, QuadExtension<AIR::BaseField>, Blake3_256<AIR::BaseField>>(air, channel, public_coin)
+                    verify_generic::<AIR, QuadExtension<AIR::BaseField>, Blake3_256<AIR::BaseField>>(air, proof, &public_coin_seed, constraint_subset, transcript)
                 }
                 HashFunction::Blake3_192 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, QuadExtension<AIR::BaseField>, Blake3_192<AIR::BaseField>>(air, channel, public_coin)
+                    verify_generic::<AIR, QuadExtension<AIR::BaseField>, Blake3_192<AIR::BaseField>>(air, proof, &public_coin_seed, constraint_subset, transcript)
                 }
                 HashFunction::Sha3_256 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, QuadExtension<AIR::BaseField>, Sha3_256<AIR::BaseField>>(air, channel, public_coin)
+                    verify_generic::<AIR, QuadExtension<AIR::BaseField>, Sha3_256<AIR::BaseField>>(air, proof, &public_coin_seed, constraint_subset, transcript)
                 }
                 HashFunction::Blake2s_256 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, QuadExtension<AIR::BaseField>, Blake2s_256<AIR::BaseField>>(air, channel, public_coin)
+                    verify_generic::<AIR, QuadExtension<AIR::BaseField>, Blake2s_256<AIR::BaseField>>(air, proof, &public_coin_seed, constraint_subset, transcript)
                 }
                 HashFunction::Pedersen_256 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, QuadExtension<AIR::BaseField>, Pedersen_256<AIR::BaseField>>(air, channel, public_coin)
+                    verify_generic::<AIR, QuadExtension<AIR::BaseField>, Pedersen_256<AIR::BaseField>>(air, proof, &public_coin_seed, constraint_subset, transcript)
                 }
             }
         },
@@ -165,29 +307,19 @@ pub fn verify<AIR: Air>(
             }
             match air.options().hash_fn() {
                 HashFunction::Blake3_256 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, CubeExtension<AIR::BaseField>, Blake3_256<AIR::BaseField>>(air, channel, public_coin)
+                    verify_generic::<AIR, CubeExtension<AIR::BaseField>, Blake3_256<AIR::BaseField>>(air, proof, &public_coin_seed, constraint_subset, transcript)
                 }
                 HashFunction::Blake3_192 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, CubeExtension<AIR::BaseField>, Blake3_192<AIR::BaseField>>(air, channel, public_coin)
+                    verify_generic::<AIR, CubeExtension<AIR::BaseField>, Blake3_192<AIR::BaseField>>(air, proof, &public_coin_seed, constraint_subset, transcript)
                 }
                 HashFunction::Sha3_256 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, CubeExtension<AIR::BaseField>, Sha3_256<AIR::BaseField>>(air, channel, public_coin)
+                    verify_generic::<AIR, CubeExtension<AIR::BaseField>, Sha3_256<AIR::BaseField>>(air, proof, &public_coin_seed, constraint_subset, transcript)
                 }
                 HashFunction::Blake2s_256 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, CubeExtension<AIR::BaseField>, Blake2s_256<AIR::BaseField>>(air, channel, public_coin)
+                    verify_generic::<AIR, CubeExtension<AIR::BaseField>, Blake2s_256<AIR::BaseField>>(air, proof, &public_coin_seed, constraint_subset, transcript)
                 }
                 HashFunction::Pedersen_256 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, CubeExtension<AIR::BaseField>, Pedersen_256<AIR::BaseField>>(air, channel, public_coin)
+                    verify_generic::<AIR, CubeExtension<AIR::BaseField>, Pedersen_256<AIR::BaseField>>(air, proof, &public_coin_seed, constraint_subset, transcript)
                 }
             }
         },
@@ -196,13 +328,42 @@ pub fn verify<AIR: Air>(
 
 // VERIFICATION PROCEDURE
 // ================================================================================================
+/// Instantiates the public coin and the verifier channel for the specified field extension `E`
+/// and hash function `H`, verifies the proof, and appends the events recorded by the public coin
+/// to `transcript`.
+fn verify_generic<A, E, H>(
+    air: A,
+    proof: StarkProof,
+    public_coin_seed: &[A::BaseField],
+    constraint_subset: Option<&[usize]>,
+    transcript: &mut Vec<TranscriptEvent>,
+) -> Result<Vec<usize>, VerifierError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    H: ElementHasher<BaseField = A::BaseField>,
+{
+    let mut public_coin = RandomCoin::<A::BaseField, H>::from_elements(public_coin_seed);
+    let channel = VerifierChannel::new(&air, proof)?;
+    let result = perform_verification::<A, E, H>(air, channel, &mut public_coin, constraint_subset);
+    transcript.extend_from_slice(public_coin.transcript());
+    result
+}
+
 /// Performs the actual verification by reading the data from the `channel` and making sure it
 /// attests to a correct execution of the computation specified by the provided `air`.
+///
+/// If `constraint_subset` is provided, the out-of-domain consistency check is replaced with a
+/// check of the specified transition constraints against the execution trace reconstructed from
+/// trace queries.
+///
+/// On success, returns the positions in the LDE domain at which the proof was queried.
 fn perform_verification<A, E, H>(
     air: A,
     mut channel: VerifierChannel<E, H, A::Frame<E>, A::AuxFrame<E>>,
-    mut public_coin: RandomCoin<A::BaseField, H>,
-) -> Result<(), VerifierError>
+    public_coin: &mut RandomCoin<A::BaseField, H>,
+    constraint_subset: Option<&[usize]>,
+) -> Result<Vec<usize>, VerifierError>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
@@ -226,7 +387,7 @@ where
     let mut aux_trace_rand_elements = AuxTraceRandElements::<E>::new();
     for (i, commitment) in trace_commitments.iter().skip(1).enumerate() {
         let rand_elements = air
-            .get_aux_trace_segment_random_elements(i, &mut public_coin)
+            .get_aux_trace_segment_random_elements(i, public_coin)
             .map_err(|_| VerifierError::RandomCoinError)?;
         aux_trace_rand_elements.add_segment_elements(rand_elements);
         public_coin.reseed(*commitment);
@@ -234,7 +395,7 @@ where
 
     // build random coefficients for the composition polynomial
     let constraint_coeffs = air
-        .get_constraint_composition_coefficients(&mut public_coin)
+        .get_constraint_composition_coefficients(public_coin)
         .map_err(|_| VerifierError::RandomCoinError)?;
 
     // 2 ----- constraint commitment --------------------------------------------------------------
@@ -262,7 +423,7 @@ where
         constraint_coeffs,
         &ood_main_trace_frame,
         &ood_aux_trace_frame,
-        aux_trace_rand_elements,
+        aux_trace_rand_elements.clone(),
         z,
     );
 
@@ -291,8 +452,9 @@ where
         });
     public_coin.reseed(H::hash_elements(&ood_constraint_evaluations));
 
-    // finally, make sure the values are the same
-    if ood_constraint_evaluation_1 != ood_constraint_evaluation_2 {
+    // finally, make sure the values are the same; when only a subset of constraints is checked,
+    // the values are expected to differ as the composition polynomial covers all constraints
+    if constraint_subset.is_none() && ood_constraint_evaluation_1 != ood_constraint_evaluation_2 {
         return Err(VerifierError::InconsistentOodConstraintEvaluations);
     }
 
@@ -302,7 +464,7 @@ where
     // and the prover uses them to compute the DEEP composition polynomial. the prover, then
     // applies FRI protocol to the evaluations of the DEEP composition polynomial.
     let deep_coefficients = air
-        .get_deep_composition_coefficients::<E, H>(&mut public_coin)
+        .get_deep_composition_coefficients::<E, H>(public_coin)
         .map_err(|_| VerifierError::RandomCoinError)?;
 
     // instantiates a FRI verifier with the FRI layer commitments read from the channel. From the
@@ -312,30 +474,22 @@ where
     // the prover, and the prover uses them to compute and commit to the subsequent FRI layers.
     let fri_verifier = FriVerifier::new(
         &mut channel,
-        &mut public_coin,
+        public_coin,
         air.options().to_fri_options(),
         air.trace_poly_degree(),
     )
     .map_err(VerifierError::FriVerificationFailed)?;
-    // TODO: make sure air.lde_domain_size() == fri_verifier.domain_size()
+    debug_assert_eq!(air.lde_domain_size(), fri_verifier.domain_size());
 
     // 5 ----- trace and constraint queries -------------------------------------------------------
-    // read proof-of-work nonce sent by the prover and update the public coin with it
-    let pow_nonce = channel.read_pow_nonce();
-    public_coin.reseed_with_int(pow_nonce);
-
-    // make sure the proof-of-work specified by the grinding factor is satisfied
-    if public_coin.leading_zeros() < air.options().grinding_factor() {
-        return Err(VerifierError::QuerySeedProofOfWorkVerificationFailed);
-    }
-
-    // draw pseudo-random query positions for the LDE domain from the public coin; in the
-    // interactive version of the protocol, the verifier sends these query positions to the prover,
-    // and the prover responds with decommitments against these positions for trace and constraint
+    // read proof-of-work nonce sent by the prover, update the public coin with it, and draw
+    // pseudo-random query positions for the LDE domain from the public coin; in the interactive
+    // version of the protocol, the verifier sends these query positions to the prover, and the
+    // prover responds with decommitments against these positions for trace and constraint
     // composition polynomial evaluations.
-    let query_positions = public_coin
-        .draw_integers(air.options().num_queries(), air.lde_domain_size())
-        .map_err(|_| VerifierError::RandomCoinError)?;
+    let pow_nonce = channel.read_pow_nonce();
+    let query_positions =
+        query_positions(public_coin, pow_nonce, air.options(), air.lde_domain_size())?;
 
     // read evaluations of trace and constraint composition polynomials at the queried positions;
     // this also checks that the read values are valid against trace and constraint commitments
@@ -343,6 +497,19 @@ where
         channel.read_queried_trace_states(&query_positions)?;
     let queried_constraint_evaluations = channel.read_constraint_evaluations(&query_positions)?;
 
+    // when only a subset of constraints is checked, check these constraints against the
+    // execution trace reconstructed from the queried trace states
+    if let Some(constraint_indices) = constraint_subset {
+        subset::check_transition_constraints(
+            &air,
+            constraint_indices,
+            &query_positions,
+            &queried_main_trace_states,
+            queried_aux_trace_states.as_ref(),
+            &aux_trace_rand_elements,
+        )?;
+    }
+
     // 6 ----- DEEP composition -------------------------------------------------------------------
     // compute evaluations of the DEEP composition polynomial at the queried positions
     let composer = DeepComposer::new(&air, &query_positions, z, deep_coefficients);
@@ -361,5 +528,46 @@ where
     // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree
     fri_verifier
         .verify(&mut channel, &deep_evaluations, &query_positions)
-        .map_err(VerifierError::FriVerificationFailed)
+        .map_err(VerifierError::FriVerificationFailed)?;
+
+    Ok(query_positions)
+}
+
+// QUERY POSITIONS
+// ================================================================================================
+
+/// Reseeds the `public_coin` with the proof-of-work `pow_nonce` and draws the positions in the
+/// LDE domain at which a STARK proof is queried.
+///
+/// The `public_coin` is expected to be in the state reached by the verifier after absorbing all
+/// commitments, out-of-domain evaluations, and FRI layer commitments of the proof. The prover
+/// draws query positions from its public coin in the same way, and thus, the returned positions
+/// are the positions at which the proof opens trace and constraint commitments. Note that the
+/// positions cannot be drawn without the `pow_nonce` as the nonce is a part of the transcript.
+///
+/// # Errors
+/// Returns an error if:
+/// * The reseeded coin does not satisfy the proof-of-work specified by the grinding factor in
+///   `options`.
+/// * The number of query positions specified in `options` could not be drawn from the coin.
+pub fn query_positions<B, H>(
+    public_coin: &mut RandomCoin<B, H>,
+    pow_nonce: u64,
+    options: &ProofOptions,
+    lde_domain_size: usize,
+) -> Result<Vec<usize>, VerifierError>
+where
+    B: StarkField,
+    H: ElementHasher<BaseField = B>,
+{
+    public_coin.reseed_with_int(pow_nonce);
+
+    // make sure the proof-of-work specified by the grinding factor is satisfied
+    if public_coin.leading_zeros() < options.grinding_factor() {
+        return Err(VerifierError::QuerySeedProofOfWorkVerificationFailed);
+    }
+
+    public_coin
+        .draw_integers(options.num_queries(), lde_domain_size)
+        .map_err(|_| VerifierError::RandomCoinError)
 }
@@ -0,0 +1,175 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::VerifierError;
+use air::{Air, AuxTraceRandElements, EvaluationFrame, Table};
+use math::{log2, polynom, FieldElement, StarkField};
+use utils::collections::Vec;
+
+// CONSTRAINT SUBSET CHECK
+// ================================================================================================
+
+/// Checks transition constraints with the specified indices against the execution trace
+/// reconstructed from queried trace states.
+///
+/// Constraint indices refer to main transition constraints first, followed by auxiliary
+/// transition constraints (in the same order as they are defined in the
+/// [AirContext](air::AirContext)). The trace columns are interpolated from the states queried at
+/// the first `trace_length` positions of the LDE domain, and evaluated over the trace domain;
+/// every selected constraint is then evaluated at every step of the trace to which it applies.
+///
+/// This is meant as a debugging tool for localizing unsatisfied constraints; it runs in time
+/// quadratic in trace length and requires at least as many unique queries as there are steps in
+/// the trace. Constraints of the Lagrange kernel column are not checked.
+///
+/// # Errors
+/// Returns an error if:
+/// * Fewer than `trace_length` positions were queried.
+/// * Any of the selected constraints does not evaluate to zero at some step of the trace.
+///
+/// # Panics
+/// Panics if any of the constraint indices is greater than or equal to the total number of
+/// transition constraints defined by the AIR.
+pub fn check_transition_constraints<A, E>(
+    air: &A,
+    constraint_indices: &[usize],
+    query_positions: &[usize],
+    main_states: &Table<A::BaseField>,
+    aux_states: Option<&Table<E>>,
+    aux_rand_elements: &AuxTraceRandElements<E>,
+) -> Result<(), VerifierError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+{
+    let context = air.context();
+    let num_main_constraints = context.num_main_transition_constraints();
+    let num_constraints = context.num_transition_constraints();
+    for &index in constraint_indices {
+        assert!(
+            index < num_constraints,
+            "constraint index must be smaller than {}, but was {}",
+            num_constraints,
+            index
+        );
+    }
+
+    let trace_length = air.trace_length();
+    if query_positions.len() < trace_length {
+        return Err(VerifierError::NotEnoughTraceQueries(
+            trace_length,
+            query_positions.len(),
+        ));
+    }
+
+    // interpolate trace columns from the first trace_length queried states, and evaluate them
+    // over the trace domain
+    let g_lde = A::BaseField::get_root_of_unity(log2(air.lde_domain_size()));
+    let xs = query_positions[..trace_length]
+        .iter()
+        .map(|&p| E::from(g_lde.exp((p as u64).into()) * air.domain_offset()))
+        .collect::<Vec<_>>();
+    let g_trace = air.trace_domain_generator();
+    let trace_domain = (0..trace_length)
+        .map(|i| E::from(g_trace.exp((i as u64).into())))
+        .collect::<Vec<_>>();
+
+    let main_trace = rebuild_trace(&xs, &trace_domain, main_states, E::from);
+    let aux_trace = aux_states.map(|states| rebuild_trace(&xs, &trace_domain, states, |v| v));
+
+    // evaluate constraints at each step of the trace, and make sure all selected constraints
+    // which apply to this step are satisfied
+    let exemptions = context.num_transition_exemptions();
+    let mut main_evaluations = E::zeroed_vector(num_main_constraints);
+    let mut aux_evaluations = E::zeroed_vector(num_constraints - num_main_constraints);
+    for (step, &x) in trace_domain.iter().enumerate() {
+        let periodic_values = air.get_periodic_column_values_at(x);
+        let next_periodic_values = air.get_periodic_column_values_at(x * E::from(g_trace));
+
+        let main_frame = A::Frame::<E>::from_table(build_frame_table(
+            &main_trace,
+            step,
+            A::Frame::<E>::offsets(),
+        ));
+        air.evaluate_transition(
+            &main_frame,
+            &periodic_values,
+            &next_periodic_values,
+            &mut main_evaluations,
+        );
+
+        if let Some(ref aux_trace) = aux_trace {
+            let aux_frame = A::AuxFrame::<E>::from_table(build_frame_table(
+                aux_trace,
+                step,
+                A::AuxFrame::<E>::offsets(),
+            ));
+            air.evaluate_aux_transition(
+                &main_frame,
+                &aux_frame,
+                &periodic_values,
+                &next_periodic_values,
+                aux_rand_elements,
+                &mut aux_evaluations,
+            );
+        }
+
+        for &index in constraint_indices {
+            if step >= trace_length - exemptions[index] {
+                continue;
+            }
+            let evaluation = if index < num_main_constraints {
+                main_evaluations[index]
+            } else {
+                aux_evaluations[index - num_main_constraints]
+            };
+            if evaluation != E::ZERO {
+                return Err(VerifierError::TransitionConstraintNotSatisfied(index, step));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Interpolates each column of the queried `states` from its values at `xs`, and returns the
+/// evaluations of the resulting polynomials over the trace domain as a list of columns.
+fn rebuild_trace<F, E, M>(xs: &[E], trace_domain: &[E], states: &Table<F>, map: M) -> Vec<Vec<E>>
+where
+    F: FieldElement,
+    E: FieldElement,
+    M: Fn(F) -> E,
+{
+    (0..states.num_columns())
+        .map(|col_idx| {
+            let ys = (0..xs.len())
+                .map(|row_idx| map(states.get_row(row_idx)[col_idx]))
+                .collect::<Vec<_>>();
+            let poly = polynom::interpolate(xs, &ys, false);
+            polynom::eval_many(&poly, trace_domain)
+        })
+        .collect()
+}
+
+/// Returns a table with the rows of the `trace` located at the specified `offsets` from `step`;
+/// offsets wrap around the end of the trace.
+fn build_frame_table<E: FieldElement>(
+    trace: &[Vec<E>],
+    step: usize,
+    offsets: &[usize],
+) -> Table<E> {
+    let trace_length = trace[0].len();
+    let rows = offsets
+        .iter()
+        .map(|offset| {
+            let row_idx = (step + offset) % trace_length;
+            trace.iter().map(|column| column[row_idx]).collect()
+        })
+        .collect();
+    Table::from_rows(rows)
+}
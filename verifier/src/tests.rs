@@ -0,0 +1,142 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{verify, VerifierError};
+use air::{
+    proof::StarkProof, Air, AirContext, Assertion, DefaultEvaluationFrame, EvaluationFrame,
+    FieldExtension, HashFunction, ProofOptions, TraceInfo, TransitionConstraintDegree,
+};
+use math::{fields::f128::BaseElement, FieldElement};
+use prover::{Prover, Trace, TraceTable};
+use utils::collections::Vec;
+
+// SERIALIZED PROOFS
+// ================================================================================================
+
+// these tests are run with default features disabled in CI, and thus, verify complete proofs
+// against the no_std build of the verifier and its dependencies
+
+#[test]
+fn verify_serialized_proof() {
+    let (proof_bytes, result) = build_proof_fixture(16);
+
+    let proof = StarkProof::from_bytes(&proof_bytes).unwrap();
+    assert!(verify::<CounterAir>(proof, result).is_ok());
+
+    let proof = StarkProof::from_bytes(&proof_bytes).unwrap();
+    assert!(verify::<CounterAir>(proof, result + BaseElement::ONE).is_err());
+}
+
+#[test]
+fn reject_tampered_serialized_proof() {
+    let (mut proof_bytes, result) = build_proof_fixture(16);
+
+    // the proof-of-work nonce is serialized as the last 8 bytes of the proof
+    let nonce_index = proof_bytes.len() - 8;
+    proof_bytes[nonce_index] ^= 1;
+    let proof = StarkProof::from_bytes(&proof_bytes).unwrap();
+    assert!(verify::<CounterAir>(proof, result).is_err());
+
+    // a truncated proof cannot be deserialized
+    assert!(StarkProof::from_bytes(&proof_bytes[..nonce_index]).is_err());
+}
+
+#[test]
+fn reject_serialized_proof_for_different_trace_length() {
+    let (proof_bytes, result) = build_proof_fixture(16);
+    let (other_bytes, _) = build_proof_fixture(32);
+
+    let mut proof = StarkProof::from_bytes(&proof_bytes).unwrap();
+    proof.fri_proof = StarkProof::from_bytes(&other_bytes).unwrap().fri_proof;
+    assert_eq!(
+        Err(VerifierError::DegreeMismatch(15, 31)),
+        verify::<CounterAir>(proof, result)
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Generates a proof for a counter which counts up to `num_steps - 1`, and returns the serialized
+/// proof together with the value of the counter at the last step.
+fn build_proof_fixture(num_steps: usize) -> (Vec<u8>, BaseElement) {
+    let counter = (0..num_steps as u128).map(BaseElement::new).collect();
+    let trace = TraceTable::init(vec![counter]);
+
+    let prover = CounterProver(ProofOptions::new(
+        32,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    ));
+    let result = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    (proof.to_bytes(), result)
+}
+
+// COUNTER AIR
+// ================================================================================================
+
+/// AIR for a counter which is incremented by one at every step.
+struct CounterAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for CounterAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+    type Frame<E: FieldElement> = DefaultEvaluationFrame<E>;
+    type AuxFrame<E: FieldElement> = DefaultEvaluationFrame<E>;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1)];
+        CounterAir {
+            context: AirContext::new(trace_info, degrees, 2, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &Self::Frame<E>,
+        _periodic_values: &[E],
+        _next_periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        result[0] = frame.next()[0] - frame.current()[0] - E::ONE;
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ZERO),
+            Assertion::single(0, last_step, self.result),
+        ]
+    }
+}
+
+struct CounterProver(ProofOptions);
+
+impl Prover for CounterProver {
+    type BaseField = BaseElement;
+    type Air = CounterAir;
+    type Trace = TraceTable<BaseElement>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(0, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.0
+    }
+}
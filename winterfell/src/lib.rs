@@ -150,8 +150,8 @@
 //! ```no_run
 //! use winterfell::{
 //!     math::{fields::f128::BaseElement, FieldElement},
-//!     Air, AirContext, Assertion, ByteWriter, EvaluationFrame, ProofOptions, Serializable,
-//!     TraceInfo, TransitionConstraintDegree,
+//!     Air, AirContext, Assertion, EvaluationFrame, ProofOptions, PublicCoin, TraceInfo,
+//!     TransitionConstraintDegree,
 //! };
 //!
 //! // Public inputs for our computation will consist of the starting value and the end result.
@@ -160,11 +160,13 @@
 //!     result: BaseElement,
 //! }
 //!
-//! // We need to describe how public inputs can be converted to bytes.
-//! impl Serializable for PublicInputs {
-//!     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-//!         target.write(self.start);
-//!         target.write(self.result);
+//! // We need to describe how public inputs can be converted to field elements; these
+//! // elements are used to seed the public coin.
+//! impl PublicCoin for PublicInputs {
+//!     type BaseField = BaseElement;
+//!
+//!     fn to_elements(&self) -> Vec<BaseElement> {
+//!         vec![self.start, self.result]
 //!     }
 //! }
 //!
@@ -218,6 +220,7 @@
 //!         &self,
 //!         frame: &EvaluationFrame<E>,
 //!         _periodic_values: &[E],
+//!         _next_periodic_values: &[E],
 //!         result: &mut [E],
 //!     ) {
 //!         // First, we'll read the current state, and use it to compute the expected next state
@@ -261,7 +264,7 @@
 //! };
 //!
 //! # use winterfell::{
-//! #   Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable,
+//! #   Air, AirContext, Assertion, EvaluationFrame, PublicCoin,
 //! #   TraceInfo, TransitionConstraintDegree,
 //! # };
 //! #
@@ -270,10 +273,11 @@
 //! #     result: BaseElement,
 //! # }
 //! #
-//! # impl Serializable for PublicInputs {
-//! #     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-//! #         target.write(self.start);
-//! #         target.write(self.result);
+//! # impl PublicCoin for PublicInputs {
+//! #     type BaseField = BaseElement;
+//! #
+//! #     fn to_elements(&self) -> Vec<BaseElement> {
+//! #         vec![self.start, self.result]
 //! #     }
 //! # }
 //! #
@@ -301,6 +305,7 @@
 //! #         &self,
 //! #         frame: &EvaluationFrame<E>,
 //! #         _periodic_values: &[E],
+//! #         _next_periodic_values: &[E],
 //! #         result: &mut [E],
 //! #     ) {
 //! #         let current_state = &frame.current()[0];
@@ -366,7 +371,7 @@
 //! ```
 //! # use winterfell::{
 //! #    math::{fields::f128::BaseElement, FieldElement},
-//! #    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable,
+//! #    Air, AirContext, Assertion, EvaluationFrame, PublicCoin,
 //! #    TraceInfo, TransitionConstraintDegree, TraceTable, FieldExtension,
 //! #    HashFunction, Prover, ProofOptions, StarkProof, Trace,
 //! # };
@@ -391,10 +396,11 @@
 //! #     result: BaseElement,
 //! # }
 //! #
-//! # impl Serializable for PublicInputs {
-//! #     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-//! #         target.write(self.start);
-//! #         target.write(self.result);
+//! # impl PublicCoin for PublicInputs {
+//! #     type BaseField = BaseElement;
+//! #
+//! #     fn to_elements(&self) -> Vec<BaseElement> {
+//! #         vec![self.start, self.result]
 //! #     }
 //! # }
 //! #
@@ -422,6 +428,7 @@
 //! #         &self,
 //! #         frame: &EvaluationFrame<E>,
 //! #         _periodic_values: &[E],
+//! #         _next_periodic_values: &[E],
 //! #         result: &mut [E],
 //! #     ) {
 //! #         let current_state = &frame.current()[0];
@@ -526,12 +533,26 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use prover::{
-    crypto, iterators, math, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
+    build_lagrange_kernel_column, crypto, iterators, math, prove_column_membership,
+    select_constraint, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
     BoundaryConstraintGroup, ByteReader, ByteWriter, ConstraintCompositionCoefficients,
     ConstraintDivisor, DeepCompositionCoefficients, DefaultEvaluationFrame, Deserializable,
-    DeserializationError, EvaluationFrame, FieldExtension, HashFunction, Matrix, ProofOptions,
-    Prover, ProverError, Serializable, SliceReader, StarkProof, Table, Trace, TraceInfo,
-    TraceLayout, TraceTable, TraceTableFragment, TransitionConstraintDegree,
-    TransitionConstraintGroup,
+    DeserializationError, DivisorCache, DomainInfo, EvaluationFrame, FieldExtension, HashFunction,
+    Matrix, MembershipProof, MultiTrace, OodEvaluations, OodFrame, ProofOptions,
+    ProofSizeBreakdown, ProofStats, Prover, ProverError, ProverObserver, ProverPhase, PublicCoin,
+    RngCore, RowView, Serializable, SliceReader, SparseColumn, SparseTrace, StarkProof, Table,
+    Trace, TraceColumn, TraceError, TraceInfo, TraceLayout, TraceLde, TraceMeta, TraceMetaError,
+    TraceMetaSchema, TracePolyCache, TracePolyTable, TraceTable, TraceTableFragment,
+    TransitionConstraintDegree, TransitionConstraintGroup,
+};
+pub use verifier::{
+    evaluate_constraints, get_query_positions, query_positions, reconstruct_column_lde, verify,
+    verify_batch, verify_column_membership, verify_with_seed, BatchVerifier, LdeConsistencyError,
+    MembershipError, VerifierError,
 };
-pub use verifier::{evaluate_constraints, verify, VerifierError};
+
+#[cfg(feature = "transcript")]
+pub use verifier::verify_with_transcript;
+
+#[cfg(feature = "diagnostics")]
+pub use verifier::verify_constraint_subset;